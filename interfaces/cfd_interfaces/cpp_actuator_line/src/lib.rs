@@ -13,7 +13,7 @@ mod ffi {
         type CppActuatorLine;
 
         // ---- Constructors ----
-        fn new_actuator_line_from_file(file_path: &str) -> *mut CppActuatorLine;
+        fn new_actuator_line_from_file(file_path: &str) -> Result<*mut CppActuatorLine>;
 
         // ---- Settings accessors ----
         fn use_point_sampling(&self) -> bool;
@@ -53,7 +53,7 @@ mod ffi {
         fn summed_projection_weights_at_point(&self, point: &[f64; 3]) -> f64;
 
         // ---- Export data ----
-        fn write_results(&self, folder_path: &str);
+        fn write_results(&mut self, folder_path: &str);
     }
 }
 
@@ -61,17 +61,23 @@ pub struct CppActuatorLine {
     model: ActuatorLine
 }
 
-fn new_actuator_line_from_file(file_path: &str) -> *mut CppActuatorLine {
-    let mut model = ActuatorLine::new_from_file(file_path);
+/// Constructs a new model from a setup file. Errors are returned as a C++ exception, rather than
+/// a panic, so that the CFD solver can report the problem with the setup file.
+fn new_actuator_line_from_file(
+    file_path: &str
+) -> Result<*mut CppActuatorLine, stormbird::error::Error> {
+    let mut model = ActuatorLine::new_from_file(file_path)?;
 
     // TODO: this is currently a hack. The density needs to be set to one for incompressible,
     // single-phase, flow, but should ideally take in the values from the CFD simulations in cases
     // where the density might vary. This needs an update to handle such cases.
     model.line_force_model.density = 1.0;
 
-    Box::into_raw(
-        Box::new(
-            CppActuatorLine{model}
+    Ok(
+        Box::into_raw(
+            Box::new(
+                CppActuatorLine{model}
+            )
         )
     )
 }
@@ -156,7 +162,7 @@ impl CppActuatorLine {
         self.model.summed_projection_weights_at_point(SpatialVector::from(*point))
     }
 
-    pub fn write_results(&self, folder_path: &str) {
+    pub fn write_results(&mut self, folder_path: &str) {
         self.model.write_results(folder_path);
     }
 }
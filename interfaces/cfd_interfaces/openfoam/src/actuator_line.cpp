@@ -33,7 +33,13 @@ Foam::fv::ActuatorLine::ActuatorLine(
     coeffs_.readEntry("fields", fieldNames_);
     applied_.setSize(fieldNames_.size(), false);
 
-    this->model = stormbird_interface::new_actuator_line_from_file("system/stormbird_actuator_line.json");
+    try {
+        this->model = stormbird_interface::new_actuator_line_from_file("system/stormbird_actuator_line.json");
+    } catch (const rust::Error& error) {
+        FatalErrorInFunction
+            << "Could not set up the Stormbird actuator line model: " << error.what()
+            << exit(FatalError);
+    }
 
     this->body_force_field = new volVectorField(
         IOobject(
@@ -2,40 +2,180 @@
 // Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
+use serde::{Deserialize, Serialize};
 
 use stormath:: smoothing::moving_average::MovingAverage;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// The type of filter applied to the input
+pub enum InputFilterType {
+    #[default]
+    /// Average of the last values, where the number of values is given by the window size
+    MovingAverage,
+    /// First-order exponential filter, where the smoothing is given by a time constant
+    Exponential,
+}
+
 #[derive(Debug, Default, Clone)]
+/// First-order exponential filter with a time constant, `tau`. Each new value is weighted based on
+/// the time step since the previous value, so that the phase lag does not depend on the time step
+/// used in the co-simulation.
+pub struct ExponentialFilter {
+    pub tau: f64,
+    value: Option<f64>,
+}
+
+impl ExponentialFilter {
+    pub fn new(tau: f64) -> Self {
+        Self {
+            tau,
+            value: None,
+        }
+    }
+
+    /// Adds a new value to the filter, where `time_step` is the time since the previous value. The
+    /// first value is used directly to avoid a start-up transient from zero.
+    pub fn add(&mut self, value: f64, time_step: f64) {
+        self.value = Some(match self.value {
+            Some(previous) => {
+                let smoothing_factor = if self.tau > 0.0 {
+                    1.0 - (-time_step / self.tau).exp()
+                } else {
+                    1.0
+                };
+
+                previous + smoothing_factor * (value - previous)
+            },
+            None => value,
+        });
+    }
+
+    pub fn get_average(&self) -> f64 {
+        self.value.unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Filter for a single input channel
+pub enum InputFilter {
+    MovingAverage(MovingAverage),
+    Exponential(ExponentialFilter),
+}
+
+impl InputFilter {
+    /// Adds the value to the filter and returns the filtered value
+    pub fn apply(&mut self, value: f64, time_step: f64) -> f64 {
+        match self {
+            Self::MovingAverage(filter) => {
+                filter.add(value);
+
+                filter.get_average()
+            },
+            Self::Exponential(filter) => {
+                filter.add(value, time_step);
+
+                filter.get_average()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Filters for the three components of a vector input
+pub struct VectorInputFilter {
+    pub x: InputFilter,
+    pub y: InputFilter,
+    pub z: InputFilter,
+}
+
+impl VectorInputFilter {
+    /// Returns a moving average filter if the window size is larger than one. Otherwise, the input
+    /// is not filtered, and `None` is returned.
+    pub fn moving_average_if_active(window_size: usize) -> Option<Self> {
+        if window_size > 1 {
+            Some(Self {
+                x: InputFilter::MovingAverage(MovingAverage::new(window_size)),
+                y: InputFilter::MovingAverage(MovingAverage::new(window_size)),
+                z: InputFilter::MovingAverage(MovingAverage::new(window_size)),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an exponential filter if the time constant is larger than zero. Otherwise, the
+    /// input is not filtered, and `None` is returned.
+    pub fn exponential_if_active(tau: f64) -> Option<Self> {
+        if tau > 0.0 {
+            Some(Self {
+                x: InputFilter::Exponential(ExponentialFilter::new(tau)),
+                y: InputFilter::Exponential(ExponentialFilter::new(tau)),
+                z: InputFilter::Exponential(ExponentialFilter::new(tau)),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Filters the three components in place
+    pub fn apply(&mut self, x: &mut f64, y: &mut f64, z: &mut f64, time_step: f64) {
+        *x = self.x.apply(*x, time_step);
+        *y = self.y.apply(*y, time_step);
+        *z = self.z.apply(*z, time_step);
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+/// Filters for the motion input. Channels without a filter are passed through unchanged.
 pub struct InputFilters {
-    pub translation_x: MovingAverage,
-    pub translation_y: MovingAverage,
-    pub translation_z: MovingAverage,
-    pub rotation_x: MovingAverage,
-    pub rotation_y: MovingAverage,
-    pub rotation_z: MovingAverage,
-    pub motion_velocity_linear_x: MovingAverage,
-    pub motion_velocity_linear_y: MovingAverage,
-    pub motion_velocity_linear_z: MovingAverage,
-    pub motion_velocity_angular_x: MovingAverage,
-    pub motion_velocity_angular_y: MovingAverage,
-    pub motion_velocity_angular_z: MovingAverage,
+    pub translation: Option<VectorInputFilter>,
+    pub rotation: Option<VectorInputFilter>,
+    pub motion_velocity_linear: Option<VectorInputFilter>,
+    pub motion_velocity_angular: Option<VectorInputFilter>,
 }
 
 impl InputFilters {
-    pub fn new(window_size: usize) -> Self {
+    /// Creates moving average filters with separate window sizes for the translation, the rotation
+    /// and the motion velocities. A window size of one or less means that the channel is not
+    /// filtered.
+    pub fn new_moving_average(
+        translation_window_size: usize,
+        rotation_window_size: usize,
+        velocity_window_size: usize,
+    ) -> Self {
+        Self {
+            translation: VectorInputFilter::moving_average_if_active(translation_window_size),
+            rotation: VectorInputFilter::moving_average_if_active(rotation_window_size),
+            motion_velocity_linear: VectorInputFilter::moving_average_if_active(
+                velocity_window_size
+            ),
+            motion_velocity_angular: VectorInputFilter::moving_average_if_active(
+                velocity_window_size
+            ),
+        }
+    }
+
+    /// Creates exponential filters with separate time constants for the translation, the rotation
+    /// and the motion velocities. A time constant of zero or less means that the channel is not 
+    /// filtered.
+    pub fn new_exponential(
+        translation_tau: f64,
+        rotation_tau: f64,
+        velocity_tau: f64,
+    ) -> Self {
         Self {
-            translation_x: MovingAverage::new(window_size),
-            translation_y: MovingAverage::new(window_size),
-            translation_z: MovingAverage::new(window_size),
-            rotation_x: MovingAverage::new(window_size),
-            rotation_y: MovingAverage::new(window_size),
-            rotation_z: MovingAverage::new(window_size),
-            motion_velocity_linear_x: MovingAverage::new(window_size),
-            motion_velocity_linear_y: MovingAverage::new(window_size),
-            motion_velocity_linear_z: MovingAverage::new(window_size),
-            motion_velocity_angular_x: MovingAverage::new(window_size),
-            motion_velocity_angular_y: MovingAverage::new(window_size),
-            motion_velocity_angular_z: MovingAverage::new(window_size),
+            translation: VectorInputFilter::exponential_if_active(translation_tau),
+            rotation: VectorInputFilter::exponential_if_active(rotation_tau),
+            motion_velocity_linear: VectorInputFilter::exponential_if_active(velocity_tau),
+            motion_velocity_angular: VectorInputFilter::exponential_if_active(velocity_tau),
         }
     }
-}
\ No newline at end of file
+
+    /// Returns true if at least one channel is filtered
+    pub fn is_active(&self) -> bool {
+        self.translation.is_some() || 
+        self.rotation.is_some() || 
+        self.motion_velocity_linear.is_some() || 
+        self.motion_velocity_angular.is_some()
+    }
+}
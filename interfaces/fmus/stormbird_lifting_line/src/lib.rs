@@ -6,6 +6,10 @@ mod input_filters;
 mod parameters;
 mod model_scaling;
 mod setup;
+mod packed_input;
+
+#[cfg(test)]
+mod tests;
 
 use std::f64::consts::PI;
 use std::path::PathBuf;
@@ -15,7 +19,8 @@ use fmu_from_struct::prelude::*;
 use stormath::spatial_vector::SpatialVector;
 
 use stormbird::common_utils::results::simulation::SimulationResult;
-use stormbird::lifting_line::simulation::Simulation;
+use stormbird::io_utils::columnar_data::ColumnarData;
+use stormbird::lifting_line::simulation::{Simulation, NonConvergencePolicy};
 use stormbird::lifting_line::simulation_builder::SimulationBuilder;
 
 use stormbird::wind::{
@@ -28,30 +33,60 @@ use stormbird::controller::{
     builder::ControllerBuilder,
     input::ControllerInput,
     output::ControllerOutput,
-    measurements::FlowMeasurementSettings
+    measurements::{
+        FlowMeasurementSettings,
+        MeasurementSettings,
+        measure_angles_of_attack,
+        measure_geometric_angles_of_attack
+    }
 };
 
 use stormbird::empirical_models::blendermann_superstructre_forces::BlendermannSuperstructureForces;
 
 use fmu_from_struct::FmuInfo;
 
-use input_filters::InputFilters;
+use input_filters::{InputFilters, InputFilterType};
 use parameters::FmuParameters;
 use model_scaling::ModelScaling;
 
+/// The maximum number of sails that can be represented in the FMU. The input and output variables
+/// for individual sails are fixed fields in the FMU struct, numbered from 1 to this value. Models 
+/// with more wings are rejected when the FMU exits the initialization mode, see 
+/// [setup::check_nr_sails].
+pub const MAX_NR_SAILS: usize = 16;
+
 #[derive(Debug, Default, Clone, Fmu)]
 #[fmu_from_struct(fmi_version = 2)]
 /// FMU for a lifting line model using the Stormbird library.
 pub struct StormbirdLiftingLine {
     #[fmu_from_struct(parameter)]
     /// Path to the parameters file. If empty, the parameters file is expected to be in the resource
-    /// directory of the FMU.
+    /// directory of the FMU. Relative paths are resolved against the resource directory, and the 
+    /// path can start with an environment variable, e.g., `$STORMBIRD_CONFIG/parameters.json`.
     pub parameters_path: String,
+    /// Scale factor for the time, used when the FMU is coupled to a model scale simulation. Time
+    /// is upscaled with the square root of the factor. Not used if zero.
     pub time_model_scale: f64,
+    /// Geometric scale factor used to upscale the force, moment and power outputs from model scale
+    /// to full scale, assuming Froude scaling. Not used if zero or one. See the `model_scaling`
+    /// module for the assumptions.
+    pub geometric_model_scale: f64,
     #[fmu_from_struct(input)]
     /// Variables specifying the wind conditions.
     pub wind_velocity: f64,
     pub wind_direction_coming_from: f64,
+    /// Optional wind velocity vector, given in the same coordinate system as the wind environment,
+    /// i.e., a vector along the zero direction vector corresponds to a wind direction of zero. If
+    /// any of the components are nonzero, the vector is used directly as the true wind, and the
+    /// scalar wind velocity and direction, as well as any wind condition time series and height
+    /// variation model, are bypassed. This makes it possible to supply the wind from an external
+    /// model.
+    pub wind_vector_x: f64,
+    pub wind_vector_y: f64,
+    pub wind_vector_z: f64,
+    /// Optional air density used in the force calculations. The density in the lifting line setup
+    /// file, and in the superstructure model, is used when this is left at 0.0.
+    pub air_density: f64,
     /// Variables used to set the position and rotation of the model.
     pub translation_x: f64,
     pub translation_y: f64,
@@ -81,6 +116,12 @@ pub struct StormbirdLiftingLine {
     pub local_wing_angle_8: f64,
     pub local_wing_angle_9: f64,
     pub local_wing_angle_10: f64,
+    pub local_wing_angle_11: f64,
+    pub local_wing_angle_12: f64,
+    pub local_wing_angle_13: f64,
+    pub local_wing_angle_14: f64,
+    pub local_wing_angle_15: f64,
+    pub local_wing_angle_16: f64,
     /// Internal state of the section models for each wing. The internal state is a single value
     /// that can be used to represent the state of the section model. The interpretation of the
     /// internal state depends on the sail type. For a wing sail, it can be the flap angle. For a
@@ -95,8 +136,56 @@ pub struct StormbirdLiftingLine {
     pub section_models_internal_state_8: f64,
     pub section_models_internal_state_9: f64,
     pub section_models_internal_state_10: f64,
+    pub section_models_internal_state_11: f64,
+    pub section_models_internal_state_12: f64,
+    pub section_models_internal_state_13: f64,
+    pub section_models_internal_state_14: f64,
+    pub section_models_internal_state_15: f64,
+    pub section_models_internal_state_16: f64,
     /// Optional variable to control the amount of thrust from controller
     pub controller_loading: f64,
+    /// Packed input vector, which can be used instead of the individual input variables above when
+    /// the parameter `use_packed_input_vector` is set to true. The layout is documented in the 
+    /// `packed_input` module.
+    pub input_vector_1: f64,
+    pub input_vector_2: f64,
+    pub input_vector_3: f64,
+    pub input_vector_4: f64,
+    pub input_vector_5: f64,
+    pub input_vector_6: f64,
+    pub input_vector_7: f64,
+    pub input_vector_8: f64,
+    pub input_vector_9: f64,
+    pub input_vector_10: f64,
+    pub input_vector_11: f64,
+    pub input_vector_12: f64,
+    pub input_vector_13: f64,
+    pub input_vector_14: f64,
+    pub input_vector_15: f64,
+    pub input_vector_16: f64,
+    pub input_vector_17: f64,
+    pub input_vector_18: f64,
+    pub input_vector_19: f64,
+    pub input_vector_20: f64,
+    pub input_vector_21: f64,
+    pub input_vector_22: f64,
+    pub input_vector_23: f64,
+    pub input_vector_24: f64,
+    pub input_vector_25: f64,
+    pub input_vector_26: f64,
+    pub input_vector_27: f64,
+    pub input_vector_28: f64,
+    pub input_vector_29: f64,
+    pub input_vector_30: f64,
+    pub input_vector_31: f64,
+    pub input_vector_32: f64,
+    pub input_vector_33: f64,
+    pub input_vector_34: f64,
+    pub input_vector_35: f64,
+    pub input_vector_36: f64,
+    pub input_vector_37: f64,
+    pub input_vector_38: f64,
+    pub input_vector_39: f64,
     #[fmu_from_struct(output)]
     /// Global forces and moments acting on the lifting line model.
     pub force_x: f64,
@@ -177,6 +266,48 @@ pub struct StormbirdLiftingLine {
     pub moment_sail_10_y: f64,
     pub moment_sail_10_z: f64,
 
+    pub force_sail_11_x: f64,
+    pub force_sail_11_y: f64,
+    pub force_sail_11_z: f64,
+    pub moment_sail_11_x: f64,
+    pub moment_sail_11_y: f64,
+    pub moment_sail_11_z: f64,
+
+    pub force_sail_12_x: f64,
+    pub force_sail_12_y: f64,
+    pub force_sail_12_z: f64,
+    pub moment_sail_12_x: f64,
+    pub moment_sail_12_y: f64,
+    pub moment_sail_12_z: f64,
+
+    pub force_sail_13_x: f64,
+    pub force_sail_13_y: f64,
+    pub force_sail_13_z: f64,
+    pub moment_sail_13_x: f64,
+    pub moment_sail_13_y: f64,
+    pub moment_sail_13_z: f64,
+
+    pub force_sail_14_x: f64,
+    pub force_sail_14_y: f64,
+    pub force_sail_14_z: f64,
+    pub moment_sail_14_x: f64,
+    pub moment_sail_14_y: f64,
+    pub moment_sail_14_z: f64,
+
+    pub force_sail_15_x: f64,
+    pub force_sail_15_y: f64,
+    pub force_sail_15_z: f64,
+    pub moment_sail_15_x: f64,
+    pub moment_sail_15_y: f64,
+    pub moment_sail_15_z: f64,
+
+    pub force_sail_16_x: f64,
+    pub force_sail_16_y: f64,
+    pub force_sail_16_z: f64,
+    pub moment_sail_16_x: f64,
+    pub moment_sail_16_y: f64,
+    pub moment_sail_16_z: f64,
+
     /// Forces on the superstructure, if that is included in the model
     pub force_superstructure_x: f64,
     pub force_superstructure_y: f64,
@@ -185,7 +316,7 @@ pub struct StormbirdLiftingLine {
     pub moment_superstructure_y: f64,
     pub moment_superstructure_z: f64,
 
-    /// Measurements of the effective angle of attack at different wings. Max 10 as output in the
+    /// Measurements of the effective angle of attack at different wings. Max 16 as output in the
     /// FMU
     pub angle_of_attack_measurement_1: f64,
     pub angle_of_attack_measurement_2: f64,
@@ -197,7 +328,113 @@ pub struct StormbirdLiftingLine {
     pub angle_of_attack_measurement_8: f64,
     pub angle_of_attack_measurement_9: f64,
     pub angle_of_attack_measurement_10: f64,
-    /// Measurements of the wind velocity at different wings. Max 10 as output in the FMU
+    pub angle_of_attack_measurement_11: f64,
+    pub angle_of_attack_measurement_12: f64,
+    pub angle_of_attack_measurement_13: f64,
+    pub angle_of_attack_measurement_14: f64,
+    pub angle_of_attack_measurement_15: f64,
+    pub angle_of_attack_measurement_16: f64,
+    /// Geometric angle of attack at different wings, measured from the freestream velocity without
+    /// lift-induced velocities. Max 16 as output in the FMU
+    pub geometric_angle_of_attack_1: f64,
+    pub geometric_angle_of_attack_2: f64,
+    pub geometric_angle_of_attack_3: f64,
+    pub geometric_angle_of_attack_4: f64,
+    pub geometric_angle_of_attack_5: f64,
+    pub geometric_angle_of_attack_6: f64,
+    pub geometric_angle_of_attack_7: f64,
+    pub geometric_angle_of_attack_8: f64,
+    pub geometric_angle_of_attack_9: f64,
+    pub geometric_angle_of_attack_10: f64,
+    pub geometric_angle_of_attack_11: f64,
+    pub geometric_angle_of_attack_12: f64,
+    pub geometric_angle_of_attack_13: f64,
+    pub geometric_angle_of_attack_14: f64,
+    pub geometric_angle_of_attack_15: f64,
+    pub geometric_angle_of_attack_16: f64,
+    /// Effective angle of attack at different wings, measured from the velocity including the 
+    /// lift-induced velocities. Max 16 as output in the FMU
+    pub effective_angle_of_attack_1: f64,
+    pub effective_angle_of_attack_2: f64,
+    pub effective_angle_of_attack_3: f64,
+    pub effective_angle_of_attack_4: f64,
+    pub effective_angle_of_attack_5: f64,
+    pub effective_angle_of_attack_6: f64,
+    pub effective_angle_of_attack_7: f64,
+    pub effective_angle_of_attack_8: f64,
+    pub effective_angle_of_attack_9: f64,
+    pub effective_angle_of_attack_10: f64,
+    pub effective_angle_of_attack_11: f64,
+    pub effective_angle_of_attack_12: f64,
+    pub effective_angle_of_attack_13: f64,
+    pub effective_angle_of_attack_14: f64,
+    pub effective_angle_of_attack_15: f64,
+    pub effective_angle_of_attack_16: f64,
+    /// Input power coefficient for each sail, i.e., the input power made non-dimensional by the 
+    /// density, sail area and the velocity to the third power. Max 16 as output in the FMU
+    pub power_coefficient_1: f64,
+    pub power_coefficient_2: f64,
+    pub power_coefficient_3: f64,
+    pub power_coefficient_4: f64,
+    pub power_coefficient_5: f64,
+    pub power_coefficient_6: f64,
+    pub power_coefficient_7: f64,
+    pub power_coefficient_8: f64,
+    pub power_coefficient_9: f64,
+    pub power_coefficient_10: f64,
+    pub power_coefficient_11: f64,
+    pub power_coefficient_12: f64,
+    pub power_coefficient_13: f64,
+    pub power_coefficient_14: f64,
+    pub power_coefficient_15: f64,
+    pub power_coefficient_16: f64,
+    /// Ratio between the delivered thrust power and the input power for each sail. The delivered
+    /// power is the sail force dotted with the linear motion velocity, upscaled in the same way as
+    /// `delivered_thrust_power`. Zero for sails without input power. Max 16 as output in the FMU
+    pub efficiency_1: f64,
+    pub efficiency_2: f64,
+    pub efficiency_3: f64,
+    pub efficiency_4: f64,
+    pub efficiency_5: f64,
+    pub efficiency_6: f64,
+    pub efficiency_7: f64,
+    pub efficiency_8: f64,
+    pub efficiency_9: f64,
+    pub efficiency_10: f64,
+    pub efficiency_11: f64,
+    pub efficiency_12: f64,
+    pub efficiency_13: f64,
+    pub efficiency_14: f64,
+    pub efficiency_15: f64,
+    pub efficiency_16: f64,
+    /// Total input power to all sails, e.g., to drive rotor or suction sails
+    pub input_power_total: f64,
+    /// Input power to each sail. Max 16 as output in the FMU
+    pub input_power_sail_1: f64,
+    pub input_power_sail_2: f64,
+    pub input_power_sail_3: f64,
+    pub input_power_sail_4: f64,
+    pub input_power_sail_5: f64,
+    pub input_power_sail_6: f64,
+    pub input_power_sail_7: f64,
+    pub input_power_sail_8: f64,
+    pub input_power_sail_9: f64,
+    pub input_power_sail_10: f64,
+    pub input_power_sail_11: f64,
+    pub input_power_sail_12: f64,
+    pub input_power_sail_13: f64,
+    pub input_power_sail_14: f64,
+    pub input_power_sail_15: f64,
+    pub input_power_sail_16: f64,
+    /// Power delivered to the ship from the sail forces, computed as the total sail force dotted 
+    /// with the linear motion velocity in the global frame. Positive when the sails push the ship 
+    /// in the direction it is moving. The motion velocity is used even if it is not applied to the
+    /// freestream, and the output is therefore zero if the motion velocity is not given. Forces on
+    /// the superstructure are not included.
+    pub delivered_thrust_power: f64,
+    /// Delivered thrust power minus the total input power to the sails
+    pub net_power: f64,
+    /// Measurements of the wind velocity at different wings. Max 16 as output in the FMU
     pub velocity_measurement_1: f64,
     pub velocity_measurement_2: f64,
     pub velocity_measurement_3: f64,
@@ -208,7 +445,13 @@ pub struct StormbirdLiftingLine {
     pub velocity_measurement_8: f64,
     pub velocity_measurement_9: f64,
     pub velocity_measurement_10: f64,
-    /// Measurements of the apparent wind directions at different wings. Max 10 as output in the FMU
+    pub velocity_measurement_11: f64,
+    pub velocity_measurement_12: f64,
+    pub velocity_measurement_13: f64,
+    pub velocity_measurement_14: f64,
+    pub velocity_measurement_15: f64,
+    pub velocity_measurement_16: f64,
+    /// Measurements of the apparent wind directions at different wings. Max 16 as output in the FMU
     pub apparent_wind_direction_measurement_1: f64,
     pub apparent_wind_direction_measurement_2: f64,
     pub apparent_wind_direction_measurement_3: f64,
@@ -219,8 +462,51 @@ pub struct StormbirdLiftingLine {
     pub apparent_wind_direction_measurement_8: f64,
     pub apparent_wind_direction_measurement_9: f64,
     pub apparent_wind_direction_measurement_10: f64,
-
-    /// Controller variables
+    pub apparent_wind_direction_measurement_11: f64,
+    pub apparent_wind_direction_measurement_12: f64,
+    pub apparent_wind_direction_measurement_13: f64,
+    pub apparent_wind_direction_measurement_14: f64,
+    pub apparent_wind_direction_measurement_15: f64,
+    pub apparent_wind_direction_measurement_16: f64,
+    /// Apparent wind direction at each wing relative to the forward axis of the ship, i.e., the 
+    /// angle off the bow. Max 16 as output in the FMU
+    pub apparent_wind_direction_off_bow_1: f64,
+    pub apparent_wind_direction_off_bow_2: f64,
+    pub apparent_wind_direction_off_bow_3: f64,
+    pub apparent_wind_direction_off_bow_4: f64,
+    pub apparent_wind_direction_off_bow_5: f64,
+    pub apparent_wind_direction_off_bow_6: f64,
+    pub apparent_wind_direction_off_bow_7: f64,
+    pub apparent_wind_direction_off_bow_8: f64,
+    pub apparent_wind_direction_off_bow_9: f64,
+    pub apparent_wind_direction_off_bow_10: f64,
+    pub apparent_wind_direction_off_bow_11: f64,
+    pub apparent_wind_direction_off_bow_12: f64,
+    pub apparent_wind_direction_off_bow_13: f64,
+    pub apparent_wind_direction_off_bow_14: f64,
+    pub apparent_wind_direction_off_bow_15: f64,
+    pub apparent_wind_direction_off_bow_16: f64,
+    /// Measurements of the current section model internal state at different wings. Max 16 as 
+    /// output in the FMU
+    pub section_models_internal_state_measurement_1: f64,
+    pub section_models_internal_state_measurement_2: f64,
+    pub section_models_internal_state_measurement_3: f64,
+    pub section_models_internal_state_measurement_4: f64,
+    pub section_models_internal_state_measurement_5: f64,
+    pub section_models_internal_state_measurement_6: f64,
+    pub section_models_internal_state_measurement_7: f64,
+    pub section_models_internal_state_measurement_8: f64,
+    pub section_models_internal_state_measurement_9: f64,
+    pub section_models_internal_state_measurement_10: f64,
+    pub section_models_internal_state_measurement_11: f64,
+    pub section_models_internal_state_measurement_12: f64,
+    pub section_models_internal_state_measurement_13: f64,
+    pub section_models_internal_state_measurement_14: f64,
+    pub section_models_internal_state_measurement_15: f64,
+    pub section_models_internal_state_measurement_16: f64,
+
+    /// Section model internal states commanded by the controller. Only applied to the model when 
+    /// the manual control override is not active.
     pub controller_section_models_internal_state_1: f64,
     pub controller_section_models_internal_state_2: f64,
     pub controller_section_models_internal_state_3: f64,
@@ -231,6 +517,30 @@ pub struct StormbirdLiftingLine {
     pub controller_section_models_internal_state_8: f64,
     pub controller_section_models_internal_state_9: f64,
     pub controller_section_models_internal_state_10: f64,
+    pub controller_section_models_internal_state_11: f64,
+    pub controller_section_models_internal_state_12: f64,
+    pub controller_section_models_internal_state_13: f64,
+    pub controller_section_models_internal_state_14: f64,
+    pub controller_section_models_internal_state_15: f64,
+    pub controller_section_models_internal_state_16: f64,
+    /// Local wing angles commanded by the controller. Only applied to the model when the manual 
+    /// control override is not active.
+    pub controller_local_wing_angle_1: f64,
+    pub controller_local_wing_angle_2: f64,
+    pub controller_local_wing_angle_3: f64,
+    pub controller_local_wing_angle_4: f64,
+    pub controller_local_wing_angle_5: f64,
+    pub controller_local_wing_angle_6: f64,
+    pub controller_local_wing_angle_7: f64,
+    pub controller_local_wing_angle_8: f64,
+    pub controller_local_wing_angle_9: f64,
+    pub controller_local_wing_angle_10: f64,
+    pub controller_local_wing_angle_11: f64,
+    pub controller_local_wing_angle_12: f64,
+    pub controller_local_wing_angle_13: f64,
+    pub controller_local_wing_angle_14: f64,
+    pub controller_local_wing_angle_15: f64,
+    pub controller_local_wing_angle_16: f64,
 
     /// Calculated rigid body velocity, primarily used for debugging purposes.
     pub calculated_motion_velocity_linear_x: f64,
@@ -240,6 +550,11 @@ pub struct StormbirdLiftingLine {
     pub calculated_motion_velocity_angular_y: f64,
     pub calculated_motion_velocity_angular_z: f64,
 
+    /// Status of the last time step. Set to 1.0 if the step did not converge, in which case the 
+    /// force output is given by the non-convergence policy in the simulation settings, and to 0.0
+    /// otherwise.
+    pub step_non_converged: f64,
+
     /// The FmuInfo variable is used by the fmu_from_struct macro to store information given about
     /// the FMU using the FMI-standard. This includes, for instance, the path to the unzipped
     /// resource directory which is later used to set a default path to the parameters file.
@@ -247,15 +562,28 @@ pub struct StormbirdLiftingLine {
 
     /// Tracker of number of iterations completed
     iterations_completed: usize,
+    /// Time at the start of the current step, in full scale. Used to evaluate the gust model in the
+    /// wind environment.
+    current_time: f64,
 
     /// Non public variables containing functionality from the Stormbird library
     parameters: FmuParameters,
     stormbird_model: Option<Simulation>,
-    wind_environment: Option<WindEnvironment>,
+    wind_environment: WindEnvironment,
     controller: Option<Controller>,
     input_filters: Option<InputFilters>,
-    time_model_scaling: Option<ModelScaling>,
+    model_scaling: Option<ModelScaling>,
     superstructure_force_model: Option<BlendermannSuperstructureForces>,
+    /// The density of the lifting line model before it is overridden by the air density input. 
+    /// Stored the first time the density is overridden, and restored when the input is no longer 
+    /// positive.
+    lifting_line_default_density: Option<f64>,
+    /// Wind condition at the current time from the time series in the wind environment, if given.
+    /// Replaces the wind velocity and direction inputs.
+    time_series_wind_condition: Option<WindCondition>,
+    /// Results from every time step, stored column by column, if a columnar output file is given
+    /// in the parameters. Written to the file when the FMU instance is freed.
+    columnar_output: Option<ColumnarData>,
 }
 
 impl FmuFunctions for StormbirdLiftingLine {
@@ -267,23 +595,60 @@ impl FmuFunctions for StormbirdLiftingLine {
         self.build_lifting_line_model();
         self.build_superstructure_force_model();
 
-        if self.time_model_scale > 0.0 {
-            self.time_model_scaling = Some(
+        // The model is not used if it has more wings than the FMU can represent, so that the 
+        // error is reported at initialization rather than in the middle of the simulation.
+        if let Err(e) = setup::check_nr_sails(self.nr_wings()) {
+            println!("Error in lifting line setup: {}", e);
+
+            self.stormbird_model = None;
+        }
+
+        if self.parameters.use_packed_input_vector {
+            if let Err(e) = packed_input::check_nr_sails_in_packed_input(self.nr_wings()) {
+                println!("Error in lifting line setup: {}", e);
+
+                self.stormbird_model = None;
+            }
+        }
+
+        self.build_columnar_output();
+
+        if self.time_model_scale > 0.0 || self.geometric_model_scale > 0.0 {
+            let default_scaling = ModelScaling::default();
+
+            self.model_scaling = Some(
                 ModelScaling{
-                    scale: self.time_model_scale
+                    scale: if self.time_model_scale > 0.0 {
+                        self.time_model_scale
+                    } else {
+                        default_scaling.scale
+                    },
+                    geometric_scale: if self.geometric_model_scale > 0.0 {
+                        self.geometric_model_scale
+                    } else {
+                        default_scaling.geometric_scale
+                    },
                 }
             );
         }
     }
 
     fn do_step(&mut self, current_time_in: f64, time_step_in: f64) {
-        let (current_time, time_step) = if let Some(scaling) = self.time_model_scaling {
+        let (current_time, time_step) = if let Some(scaling) = self.model_scaling {
             (scaling.upscale_time(current_time_in), scaling.upscale_time(time_step_in))
         } else {
             (current_time_in, time_step_in)
         };
 
-        self.apply_filters_to_input_if_activated();
+        if self.parameters.use_packed_input_vector {
+            self.apply_packed_input_vector();
+        }
+
+        self.apply_filters_to_input_if_activated(time_step);
+
+        self.current_time = current_time;
+
+        self.time_series_wind_condition = self.wind_environment.wind_condition_at_time(current_time);
 
         let waiting_iterations_is_done =
             self.iterations_completed >= self.parameters.number_of_iterations_before_building_model;
@@ -305,9 +670,21 @@ impl FmuFunctions for StormbirdLiftingLine {
 
             let result = if let Some(model) = &mut self.stormbird_model {
                 if non_zero_input {
-                    Some(
-                        model.do_step(current_time, time_step, &freestream_velocity)
-                    )
+                    let result = model.do_step(current_time, time_step, &freestream_velocity);
+
+                    // The FMU can not return an error from a time step, so the error policy is 
+                    // reported as an error message, in addition to the non-converged output.
+                    let error_policy = model.on_nonconvergence == NonConvergencePolicy::Error;
+
+                    if result.non_converged && error_policy {
+                        println!(
+                            "Error in Stormbird FMU: time step at time {} did not converge. \
+                            Iterations: {}, residual: {}",
+                            current_time, result.iterations, result.residual
+                        );
+                    }
+
+                    Some(result)
                 } else {
                     self.set_zero_force_output();
 
@@ -325,6 +702,20 @@ impl FmuFunctions for StormbirdLiftingLine {
 
                 self.set_controller_measurement_output(&controller_input);
 
+                self.set_angle_of_attack_output(&result);
+
+                self.set_apparent_wind_direction_off_bow_output(&result);
+
+                self.set_power_output(&result);
+
+                self.set_net_power_output(&result);
+
+                if let Some(columnar_output) = &mut self.columnar_output {
+                    if let Err(e) = columnar_output.add_simulation_result(&result) {
+                        println!("Error adding result to the columnar output: {}", e);
+                    }
+                }
+
                 self.apply_controller(current_time, time_step, &controller_input)
             }
         }
@@ -333,6 +724,20 @@ impl FmuFunctions for StormbirdLiftingLine {
     }
 }
 
+#[cfg(feature = "columnar_output")]
+impl Drop for StormbirdLiftingLine {
+    /// Writes the columnar output to the Parquet file given in the parameters, if it is collected.
+    fn drop(&mut self) {
+        if let Some(columnar_output) = &self.columnar_output {
+            let file_path = &self.parameters.columnar_output_file_path;
+
+            if let Err(e) = columnar_output.write_to_parquet_file(file_path) {
+                println!("Error writing columnar output to {}: {}", file_path, e);
+            }
+        }
+    }
+}
+
 impl StormbirdLiftingLine {
     fn apply_controller(
         &mut self,
@@ -340,7 +745,7 @@ impl StormbirdLiftingLine {
         time_step: f64,
         controller_input: &[ControllerInput]
     ) {
-        if let Some(controller) = &self.controller {
+        if let Some(controller) = &mut self.controller {
             let controller_output = controller.update(
                 current_time,
                 time_step,
@@ -348,7 +753,11 @@ impl StormbirdLiftingLine {
             );
 
             if let Some(output) = &controller_output {
-                self.set_model_control_values_from_controller_output(output);
+                self.set_controller_command_output(output);
+
+                if !self.parameters.manual_control_override {
+                    self.set_model_control_values_from_controller_output(output);
+                }
             }
         }
     }
@@ -380,11 +789,30 @@ impl StormbirdLiftingLine {
         let motion_velocity_linear  = self.motion_velocity_linear_vector();
         let motion_velocity_angular = self.motion_velocity_angular_vector();
 
-        if self.controller.is_none() || self.iterations_completed == 0 {
+        let use_input_control_values = self.controller.is_none() ||
+            self.iterations_completed == 0 ||
+            self.parameters.manual_control_override;
+
+        if use_input_control_values {
             self.set_model_control_values_from_input()
         }
 
+        let density_override = self.air_density_override();
+
         if let Some(model) = &mut self.stormbird_model {
+            match density_override {
+                Some(density) => {
+                    self.lifting_line_default_density.get_or_insert(model.line_force_model.density);
+
+                    model.line_force_model.density = density;
+                },
+                None => {
+                    if let Some(default_density) = self.lifting_line_default_density {
+                        model.line_force_model.density = default_density;
+                    }
+                }
+            }
+
             // Apply translation, and compute the velocity using finite difference
             model
                 .line_force_model
@@ -427,39 +855,46 @@ impl StormbirdLiftingLine {
 
     /// Function that checks if the filters are activated, and if yes, applies the filters to the
     /// input data.
-    fn apply_filters_to_input_if_activated(&mut self) {
+    fn apply_filters_to_input_if_activated(&mut self, time_step: f64) {
         if let Some(filters) = &mut self.input_filters {
-            filters.translation_x.add(self.translation_x);
-            filters.translation_y.add(self.translation_y);
-            filters.translation_z.add(self.translation_z);
-
-            filters.rotation_x.add(self.rotation_x);
-            filters.rotation_y.add(self.rotation_y);
-            filters.rotation_z.add(self.rotation_z);
-
-            filters.motion_velocity_linear_x.add(self.motion_velocity_linear_x);
-            filters.motion_velocity_linear_y.add(self.motion_velocity_linear_y);
-            filters.motion_velocity_linear_z.add(self.motion_velocity_linear_z);
-
-            filters.motion_velocity_angular_x.add(self.motion_velocity_angular_x);
-            filters.motion_velocity_angular_y.add(self.motion_velocity_angular_y);
-            filters.motion_velocity_angular_z.add(self.motion_velocity_angular_z);
+            if let Some(filter) = &mut filters.translation {
+                filter.apply(
+                    &mut self.translation_x, &mut self.translation_y, &mut self.translation_z, time_step
+                );
+            }
 
-            self.translation_x = filters.translation_x.get_average();
-            self.translation_y = filters.translation_y.get_average();
-            self.translation_z = filters.translation_z.get_average();
+            if let Some(filter) = &mut filters.rotation {
+                filter.apply(
+                    &mut self.rotation_x, &mut self.rotation_y, &mut self.rotation_z, time_step
+                );
+            }
 
-            self.rotation_x = filters.rotation_x.get_average();
-            self.rotation_y = filters.rotation_y.get_average();
-            self.rotation_z = filters.rotation_z.get_average();
+            if let Some(filter) = &mut filters.motion_velocity_linear {
+                filter.apply(
+                    &mut self.motion_velocity_linear_x, 
+                    &mut self.motion_velocity_linear_y, 
+                    &mut self.motion_velocity_linear_z,
+                    time_step
+                );
+            }
 
-            self.motion_velocity_linear_x = filters.motion_velocity_linear_x.get_average();
-            self.motion_velocity_linear_y = filters.motion_velocity_linear_y.get_average();
-            self.motion_velocity_linear_z = filters.motion_velocity_linear_z.get_average();
+            if let Some(filter) = &mut filters.motion_velocity_angular {
+                filter.apply(
+                    &mut self.motion_velocity_angular_x, 
+                    &mut self.motion_velocity_angular_y, 
+                    &mut self.motion_velocity_angular_z,
+                    time_step
+                );
+            }
+        }
+    }
 
-            self.motion_velocity_angular_x = filters.motion_velocity_angular_x.get_average();
-            self.motion_velocity_angular_y = filters.motion_velocity_angular_y.get_average();
-            self.motion_velocity_angular_z = filters.motion_velocity_angular_z.get_average();
+    /// Returns the air density given as input, if it is set to a positive value
+    fn air_density_override(&self) -> Option<f64> {
+        if self.air_density > 0.0 {
+            Some(self.air_density)
+        } else {
+            None
         }
     }
 
@@ -533,6 +968,26 @@ impl StormbirdLiftingLine {
         }
     }
 
+    /// Returns the wind condition from the time series in the wind environment, if given, and 
+    /// from the wind velocity and direction inputs otherwise.
+    fn wind_condition(&self) -> WindCondition {
+        self.time_series_wind_condition.unwrap_or(WindCondition {
+            velocity: self.wind_velocity,
+            direction_coming_from: self.wind_direction()
+        })
+    }
+
+    /// Returns the wind vector given as input, if any of the components are nonzero
+    fn wind_vector_input(&self) -> Option<SpatialVector> {
+        let wind_vector = SpatialVector([self.wind_vector_x, self.wind_vector_y, self.wind_vector_z]);
+
+        if wind_vector.length() > 0.0 {
+            Some(wind_vector)
+        } else {
+            None
+        }
+    }
+
     fn wind_direction(&self) -> f64 {
         let mut wind_direction = if self.parameters.angles_in_degrees {
             self.wind_direction_coming_from.to_radians()
@@ -562,10 +1017,7 @@ impl StormbirdLiftingLine {
             };
 
         // Get the wind field from the wind environment, based on the wind condition
-        let wind_condition = WindCondition {
-            velocity: self.wind_velocity,
-            direction_coming_from: self.wind_direction()
-        };
+        let wind_condition = self.wind_condition();
 
         // Apply the linear motion of the wings to the freestream if this option is activated
         let linear_velocity = if self.parameters.use_motion_velocity_linear_as_freestream {
@@ -574,17 +1026,46 @@ impl StormbirdLiftingLine {
             SpatialVector([0.0, 0.0, 0.0])
         };
 
-        let out = if let Some(env) = &self.wind_environment {
+        let out = if let Some(wind_vector) = self.wind_vector_input() {
+            let apparent_wind_vector = wind_vector + linear_velocity;
+
+            let mut freestream_velocity = vec![apparent_wind_vector; freestream_velocity_points.len()];
+
+            let env = &self.wind_environment;
+
+            if let Some(model) = &self.stormbird_model {
+                let apparent_wind_direction = env.zero_direction_vector.signed_angle_between(
+                    apparent_wind_vector,
+                    env.wind_rotation_axis
+                );
+
+                let ctrl_points = &model.line_force_model.ctrl_points_global;
+                let wing_indices = model.line_force_model.wing_indices.clone();
+
+                env.apply_inflow_corrections(
+                    apparent_wind_direction,
+                    &mut freestream_velocity,
+                    ctrl_points,
+                    &wing_indices
+                );
+            }
+
+            freestream_velocity
+        } else {
+            let env = &self.wind_environment;
+
             let apparent_wind_direction = env.apparent_wind_direction_from_condition_and_linear_velocity_and_height(
                 wind_condition,
                 linear_velocity,
-                10.0
+                10.0,
+                self.current_time
             );
 
             let mut freestream_velocity = env.apparent_wind_velocity_vectors_at_locations(
                 wind_condition,
                 &freestream_velocity_points,
-                linear_velocity
+                linear_velocity,
+                self.current_time
             );
 
             if let Some(model) = &self.stormbird_model {
@@ -600,9 +1081,6 @@ impl StormbirdLiftingLine {
             }
 
             freestream_velocity
-
-        } else {
-            panic!("Wind environment is not defined!")
         };
 
        out
@@ -616,6 +1094,10 @@ impl StormbirdLiftingLine {
             return vec![];
         }
 
+        if let Err(e) = setup::check_nr_sails(nr_wings) {
+            panic!("Cannot read local wing angles. {}", e);
+        }
+
         let mut local_wing_angles: Vec<f64> = vec![0.0; nr_wings];
 
         let raw_local_wing_angles = vec![
@@ -629,6 +1111,12 @@ impl StormbirdLiftingLine {
             self.local_wing_angle_8,
             self.local_wing_angle_9,
             self.local_wing_angle_10,
+            self.local_wing_angle_11,
+            self.local_wing_angle_12,
+            self.local_wing_angle_13,
+            self.local_wing_angle_14,
+            self.local_wing_angle_15,
+            self.local_wing_angle_16,
         ];
 
         for i in 0..nr_wings {
@@ -652,6 +1140,10 @@ impl StormbirdLiftingLine {
             return vec![];
         }
 
+        if let Err(e) = setup::check_nr_sails(nr_wings) {
+            panic!("Cannot read section model internal states. {}", e);
+        }
+
         let section_models_internal_state_raw = vec![
             self.section_models_internal_state_1,
             self.section_models_internal_state_2,
@@ -663,6 +1155,12 @@ impl StormbirdLiftingLine {
             self.section_models_internal_state_8,
             self.section_models_internal_state_9,
             self.section_models_internal_state_10,
+            self.section_models_internal_state_11,
+            self.section_models_internal_state_12,
+            self.section_models_internal_state_13,
+            self.section_models_internal_state_14,
+            self.section_models_internal_state_15,
+            self.section_models_internal_state_16,
         ];
 
         let mut section_models_internal_state = vec![0.0; nr_wings];
@@ -679,28 +1177,34 @@ impl StormbirdLiftingLine {
             let representative_height = model.center_of_effort[2].abs();
 
             // Get the wind field from the wind environment, based on the wind condition
-            let true_wind_condition = WindCondition {
-                velocity: self.wind_velocity,
-                direction_coming_from: self.wind_direction()
-            };
+            let true_wind_condition = self.wind_condition();
 
             // Apply the linear motion of the ship to the freestream
             let linear_velocity =  -1.0 * self.motion_velocity_linear_vector();
 
             // TODO: consider including angular motion on the apparent wind calculation
-            let apparent_wind_vector = if let Some(env) = &self.wind_environment {
+            let apparent_wind_vector = if let Some(wind_vector) = self.wind_vector_input() {
+                wind_vector + linear_velocity
+            } else {
+                let env = &self.wind_environment;
+
                 let locations = vec![representative_height * env.up_direction];
 
                 env.apparent_wind_velocity_vectors_at_locations(
                     true_wind_condition,
                     &locations,
-                    linear_velocity
+                    linear_velocity,
+                    self.current_time
                 )[0]
-            } else {
-                panic!("Wind environment is not defined!")
             };
 
-            let force = model.body_fixed_force(apparent_wind_vector);
+            let mut force = model.body_fixed_force(apparent_wind_vector);
+
+            // The superstructure forces are proportional to the density
+            if let Some(density) = self.air_density_override() {
+                force = (density / model.density) * force;
+            }
+
             let moment = model.body_fixed_moment(force);
 
             (force, moment)
@@ -710,6 +1214,12 @@ impl StormbirdLiftingLine {
     }
 
     fn set_zero_force_output(&mut self) {
+        self.input_power_total = 0.0;
+        self.delivered_thrust_power = 0.0;
+        self.net_power = 0.0;
+
+        self.set_input_power_sail_output(&[0.0; MAX_NR_SAILS]);
+
         self.force_x = 0.0;
         self.force_y = 0.0;
         self.force_z = 0.0;
@@ -795,21 +1305,97 @@ impl StormbirdLiftingLine {
         self.moment_sail_10_x = 0.0;
         self.moment_sail_10_y = 0.0;
         self.moment_sail_10_z = 0.0;
+
+        self.force_sail_11_x = 0.0;
+        self.force_sail_11_y = 0.0;
+        self.force_sail_11_z = 0.0;
+        self.moment_sail_11_x = 0.0;
+        self.moment_sail_11_y = 0.0;
+        self.moment_sail_11_z = 0.0;
+
+        self.force_sail_12_x = 0.0;
+        self.force_sail_12_y = 0.0;
+        self.force_sail_12_z = 0.0;
+        self.moment_sail_12_x = 0.0;
+        self.moment_sail_12_y = 0.0;
+        self.moment_sail_12_z = 0.0;
+
+        self.force_sail_13_x = 0.0;
+        self.force_sail_13_y = 0.0;
+        self.force_sail_13_z = 0.0;
+        self.moment_sail_13_x = 0.0;
+        self.moment_sail_13_y = 0.0;
+        self.moment_sail_13_z = 0.0;
+
+        self.force_sail_14_x = 0.0;
+        self.force_sail_14_y = 0.0;
+        self.force_sail_14_z = 0.0;
+        self.moment_sail_14_x = 0.0;
+        self.moment_sail_14_y = 0.0;
+        self.moment_sail_14_z = 0.0;
+
+        self.force_sail_15_x = 0.0;
+        self.force_sail_15_y = 0.0;
+        self.force_sail_15_z = 0.0;
+        self.moment_sail_15_x = 0.0;
+        self.moment_sail_15_y = 0.0;
+        self.moment_sail_15_z = 0.0;
+
+        self.force_sail_16_x = 0.0;
+        self.force_sail_16_y = 0.0;
+        self.force_sail_16_z = 0.0;
+        self.moment_sail_16_x = 0.0;
+        self.moment_sail_16_y = 0.0;
+        self.moment_sail_16_z = 0.0;
+    }
+
+    /// Applies the input power for each sail to the output variables in the FMU
+    fn set_input_power_sail_output(&mut self, input_power: &[f64; MAX_NR_SAILS]) {
+        self.input_power_sail_1  = input_power[0];
+        self.input_power_sail_2  = input_power[1];
+        self.input_power_sail_3  = input_power[2];
+        self.input_power_sail_4  = input_power[3];
+        self.input_power_sail_5  = input_power[4];
+        self.input_power_sail_6  = input_power[5];
+        self.input_power_sail_7  = input_power[6];
+        self.input_power_sail_8  = input_power[7];
+        self.input_power_sail_9  = input_power[8];
+        self.input_power_sail_10 = input_power[9];
+        self.input_power_sail_11 = input_power[10];
+        self.input_power_sail_12 = input_power[11];
+        self.input_power_sail_13 = input_power[12];
+        self.input_power_sail_14 = input_power[13];
+        self.input_power_sail_15 = input_power[14];
+        self.input_power_sail_16 = input_power[15];
     }
 
     fn set_force_output(&mut self, result: &SimulationResult) {
-        let integrated_forces = result.integrated_forces_sum();
-        let integrated_moments = result.integrated_moments_sum();
+        self.step_non_converged = if result.non_converged { 1.0 } else { 0.0 };
+
+        let scaling = self.model_scaling.unwrap_or_default();
 
-        let (superstructure_force, superstructure_moment) = self.superstructure_force_and_moment();
+        let integrated_forces = scaling.upscale_force_vector(result.integrated_forces_sum());
+        let integrated_moments = scaling.upscale_moment_vector(result.integrated_moments_sum());
 
-        self.force_x = integrated_forces[0] + superstructure_force[0];
-        self.force_y = integrated_forces[1] + superstructure_force[1];
-        self.force_z = integrated_forces[2] + superstructure_force[2];
+        let (superstructure_force_raw, superstructure_moment_raw) = 
+            self.superstructure_force_and_moment();
 
-        self.moment_x = integrated_moments[0] + superstructure_moment[0];
-        self.moment_y = integrated_moments[1] + superstructure_moment[1];
-        self.moment_z = integrated_moments[2] + superstructure_moment[2];
+        let superstructure_force = scaling.upscale_force_vector(superstructure_force_raw);
+        let superstructure_moment = scaling.upscale_moment_vector(superstructure_moment_raw);
+
+        let (total_force, total_moment) = if self.parameters.include_superstructure_in_total {
+            (integrated_forces + superstructure_force, integrated_moments + superstructure_moment)
+        } else {
+            (integrated_forces, integrated_moments)
+        };
+
+        self.force_x = total_force[0];
+        self.force_y = total_force[1];
+        self.force_z = total_force[2];
+
+        self.moment_x = total_moment[0];
+        self.moment_y = total_moment[1];
+        self.moment_z = total_moment[2];
 
         self.force_superstructure_x = superstructure_force[0];
         self.force_superstructure_y = superstructure_force[1];
@@ -819,22 +1405,39 @@ impl StormbirdLiftingLine {
         self.moment_superstructure_y = superstructure_moment[1];
         self.moment_superstructure_z = superstructure_moment[2];
 
-        let mut individual_force_x_raw = [0.0; 10];
-        let mut individual_force_y_raw = [0.0; 10];
-        let mut individual_force_z_raw = [0.0; 10];
+        if let Err(e) = setup::check_nr_sails(result.nr_of_wings()) {
+            panic!("Cannot write force output. {}", e);
+        }
+
+        self.input_power_total = scaling.upscale_power(result.input_power_sum());
+
+        let mut individual_input_power = [0.0; MAX_NR_SAILS];
+
+        for i in 0..result.input_power.len().min(MAX_NR_SAILS) {
+            individual_input_power[i] = scaling.upscale_power(result.input_power[i]);
+        }
+
+        self.set_input_power_sail_output(&individual_input_power);
+
+        let mut individual_force_x_raw = [0.0; MAX_NR_SAILS];
+        let mut individual_force_y_raw = [0.0; MAX_NR_SAILS];
+        let mut individual_force_z_raw = [0.0; MAX_NR_SAILS];
 
-        let mut individual_moment_x_raw = [0.0; 10];
-        let mut individual_moment_y_raw = [0.0; 10];
-        let mut individual_moment_z_raw = [0.0; 10];
+        let mut individual_moment_x_raw = [0.0; MAX_NR_SAILS];
+        let mut individual_moment_y_raw = [0.0; MAX_NR_SAILS];
+        let mut individual_moment_z_raw = [0.0; MAX_NR_SAILS];
 
         for i in 0..result.nr_of_wings() {
-            individual_force_x_raw[i] = result.integrated_forces[i].total[0];
-            individual_force_y_raw[i] = result.integrated_forces[i].total[1];
-            individual_force_z_raw[i] = result.integrated_forces[i].total[2];
+            let force = scaling.upscale_force_vector(result.integrated_forces[i].total);
+            let moment = scaling.upscale_moment_vector(result.integrated_moments[i].total);
 
-            individual_moment_x_raw[i] = result.integrated_moments[i].total[0];
-            individual_moment_y_raw[i] = result.integrated_moments[i].total[1];
-            individual_moment_z_raw[i] = result.integrated_moments[i].total[2];
+            individual_force_x_raw[i] = force[0];
+            individual_force_y_raw[i] = force[1];
+            individual_force_z_raw[i] = force[2];
+
+            individual_moment_x_raw[i] = moment[0];
+            individual_moment_y_raw[i] = moment[1];
+            individual_moment_z_raw[i] = moment[2];
         }
 
         self.force_sail_1_x = individual_force_x_raw[0];
@@ -906,40 +1509,297 @@ impl StormbirdLiftingLine {
         self.moment_sail_10_x = individual_moment_x_raw[9];
         self.moment_sail_10_y = individual_moment_y_raw[9];
         self.moment_sail_10_z = individual_moment_z_raw[9];
+
+        self.force_sail_11_x = individual_force_x_raw[10];
+        self.force_sail_11_y = individual_force_y_raw[10];
+        self.force_sail_11_z = individual_force_z_raw[10];
+        self.moment_sail_11_x = individual_moment_x_raw[10];
+        self.moment_sail_11_y = individual_moment_y_raw[10];
+        self.moment_sail_11_z = individual_moment_z_raw[10];
+
+        self.force_sail_12_x = individual_force_x_raw[11];
+        self.force_sail_12_y = individual_force_y_raw[11];
+        self.force_sail_12_z = individual_force_z_raw[11];
+        self.moment_sail_12_x = individual_moment_x_raw[11];
+        self.moment_sail_12_y = individual_moment_y_raw[11];
+        self.moment_sail_12_z = individual_moment_z_raw[11];
+
+        self.force_sail_13_x = individual_force_x_raw[12];
+        self.force_sail_13_y = individual_force_y_raw[12];
+        self.force_sail_13_z = individual_force_z_raw[12];
+        self.moment_sail_13_x = individual_moment_x_raw[12];
+        self.moment_sail_13_y = individual_moment_y_raw[12];
+        self.moment_sail_13_z = individual_moment_z_raw[12];
+
+        self.force_sail_14_x = individual_force_x_raw[13];
+        self.force_sail_14_y = individual_force_y_raw[13];
+        self.force_sail_14_z = individual_force_z_raw[13];
+        self.moment_sail_14_x = individual_moment_x_raw[13];
+        self.moment_sail_14_y = individual_moment_y_raw[13];
+        self.moment_sail_14_z = individual_moment_z_raw[13];
+
+        self.force_sail_15_x = individual_force_x_raw[14];
+        self.force_sail_15_y = individual_force_y_raw[14];
+        self.force_sail_15_z = individual_force_z_raw[14];
+        self.moment_sail_15_x = individual_moment_x_raw[14];
+        self.moment_sail_15_y = individual_moment_y_raw[14];
+        self.moment_sail_15_z = individual_moment_z_raw[14];
+
+        self.force_sail_16_x = individual_force_x_raw[15];
+        self.force_sail_16_y = individual_force_y_raw[15];
+        self.force_sail_16_z = individual_force_z_raw[15];
+        self.moment_sail_16_x = individual_moment_x_raw[15];
+        self.moment_sail_16_y = individual_moment_y_raw[15];
+        self.moment_sail_16_z = individual_moment_z_raw[15];
     }
 
+    /// Creates the controller input from the simulation result. The thrust is measured along the 
+    /// horizontal direction of the linear motion velocity, which is taken as the ship velocity.
     fn controller_input(&self, result: &SimulationResult) -> Vec<ControllerInput> {
-        match (&self.stormbird_model, &self.wind_environment, &self.controller) {
-            (Some(model), Some(environment), Some(controller)) => {
-                return ControllerInput::new_from_simulation_result(
+        let environment = &self.wind_environment;
+
+        let motion_velocity = self.motion_velocity_linear_vector();
+        let horizontal_velocity = SpatialVector([motion_velocity[0], motion_velocity[1], 0.0]);
+
+        let ship_velocity = horizontal_velocity.length();
+
+        let thrust_direction = if ship_velocity > 0.0 {
+            horizontal_velocity / ship_velocity
+        } else {
+            ControllerInput::default_thrust_direction()
+        };
+
+        match (&self.stormbird_model, &self.controller) {
+            (Some(model), Some(controller)) => {
+                ControllerInput::new_from_simulation_result(
                     self.controller_loading,
                     &model.line_force_model,
                     result,
                     &controller.flow_measurement_settings,
                     environment,
-                    controller.use_input_velocity_for_apparent_wind_direction
+                    controller.use_input_velocity_for_apparent_wind_direction,
+                    thrust_direction,
+                    ship_velocity
                 )
             },
-            (Some(model), Some(environment), None) => {
-                return ControllerInput::new_from_simulation_result(
+            (Some(model), None) => {
+                ControllerInput::new_from_simulation_result(
                     self.controller_loading,
                     &model.line_force_model,
                     result,
                     &FlowMeasurementSettings::default(),
                     environment,
-                    false
+                    false,
+                    thrust_direction,
+                    ship_velocity
                 )
             },
             _ => {
-                panic!("Missing either lifting line model or wind environment")
+                panic!("Missing lifting line model")
+            }
+        }
+    }
+
+    /// Measures both the geometric and the effective angle of attack on each wing, and applies the
+    /// values to the output variables in the FMU
+    fn set_angle_of_attack_output(&mut self, result: &SimulationResult) {
+        let output_size = MAX_NR_SAILS;
+
+        let mut geometric_extended = vec![0.0; output_size];
+        let mut effective_extended = vec![0.0; output_size];
+
+        if let Some(model) = &self.stormbird_model {
+            let measurement_settings = if let Some(controller) = &self.controller {
+                controller.flow_measurement_settings.angle_of_attack.clone()
+            } else {
+                MeasurementSettings::default()
+            };
+
+            let geometric = measure_geometric_angles_of_attack(
+                result,
+                &model.line_force_model,
+                &measurement_settings
+            );
+
+            let effective = measure_angles_of_attack(result, &measurement_settings);
+
+            for i in 0..self.nr_wings().min(output_size) {
+                if self.parameters.angles_in_degrees {
+                    geometric_extended[i] = geometric[i].to_degrees();
+                    effective_extended[i] = effective[i].to_degrees();
+                } else {
+                    geometric_extended[i] = geometric[i];
+                    effective_extended[i] = effective[i];
+                }
             }
         }
+
+        self.geometric_angle_of_attack_1  = geometric_extended[0];
+        self.geometric_angle_of_attack_2  = geometric_extended[1];
+        self.geometric_angle_of_attack_3  = geometric_extended[2];
+        self.geometric_angle_of_attack_4  = geometric_extended[3];
+        self.geometric_angle_of_attack_5  = geometric_extended[4];
+        self.geometric_angle_of_attack_6  = geometric_extended[5];
+        self.geometric_angle_of_attack_7  = geometric_extended[6];
+        self.geometric_angle_of_attack_8  = geometric_extended[7];
+        self.geometric_angle_of_attack_9  = geometric_extended[8];
+        self.geometric_angle_of_attack_10 = geometric_extended[9];
+        self.geometric_angle_of_attack_11 = geometric_extended[10];
+        self.geometric_angle_of_attack_12 = geometric_extended[11];
+        self.geometric_angle_of_attack_13 = geometric_extended[12];
+        self.geometric_angle_of_attack_14 = geometric_extended[13];
+        self.geometric_angle_of_attack_15 = geometric_extended[14];
+        self.geometric_angle_of_attack_16 = geometric_extended[15];
+
+        self.effective_angle_of_attack_1  = effective_extended[0];
+        self.effective_angle_of_attack_2  = effective_extended[1];
+        self.effective_angle_of_attack_3  = effective_extended[2];
+        self.effective_angle_of_attack_4  = effective_extended[3];
+        self.effective_angle_of_attack_5  = effective_extended[4];
+        self.effective_angle_of_attack_6  = effective_extended[5];
+        self.effective_angle_of_attack_7  = effective_extended[6];
+        self.effective_angle_of_attack_8  = effective_extended[7];
+        self.effective_angle_of_attack_9  = effective_extended[8];
+        self.effective_angle_of_attack_10 = effective_extended[9];
+        self.effective_angle_of_attack_11 = effective_extended[10];
+        self.effective_angle_of_attack_12 = effective_extended[11];
+        self.effective_angle_of_attack_13 = effective_extended[12];
+        self.effective_angle_of_attack_14 = effective_extended[13];
+        self.effective_angle_of_attack_15 = effective_extended[14];
+        self.effective_angle_of_attack_16 = effective_extended[15];
+    }
+
+    /// Computes the input power coefficient and the thrust power efficiency for each sail, and
+    /// applies the values to the output variables in the FMU
+    fn set_power_output(&mut self, result: &SimulationResult) {
+        let output_size = MAX_NR_SAILS;
+
+        let mut power_coefficients_extended = vec![0.0; output_size];
+        let mut efficiencies_extended = vec![0.0; output_size];
+
+        let nr_wings = result.integrated_forces.len().min(result.input_power.len());
+
+        if let Some(model) = &self.stormbird_model {
+            let power_coefficients = result.input_power_coefficients(
+                model.line_force_model.density
+            );
+
+            for i in 0..nr_wings.min(output_size) {
+                power_coefficients_extended[i] = power_coefficients.get(i).copied().unwrap_or(0.0);
+            }
+        }
+
+        // Same convention as the delivered thrust power in set_net_power_output
+        let scaling = self.model_scaling.unwrap_or_default();
+
+        let ship_velocity = scaling.upscale_velocity_vector(self.motion_velocity_linear_vector());
+
+        for i in 0..nr_wings.min(output_size) {
+            let delivered_power = scaling.upscale_force_vector(
+                result.integrated_forces[i].total
+            ).dot(ship_velocity);
+
+            let input_power = scaling.upscale_power(result.input_power[i]);
+
+            efficiencies_extended[i] = if input_power > 0.0 {
+                delivered_power / input_power
+            } else {
+                0.0
+            };
+        }
+
+        self.power_coefficient_1  = power_coefficients_extended[0];
+        self.power_coefficient_2  = power_coefficients_extended[1];
+        self.power_coefficient_3  = power_coefficients_extended[2];
+        self.power_coefficient_4  = power_coefficients_extended[3];
+        self.power_coefficient_5  = power_coefficients_extended[4];
+        self.power_coefficient_6  = power_coefficients_extended[5];
+        self.power_coefficient_7  = power_coefficients_extended[6];
+        self.power_coefficient_8  = power_coefficients_extended[7];
+        self.power_coefficient_9  = power_coefficients_extended[8];
+        self.power_coefficient_10 = power_coefficients_extended[9];
+        self.power_coefficient_11 = power_coefficients_extended[10];
+        self.power_coefficient_12 = power_coefficients_extended[11];
+        self.power_coefficient_13 = power_coefficients_extended[12];
+        self.power_coefficient_14 = power_coefficients_extended[13];
+        self.power_coefficient_15 = power_coefficients_extended[14];
+        self.power_coefficient_16 = power_coefficients_extended[15];
+
+        self.efficiency_1  = efficiencies_extended[0];
+        self.efficiency_2  = efficiencies_extended[1];
+        self.efficiency_3  = efficiencies_extended[2];
+        self.efficiency_4  = efficiencies_extended[3];
+        self.efficiency_5  = efficiencies_extended[4];
+        self.efficiency_6  = efficiencies_extended[5];
+        self.efficiency_7  = efficiencies_extended[6];
+        self.efficiency_8  = efficiencies_extended[7];
+        self.efficiency_9  = efficiencies_extended[8];
+        self.efficiency_10 = efficiencies_extended[9];
+        self.efficiency_11 = efficiencies_extended[10];
+        self.efficiency_12 = efficiencies_extended[11];
+        self.efficiency_13 = efficiencies_extended[12];
+        self.efficiency_14 = efficiencies_extended[13];
+        self.efficiency_15 = efficiencies_extended[14];
+        self.efficiency_16 = efficiencies_extended[15];
+    }
+
+    /// Sets the apparent wind direction relative to the ship for each wing, based on the input 
+    /// velocity in the result.
+    fn set_apparent_wind_direction_off_bow_output(&mut self, result: &SimulationResult) {
+        let output_size = MAX_NR_SAILS;
+
+        let mut directions_extended = vec![0.0; output_size];
+
+        if let Some(model) = &self.stormbird_model {
+            let directions = self.wind_environment.apparent_wind_directions_relative_to_ship(
+                &result.felt_input_velocity_minus_rotational_motion(),
+                &model.line_force_model
+            );
+
+            for i in 0..directions.len().min(output_size) {
+                directions_extended[i] = if self.parameters.angles_in_degrees {
+                    directions[i].to_degrees()
+                } else {
+                    directions[i]
+                };
+            }
+        }
+
+        self.apparent_wind_direction_off_bow_1  = directions_extended[0];
+        self.apparent_wind_direction_off_bow_2  = directions_extended[1];
+        self.apparent_wind_direction_off_bow_3  = directions_extended[2];
+        self.apparent_wind_direction_off_bow_4  = directions_extended[3];
+        self.apparent_wind_direction_off_bow_5  = directions_extended[4];
+        self.apparent_wind_direction_off_bow_6  = directions_extended[5];
+        self.apparent_wind_direction_off_bow_7  = directions_extended[6];
+        self.apparent_wind_direction_off_bow_8  = directions_extended[7];
+        self.apparent_wind_direction_off_bow_9  = directions_extended[8];
+        self.apparent_wind_direction_off_bow_10 = directions_extended[9];
+        self.apparent_wind_direction_off_bow_11 = directions_extended[10];
+        self.apparent_wind_direction_off_bow_12 = directions_extended[11];
+        self.apparent_wind_direction_off_bow_13 = directions_extended[12];
+        self.apparent_wind_direction_off_bow_14 = directions_extended[13];
+        self.apparent_wind_direction_off_bow_15 = directions_extended[14];
+        self.apparent_wind_direction_off_bow_16 = directions_extended[15];
+    }
+
+    /// Computes the power delivered to the ship from the sail forces, and the net power after the
+    /// input power is subtracted, and applies the values to the output variables in the FMU
+    fn set_net_power_output(&mut self, result: &SimulationResult) {
+        let scaling = self.model_scaling.unwrap_or_default();
+
+        let ship_velocity = scaling.upscale_velocity_vector(self.motion_velocity_linear_vector());
+
+        let sail_force = scaling.upscale_force_vector(result.integrated_forces_sum());
+
+        self.delivered_thrust_power = sail_force.dot(ship_velocity);
+        self.net_power = self.delivered_thrust_power - scaling.upscale_power(result.input_power_sum());
     }
 
     /// Takes a ControllerInput variable as input, an applies the data to the output variables in
     /// the FMU
     fn set_controller_measurement_output(&mut self, controller_input: &[ControllerInput]) {
-        let output_size = 10;
+        let output_size = MAX_NR_SAILS;
 
         let mut angles_of_attack_extended = vec![0.0; output_size];
         let mut velocity_extended = vec![0.0; output_size];
@@ -948,6 +1808,10 @@ impl StormbirdLiftingLine {
 
         let nr_wings = self.nr_wings();
 
+        if let Err(e) = setup::check_nr_sails(nr_wings) {
+            panic!("Cannot write measurement output. {}", e);
+        }
+
         for i in 0..nr_wings {
             velocity_extended[i] = controller_input[i].velocity;
             section_models_internal_state[i] = controller_input[i].current_section_model_internal_state;
@@ -971,6 +1835,12 @@ impl StormbirdLiftingLine {
         self.angle_of_attack_measurement_8  = angles_of_attack_extended[7];
         self.angle_of_attack_measurement_9  = angles_of_attack_extended[8];
         self.angle_of_attack_measurement_10 = angles_of_attack_extended[9];
+        self.angle_of_attack_measurement_11 = angles_of_attack_extended[10];
+        self.angle_of_attack_measurement_12 = angles_of_attack_extended[11];
+        self.angle_of_attack_measurement_13 = angles_of_attack_extended[12];
+        self.angle_of_attack_measurement_14 = angles_of_attack_extended[13];
+        self.angle_of_attack_measurement_15 = angles_of_attack_extended[14];
+        self.angle_of_attack_measurement_16 = angles_of_attack_extended[15];
 
         self.velocity_measurement_1  = velocity_extended[0];
         self.velocity_measurement_2  = velocity_extended[1];
@@ -982,6 +1852,12 @@ impl StormbirdLiftingLine {
         self.velocity_measurement_8  = velocity_extended[7];
         self.velocity_measurement_9  = velocity_extended[8];
         self.velocity_measurement_10 = velocity_extended[9];
+        self.velocity_measurement_11 = velocity_extended[10];
+        self.velocity_measurement_12 = velocity_extended[11];
+        self.velocity_measurement_13 = velocity_extended[12];
+        self.velocity_measurement_14 = velocity_extended[13];
+        self.velocity_measurement_15 = velocity_extended[14];
+        self.velocity_measurement_16 = velocity_extended[15];
 
         self.apparent_wind_direction_measurement_1  = apparent_wind_directions_extended[0];
         self.apparent_wind_direction_measurement_2  = apparent_wind_directions_extended[1];
@@ -993,6 +1869,65 @@ impl StormbirdLiftingLine {
         self.apparent_wind_direction_measurement_8  = apparent_wind_directions_extended[7];
         self.apparent_wind_direction_measurement_9  = apparent_wind_directions_extended[8];
         self.apparent_wind_direction_measurement_10 = apparent_wind_directions_extended[9];
+        self.apparent_wind_direction_measurement_11 = apparent_wind_directions_extended[10];
+        self.apparent_wind_direction_measurement_12 = apparent_wind_directions_extended[11];
+        self.apparent_wind_direction_measurement_13 = apparent_wind_directions_extended[12];
+        self.apparent_wind_direction_measurement_14 = apparent_wind_directions_extended[13];
+        self.apparent_wind_direction_measurement_15 = apparent_wind_directions_extended[14];
+        self.apparent_wind_direction_measurement_16 = apparent_wind_directions_extended[15];
+
+        self.section_models_internal_state_measurement_1  = section_models_internal_state[0];
+        self.section_models_internal_state_measurement_2  = section_models_internal_state[1];
+        self.section_models_internal_state_measurement_3  = section_models_internal_state[2];
+        self.section_models_internal_state_measurement_4  = section_models_internal_state[3];
+        self.section_models_internal_state_measurement_5  = section_models_internal_state[4];
+        self.section_models_internal_state_measurement_6  = section_models_internal_state[5];
+        self.section_models_internal_state_measurement_7  = section_models_internal_state[6];
+        self.section_models_internal_state_measurement_8  = section_models_internal_state[7];
+        self.section_models_internal_state_measurement_9  = section_models_internal_state[8];
+        self.section_models_internal_state_measurement_10 = section_models_internal_state[9];
+        self.section_models_internal_state_measurement_11 = section_models_internal_state[10];
+        self.section_models_internal_state_measurement_12 = section_models_internal_state[11];
+        self.section_models_internal_state_measurement_13 = section_models_internal_state[12];
+        self.section_models_internal_state_measurement_14 = section_models_internal_state[13];
+        self.section_models_internal_state_measurement_15 = section_models_internal_state[14];
+        self.section_models_internal_state_measurement_16 = section_models_internal_state[15];
+    }
+
+    /// Sets the values commanded by the controller to the controller outputs. The values are only
+    /// applied to the model when the manual control override is not active.
+    fn set_controller_command_output(&mut self, controller_output: &[ControllerOutput]) {
+        let output_size = MAX_NR_SAILS;
+
+        let mut local_wing_angles = vec![0.0; output_size];
+        let mut section_models_internal_state = vec![0.0; output_size];
+
+        for (i, output) in controller_output.iter().take(output_size).enumerate() {
+            local_wing_angles[i] = if self.parameters.angles_in_degrees {
+                output.local_wing_angle.to_degrees()
+            } else {
+                output.local_wing_angle
+            };
+
+            section_models_internal_state[i] = output.section_model_internal_state;
+        }
+
+        self.controller_local_wing_angle_1  = local_wing_angles[0];
+        self.controller_local_wing_angle_2  = local_wing_angles[1];
+        self.controller_local_wing_angle_3  = local_wing_angles[2];
+        self.controller_local_wing_angle_4  = local_wing_angles[3];
+        self.controller_local_wing_angle_5  = local_wing_angles[4];
+        self.controller_local_wing_angle_6  = local_wing_angles[5];
+        self.controller_local_wing_angle_7  = local_wing_angles[6];
+        self.controller_local_wing_angle_8  = local_wing_angles[7];
+        self.controller_local_wing_angle_9  = local_wing_angles[8];
+        self.controller_local_wing_angle_10 = local_wing_angles[9];
+        self.controller_local_wing_angle_11 = local_wing_angles[10];
+        self.controller_local_wing_angle_12 = local_wing_angles[11];
+        self.controller_local_wing_angle_13 = local_wing_angles[12];
+        self.controller_local_wing_angle_14 = local_wing_angles[13];
+        self.controller_local_wing_angle_15 = local_wing_angles[14];
+        self.controller_local_wing_angle_16 = local_wing_angles[15];
 
         self.controller_section_models_internal_state_1  = section_models_internal_state[0];
         self.controller_section_models_internal_state_2  = section_models_internal_state[1];
@@ -1004,5 +1939,11 @@ impl StormbirdLiftingLine {
         self.controller_section_models_internal_state_8  = section_models_internal_state[7];
         self.controller_section_models_internal_state_9  = section_models_internal_state[8];
         self.controller_section_models_internal_state_10 = section_models_internal_state[9];
+        self.controller_section_models_internal_state_11 = section_models_internal_state[10];
+        self.controller_section_models_internal_state_12 = section_models_internal_state[11];
+        self.controller_section_models_internal_state_13 = section_models_internal_state[12];
+        self.controller_section_models_internal_state_14 = section_models_internal_state[13];
+        self.controller_section_models_internal_state_15 = section_models_internal_state[14];
+        self.controller_section_models_internal_state_16 = section_models_internal_state[15];
     }
 }
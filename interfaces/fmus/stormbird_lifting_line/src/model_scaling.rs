@@ -2,10 +2,42 @@
 // Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
+//! Conversion between model scale and full scale values.
+//!
+//! The conversion assumes Froude scaling, which is the common choice for towing tank and basin
+//! tests of ships: the Froude number, `U / sqrt(g L)`, is the same in both scales, and the fluid
+//! density is assumed to be the same. With a geometric scale factor `lambda = L_full / L_model`,
+//! this gives the following relations:
+//!
+//! - velocity: `sqrt(lambda)`
+//! - time: `sqrt(lambda)`
+//! - force: `lambda^3`
+//! - moment: `lambda^4`
+//! - power: `lambda^3.5`
+//!
+//! The Reynolds number is *not* preserved, as it scales with `lambda^1.5`. Viscous effects, such as
+//! the maximum lift and stall behavior of the sails, will therefore differ between the model and
+//! full scale. This must be accounted for in the section models, if relevant.
+
+use stormath::spatial_vector::SpatialVector;
 
 #[derive(Clone, Debug, Copy)]
 pub struct ModelScaling {
-    pub scale: f64
+    /// Scale factor used for the time. Kept separate from the geometric scale, so that it is
+    /// possible to only scale the time.
+    pub scale: f64,
+    /// Geometric scale factor used for forces, moments, velocities and power. A value of one means
+    /// that these values are not scaled.
+    pub geometric_scale: f64,
+}
+
+impl Default for ModelScaling {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            geometric_scale: 1.0,
+        }
+    }
 }
 
 impl ModelScaling {
@@ -13,4 +45,39 @@ impl ModelScaling {
     pub fn upscale_time(&self, time_value: f64) -> f64 {
         time_value * self.scale.sqrt()
     }
-}
\ No newline at end of file
+
+    /// Function to scale a velocity from model scale to full scale
+    pub fn upscale_velocity(&self, velocity_value: f64) -> f64 {
+        velocity_value * self.geometric_scale.sqrt()
+    }
+
+    /// Function to scale a force from model scale to full scale
+    pub fn upscale_force(&self, force_value: f64) -> f64 {
+        force_value * self.geometric_scale.powi(3)
+    }
+
+    /// Function to scale a moment from model scale to full scale
+    pub fn upscale_moment(&self, moment_value: f64) -> f64 {
+        moment_value * self.geometric_scale.powi(4)
+    }
+
+    /// Function to scale a power from model scale to full scale. Power is force times velocity.
+    pub fn upscale_power(&self, power_value: f64) -> f64 {
+        self.upscale_velocity(self.upscale_force(power_value))
+    }
+
+    /// Function to scale a velocity vector from model scale to full scale
+    pub fn upscale_velocity_vector(&self, velocity: SpatialVector) -> SpatialVector {
+        velocity * self.upscale_velocity(1.0)
+    }
+
+    /// Function to scale a force vector from model scale to full scale
+    pub fn upscale_force_vector(&self, force: SpatialVector) -> SpatialVector {
+        force * self.upscale_force(1.0)
+    }
+
+    /// Function to scale a moment vector from model scale to full scale
+    pub fn upscale_moment_vector(&self, moment: SpatialVector) -> SpatialVector {
+        moment * self.upscale_moment(1.0)
+    }
+}
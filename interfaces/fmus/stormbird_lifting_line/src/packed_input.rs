@@ -0,0 +1,311 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Functionality for setting all the inputs to the FMU from a single packed input vector, given by
+//! the variables `input_vector_1` to `input_vector_39`. The value references of these variables 
+//! are contiguous, so that a master algorithm can set all of them with a single call to 
+//! `fmi2SetReal`. The vector is unpacked to the individual input variables once per step, before 
+//! any filters are applied. 
+//! 
+//! The layout of the packed input vector is as follows:
+//! 
+//! | Index | Input variable |
+//! |-------|----------------|
+//! | 1 | `wind_velocity` |
+//! | 2 | `wind_direction_coming_from` |
+//! | 3 | `translation_x` |
+//! | 4 | `translation_y` |
+//! | 5 | `translation_z` |
+//! | 6 | `rotation_x` |
+//! | 7 | `rotation_y` |
+//! | 8 | `rotation_z` |
+//! | 9 | `motion_velocity_linear_x` |
+//! | 10 | `motion_velocity_linear_y` |
+//! | 11 | `motion_velocity_linear_z` |
+//! | 12 | `motion_velocity_angular_x` |
+//! | 13 | `motion_velocity_angular_y` |
+//! | 14 | `motion_velocity_angular_z` |
+//! | 15 | `local_wing_angle_1` |
+//! | 16 | `local_wing_angle_2` |
+//! | 17 | `local_wing_angle_3` |
+//! | 18 | `local_wing_angle_4` |
+//! | 19 | `local_wing_angle_5` |
+//! | 20 | `local_wing_angle_6` |
+//! | 21 | `local_wing_angle_7` |
+//! | 22 | `local_wing_angle_8` |
+//! | 23 | `local_wing_angle_9` |
+//! | 24 | `local_wing_angle_10` |
+//! | 25 | `section_models_internal_state_1` |
+//! | 26 | `section_models_internal_state_2` |
+//! | 27 | `section_models_internal_state_3` |
+//! | 28 | `section_models_internal_state_4` |
+//! | 29 | `section_models_internal_state_5` |
+//! | 30 | `section_models_internal_state_6` |
+//! | 31 | `section_models_internal_state_7` |
+//! | 32 | `section_models_internal_state_8` |
+//! | 33 | `section_models_internal_state_9` |
+//! | 34 | `section_models_internal_state_10` |
+//! | 35 | `controller_loading` |
+//! | 36 | `wind_vector_x` |
+//! | 37 | `wind_vector_y` |
+//! | 38 | `wind_vector_z` |
+//! | 39 | `air_density` |
+//! 
+//! The packed input vector only covers the first 10 sails. Models with more sails are rejected at
+//! initialization when the packed input vector is used, as the local wing angles and internal 
+//! states of the remaining sails would otherwise be silently left at zero.
+use super::*;
+
+/// The number of values in the packed input vector
+pub const PACKED_INPUT_VECTOR_SIZE: usize = 39;
+
+/// The number of sails with local wing angles and internal states in the packed input vector
+pub const PACKED_INPUT_MAX_NR_SAILS: usize = 10;
+
+/// Checks that the number of wings in a model can be represented by the packed input vector, and
+/// returns an error message if not.
+pub fn check_nr_sails_in_packed_input(nr_wings: usize) -> Result<(), String> {
+    if nr_wings > PACKED_INPUT_MAX_NR_SAILS {
+        Err(
+            format!(
+                "The packed input vector supports at most {} sails, model has {}",
+                PACKED_INPUT_MAX_NR_SAILS,
+                nr_wings
+            )
+        )
+    } else {
+        Ok(())
+    }
+}
+
+impl StormbirdLiftingLine {
+    /// Returns the packed input vector as an array
+    pub fn packed_input_vector(&self) -> [f64; PACKED_INPUT_VECTOR_SIZE] {
+        [
+            self.input_vector_1,
+            self.input_vector_2,
+            self.input_vector_3,
+            self.input_vector_4,
+            self.input_vector_5,
+            self.input_vector_6,
+            self.input_vector_7,
+            self.input_vector_8,
+            self.input_vector_9,
+            self.input_vector_10,
+            self.input_vector_11,
+            self.input_vector_12,
+            self.input_vector_13,
+            self.input_vector_14,
+            self.input_vector_15,
+            self.input_vector_16,
+            self.input_vector_17,
+            self.input_vector_18,
+            self.input_vector_19,
+            self.input_vector_20,
+            self.input_vector_21,
+            self.input_vector_22,
+            self.input_vector_23,
+            self.input_vector_24,
+            self.input_vector_25,
+            self.input_vector_26,
+            self.input_vector_27,
+            self.input_vector_28,
+            self.input_vector_29,
+            self.input_vector_30,
+            self.input_vector_31,
+            self.input_vector_32,
+            self.input_vector_33,
+            self.input_vector_34,
+            self.input_vector_35,
+            self.input_vector_36,
+            self.input_vector_37,
+            self.input_vector_38,
+            self.input_vector_39,
+        ]
+    }
+
+    /// Sets the individual input variables based on the values in the packed input vector
+    pub fn apply_packed_input_vector(&mut self) {
+        let input_vector = self.packed_input_vector();
+
+        self.wind_velocity = input_vector[0];
+        self.wind_direction_coming_from = input_vector[1];
+        self.translation_x = input_vector[2];
+        self.translation_y = input_vector[3];
+        self.translation_z = input_vector[4];
+        self.rotation_x = input_vector[5];
+        self.rotation_y = input_vector[6];
+        self.rotation_z = input_vector[7];
+        self.motion_velocity_linear_x = input_vector[8];
+        self.motion_velocity_linear_y = input_vector[9];
+        self.motion_velocity_linear_z = input_vector[10];
+        self.motion_velocity_angular_x = input_vector[11];
+        self.motion_velocity_angular_y = input_vector[12];
+        self.motion_velocity_angular_z = input_vector[13];
+        self.local_wing_angle_1 = input_vector[14];
+        self.local_wing_angle_2 = input_vector[15];
+        self.local_wing_angle_3 = input_vector[16];
+        self.local_wing_angle_4 = input_vector[17];
+        self.local_wing_angle_5 = input_vector[18];
+        self.local_wing_angle_6 = input_vector[19];
+        self.local_wing_angle_7 = input_vector[20];
+        self.local_wing_angle_8 = input_vector[21];
+        self.local_wing_angle_9 = input_vector[22];
+        self.local_wing_angle_10 = input_vector[23];
+        self.section_models_internal_state_1 = input_vector[24];
+        self.section_models_internal_state_2 = input_vector[25];
+        self.section_models_internal_state_3 = input_vector[26];
+        self.section_models_internal_state_4 = input_vector[27];
+        self.section_models_internal_state_5 = input_vector[28];
+        self.section_models_internal_state_6 = input_vector[29];
+        self.section_models_internal_state_7 = input_vector[30];
+        self.section_models_internal_state_8 = input_vector[31];
+        self.section_models_internal_state_9 = input_vector[32];
+        self.section_models_internal_state_10 = input_vector[33];
+        self.controller_loading = input_vector[34];
+        self.wind_vector_x = input_vector[35];
+        self.wind_vector_y = input_vector[36];
+        self.wind_vector_z = input_vector[37];
+        self.air_density = input_vector[38];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_input_vector_equals_scalar_input() {
+        let mut fmu_scalar = StormbirdLiftingLine::default();
+        let mut fmu_packed = StormbirdLiftingLine::default();
+
+        let values: Vec<f64> = (0..PACKED_INPUT_VECTOR_SIZE).map(|i| 0.5 + i as f64).collect();
+
+        fmu_scalar.wind_velocity = values[0];
+        fmu_scalar.wind_direction_coming_from = values[1];
+        fmu_scalar.translation_x = values[2];
+        fmu_scalar.translation_y = values[3];
+        fmu_scalar.translation_z = values[4];
+        fmu_scalar.rotation_x = values[5];
+        fmu_scalar.rotation_y = values[6];
+        fmu_scalar.rotation_z = values[7];
+        fmu_scalar.motion_velocity_linear_x = values[8];
+        fmu_scalar.motion_velocity_linear_y = values[9];
+        fmu_scalar.motion_velocity_linear_z = values[10];
+        fmu_scalar.motion_velocity_angular_x = values[11];
+        fmu_scalar.motion_velocity_angular_y = values[12];
+        fmu_scalar.motion_velocity_angular_z = values[13];
+        fmu_scalar.local_wing_angle_1 = values[14];
+        fmu_scalar.local_wing_angle_2 = values[15];
+        fmu_scalar.local_wing_angle_3 = values[16];
+        fmu_scalar.local_wing_angle_4 = values[17];
+        fmu_scalar.local_wing_angle_5 = values[18];
+        fmu_scalar.local_wing_angle_6 = values[19];
+        fmu_scalar.local_wing_angle_7 = values[20];
+        fmu_scalar.local_wing_angle_8 = values[21];
+        fmu_scalar.local_wing_angle_9 = values[22];
+        fmu_scalar.local_wing_angle_10 = values[23];
+        fmu_scalar.section_models_internal_state_1 = values[24];
+        fmu_scalar.section_models_internal_state_2 = values[25];
+        fmu_scalar.section_models_internal_state_3 = values[26];
+        fmu_scalar.section_models_internal_state_4 = values[27];
+        fmu_scalar.section_models_internal_state_5 = values[28];
+        fmu_scalar.section_models_internal_state_6 = values[29];
+        fmu_scalar.section_models_internal_state_7 = values[30];
+        fmu_scalar.section_models_internal_state_8 = values[31];
+        fmu_scalar.section_models_internal_state_9 = values[32];
+        fmu_scalar.section_models_internal_state_10 = values[33];
+        fmu_scalar.controller_loading = values[34];
+        fmu_scalar.wind_vector_x = values[35];
+        fmu_scalar.wind_vector_y = values[36];
+        fmu_scalar.wind_vector_z = values[37];
+        fmu_scalar.air_density = values[38];
+        fmu_packed.input_vector_1 = values[0];
+        fmu_packed.input_vector_2 = values[1];
+        fmu_packed.input_vector_3 = values[2];
+        fmu_packed.input_vector_4 = values[3];
+        fmu_packed.input_vector_5 = values[4];
+        fmu_packed.input_vector_6 = values[5];
+        fmu_packed.input_vector_7 = values[6];
+        fmu_packed.input_vector_8 = values[7];
+        fmu_packed.input_vector_9 = values[8];
+        fmu_packed.input_vector_10 = values[9];
+        fmu_packed.input_vector_11 = values[10];
+        fmu_packed.input_vector_12 = values[11];
+        fmu_packed.input_vector_13 = values[12];
+        fmu_packed.input_vector_14 = values[13];
+        fmu_packed.input_vector_15 = values[14];
+        fmu_packed.input_vector_16 = values[15];
+        fmu_packed.input_vector_17 = values[16];
+        fmu_packed.input_vector_18 = values[17];
+        fmu_packed.input_vector_19 = values[18];
+        fmu_packed.input_vector_20 = values[19];
+        fmu_packed.input_vector_21 = values[20];
+        fmu_packed.input_vector_22 = values[21];
+        fmu_packed.input_vector_23 = values[22];
+        fmu_packed.input_vector_24 = values[23];
+        fmu_packed.input_vector_25 = values[24];
+        fmu_packed.input_vector_26 = values[25];
+        fmu_packed.input_vector_27 = values[26];
+        fmu_packed.input_vector_28 = values[27];
+        fmu_packed.input_vector_29 = values[28];
+        fmu_packed.input_vector_30 = values[29];
+        fmu_packed.input_vector_31 = values[30];
+        fmu_packed.input_vector_32 = values[31];
+        fmu_packed.input_vector_33 = values[32];
+        fmu_packed.input_vector_34 = values[33];
+        fmu_packed.input_vector_35 = values[34];
+        fmu_packed.input_vector_36 = values[35];
+        fmu_packed.input_vector_37 = values[36];
+        fmu_packed.input_vector_38 = values[37];
+        fmu_packed.input_vector_39 = values[38];
+        fmu_packed.apply_packed_input_vector();
+
+        assert_eq!(fmu_scalar.wind_velocity, fmu_packed.wind_velocity);
+        assert_eq!(fmu_scalar.wind_direction_coming_from, fmu_packed.wind_direction_coming_from);
+        assert_eq!(fmu_scalar.translation_x, fmu_packed.translation_x);
+        assert_eq!(fmu_scalar.translation_y, fmu_packed.translation_y);
+        assert_eq!(fmu_scalar.translation_z, fmu_packed.translation_z);
+        assert_eq!(fmu_scalar.rotation_x, fmu_packed.rotation_x);
+        assert_eq!(fmu_scalar.rotation_y, fmu_packed.rotation_y);
+        assert_eq!(fmu_scalar.rotation_z, fmu_packed.rotation_z);
+        assert_eq!(fmu_scalar.motion_velocity_linear_x, fmu_packed.motion_velocity_linear_x);
+        assert_eq!(fmu_scalar.motion_velocity_linear_y, fmu_packed.motion_velocity_linear_y);
+        assert_eq!(fmu_scalar.motion_velocity_linear_z, fmu_packed.motion_velocity_linear_z);
+        assert_eq!(fmu_scalar.motion_velocity_angular_x, fmu_packed.motion_velocity_angular_x);
+        assert_eq!(fmu_scalar.motion_velocity_angular_y, fmu_packed.motion_velocity_angular_y);
+        assert_eq!(fmu_scalar.motion_velocity_angular_z, fmu_packed.motion_velocity_angular_z);
+        assert_eq!(fmu_scalar.local_wing_angle_1, fmu_packed.local_wing_angle_1);
+        assert_eq!(fmu_scalar.local_wing_angle_2, fmu_packed.local_wing_angle_2);
+        assert_eq!(fmu_scalar.local_wing_angle_3, fmu_packed.local_wing_angle_3);
+        assert_eq!(fmu_scalar.local_wing_angle_4, fmu_packed.local_wing_angle_4);
+        assert_eq!(fmu_scalar.local_wing_angle_5, fmu_packed.local_wing_angle_5);
+        assert_eq!(fmu_scalar.local_wing_angle_6, fmu_packed.local_wing_angle_6);
+        assert_eq!(fmu_scalar.local_wing_angle_7, fmu_packed.local_wing_angle_7);
+        assert_eq!(fmu_scalar.local_wing_angle_8, fmu_packed.local_wing_angle_8);
+        assert_eq!(fmu_scalar.local_wing_angle_9, fmu_packed.local_wing_angle_9);
+        assert_eq!(fmu_scalar.local_wing_angle_10, fmu_packed.local_wing_angle_10);
+        assert_eq!(fmu_scalar.section_models_internal_state_1, fmu_packed.section_models_internal_state_1);
+        assert_eq!(fmu_scalar.section_models_internal_state_2, fmu_packed.section_models_internal_state_2);
+        assert_eq!(fmu_scalar.section_models_internal_state_3, fmu_packed.section_models_internal_state_3);
+        assert_eq!(fmu_scalar.section_models_internal_state_4, fmu_packed.section_models_internal_state_4);
+        assert_eq!(fmu_scalar.section_models_internal_state_5, fmu_packed.section_models_internal_state_5);
+        assert_eq!(fmu_scalar.section_models_internal_state_6, fmu_packed.section_models_internal_state_6);
+        assert_eq!(fmu_scalar.section_models_internal_state_7, fmu_packed.section_models_internal_state_7);
+        assert_eq!(fmu_scalar.section_models_internal_state_8, fmu_packed.section_models_internal_state_8);
+        assert_eq!(fmu_scalar.section_models_internal_state_9, fmu_packed.section_models_internal_state_9);
+        assert_eq!(fmu_scalar.section_models_internal_state_10, fmu_packed.section_models_internal_state_10);
+        assert_eq!(fmu_scalar.controller_loading, fmu_packed.controller_loading);
+        assert_eq!(fmu_scalar.wind_vector_x, fmu_packed.wind_vector_x);
+        assert_eq!(fmu_scalar.wind_vector_y, fmu_packed.wind_vector_y);
+        assert_eq!(fmu_scalar.wind_vector_z, fmu_packed.wind_vector_z);
+        assert_eq!(fmu_scalar.air_density, fmu_packed.air_density);
+    }
+
+    #[test]
+    fn packed_input_rejects_too_many_sails() {
+        assert!(check_nr_sails_in_packed_input(PACKED_INPUT_MAX_NR_SAILS).is_ok());
+        assert!(check_nr_sails_in_packed_input(PACKED_INPUT_MAX_NR_SAILS + 1).is_err());
+    }
+}
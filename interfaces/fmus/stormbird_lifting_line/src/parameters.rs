@@ -10,7 +10,11 @@ use std::path::Path;
 
 use stormbird::error::Error;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+use stormbird::empirical_models::blendermann_superstructre_forces::BlendermannSuperstructureForcesBuilder;
+
+use crate::input_filters::InputFilterType;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 /// Parameters for the Stormbird lifting line FMU. These variables could also be part of the FMU
 /// directly. However, they are stored in a separate JSON file to facilitate easier usage when using
@@ -28,6 +32,10 @@ pub struct FmuParameters {
     #[serde(default)]
     pub superstructure_force_setup_path: String,
     #[serde(default)]
+    /// Optional setup of the superstructure force model given directly in the parameters file. 
+    /// Used instead of the setup path, if both are given.
+    pub superstructure_force_model: Option<BlendermannSuperstructureForcesBuilder>,
+    #[serde(default)]
     /// Switch to specify if angles is given in degrees or radians.
     pub angles_in_degrees: bool,
     #[serde(default)]
@@ -46,16 +54,94 @@ pub struct FmuParameters {
     /// Non-dimensional spanwise position for measuring effective angle of attack
     pub non_dim_spanwise_measurement_position: f64,
     #[serde(default)]
-    /// If larger than zero, this variable is used to construct moving average filters on the input
+    /// If larger than one, this variable is used to construct filters on the input. Used for all 
+    /// channels that do not have a specific window size.
     pub input_moving_average_window_size: usize,
     #[serde(default)]
+    /// The type of filter used on the input
+    pub input_filter_type: InputFilterType,
+    #[serde(default)]
+    /// Time constant for the exponential input filter, given in full scale seconds. That is, the
+    /// value is compared to the upscaled time when `time_model_scale` is active. Used for all 
+    /// channels that do not have a specific time constant.
+    pub input_filter_time_constant: f64,
+    #[serde(default)]
+    /// Optional time constant for the exponential filter on the translation input
+    pub translation_filter_time_constant: Option<f64>,
+    #[serde(default)]
+    /// Optional time constant for the exponential filter on the rotation input
+    pub rotation_filter_time_constant: Option<f64>,
+    #[serde(default)]
+    /// Optional time constant for the exponential filter on the linear and angular motion velocity input
+    pub velocity_filter_time_constant: Option<f64>,
+    #[serde(default)]
+    /// Optional window size for the moving average filter on the translation input
+    pub translation_filter_window_size: Option<usize>,
+    #[serde(default)]
+    /// Optional window size for the moving average filter on the rotation input
+    pub rotation_filter_window_size: Option<usize>,
+    #[serde(default)]
+    /// Optional window size for the moving average filter on the linear and angular motion velocity input
+    pub velocity_filter_window_size: Option<usize>,
+    #[serde(default)]
     /// If larger than zero, this variable can be used to delay the construction of the model. This
     /// is useful if situations where the input velocity may not be properly set until a couple if
     /// time steps in to the simulation.
-    pub number_of_iterations_before_building_model: usize
+    pub number_of_iterations_before_building_model: usize,
+    #[serde(default)]
+    /// Switch to specify whether the input should be read from the packed input vector instead of 
+    /// the individual input variables.
+    pub use_packed_input_vector: bool,
+    #[serde(default="FmuParameters::default_include_superstructure_in_total")]
+    /// Switch to specify whether the superstructure forces should be included in the total force 
+    /// and moment output. The superstructure forces are always available in the dedicated outputs.
+    pub include_superstructure_in_total: bool,
+    #[serde(default)]
+    /// Switch to activate manual control. The local wing angles and section model internal states
+    /// are then taken directly from the inputs, even if a controller is used. The controller still
+    /// runs, so that the measurements and the values it would have commanded are available in the
+    /// controller outputs.
+    pub manual_control_override: bool,
+    #[serde(default)]
+    /// Optional path to a Parquet file where the time, forces and moments from every time step are
+    /// written when the simulation is finished. Requires the `columnar_output` feature.
+    pub columnar_output_file_path: String,
+}
+
+impl Default for FmuParameters {
+    fn default() -> Self {
+        Self {
+            lifting_line_setup_file_path: String::new(),
+            wind_environment_setup_file_path: String::new(),
+            controller_setup_file_path: String::new(),
+            superstructure_force_setup_path: String::new(),
+            superstructure_force_model: None,
+            angles_in_degrees: false,
+            use_motion_velocity: false,
+            motion_velocity_in_body_fixed_frame: false,
+            use_motion_velocity_linear_as_freestream: false,
+            non_dim_spanwise_measurement_position: 0.0,
+            input_moving_average_window_size: 0,
+            input_filter_type: InputFilterType::default(),
+            input_filter_time_constant: 0.0,
+            translation_filter_time_constant: None,
+            rotation_filter_time_constant: None,
+            velocity_filter_time_constant: None,
+            translation_filter_window_size: None,
+            rotation_filter_window_size: None,
+            velocity_filter_window_size: None,
+            number_of_iterations_before_building_model: 0,
+            use_packed_input_vector: false,
+            include_superstructure_in_total: Self::default_include_superstructure_in_total(),
+            manual_control_override: false,
+            columnar_output_file_path: String::new(),
+        }
+    }
 }
 
 impl FmuParameters {
+    pub fn default_include_superstructure_in_total() -> bool {true}
+
     /// Construct a new Parameters object from a JSON file.
     pub fn from_json_file(file_path: &Path) -> Result<Self, Error> {
         let file = File::open(file_path)?;
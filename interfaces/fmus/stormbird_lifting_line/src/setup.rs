@@ -5,21 +5,90 @@
 
 use super::*;
 
-impl StormbirdLiftingLine {
-    /// Function that checks if the parameters file path is set, and if not, sets the default path
-    /// to the resource directory of the FMU. 
-    pub fn parameters_path(&self) -> PathBuf {
-        let parameters_path: PathBuf = if self.parameters_path.is_empty() {
-            let mut path = self.fmu_info.resource_path.clone();
+use std::path::Path;
 
-            path.push("parameters.json");
+/// Expands a leading environment variable in a path, given either as `$NAME` or `${NAME}`. The
+/// variable must be the first component of the path. Paths without a leading `$` are returned
+/// unchanged.
+pub fn expand_leading_environment_variable(path: &str) -> Result<String, String> {
+    let Some(stripped_path) = path.strip_prefix('$') else {
+        return Ok(path.to_string());
+    };
 
-            path
-        } else {
-            PathBuf::from(&self.parameters_path)
-        };
+    let braced_path = stripped_path.strip_prefix('{');
+
+    let (variable_name, remaining_path) = if let Some(braced_path) = braced_path {
+        match braced_path.find('}') {
+            Some(end_index) => (&braced_path[..end_index], &braced_path[end_index + 1..]),
+            None => return Err(format!("Missing closing brace in the path: {}", path)),
+        }
+    } else {
+        let end_index = stripped_path.find(['/', '\\']).unwrap_or(stripped_path.len());
+
+        (&stripped_path[..end_index], &stripped_path[end_index..])
+    };
+
+    match std::env::var(variable_name) {
+        Ok(value) => Ok(format!("{}{}", value, remaining_path)),
+        Err(_) => Err(
+            format!(
+                "The environment variable {} used in the path {} is not set", 
+                variable_name, 
+                path
+            )
+        ),
+    }
+}
+
+/// Resolves the path to the parameters file. An empty path gives the default file in the resource
+/// directory. A leading environment variable is expanded, and relative paths are resolved against 
+/// the resource directory.
+pub fn resolve_parameters_path(
+    parameters_path: &str, 
+    resource_path: &Path
+) -> Result<PathBuf, String> {
+    if parameters_path.is_empty() {
+        return Ok(resource_path.join("parameters.json"));
+    }
+
+    let expanded_path = PathBuf::from(expand_leading_environment_variable(parameters_path)?);
 
-        parameters_path
+    if expanded_path.is_absolute() {
+        Ok(expanded_path)
+    } else {
+        Ok(resource_path.join(expanded_path))
+    }
+}
+
+/// Checks that the number of wings in a model can be represented by the sail variables in the FMU,
+/// and returns an error message if not.
+pub fn check_nr_sails(nr_wings: usize) -> Result<(), String> {
+    if nr_wings > MAX_NR_SAILS {
+        Err(
+            format!(
+                "Stormbird FMU supports at most {} sails, model has {}",
+                MAX_NR_SAILS,
+                nr_wings
+            )
+        )
+    } else {
+        Ok(())
+    }
+}
+
+impl StormbirdLiftingLine {
+    /// Function that resolves the parameters file path. If the path is not set, the default path
+    /// in the resource directory of the FMU is used. Relative paths are resolved against the 
+    /// resource directory, and a leading environment variable is expanded.
+    pub fn parameters_path(&self) -> PathBuf {
+        match resolve_parameters_path(&self.parameters_path, &self.fmu_info.resource_path) {
+            Ok(path) => path,
+            Err(e) => {
+                println!("Error resolving parameters file path: {}", e);
+
+                PathBuf::from(&self.parameters_path)
+            }
+        }
     }
 
     /// Function that reads the parameters from the parameters file.
@@ -61,16 +130,41 @@ impl StormbirdLiftingLine {
         }
     }
 
-    /// Builds filters for the input
+    /// Builds filters for the input. The window size, or time constant, for each channel is taken 
+    /// from the channel-specific parameter if given, and from the common value otherwise. No 
+    /// filters are built if no channel is active.
     pub fn build_filters(&mut self) {
-        if self.parameters.input_moving_average_window_size > 0 {
-            self.input_filters = Some(
-                InputFilters::new(self.parameters.input_moving_average_window_size)
-            );
-        }
+        let filters = match self.parameters.input_filter_type {
+            InputFilterType::MovingAverage => {
+                let common_window_size = self.parameters.input_moving_average_window_size;
+
+                InputFilters::new_moving_average(
+                    self.parameters.translation_filter_window_size.unwrap_or(common_window_size),
+                    self.parameters.rotation_filter_window_size.unwrap_or(common_window_size),
+                    self.parameters.velocity_filter_window_size.unwrap_or(common_window_size),
+                )
+            },
+            InputFilterType::Exponential => {
+                let common_tau = self.parameters.input_filter_time_constant;
+
+                InputFilters::new_exponential(
+                    self.parameters.translation_filter_time_constant.unwrap_or(common_tau),
+                    self.parameters.rotation_filter_time_constant.unwrap_or(common_tau),
+                    self.parameters.velocity_filter_time_constant.unwrap_or(common_tau),
+                )
+            }
+        };
+
+        self.input_filters = if filters.is_active() {
+            Some(filters)
+        } else {
+            None
+        };
     }
 
-    /// Builds a wind environment model
+    /// Builds the wind environment. The default environment is used if no setup file is given, or
+    /// if the setup file can't be read, so that an environment is always present during the
+    /// simulation.
     pub fn build_wind_model(&mut self) {
         if !self.parameters.wind_environment_setup_file_path.is_empty() {
             let mut setup_path = self.parameters_path();
@@ -81,18 +175,21 @@ impl StormbirdLiftingLine {
 
             match environment {
                 Ok(env) => {
-                    self.wind_environment = Some(env);
+                    self.wind_environment = env;
                 },
                 Err(e) => {
                     println!(
-                        "Error reading wind environment setup file from path: {}. Error: {}", 
+                        "Error reading wind environment setup file from path: {}. Error: {}. \
+                        Using the default wind environment instead.", 
                         &self.parameters.wind_environment_setup_file_path, 
                         e
                     );
+
+                    self.wind_environment = WindEnvironment::default();
                 }
             }
         } else {
-            self.wind_environment = Some(WindEnvironment::default());
+            self.wind_environment = WindEnvironment::default();
         }
     }
 
@@ -102,13 +199,13 @@ impl StormbirdLiftingLine {
             setup_path.pop();
             setup_path.push(self.parameters.controller_setup_file_path.clone());
             
-            let controller_builder = ControllerBuilder::from_json_file(
+            let controller = ControllerBuilder::from_json_file(
                 &setup_path.to_string_lossy()
-            );
+            ).and_then(|builder| builder.build());
 
-            match controller_builder {
-                Ok(builder) => {
-                    self.controller = Some(builder.build());
+            match controller {
+                Ok(controller) => {
+                    self.controller = Some(controller);
                 },
                 Err(e) => {
                     println!(
@@ -122,7 +219,16 @@ impl StormbirdLiftingLine {
     }
 
     pub fn build_superstructure_force_model(&mut self) {
-        if !self.parameters.superstructure_force_setup_path.is_empty() {
+        if let Some(builder) = &self.parameters.superstructure_force_model {
+            match builder.build() {
+                Ok(model) => {
+                    self.superstructure_force_model = Some(model);
+                },
+                Err(e) => {
+                    println!("Error building superstructure force model from parameters: {}", e);
+                }
+            }
+        } else if !self.parameters.superstructure_force_setup_path.is_empty() {
             let mut setup_path = self.parameters_path();
             setup_path.pop();
             setup_path.push(self.parameters.superstructure_force_setup_path.clone());
@@ -145,4 +251,21 @@ impl StormbirdLiftingLine {
             }
         }
     }
+
+    /// Starts collecting the columnar output if a file path is given in the parameters. The output
+    /// can only be written when the `columnar_output` feature is active, and is otherwise ignored.
+    pub fn build_columnar_output(&mut self) {
+        if self.parameters.columnar_output_file_path.is_empty() {
+            return;
+        }
+
+        if cfg!(feature = "columnar_output") {
+            self.columnar_output = Some(ColumnarData::default());
+        } else {
+            println!(
+                "The columnar output file path is set, but the FMU is built without the \
+                columnar_output feature. No columnar output is written."
+            );
+        }
+    }
 }
\ No newline at end of file
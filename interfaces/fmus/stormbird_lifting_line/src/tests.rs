@@ -0,0 +1,492 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the lifting line FMU.
+
+use super::*;
+
+use stormbird::common_utils::forces_and_moments::IntegratedValues;
+use stormbird::lifting_line::simulation_builder::SimulationSettings;
+use stormbird::line_force_model::prelude::*;
+use stormbird::section_models::{
+    SectionModel,
+    rotating_cylinder::RotatingCylinder
+};
+
+#[test]
+/// Tests that the superstructure forces can be excluded from the total force output, while still
+/// being reported in the dedicated superstructure outputs.
+fn exclude_superstructure_from_total() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters.include_superstructure_in_total = false;
+    fmu.wind_velocity = 10.0;
+    fmu.wind_direction_coming_from = 0.5;
+    fmu.superstructure_force_model = Some(
+        BlendermannSuperstructureForces::from_json_string(
+            r#"{
+                "frontal_area": 200.0,
+                "side_area": 1000.0,
+                "center_of_effort": [-10.0, 0.0, -15.0]
+            }"#
+        ).unwrap()
+    );
+
+    let sail_force = SpatialVector([1000.0, 2000.0, 0.0]);
+    let sail_moment = SpatialVector([-5000.0, 100.0, 300.0]);
+
+    let result = SimulationResult {
+        integrated_forces: vec![
+            IntegratedValues {
+                total: sail_force,
+                ..Default::default()
+            }
+        ],
+        integrated_moments: vec![
+            IntegratedValues {
+                total: sail_moment,
+                ..Default::default()
+            }
+        ],
+        ..Default::default()
+    };
+
+    fmu.set_force_output(&result);
+
+    assert_eq!(fmu.force_x, sail_force[0]);
+    assert_eq!(fmu.force_y, sail_force[1]);
+    assert_eq!(fmu.force_z, sail_force[2]);
+
+    assert_eq!(fmu.moment_x, sail_moment[0]);
+    assert_eq!(fmu.moment_y, sail_moment[1]);
+    assert_eq!(fmu.moment_z, sail_moment[2]);
+
+    assert!(fmu.force_superstructure_x.abs() > 0.0);
+    assert!(fmu.force_superstructure_y.abs() > 0.0);
+}
+
+#[test]
+/// Tests that a parameters path starting with an environment variable is expanded when the 
+/// variable is set, and gives a clear error when it is not.
+fn parameters_path_with_environment_variable() {
+    let resource_path = PathBuf::from("/fmu/resources");
+
+    std::env::set_var("STORMBIRD_TEST_CONFIG_DIR", "/shared/config");
+
+    let resolved_path = setup::resolve_parameters_path(
+        "$STORMBIRD_TEST_CONFIG_DIR/parameters.json", &resource_path
+    ).unwrap();
+
+    assert_eq!(resolved_path, PathBuf::from("/shared/config/parameters.json"));
+
+    let braced_path = setup::resolve_parameters_path(
+        "${STORMBIRD_TEST_CONFIG_DIR}/parameters.json", &resource_path
+    ).unwrap();
+
+    assert_eq!(braced_path, resolved_path);
+
+    let error = setup::resolve_parameters_path(
+        "$STORMBIRD_TEST_UNSET_VARIABLE/parameters.json", &resource_path
+    ).unwrap_err();
+
+    assert!(error.contains("STORMBIRD_TEST_UNSET_VARIABLE"));
+    assert!(error.contains("not set"));
+}
+
+#[test]
+/// Tests that relative parameter paths are resolved against the resource directory
+fn relative_parameters_path_is_resolved_against_resources() {
+    let resource_path = PathBuf::from("/fmu/resources");
+
+    let default_path = setup::resolve_parameters_path("", &resource_path).unwrap();
+    let relative_path = setup::resolve_parameters_path("setup/parameters.json", &resource_path)
+        .unwrap();
+    let absolute_path = setup::resolve_parameters_path("/data/parameters.json", &resource_path)
+        .unwrap();
+
+    assert_eq!(default_path, PathBuf::from("/fmu/resources/parameters.json"));
+    assert_eq!(relative_path, PathBuf::from("/fmu/resources/setup/parameters.json"));
+    assert_eq!(absolute_path, PathBuf::from("/data/parameters.json"));
+}
+
+#[test]
+/// Tests that the inputs are applied directly to the model when the manual control override is
+/// active, while the controller still runs and reports the values it would have commanded. The 
+/// measured internal state is reported separately from the commanded one.
+fn manual_control_override() {
+    let manual_internal_state = 2.0;
+    let controller_internal_state = 5.0;
+
+    let mut line_force_model_builder = LineForceModelBuilder::new(10);
+
+    line_force_model_builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector([0.0, 0.0, 0.0]),
+                SpatialVector([0.0, 0.0, 5.0]),
+            ],
+            chord_vectors: vec![
+                SpatialVector([1.0, 0.0, 0.0]),
+                SpatialVector([1.0, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::RotatingCylinder(RotatingCylinder::default()),
+            non_zero_circulation_at_ends: [false, false],
+            ..Default::default()
+        }
+    );
+
+    let controller_setup = format!(r#"{{
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-3.2, 3.2],
+                "section_model_internal_state_data": [
+                    {controller_internal_state}, {controller_internal_state}
+                ]
+            }}
+        ]
+    }}"#);
+
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters.manual_control_override = true;
+    fmu.stormbird_model = Some(
+        SimulationBuilder::new(
+            line_force_model_builder,
+            SimulationSettings::default()
+        ).build()
+    );
+    fmu.controller = Some(Controller::new_from_string(&controller_setup).unwrap());
+
+    fmu.wind_velocity = 8.0;
+    fmu.wind_direction_coming_from = 0.5;
+    fmu.controller_loading = 1.0;
+    fmu.section_models_internal_state_1 = manual_internal_state;
+
+    let time_step = 0.1;
+
+    for i in 0..3 {
+        fmu.do_step(i as f64 * time_step, time_step);
+
+        let applied_state = fmu.stormbird_model.as_ref().unwrap()
+            .line_force_model
+            .section_models_internal_state();
+
+        assert_eq!(applied_state[0], manual_internal_state);
+        assert_eq!(fmu.section_models_internal_state_measurement_1, manual_internal_state);
+        assert_eq!(fmu.controller_section_models_internal_state_1, controller_internal_state);
+    }
+}
+
+#[test]
+/// Tests that the force output is written for all sails when the model has more than 10 wings.
+fn force_output_for_twelve_sails() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+
+    let nr_sails = 12;
+
+    let result = SimulationResult {
+        integrated_forces: (0..nr_sails).map(|i| IntegratedValues {
+            total: SpatialVector([100.0 * (i + 1) as f64, 0.0, 0.0]),
+            ..Default::default()
+        }).collect(),
+        integrated_moments: (0..nr_sails).map(|_| IntegratedValues::default()).collect(),
+        ..Default::default()
+    };
+
+    fmu.set_force_output(&result);
+
+    assert_eq!(fmu.force_sail_1_x, 100.0);
+    assert_eq!(fmu.force_sail_10_x, 1000.0);
+    assert_eq!(fmu.force_sail_11_x, 1100.0);
+    assert_eq!(fmu.force_sail_12_x, 1200.0);
+    assert_eq!(fmu.force_sail_13_x, 0.0);
+}
+
+#[test]
+#[should_panic]
+/// Tests that writing the force output for more wings than the FMU supports panics, instead of 
+/// silently dropping the data for the extra wings.
+fn force_output_for_too_many_sails() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+
+    let nr_sails = MAX_NR_SAILS + 1;
+
+    let result = SimulationResult {
+        integrated_forces: vec![IntegratedValues::default(); nr_sails],
+        integrated_moments: vec![IntegratedValues::default(); nr_sails],
+        ..Default::default()
+    };
+
+    fmu.set_force_output(&result);
+}
+
+#[test]
+/// Tests that the number of wings is checked against the number of sail variables in the FMU.
+fn nr_sails_check() {
+    assert!(setup::check_nr_sails(12).is_ok());
+    assert!(setup::check_nr_sails(MAX_NR_SAILS).is_ok());
+
+    let error = setup::check_nr_sails(MAX_NR_SAILS + 2).unwrap_err();
+
+    assert_eq!(
+        error,
+        format!(
+            "Stormbird FMU supports at most {} sails, model has {}", 
+            MAX_NR_SAILS, 
+            MAX_NR_SAILS + 2
+        )
+    );
+}
+
+#[test]
+/// Tests that the air density input scales the superstructure forces, and that the density of the
+/// superstructure model is used when the input is left at zero.
+fn air_density_input() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.wind_velocity = 10.0;
+    fmu.wind_direction_coming_from = 0.5;
+    fmu.superstructure_force_model = Some(
+        BlendermannSuperstructureForces::from_json_string(
+            r#"{
+                "frontal_area": 200.0,
+                "side_area": 1000.0,
+                "center_of_effort": [-10.0, 0.0, -15.0]
+            }"#
+        ).unwrap()
+    );
+
+    let (default_force, _) = fmu.superstructure_force_and_moment();
+
+    let model_density = fmu.superstructure_force_model.as_ref().unwrap().density;
+
+    fmu.air_density = 1.5 * model_density;
+
+    let (dense_force, _) = fmu.superstructure_force_and_moment();
+
+    assert!(default_force[1].abs() > 0.0);
+    assert!((dense_force[1] - 1.5 * default_force[1]).abs() < 1e-9 * default_force[1].abs());
+}
+
+#[test]
+/// Tests that the moving average filters are built per channel, and that channels without a window
+/// size larger than one are passed through unchanged.
+fn per_channel_input_filters() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters.rotation_filter_window_size = Some(2);
+
+    fmu.build_filters();
+
+    let filters = fmu.input_filters.as_ref().unwrap();
+
+    assert!(filters.translation.is_none());
+    assert!(filters.rotation.is_some());
+    assert!(filters.motion_velocity_linear.is_none());
+
+    fmu.rotation_z = 1.0;
+    fmu.translation_x = 1.0;
+    fmu.apply_filters_to_input_if_activated(0.1);
+
+    fmu.rotation_z = 0.0;
+    fmu.translation_x = 0.0;
+    fmu.apply_filters_to_input_if_activated(0.1);
+
+    assert!((fmu.rotation_z - 0.5).abs() < 1e-12);
+    assert_eq!(fmu.translation_x, 0.0);
+
+    fmu.parameters.rotation_filter_window_size = None;
+    fmu.build_filters();
+
+    assert!(fmu.input_filters.is_none());
+}
+
+#[test]
+/// Tests that the exponential input filter responds to a step according to its time constant, and
+/// that the time constant is compared to the upscaled time when model scaling is active.
+fn exponential_input_filter_in_upscaled_time() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters.input_filter_type = InputFilterType::Exponential;
+    fmu.parameters.input_filter_time_constant = 2.0;
+    fmu.time_model_scale = 4.0;
+
+    fmu.build_filters();
+    fmu.model_scaling = Some(ModelScaling { scale: fmu.time_model_scale, geometric_scale: 1.0 });
+
+    fmu.apply_filters_to_input_if_activated(0.0);
+
+    // One model scale second corresponds to two full scale seconds, which is one time constant.
+    let time_step = fmu.model_scaling.unwrap().upscale_time(1.0);
+
+    fmu.motion_velocity_linear_x = 1.0;
+    fmu.apply_filters_to_input_if_activated(time_step);
+
+    let expected_value = 1.0 - (-1.0_f64).exp();
+
+    assert!((fmu.motion_velocity_linear_x - expected_value).abs() < 1e-12);
+}
+
+#[test]
+/// Tests that the exponential filters use the channel-specific time constants when given, and the
+/// common time constant otherwise.
+fn per_channel_exponential_input_filters() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters.input_filter_type = InputFilterType::Exponential;
+    fmu.parameters.input_filter_time_constant = 1.0;
+    fmu.parameters.translation_filter_time_constant = Some(0.0);
+
+    fmu.build_filters();
+
+    let filters = fmu.input_filters.as_ref().unwrap();
+
+    assert!(filters.translation.is_none());
+    assert!(filters.rotation.is_some());
+    assert!(filters.motion_velocity_linear.is_some());
+
+    fmu.apply_filters_to_input_if_activated(0.0);
+
+    fmu.translation_x = 1.0;
+    fmu.rotation_z = 1.0;
+    fmu.apply_filters_to_input_if_activated(1.0);
+
+    assert_eq!(fmu.translation_x, 1.0);
+    assert!((fmu.rotation_z - (1.0 - (-1.0_f64).exp())).abs() < 1e-12);
+
+    fmu.parameters.input_filter_time_constant = 0.0;
+    fmu.build_filters();
+
+    assert!(fmu.input_filters.is_none());
+}
+
+#[test]
+/// Tests that the force and moment outputs are Froude scaled with the geometric scale factor, and
+/// that a geometric scale of one gives the unscaled output.
+fn geometric_model_scaling_of_force_output() {
+    let sail_force = SpatialVector([1000.0, 2000.0, 0.0]);
+    let sail_moment = SpatialVector([-5000.0, 100.0, 300.0]);
+
+    let result = SimulationResult {
+        integrated_forces: vec![
+            IntegratedValues {
+                total: sail_force,
+                ..Default::default()
+            }
+        ],
+        integrated_moments: vec![
+            IntegratedValues {
+                total: sail_moment,
+                ..Default::default()
+            }
+        ],
+        ..Default::default()
+    };
+
+    let geometric_scale = 20.0;
+
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.model_scaling = Some(ModelScaling { scale: 1.0, geometric_scale });
+    fmu.set_force_output(&result);
+
+    assert!((fmu.force_y - sail_force[1] * geometric_scale.powi(3)).abs() < 1e-6);
+    assert!((fmu.moment_x - sail_moment[0] * geometric_scale.powi(4)).abs() < 1e-3);
+    assert!((fmu.force_sail_1_x - sail_force[0] * geometric_scale.powi(3)).abs() < 1e-6);
+
+    fmu.model_scaling = Some(ModelScaling { scale: 4.0, geometric_scale: 1.0 });
+    fmu.set_force_output(&result);
+
+    assert_eq!(fmu.force_y, sail_force[1]);
+    assert_eq!(fmu.moment_x, sail_moment[0]);
+}
+
+#[test]
+/// Tests that the efficiency of each sail uses the same upscaled delivered power as the net power
+/// output, also when the model is scaled.
+fn efficiency_consistent_with_delivered_thrust_power() {
+    let result = SimulationResult {
+        integrated_forces: vec![
+            IntegratedValues {
+                total: SpatialVector([1000.0, 2000.0, 0.0]),
+                ..Default::default()
+            }
+        ],
+        input_power: vec![500.0],
+        ..Default::default()
+    };
+
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.model_scaling = Some(ModelScaling { scale: 1.0, geometric_scale: 20.0 });
+    fmu.motion_velocity_linear_x = 2.0;
+    fmu.motion_velocity_linear_y = 0.5;
+
+    fmu.set_power_output(&result);
+    fmu.set_net_power_output(&result);
+
+    let upscaled_input_power = fmu.model_scaling.unwrap().upscale_power(500.0);
+
+    assert!(fmu.delivered_thrust_power.abs() > 0.0);
+    assert!(
+        (fmu.efficiency_1 * upscaled_input_power - fmu.delivered_thrust_power).abs() <
+        1e-9 * fmu.delivered_thrust_power.abs()
+    );
+}
+
+#[test]
+/// Tests that a nonzero wind vector input is used directly as the true wind, without requiring a
+/// wind environment, and that the motion velocity is still included in the apparent wind.
+fn wind_vector_input() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.superstructure_force_model = Some(
+        BlendermannSuperstructureForces::from_json_string(
+            r#"{
+                "frontal_area": 200.0,
+                "side_area": 1000.0,
+                "center_of_effort": [-10.0, 0.0, -15.0]
+            }"#
+        ).unwrap()
+    );
+
+    fmu.wind_vector_x = 5.0;
+    fmu.wind_vector_y = 8.0;
+    fmu.motion_velocity_linear_x = -6.0;
+
+    let (force, _) = fmu.superstructure_force_and_moment();
+
+    let expected_apparent_wind = SpatialVector([11.0, 8.0, 0.0]);
+
+    let expected_force = fmu.superstructure_force_model
+        .as_ref()
+        .unwrap()
+        .body_fixed_force(expected_apparent_wind);
+
+    assert!(force[1].abs() > 0.0);
+    assert_eq!(force, expected_force);
+}
+
+#[test]
+/// Tests that a wind environment setup file that can't be read falls back to the default wind
+/// environment, so that the wind inflow can still be computed.
+fn missing_wind_environment_falls_back_to_default() {
+    let mut fmu = StormbirdLiftingLine::default();
+
+    fmu.parameters_path = "/non_existing_directory/parameters.json".to_string();
+    fmu.parameters.wind_environment_setup_file_path = "wind_environment.json".to_string();
+    fmu.wind_velocity = 10.0;
+
+    fmu.build_wind_model();
+
+    let default_environment = WindEnvironment::default();
+
+    assert_eq!(
+        fmu.wind_environment.zero_direction_vector,
+        default_environment.zero_direction_vector
+    );
+    assert!(fmu.freestream_velocity().is_empty());
+}
@@ -9,9 +9,10 @@ sys.modules['pystormbird._native.section_models'] = _native.section_models
 sys.modules['pystormbird._native.line_force_model'] = _native.line_force_model
 sys.modules['pystormbird._native.wind'] = _native.wind
 sys.modules['pystormbird._native.smoothing'] = _native.smoothing
+sys.modules['pystormbird._native.controller'] = _native.controller
 
 # Re-export top-level classes
-from pystormbird._native import SimulationResult, SectionalForcesInput
+from pystormbird._native import SimulationResult, SectionalForcesInput, PolarResult
 
 # Make submodules available
 from pystormbird import lifting_line
@@ -19,13 +20,16 @@ from pystormbird import section_models
 from pystormbird import line_force_model
 from pystormbird import wind
 from pystormbird import smoothing
+from pystormbird import controller
 
 __all__ = [
     "SimulationResult",
     "SectionalForcesInput",
+    "PolarResult",
     "lifting_line",
     "section_models",
     "line_force_model",
     "wind",
     "smoothing",
+    "controller",
 ]
\ No newline at end of file
@@ -75,3 +75,19 @@ class SimulationResult:
     def integrated_moments_sum(self) -> list[float]: ...
 
     def input_power_sum(self) -> float: ...
+
+class PolarResult:
+    @property
+    def wind_velocities(self) -> list[float]: ...
+    @property
+    def wind_directions(self) -> list[float]: ...
+    @property
+    def thrust(self) -> list[list[float]]: ...
+    @property
+    def side_force(self) -> list[list[float]]: ...
+    @property
+    def input_power(self) -> list[list[float]]: ...
+    @property
+    def delivered_power(self) -> list[list[float]]: ...
+    @property
+    def net_power(self) -> list[list[float]]: ...
@@ -0,0 +1 @@
+from pystormbird._native.controller import *
\ No newline at end of file
@@ -0,0 +1,39 @@
+class ControllerInput:
+    def __init__(
+        self,
+        *,
+        apparent_wind_direction: float,
+        loading: float = 1.0,
+        current_local_wing_angle: float = 0.0,
+        current_section_model_internal_state: float = 0.0,
+        angle_of_attack: float = 0.0,
+        velocity: float = 0.0
+    ) -> None: ...
+    @property
+    def apparent_wind_direction(self) -> float: ...
+    @property
+    def loading(self) -> float: ...
+    @property
+    def current_local_wing_angle(self) -> float: ...
+    @property
+    def current_section_model_internal_state(self) -> float: ...
+    @property
+    def angle_of_attack(self) -> float: ...
+    @property
+    def velocity(self) -> float: ...
+
+class ControllerOutput:
+    @property
+    def local_wing_angle(self) -> float: ...
+    @property
+    def section_model_internal_state(self) -> float: ...
+
+class Controller:
+    def __init__(self, setup_string: str) -> None: ...
+    def update(
+        self,
+        *,
+        time: float,
+        time_step: float,
+        input: list[ControllerInput]
+    ) -> list[ControllerOutput] | None: ...
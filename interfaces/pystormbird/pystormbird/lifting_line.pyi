@@ -1,5 +1,5 @@
 
-from pystormbird import SimulationResult
+from pystormbird import SimulationResult, PolarResult
 from .line_force_model import LineForceModel
 
 class Simulation:
@@ -49,6 +49,15 @@ class CompleteSailModel:
         nr_time_steps: int = 1
     ) -> SimulationResult: ...
     
+    def simulate_polar(
+        self,
+        *,
+        wind_velocities: list[float],
+        wind_directions: list[float],
+        ship_velocity: float,
+        controller_loading: float = 1.0
+    ) -> PolarResult: ...
+    
     def section_models_internal_state(self) -> list[float]: ...
     
     def set_translation_only(self, rotation: list[float]): ...
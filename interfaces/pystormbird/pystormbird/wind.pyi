@@ -5,7 +5,8 @@ class WindEnvironment:
         *, 
         wind_velocity: float, 
         wind_direction_coming_from: float,
-        height: float
+        height: float,
+        time: float = 0.0
     ) -> float: ...
     
     def true_wind_velocity_vector_at_location(
@@ -13,7 +14,9 @@ class WindEnvironment:
         *, 
         wind_velocity: float,
         wind_direction_coming_from: float,
-        location: list[float]
+        location: list[float],
+        output_frame: str | None = None,
+        time: float = 0.0
     ) -> list[float]: ...
     
     def apparent_wind_velocity_vector_at_location(
@@ -22,16 +25,40 @@ class WindEnvironment:
         wind_velocity: float,
         wind_direction_coming_from: float,
         location: list[float],
-        linear_velocity: list[float]
+        linear_velocity: list[float],
+        output_frame: str | None = None,
+        time: float = 0.0
     ) -> list[float]: ...
     
+    def true_wind_velocity_vectors_at_locations(
+        self,
+        *,
+        wind_velocity: float,
+        wind_direction_coming_from: float,
+        locations: list[list[float]],
+        output_frame: str | None = None,
+        time: float = 0.0
+    ) -> list[list[float]]: ...
+    
+    def apparent_wind_velocity_vectors_at_locations(
+        self,
+        *,
+        wind_velocity: float,
+        wind_direction_coming_from: float,
+        locations: list[list[float]],
+        linear_velocity: list[float],
+        output_frame: str | None = None,
+        time: float = 0.0
+    ) -> list[list[float]]: ...
+    
     def apparent_wind_direction_from_condition_and_linear_velocity(
         self,
         *,
         wind_velocity: float,
         wind_direction_coming_from: float,
         linear_velocity: list[float],
-        height: float = 10.0
+        height: float = 10.0,
+        time: float = 0.0
     ) -> float: ...
     
     def apparent_wind_velocity_vectors_at_ctrl_points_with_corrections_applied(
@@ -41,5 +68,7 @@ class WindEnvironment:
         wind_direction_coming_from: float,
         ctrl_points: list[list[float]],
         linear_velocity: list[float],
-        wing_indices: list[list[int]]
+        wing_indices: list[list[int]],
+        output_frame: str | None = None,
+        time: float = 0.0
     ) -> list[list[float]]: ...
\ No newline at end of file
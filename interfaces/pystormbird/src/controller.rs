@@ -0,0 +1,157 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Interface to the controller, which can be used without any lifting line simulation.
+
+use pyo3::prelude::*;
+
+use stormbird::controller::{
+    Controller as ControllerRust,
+    input::ControllerInput as ControllerInputRust,
+    output::ControllerOutput as ControllerOutputRust,
+};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ControllerInput {
+    pub data: ControllerInputRust
+}
+
+#[pymethods]
+impl ControllerInput {
+    #[new]
+    #[pyo3(signature=(
+        *,
+        apparent_wind_direction,
+        loading = 1.0,
+        current_local_wing_angle = 0.0,
+        current_section_model_internal_state = 0.0,
+        angle_of_attack = 0.0,
+        velocity = 0.0
+    ))]
+    pub fn new(
+        apparent_wind_direction: f64,
+        loading: f64,
+        current_local_wing_angle: f64,
+        current_section_model_internal_state: f64,
+        angle_of_attack: f64,
+        velocity: f64
+    ) -> Self {
+        Self {
+            data: ControllerInputRust {
+                loading,
+                current_local_wing_angle,
+                current_section_model_internal_state,
+                angle_of_attack,
+                velocity,
+                apparent_wind_direction,
+                ..Default::default()
+            }
+        }
+    }
+
+    #[getter]
+    pub fn apparent_wind_direction(&self) -> f64 {
+        self.data.apparent_wind_direction
+    }
+
+    #[getter]
+    pub fn loading(&self) -> f64 {
+        self.data.loading
+    }
+
+    #[getter]
+    pub fn current_local_wing_angle(&self) -> f64 {
+        self.data.current_local_wing_angle
+    }
+
+    #[getter]
+    pub fn current_section_model_internal_state(&self) -> f64 {
+        self.data.current_section_model_internal_state
+    }
+
+    #[getter]
+    pub fn angle_of_attack(&self) -> f64 {
+        self.data.angle_of_attack
+    }
+
+    #[getter]
+    pub fn velocity(&self) -> f64 {
+        self.data.velocity
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ControllerOutput {
+    pub data: ControllerOutputRust
+}
+
+#[pymethods]
+impl ControllerOutput {
+    #[getter]
+    pub fn local_wing_angle(&self) -> f64 {
+        self.data.local_wing_angle
+    }
+
+    #[getter]
+    pub fn section_model_internal_state(&self) -> f64 {
+        self.data.section_model_internal_state
+    }
+
+    fn __str__(&self) -> String {
+        format!("{:?}", self.data)
+    }
+}
+
+#[pyclass]
+pub struct Controller {
+    data: ControllerRust
+}
+
+#[pymethods]
+impl Controller {
+    #[new]
+    pub fn new(
+        setup_string: String
+    ) -> Self {
+        Self {
+            data: ControllerRust::new_from_string(
+                &setup_string
+            ).unwrap()
+        }
+    }
+
+    #[pyo3(signature=(
+        *,
+        time,
+        time_step,
+        input
+    ))]
+    pub fn update(
+        &mut self,
+        time: f64,
+        time_step: f64,
+        input: Vec<ControllerInput>
+    ) -> Option<Vec<ControllerOutput>> {
+        let input_rust: Vec<ControllerInputRust> = input.iter().map(
+            |v| v.data.clone()
+        ).collect();
+
+        let output_rust = self.data.update(time, time_step, &input_rust);
+
+        output_rust.map(
+            |output| output.iter().map(|v| ControllerOutput { data: v.clone() }).collect()
+        )
+    }
+}
+
+#[pymodule]
+pub fn controller(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ControllerInput>()?;
+    m.add_class::<ControllerOutput>()?;
+    m.add_class::<Controller>()?;
+
+    Ok(())
+}
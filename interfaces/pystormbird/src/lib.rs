@@ -14,6 +14,7 @@ mod line_force_model;
 mod lifting_line;
 mod smoothing;
 mod wind;
+mod controller;
 
 #[pymodule]
 fn _native(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -21,12 +22,14 @@ fn _native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<result_structs::SectionalForces>()?;
     m.add_class::<result_structs::IntegratedValues>()?;
     m.add_class::<result_structs::SimulationResult>()?;
+    m.add_class::<result_structs::PolarResult>()?;
     
     m.add_wrapped(wrap_pymodule!(section_models::section_models))?;
     m.add_wrapped(wrap_pymodule!(line_force_model::line_force_model))?;
     m.add_wrapped(wrap_pymodule!(lifting_line::lifting_line))?;
     m.add_wrapped(wrap_pymodule!(smoothing::smoothing))?;
     m.add_wrapped(wrap_pymodule!(wind::wind))?;
+    m.add_wrapped(wrap_pymodule!(controller::controller))?;
     
     Ok(())
 }
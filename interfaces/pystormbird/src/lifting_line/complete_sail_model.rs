@@ -10,7 +10,7 @@ use stormbird::lifting_line::complete_sail_model::CompleteSailModel as CompleteS
 
 use stormbird::wind::wind_condition::WindCondition;
 
-use crate::result_structs::SimulationResult;
+use crate::result_structs::{SimulationResult, PolarResult};
 
 #[pyclass]
 pub struct CompleteSailModel {
@@ -141,6 +141,32 @@ impl CompleteSailModel {
         }
     }
 
+    #[pyo3(signature=(
+        *,
+        wind_velocities,
+        wind_directions,
+        ship_velocity,
+        controller_loading = 1.0
+    ))]
+    pub fn simulate_polar(
+        &self,
+        wind_velocities: Vec<f64>,
+        wind_directions: Vec<f64>,
+        ship_velocity: f64,
+        controller_loading: f64
+    ) -> PolarResult {
+        let result_rs = self.data.simulate_polar(
+            &wind_velocities,
+            &wind_directions,
+            ship_velocity,
+            controller_loading
+        );
+
+        PolarResult {
+            data: result_rs
+        }
+    }
+
     pub fn section_models_internal_state(&self) -> Vec<f64> {
         self.data.lifting_line_simulation.line_force_model.section_models_internal_state()
     }
@@ -9,6 +9,7 @@ use stormbird::common_utils::results::simulation::SimulationResult as Simulation
 use stormbird::common_utils::forces_and_moments::SectionalForces as SectionalForcesRust;
 use stormbird::common_utils::forces_and_moments::IntegratedValues as IntegratedValuesRust;
 use stormbird::common_utils::forces_and_moments::SectionalForcesInput as SectionalForcesInputRust;
+use stormbird::lifting_line::complete_sail_model::polar::PolarResult as PolarResultRust;
 
 #[pyclass]
 #[derive(Clone)]
@@ -203,3 +204,47 @@ impl SimulationResult {
         self.data.input_power.iter().sum()
     }
 }
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PolarResult {
+    pub data: PolarResultRust
+}
+
+#[pymethods]
+impl PolarResult {
+    #[getter]
+    pub fn wind_velocities(&self) -> Vec<f64> {
+        self.data.wind_velocities.clone()
+    }
+
+    #[getter]
+    pub fn wind_directions(&self) -> Vec<f64> {
+        self.data.wind_directions.clone()
+    }
+
+    #[getter]
+    pub fn thrust(&self) -> Vec<Vec<f64>> {
+        self.data.thrust.clone()
+    }
+
+    #[getter]
+    pub fn side_force(&self) -> Vec<Vec<f64>> {
+        self.data.side_force.clone()
+    }
+
+    #[getter]
+    pub fn input_power(&self) -> Vec<Vec<f64>> {
+        self.data.input_power.clone()
+    }
+
+    #[getter]
+    pub fn delivered_power(&self) -> Vec<Vec<f64>> {
+        self.data.delivered_power.clone()
+    }
+
+    #[getter]
+    pub fn net_power(&self) -> Vec<Vec<f64>> {
+        self.data.net_power()
+    }
+}
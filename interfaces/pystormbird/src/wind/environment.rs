@@ -13,6 +13,8 @@ use pyo3::prelude::*;
 
 use std::ops::Range;
 
+use super::frame::OutputFrame;
+
 #[pyclass]
 #[derive(Clone)]
 pub struct WindEnvironment {
@@ -36,34 +38,42 @@ impl WindEnvironment {
         *,
         wind_velocity,
         wind_direction_coming_from,
-        height
+        height,
+        time = 0.0
     ))]
     pub fn true_wind_velocity_at_height(
         &self, 
         wind_velocity: f64, 
         wind_direction_coming_from: f64,
-        height: f64
+        height: f64,
+        time: f64
     ) -> f64 {
         let wind_condition = WindCondition{
             velocity: wind_velocity,
             direction_coming_from: wind_direction_coming_from
         };
         
-        self.data.true_wind_velocity_at_height(wind_condition, height)
+        self.data.true_wind_velocity_at_height(wind_condition, height, time)
     }
     
     #[pyo3(signature=(
         *,
         wind_velocity,
         wind_direction_coming_from,
-        location
+        location,
+        output_frame = None,
+        time = 0.0
     ))]
+    /// Returns the true wind velocity vector at the location. The location is given in the 
+    /// internal global frame, while the output can optionally be given in another frame.
     pub fn true_wind_velocity_vector_at_location(
         &self, 
         wind_velocity: f64, 
         wind_direction_coming_from: f64,
-        location: [f64; 3]
-    ) -> [f64; 3] {
+        location: [f64; 3],
+        output_frame: Option<String>,
+        time: f64
+    ) -> PyResult<[f64; 3]> {
         let wind_condition = WindCondition{
             velocity: wind_velocity,
             direction_coming_from: wind_direction_coming_from
@@ -71,7 +81,13 @@ impl WindEnvironment {
         
         let location_internal = SpatialVector::from(location);
         
-        self.data.true_wind_velocity_vector_at_location(wind_condition, location_internal).0
+        let velocity = self.data.true_wind_velocity_vector_at_location(
+            wind_condition, location_internal, time
+        ).0;
+
+        let frame = OutputFrame::from_option_string(output_frame)?;
+
+        Ok(frame.convert_from_global(velocity, &self.data))
     }
     
     #[pyo3(signature=(
@@ -79,15 +95,21 @@ impl WindEnvironment {
         wind_velocity,
         wind_direction_coming_from,
         location,
-        linear_velocity
+        linear_velocity,
+        output_frame = None,
+        time = 0.0
     ))]
+    /// Returns the apparent wind velocity vector at the location. The input vectors are given in
+    /// the internal global frame, while the output can optionally be given in another frame.
     pub fn apparent_wind_velocity_vector_at_location(
         &self, 
         wind_velocity: f64, 
         wind_direction_coming_from: f64,
         location: [f64; 3],
-        linear_velocity: [f64; 3]
-    ) -> [f64; 3] {
+        linear_velocity: [f64; 3],
+        output_frame: Option<String>,
+        time: f64
+    ) -> PyResult<[f64; 3]> {
         let wind_condition = WindCondition{
             velocity: wind_velocity,
             direction_coming_from: wind_direction_coming_from
@@ -96,26 +118,118 @@ impl WindEnvironment {
         let location_internal = SpatialVector::from(location);
         let linear_velocity_internal = SpatialVector::from(linear_velocity);
         
-        self.data.apparent_wind_velocity_vector_at_location(
+        let velocity = self.data.apparent_wind_velocity_vector_at_location(
             wind_condition, 
             location_internal,
-            linear_velocity_internal
-        ).0
+            linear_velocity_internal,
+            time
+        ).0;
+
+        let frame = OutputFrame::from_option_string(output_frame)?;
+
+        Ok(frame.convert_from_global(velocity, &self.data))
     }
     
     #[pyo3(signature=(
         *,
         wind_velocity,
         wind_direction_coming_from,
+        locations,
+        output_frame = None,
+        time = 0.0
+    ))]
+    /// Batched version of `true_wind_velocity_vector_at_location`, which evaluates all the 
+    /// locations in a single call.
+    pub fn true_wind_velocity_vectors_at_locations(
+        &self, 
+        wind_velocity: f64, 
+        wind_direction_coming_from: f64,
+        locations: Vec<[f64; 3]>,
+        output_frame: Option<String>,
+        time: f64
+    ) -> PyResult<Vec<[f64; 3]>> {
+        let wind_condition = WindCondition{
+            velocity: wind_velocity,
+            direction_coming_from: wind_direction_coming_from
+        };
+
+        let locations_internal: Vec<SpatialVector> = locations.iter().map(
+            |&location| SpatialVector::from(location)
+        ).collect();
+
+        let frame = OutputFrame::from_option_string(output_frame)?;
+
+        Ok(
+            self.data.true_wind_velocity_vectors_at_locations(
+                wind_condition, 
+                &locations_internal,
+                time
+            ).iter().map(
+                |velocity| frame.convert_from_global(velocity.0, &self.data)
+            ).collect()
+        )
+    }
+
+    #[pyo3(signature=(
+        *,
+        wind_velocity,
+        wind_direction_coming_from,
+        locations,
         linear_velocity,
-        height = 10.0
+        output_frame = None,
+        time = 0.0
+    ))]
+    /// Batched version of `apparent_wind_velocity_vector_at_location`, which evaluates all the 
+    /// locations in a single call.
+    pub fn apparent_wind_velocity_vectors_at_locations(
+        &self, 
+        wind_velocity: f64, 
+        wind_direction_coming_from: f64,
+        locations: Vec<[f64; 3]>,
+        linear_velocity: [f64; 3],
+        output_frame: Option<String>,
+        time: f64
+    ) -> PyResult<Vec<[f64; 3]>> {
+        let wind_condition = WindCondition{
+            velocity: wind_velocity,
+            direction_coming_from: wind_direction_coming_from
+        };
+
+        let locations_internal: Vec<SpatialVector> = locations.iter().map(
+            |&location| SpatialVector::from(location)
+        ).collect();
+
+        let linear_velocity_internal = SpatialVector::from(linear_velocity);
+
+        let frame = OutputFrame::from_option_string(output_frame)?;
+
+        Ok(
+            self.data.apparent_wind_velocity_vectors_at_locations(
+                wind_condition, 
+                &locations_internal,
+                linear_velocity_internal,
+                time
+            ).iter().map(
+                |velocity| frame.convert_from_global(velocity.0, &self.data)
+            ).collect()
+        )
+    }
+    
+    #[pyo3(signature=(
+        *,
+        wind_velocity,
+        wind_direction_coming_from,
+        linear_velocity,
+        height = 10.0,
+        time = 0.0
     ))]
     pub fn apparent_wind_direction_from_condition_and_linear_velocity(
         &self,
         wind_velocity: f64, 
         wind_direction_coming_from: f64,
         linear_velocity: [f64; 3],
-        height: f64
+        height: f64,
+        time: f64
     ) -> f64 {
         let wind_condition = WindCondition{
             velocity: wind_velocity,
@@ -127,7 +241,8 @@ impl WindEnvironment {
         self.data.apparent_wind_direction_from_condition_and_linear_velocity(
             wind_condition,
             linear_velocity_internal,
-            height
+            height,
+            time
         )
     }
     
@@ -137,16 +252,23 @@ impl WindEnvironment {
         wind_direction_coming_from,
         ctrl_points,
         linear_velocity,
-        wing_indices
+        wing_indices,
+        output_frame = None,
+        time = 0.0
     ))]
+    /// Returns the apparent wind velocity vectors at the control points, with the inflow 
+    /// corrections applied. The input vectors are given in the internal global frame, while the 
+    /// output can optionally be given in another frame.
     pub fn apparent_wind_velocity_vectors_at_ctrl_points_with_corrections_applied(
         &self,
         wind_velocity: f64, 
         wind_direction_coming_from: f64,
         ctrl_points: Vec<[f64; 3]>,
         linear_velocity: [f64; 3],
-        wing_indices: Vec<[usize; 2]>
-    ) -> Vec<[f64; 3]> {
+        wing_indices: Vec<[usize; 2]>,
+        output_frame: Option<String>,
+        time: f64
+    ) -> PyResult<Vec<[f64; 3]>> {
         let wind_condition = WindCondition{
             velocity: wind_velocity,
             direction_coming_from: wind_direction_coming_from
@@ -175,17 +297,23 @@ impl WindEnvironment {
             wind_condition, 
             &ctrl_points_internal, 
             linear_velocity_internal, 
-            &wing_indices_internal
+            &wing_indices_internal,
+            time
         );
         
+        let frame = OutputFrame::from_option_string(output_frame)?;
+
         let mut velocity_out: Vec<[f64; 3]> = Vec::new();
         
         for i in 0..velocity_internal.len() {
             velocity_out.push(
-                [velocity_internal[i][0], velocity_internal[i][1], velocity_internal[i][2]]
+                frame.convert_from_global(
+                    [velocity_internal[i][0], velocity_internal[i][1], velocity_internal[i][2]],
+                    &self.data
+                )
             )
         }
         
-        velocity_out
+        Ok(velocity_out)
     }
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+// Copyright (C) 2024, NTNU 
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Conversion of vectors from the internal global frame to other reference frames that are 
+//! commonly used in Python code.
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use stormbird::wind::environment::WindEnvironment;
+
+use stormath::spatial_vector::SpatialVector;
+
+/// Reference frames that results from the wind environment can be returned in.
+///
+/// The directions of the geographic frames are taken from the wind environment: a wind coming 
+/// from the zero direction, which is taken as north, flows along the zero direction vector, and the
+/// wind rotation axis points down. With the default settings in the wind environment, the x-axis 
+/// of the internal global frame therefore points south, the y-axis points east, and the z-axis 
+/// points up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFrame {
+    /// The internal global frame, used when no frame is specified
+    Global,
+    /// North-East-Down
+    Ned,
+    /// East-North-Up
+    Enu,
+}
+
+impl OutputFrame {
+    /// Parses the frame from an optional string. Valid values are "global", "ned" and "enu", 
+    /// independent of case. Other values give a `ValueError` in Python.
+    pub fn from_option_string(frame: Option<String>) -> PyResult<Self> {
+        match frame {
+            None => Ok(OutputFrame::Global),
+            Some(frame) => match frame.to_lowercase().as_str() {
+                "global" => Ok(OutputFrame::Global),
+                "ned" => Ok(OutputFrame::Ned),
+                "enu" => Ok(OutputFrame::Enu),
+                _ => Err(PyValueError::new_err(format!(
+                    "Unknown output frame: {}. Valid values are 'global', 'ned' and 'enu'", 
+                    frame
+                ))),
+            }
+        }
+    }
+
+    /// Returns the components of a vector, given in the internal global frame, in this frame, 
+    /// using the axes of the input wind environment.
+    pub fn convert_from_global(
+        &self, 
+        vector: [f64; 3], 
+        environment: &WindEnvironment
+    ) -> [f64; 3] {
+        let global_vector = SpatialVector::from(vector);
+
+        let north = -1.0 * environment.zero_direction_vector.normalize();
+        let down = environment.wind_rotation_axis.normalize();
+        let east = down.cross(north);
+
+        match self {
+            OutputFrame::Global => vector,
+            OutputFrame::Ned => [
+                global_vector.dot(north), 
+                global_vector.dot(east), 
+                global_vector.dot(down)
+            ],
+            OutputFrame::Enu => [
+                global_vector.dot(east), 
+                global_vector.dot(north), 
+                -global_vector.dot(down)
+            ],
+        }
+    }
+}
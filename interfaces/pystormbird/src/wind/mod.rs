@@ -3,6 +3,7 @@
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
 pub mod environment;
+pub mod frame;
 
 use pyo3::prelude::*;
 
@@ -0,0 +1,127 @@
+'''
+Tests of the wind environment in the Python interface. Run with pytest after building the package
+with maturin.
+'''
+
+import json
+import math
+
+import pytest
+
+from pystormbird.wind import WindEnvironment
+
+WIND_VELOCITY = 8.0
+
+def assert_vectors_close(vector_1, vector_2, tolerance=1e-9):
+    for value_1, value_2 in zip(vector_1, vector_2):
+        assert abs(value_1 - value_2) < tolerance, f"{vector_1} != {vector_2}"
+
+def true_wind_vector(environment, direction_coming_from, output_frame=None):
+    return environment.true_wind_velocity_vector_at_location(
+        wind_velocity=WIND_VELOCITY,
+        wind_direction_coming_from=direction_coming_from,
+        location=[0.0, 0.0, 10.0],
+        output_frame=output_frame
+    )
+
+def test_output_frames():
+    '''
+    A wind coming from the north flows towards the south, and a wind coming from the east flows 
+    towards the west, independent of the internal global frame.
+    '''
+    environment = WindEnvironment("{}")
+
+    north_wind = true_wind_vector(environment, 0.0)
+
+    assert_vectors_close(north_wind, [WIND_VELOCITY, 0.0, 0.0])
+    assert_vectors_close(true_wind_vector(environment, 0.0, "global"), north_wind)
+
+    assert_vectors_close(true_wind_vector(environment, 0.0, "ned"), [-WIND_VELOCITY, 0.0, 0.0])
+    assert_vectors_close(true_wind_vector(environment, 0.0, "ENU"), [0.0, -WIND_VELOCITY, 0.0])
+
+    east_wind_direction = math.radians(90.0)
+
+    assert_vectors_close(
+        true_wind_vector(environment, east_wind_direction, "ned"), [0.0, -WIND_VELOCITY, 0.0]
+    )
+    assert_vectors_close(
+        true_wind_vector(environment, east_wind_direction, "enu"), [-WIND_VELOCITY, 0.0, 0.0]
+    )
+
+def test_output_frames_follow_environment_axes():
+    '''
+    The geographic frames are defined by the axes in the wind environment, so that a wind from the 
+    east is still towards the west when the zero direction vector is changed.
+    '''
+    environment = WindEnvironment(json.dumps({
+        "zero_direction_vector": [0.0, 1.0, 0.0]
+    }))
+
+    east_wind_direction = math.radians(90.0)
+
+    assert_vectors_close(
+        true_wind_vector(environment, east_wind_direction, "ned"), [0.0, -WIND_VELOCITY, 0.0]
+    )
+    assert_vectors_close(
+        true_wind_vector(environment, 0.0, "ned"), [-WIND_VELOCITY, 0.0, 0.0]
+    )
+
+def test_unknown_output_frame_raises_value_error():
+    environment = WindEnvironment("{}")
+
+    with pytest.raises(ValueError):
+        true_wind_vector(environment, 0.0, "north-west-up")
+
+def test_batched_queries_match_single_queries():
+    environment = WindEnvironment(json.dumps({
+        "height_variation_model": {
+            "PowerModel": {
+                "reference_height": 10.0,
+                "power_factor": 1.0 / 9.0
+            }
+        }
+    }))
+
+    wind_direction = math.radians(30.0)
+    locations = [[0.0, 0.0, 5.0], [10.0, 0.0, 20.0], [-10.0, 5.0, 40.0]]
+    linear_velocity = [-5.0, 0.0, 0.0]
+
+    true_vectors = environment.true_wind_velocity_vectors_at_locations(
+        wind_velocity=WIND_VELOCITY,
+        wind_direction_coming_from=wind_direction,
+        locations=locations,
+        output_frame="ned"
+    )
+
+    apparent_vectors = environment.apparent_wind_velocity_vectors_at_locations(
+        wind_velocity=WIND_VELOCITY,
+        wind_direction_coming_from=wind_direction,
+        locations=locations,
+        linear_velocity=linear_velocity,
+        output_frame="ned"
+    )
+
+    assert len(true_vectors) == len(locations)
+    assert len(apparent_vectors) == len(locations)
+
+    for location, true_vector, apparent_vector in zip(locations, true_vectors, apparent_vectors):
+        assert_vectors_close(
+            true_vector,
+            environment.true_wind_velocity_vector_at_location(
+                wind_velocity=WIND_VELOCITY,
+                wind_direction_coming_from=wind_direction,
+                location=location,
+                output_frame="ned"
+            )
+        )
+
+        assert_vectors_close(
+            apparent_vector,
+            environment.apparent_wind_velocity_vector_at_location(
+                wind_velocity=WIND_VELOCITY,
+                wind_direction_coming_from=wind_direction,
+                location=location,
+                linear_velocity=linear_velocity,
+                output_frame="ned"
+            )
+        )
@@ -1,9 +1,9 @@
 from .actuator_line_builder import ActuatorLineBuilder
-from .corrections import LiftingLineCorrectionBuilder, EmpiricalCirculationCorrection
-from .settings import Gaussian, ProjectionSettings, SamplingSettings, SolverSettings
+from .corrections import LiftingLineCorrectionBuilder, EmpiricalCirculationCorrection, PrandtlTipLoss
+from .settings import Gaussian, AnisotropicGaussian, ProjectionSettings, SamplingSettings, SolverSettings
 
 __all__ = [
     "ActuatorLineBuilder",
-    "LiftingLineCorrectionBuilder", "EmpiricalCirculationCorrection",
-    "Gaussian", "ProjectionSettings", "SamplingSettings", "SolverSettings"
+    "LiftingLineCorrectionBuilder", "EmpiricalCirculationCorrection", "PrandtlTipLoss",
+    "Gaussian", "AnisotropicGaussian", "ProjectionSettings", "SamplingSettings", "SolverSettings"
 ]
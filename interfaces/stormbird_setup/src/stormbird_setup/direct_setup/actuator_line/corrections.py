@@ -6,7 +6,13 @@ class LiftingLineCorrectionBuilder(StormbirdSetupBaseModel):
     wake_length_factor: float = 100.0
     symmetry_condition: SymmetryCondition = SymmetryCondition.NoSymmetry
     initialization_time: float | None = None
+    wake_length: float | None = None
+    wake_decay_length: float | None = None
+
+class PrandtlTipLoss(StormbirdSetupBaseModel):
+    nr_blades: int
 
 class EmpiricalCirculationCorrection(StormbirdSetupBaseModel):
     exp_factor: float = 10.0
     overall_correction: float = 1.0
+    prandtl_tip_loss: PrandtlTipLoss | None = None
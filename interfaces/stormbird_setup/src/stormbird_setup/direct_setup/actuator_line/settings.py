@@ -10,8 +10,13 @@ class Gaussian(StormbirdSetupBaseModel):
     chord_factor: float = 0.25
     thickness_factor: float = 0.25
 
+class AnisotropicGaussian(StormbirdSetupBaseModel):
+    chord_factor: float = 0.25
+    thickness_factor: float = 0.25
+    span_factor: float = 0.25
+
 class ProjectionSettings(StormbirdSetupBaseModel):
-    projection_function: Gaussian = Gaussian()
+    projection_function: Gaussian | AnisotropicGaussian = Gaussian()
     realign_sectional_forces: bool = True
     realign_to_local_velocity_at_each_cell: bool = False
     project_viscous_lift: bool = False
@@ -5,6 +5,8 @@
 
 use crate::line_force_model::builder::LineForceModelBuilder;
 use crate::controller::builder::ControllerBuilder;
+use crate::io_utils::csv_data::{CsvPrecision, RotatingCsvWriter};
+use crate::error::Error;
 
 use serde::{Serialize, Deserialize};
 
@@ -13,7 +15,7 @@ use stormath::type_aliases::Float;
 
 use super::projection::ProjectionSettings;
 use super::sampling::SamplingSettings;
-use super::solver::SolverSettings;
+use super::solver::{SolverSettings, RelaxationState};
 use super::ActuatorLine;
 
 use super::corrections::{
@@ -37,6 +39,14 @@ pub struct ActuatorLineBuilder {
     pub write_iterations_full_result: usize,
     #[serde(default)]
     pub start_time: Float,
+    /// Optional limit on the number of entries kept in the log files from the model. When the limit
+    /// is reached, CSV files are moved to a backup file with the suffix `.1` and a new file is 
+    /// started, while the oldest full result files are removed. The default is no limit.
+    #[serde(default)]
+    pub max_log_length: Option<usize>,
+    /// Formatting of the floating point values in the CSV log files. The default is six decimals.
+    #[serde(default)]
+    pub csv_precision: CsvPrecision,
     #[serde(default)]
     pub controller: Option<ControllerBuilder>,
     #[serde(default)]
@@ -57,27 +67,32 @@ impl ActuatorLineBuilder {
             controller: None,
             write_iterations_full_result: Self::default_write_iterations_full_result(),
             start_time: 0.0,
+            max_log_length: None,
+            csv_precision: CsvPrecision::default(),
             lifting_line_correction: None,
             empirical_circulation_correction: None,
         }
     }
 
-    /// Constructs a actuator line model from the builder data.
-    pub fn build(&self) -> ActuatorLine {
+    /// Constructs a actuator line model from the builder data. Returns an error if the controller
+    /// setup or the solver settings are not valid.
+    pub fn build(&self) -> Result<ActuatorLine, Error> {
+        self.solver_settings.check()?;
+
         let line_force_model = self.line_force_model.build();
 
         let nr_span_lines = line_force_model.nr_span_lines();
 
         let controller = if let Some(controller_builder) = &self.controller {
-            Some(controller_builder.build())
+            Some(controller_builder.build()?)
         } else {
             None
         };
 
         let lifting_line_correction = if let Some(lifting_line_correction_builder) = &self.lifting_line_correction {
             let viscous_core_length_factor = 0.5 * (
-                self.projection_settings.projection_function.chord_factor +
-                self.projection_settings.projection_function.thickness_factor
+                self.projection_settings.projection_function.chord_factor() +
+                self.projection_settings.projection_function.thickness_factor()
             );
 
             Some(
@@ -87,21 +102,25 @@ impl ActuatorLineBuilder {
             None
         };
 
-        ActuatorLine{
+        Ok(ActuatorLine{
             line_force_model,
             projection_settings: self.projection_settings.clone(),
             solver_settings: self.solver_settings.clone(),
+            relaxation_state: RelaxationState::default(),
             sampling_settings: self.sampling_settings.clone(),
             controller,
             start_time: self.start_time,
             current_iteration: 0,
             write_iterations_full_result: self.write_iterations_full_result,
+            max_log_length: self.max_log_length,
+            csv_precision: self.csv_precision,
+            csv_writer: RotatingCsvWriter::new(self.max_log_length),
             ctrl_points_velocity: vec![SpatialVector::default(); nr_span_lines],
             simulation_result: None,
             sectional_lift_forces_to_project: vec![SpatialVector::default(); nr_span_lines],
             sectional_drag_forces_to_project: vec![SpatialVector::default(); nr_span_lines],
             lifting_line_correction,
             empirical_circulation_correction: self.empirical_circulation_correction.clone(),
-        }
+        })
     }
 }
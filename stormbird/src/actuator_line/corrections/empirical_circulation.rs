@@ -7,12 +7,51 @@ use serde::{Deserialize, Serialize};
 use stormath::type_aliases::Float;
 use stormath::consts::PI;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Parameters for Prandtl's tip loss function. The non-dimensional span position, `s`, from the
+/// line force model is interpreted as a radial position `r / R = 2 |s|`, so that the middle of
+/// the wing corresponds to the hub and the ends to the tips. For wings where one end has non-zero
+/// circulation, the span positions in the line force model are already mapped so that this end
+/// corresponds to the hub.
+///
+/// The inflow angle in the tip loss function is the local inflow angle at each control point, 
+/// given as input when the correction is computed. For a rotor, this is the angle between the 
+/// local velocity and the rotor plane. Sails do not have an equivalent plane, and the actuator line
+/// therefore uses the local angle of attack as the inflow angle. This is an empirical choice, and
+/// not derived from the theory behind the tip loss function. It means that the loss increases with
+/// the loading of the sail, and the correction should be validated for each use.
+pub struct PrandtlTipLoss {
+    /// The number of blades, or sails, in the Prandtl model
+    pub nr_blades: usize,
+}
+
+impl PrandtlTipLoss {
+    /// The exponent in the tip loss function: `f = (B / 2) (R - r) / (r sin(phi))`, where `phi` is
+    /// the local inflow angle in radians.
+    pub fn exp_value(&self, non_dim_span_position: Float, inflow_angle: Float) -> Float {
+        let relative_radius = 2.0 * non_dim_span_position.abs();
+
+        let denominator = relative_radius * inflow_angle.sin().abs();
+
+        if denominator <= 0.0 {
+            return Float::INFINITY;
+        }
+
+        0.5 * self.nr_blades as Float * (1.0 - relative_radius).max(0.0) / denominator
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmpiricalCirculationCorrection {
     #[serde(default = "EmpiricalCirculationCorrection::default_exp_factor")]
     pub exp_factor: Float,
     #[serde(default = "EmpiricalCirculationCorrection::default_one")]
     pub overall_correction: Float,
+    #[serde(default)]
+    /// Optional Prandtl tip loss model. If present, it is used instead of the `exp_factor` to
+    /// compute the exponent in the correction function.
+    pub prandtl_tip_loss: Option<PrandtlTipLoss>,
 }
 
 impl Default for EmpiricalCirculationCorrection {
@@ -20,6 +59,7 @@ impl Default for EmpiricalCirculationCorrection {
         Self {
             exp_factor: Self::default_exp_factor(),
             overall_correction: Self::default_one(),
+            prandtl_tip_loss: None,
         }
     }
 }
@@ -28,11 +68,19 @@ impl EmpiricalCirculationCorrection {
     fn default_one() -> Float {1.0}
     fn default_exp_factor() -> Float {10.0}
 
-    pub fn correction_factor(&self, non_dim_span_position: Float) -> Float {
-        let s_in = 0.5 - non_dim_span_position.abs();
+    /// Returns the correction factor for the circulation at the given non-dimensional span 
+    /// position. The local inflow angle is only used by the Prandtl tip loss model.
+    pub fn correction_factor(&self, non_dim_span_position: Float, inflow_angle: Float) -> Float {
+        let f = if let Some(prandtl_tip_loss) = &self.prandtl_tip_loss {
+            prandtl_tip_loss.exp_value(non_dim_span_position, inflow_angle)
+        } else {
+            let s_in = 0.5 - non_dim_span_position.abs();
+
+            self.exp_factor * s_in
+        };
 
-        let exp_value = (-self.exp_factor * s_in).exp();
+        let exp_value = (-f).exp();
     
         self.overall_correction * (2.0 / PI) * exp_value.acos()
     }
-}
\ No newline at end of file
+}
@@ -4,8 +4,11 @@
 
 use crate::lifting_line::wake::frozen_wake::FrozenWake;
 use crate::lifting_line::singularity_elements::symmetry_condition::SymmetryCondition;
+use crate::lifting_line::singularity_elements::panel::Panel;
+use crate::lifting_line::singularity_elements::PotentialTheorySettings;
 
 use crate::line_force_model::LineForceModel;
+use crate::line_force_model::span_line::SpanLine;
 
 use stormath::spatial_vector::SpatialVector;
 use stormath::type_aliases::Float;
@@ -13,6 +16,9 @@ use stormath::special_functions::cosine_transition_zero_to_one;
 
 use serde::{Serialize, Deserialize};
 
+use std::collections::VecDeque;
+use std::ops::Range;
+
 /// A builder for the [LiftingLineCorrection] struct. See the documentation for
 /// [LiftingLineCorrection] for more information on how it works.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,7 +29,16 @@ pub struct LiftingLineCorrectionBuilder {
     #[serde(default)]
     pub symmetry_condition: SymmetryCondition,
     #[serde(default)]
-    pub initialization_time: Option<Float>
+    pub initialization_time: Option<Float>,
+    /// Optional length of the stored history of shed circulation, in chord lengths. This is the
+    /// same as the number of convective time units the history is kept for. If not set, the
+    /// correction only uses the current circulation strength.
+    #[serde(default)]
+    pub wake_length: Option<Float>,
+    /// Optional length, in chord lengths, over which the unsteady part of the stored wake decays
+    /// exponentially as it is convected downstream.
+    #[serde(default)]
+    pub wake_decay_length: Option<Float>,
 }
 
 impl LiftingLineCorrectionBuilder {
@@ -45,10 +60,26 @@ impl LiftingLineCorrectionBuilder {
             wake_length_factor: self.wake_length_factor,
             symmetry_condition: self.symmetry_condition,
             initialization_time: self.initialization_time,
+            average_chord_length,
+            wake_length: self.wake_length,
+            wake_decay_length: self.wake_decay_length,
+            wake_history: VecDeque::new(),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+/// Circulation strength shed from the line force model at a previous time step, together with how
+/// far it has been convected downstream of each wing since then.
+pub struct ShedCirculation {
+    /// The circulation strength on all span lines when the entry was stored
+    pub circulation_strength: Vec<Float>,
+    /// The distance the entry has been convected downstream, with one value per wing
+    pub convected_distances: Vec<Float>,
+    /// Number of time steps averaged into the entry
+    pub nr_time_steps: usize,
+}
+
 #[derive(Debug, Clone)]
 /// A structure used to compute corrections for the velocity, based on a lifting line model. The
 /// model computes two versions of induced velocities using a lifting line representation; one where
@@ -57,11 +88,25 @@ impl LiftingLineCorrectionBuilder {
 /// is sampled directly from the flow field. This is to account for the fact that realistic force
 /// projection widths tend to smear out the tip vortices, leading to under predicted induced
 /// velocities
+///
+/// By default, the wake is assumed to be steady with the current circulation strength. If a wake
+/// length is given, a rolling history of the shed circulation is stored as well. The part of the
+/// wake shed while a stored strength was active is then represented as vortex rings with the
+/// difference between the stored and the current strength, so that the correction reflects recent
+/// unsteadiness in the inflow.
 pub struct LiftingLineCorrection {
     pub viscous_core_length: Float,
     pub wake_length_factor: Float,
     pub symmetry_condition: SymmetryCondition,
     pub initialization_time: Option<Float>,
+    /// Average chord length of the line force model, used to scale the wake history lengths
+    pub average_chord_length: Float,
+    /// Length of the wake history in chord lengths. No history is stored if not set.
+    pub wake_length: Option<Float>,
+    /// Decay length of the unsteady part of the wake history in chord lengths
+    pub wake_decay_length: Option<Float>,
+    /// The stored history of shed circulation, with the most recent entry first
+    pub wake_history: VecDeque<ShedCirculation>,
 }
 
 impl LiftingLineCorrection {
@@ -91,8 +136,6 @@ impl LiftingLineCorrection {
                 wind_indices.clone()
             ];
 
-            let nr_span_lines = wing_span_lines.len();
-
             let wing_ctrl_points_velocity = &ctrl_points_velocity[
                 wind_indices.clone()
             ];
@@ -124,22 +167,218 @@ impl LiftingLineCorrection {
                 &wing_circulation_strength
             );
 
-            for i in 0..nr_span_lines {
-                u_i_correction.push(
-                    frozen_wake_default.induced_velocities_at_control_points[i] -
-                    frozen_wake_viscous.induced_velocities_at_control_points[i]
-                );
+            let wake_history_correction = self.wake_history_velocity_correction(
+                wing_index,
+                wing_span_lines,
+                wind_indices.clone(),
+                averaged_ctrl_points_velocity.normalize(),
+                wing_circulation_strength
+            );
+
+            let induced_velocities = frozen_wake_default.induced_velocities_at_control_points.iter()
+                .zip(frozen_wake_viscous.induced_velocities_at_control_points.iter())
+                .zip(wake_history_correction.iter());
+
+            for ((u_i_default, u_i_viscous), u_i_history) in induced_velocities {
+                u_i_correction.push(*u_i_default - *u_i_viscous + *u_i_history);
             }
         }
         
         if let Some(initialization_time) = self.initialization_time {
             let correction_factor = cosine_transition_zero_to_one(time, 0.0, initialization_time);
             
-            for i in 0..u_i_correction.len() {
-                u_i_correction[i] *= correction_factor;
+            for u_i in u_i_correction.iter_mut() {
+                *u_i *= correction_factor;
             }
         }
 
         u_i_correction
     }
+
+    /// Moves the stored wake history downstream with the average sampled velocity over each wing,
+    /// and removes the entries that are further downstream than the wake length. Should be called
+    /// once at the start of each time step, before the circulation strength is solved for.
+    pub fn advect_wake_history(
+        &mut self,
+        line_force_model: &LineForceModel,
+        ctrl_points_velocity: &[SpatialVector],
+        time_step: Float,
+    ) {
+        let max_distance = match self.wake_length {
+            Some(wake_length) => wake_length * self.average_chord_length,
+            None => return,
+        };
+
+        for wing_index in 0..line_force_model.nr_wings() {
+            let wing_ctrl_points_velocity = &ctrl_points_velocity[
+                line_force_model.wing_indices[wing_index].clone()
+            ];
+
+            let averaged_velocity = wing_ctrl_points_velocity.iter().sum::<SpatialVector>()
+                / wing_ctrl_points_velocity.len() as Float;
+
+            let convected_distance = averaged_velocity.length() * time_step;
+
+            for shed_circulation in self.wake_history.iter_mut() {
+                shed_circulation.convected_distances[wing_index] += convected_distance;
+            }
+        }
+
+        while let Some(oldest) = self.wake_history.back() {
+            if oldest.convected_distances.iter().all(|distance| *distance >= max_distance) {
+                self.wake_history.pop_back();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Stores the circulation strength from the current time step in the wake history. To limit
+    /// the cost of the correction, there is at most one entry per convective chord length: if the 
+    /// most recent entry has not yet been convected one average chord length downstream, the 
+    /// circulation strength is averaged into that entry instead of being stored as a new one. Does
+    /// nothing if no wake length is given.
+    pub fn store_shed_circulation(
+        &mut self,
+        line_force_model: &LineForceModel,
+        circulation_strength: &[Float],
+    ) {
+        if self.wake_length.is_none() {
+            return;
+        }
+
+        if let Some(latest) = self.wake_history.front_mut() {
+            let within_one_chord = latest.convected_distances.iter().all(
+                |distance| *distance < self.average_chord_length
+            );
+
+            if within_one_chord {
+                let weight = 1.0 / (latest.nr_time_steps + 1) as Float;
+
+                for (stored, current) in latest.circulation_strength.iter_mut()
+                    .zip(circulation_strength.iter())
+                {
+                    *stored += weight * (current - *stored);
+                }
+
+                latest.nr_time_steps += 1;
+
+                return;
+            }
+        }
+
+        self.wake_history.push_front(
+            ShedCirculation {
+                circulation_strength: circulation_strength.to_vec(),
+                convected_distances: vec![0.0; line_force_model.nr_wings()],
+                nr_time_steps: 1,
+            }
+        );
+    }
+
+    /// Computes the contribution from the stored wake history to the velocity correction for a
+    /// single wing. Each entry in the history covers the part of the wake from the end of the more
+    /// recent entry to its own convected distance. As the steady wake already assumes the current
+    /// circulation strength everywhere, each part is modelled as vortex rings with the difference
+    /// between the stored and the current strength. The same difference between a small and a
+    /// large viscous core as for the steady wake is then used as the correction.
+    fn wake_history_velocity_correction(
+        &self,
+        wing_index: usize,
+        wing_span_lines: &[SpanLine],
+        wing_indices: Range<usize>,
+        wake_direction: SpatialVector,
+        wing_circulation_strength: &[Float],
+    ) -> Vec<SpatialVector> {
+        let nr_span_lines = wing_span_lines.len();
+
+        let mut u_i_correction = vec![SpatialVector::default(); nr_span_lines];
+
+        let max_distance = match self.wake_length {
+            Some(wake_length) => wake_length * self.average_chord_length,
+            None => return u_i_correction,
+        };
+
+        let far_field_ratio = PotentialTheorySettings::default_far_field_ratio();
+
+        let ctrl_points: Vec<SpatialVector> = wing_span_lines.iter().map(
+            |span_line| span_line.ctrl_point()
+        ).collect();
+
+        let mut start_distance = 0.0;
+
+        for shed_circulation in self.wake_history.iter() {
+            let end_distance = shed_circulation.convected_distances[wing_index].min(max_distance);
+
+            if end_distance <= start_distance {
+                continue;
+            }
+
+            let decay_factor = if let Some(wake_decay_length) = self.wake_decay_length {
+                let mid_distance = 0.5 * (start_distance + end_distance);
+
+                (-mid_distance / (wake_decay_length * self.average_chord_length)).exp()
+            } else {
+                1.0
+            };
+
+            let start_offset = wake_direction * start_distance;
+            let end_offset = wake_direction * end_distance;
+
+            for col_index in 0..nr_span_lines {
+                let strength_difference = decay_factor * (
+                    shed_circulation.circulation_strength[wing_indices.start + col_index] -
+                    wing_circulation_strength[col_index]
+                );
+
+                if strength_difference == 0.0 {
+                    continue;
+                }
+
+                let span_line = &wing_span_lines[col_index];
+
+                let ring_points = [
+                    span_line.start_point + start_offset,
+                    span_line.end_point + start_offset,
+                    span_line.end_point + end_offset,
+                    span_line.start_point + end_offset,
+                ];
+
+                let ring_viscous = Panel::new(
+                    ring_points, far_field_ratio, self.viscous_core_length
+                );
+
+                let ring_default = Panel::new(
+                    ring_points, far_field_ratio, self.viscous_core_length / 100.0
+                );
+
+                for row_index in 0..nr_span_lines {
+                    let u_i_difference = self.induced_velocity_from_ring(
+                        &ring_default, ctrl_points[row_index]
+                    ) - self.induced_velocity_from_ring(
+                        &ring_viscous, ctrl_points[row_index]
+                    );
+
+                    u_i_correction[row_index] += strength_difference * u_i_difference;
+                }
+            }
+
+            start_distance = end_distance;
+        }
+
+        u_i_correction
+    }
+
+    /// Induced velocity from a vortex ring with unit strength, with the symmetry condition applied
+    fn induced_velocity_from_ring(&self, ring: &Panel, ctrl_point: SpatialVector) -> SpatialVector {
+        let u_i = ring.induced_velocity_with_unit_strength(ctrl_point);
+
+        if let Some(point_mirrored) = self.symmetry_condition.mirrored_point(ctrl_point) {
+            let u_i_mirrored = ring.induced_velocity_with_unit_strength(point_mirrored);
+
+            self.symmetry_condition.corrected_velocity(u_i, u_i_mirrored)
+        } else {
+            u_i
+        }
+    }
 }
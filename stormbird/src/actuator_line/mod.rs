@@ -13,6 +13,9 @@ pub mod builder;
 pub mod solver;
 pub mod corrections;
 
+#[cfg(test)]
+mod tests;
+
 use stormath::smoothing::gaussian::gaussian_kernel;
 
 use stormath::spatial_vector::SpatialVector;
@@ -20,15 +23,18 @@ use stormath::type_aliases::Float;
 use crate::line_force_model::LineForceModel;
 
 use crate::common_utils::prelude::*;
+use crate::error::Error;
 use crate::controller::prelude::*;
 use crate::wind::environment::WindEnvironment;
 
 use crate::io_utils;
+use crate::io_utils::json_data;
+use crate::io_utils::csv_data::{CsvPrecision, RotatingCsvWriter};
 
 use projection::ProjectionSettings;
-use sampling::SamplingSettings;
+use sampling::{SamplingSettings, SpanSmoothingReference};
 use builder::ActuatorLineBuilder;
-use solver::SolverSettings;
+use solver::{SolverSettings, RelaxationState};
 
 use corrections::{
     lifting_line::LiftingLineCorrection,
@@ -46,6 +52,8 @@ pub struct ActuatorLine {
     pub projection_settings: ProjectionSettings,
     /// Settings for the solver
     pub solver_settings: SolverSettings,
+    /// Values from the previous circulation strength update, used by the adaptive relaxation
+    pub relaxation_state: RelaxationState,
     /// Settings for the velocity sampling
     pub sampling_settings: SamplingSettings,
     /// Dynamic optimizer that can be optionally used to optimize the settings in the model
@@ -56,6 +64,14 @@ pub struct ActuatorLine {
     pub current_iteration: usize,
     /// The number of iterations between each time a full simulation result is written to file
     pub write_iterations_full_result: usize,
+    /// Optional limit on the number of entries kept in the log files. For the CSV files, this is 
+    /// the number of data lines in each file before it is rotated. For the full results, this is 
+    /// the number of result files.
+    pub max_log_length: Option<usize>,
+    /// Formatting of the floating point values in the CSV log files
+    pub csv_precision: CsvPrecision,
+    /// Writer for the CSV log files, which rotates the files when `max_log_length` is reached
+    pub csv_writer: RotatingCsvWriter,
     /// Vector to store interpolated velocity values for each control point
     pub ctrl_points_velocity: Vec<SpatialVector>,
     /// Results from the model
@@ -72,20 +88,55 @@ pub struct ActuatorLine {
 
 impl ActuatorLine {
     /// Loads a file containing settings for an actuator line builder, and constructs a new model
-    /// based on this data.
-    pub fn new_from_file<P: AsRef<Path>>(file_path: P) -> Self {
-        let read_file_result = fs::read_to_string(file_path).unwrap();
+    /// based on this data. The path of the file is included in the error if the file can't be
+    /// read.
+    pub fn new_from_file<P: AsRef<Path>>(file_path: P) -> Result<Self, Error> {
+        let file_path = file_path.as_ref();
+
+        let read_file_result = fs::read_to_string(file_path).map_err(|error| {
+            Error::IoError(
+                std::io::Error::new(
+                    error.kind(),
+                    format!("could not read '{}': {}", file_path.display(), error)
+                )
+            )
+        })?;
 
         Self::new_from_string(&read_file_result)
     }
 
-    /// Constructs a new model from a string containing settings for an actuator line builder.
-    pub fn new_from_string(builder_string: &str) -> Self {
-        let builder: ActuatorLineBuilder = serde_json::from_str(builder_string).unwrap();
+    /// Constructs a new model from a string containing settings for an actuator line builder. If
+    /// the string is not valid, the error contains the line and column of the problem.
+    pub fn new_from_string(builder_string: &str) -> Result<Self, Error> {
+        let builder: ActuatorLineBuilder = json_data::from_json_string(builder_string)?;
 
         builder.build()
     }
 
+    /// Same as [`ActuatorLine::new_from_file`], but panics with the error message if the model
+    /// can't be constructed. Kept for interfaces that can't handle the error.
+    pub fn new_from_file_or_panic<P: AsRef<Path>>(file_path: P) -> Self {
+        match Self::new_from_file(file_path) {
+            Ok(model) => model,
+            Err(error) => panic!("Could not construct actuator line model: {}", error),
+        }
+    }
+
+    /// Returns the width of the Gaussian projection in the span direction that is used when 
+    /// sampling the velocity for the strip with the given index.
+    pub fn span_smoothing_length(&self, line_index: usize) -> Float {
+        let reference_length = match self.sampling_settings.span_smoothing_reference {
+            SpanSmoothingReference::SpanLineLength => {
+                self.line_force_model.span_lines_global[line_index].length()
+            },
+            SpanSmoothingReference::LocalChord => {
+                self.line_force_model.chord_vectors_global[line_index].length()
+            },
+        };
+
+        self.sampling_settings.span_projection_factor * reference_length
+    }
+
     /// Function used to query the actuator line model for the weighted velocity integral term for
     /// a given cell. This is used when estimating the velocity at control points using the integral
     /// method.
@@ -109,8 +160,7 @@ impl ActuatorLine {
             let span_projection = if self.sampling_settings.neglect_span_projection {
                 1.0
             } else {
-                let span_smoothing_length = self.sampling_settings.span_projection_factor * 
-                    span_line.length();
+                let span_smoothing_length = self.span_smoothing_length(line_index);
 
                 gaussian_kernel(
                     line_coordinates.span,
@@ -134,11 +184,28 @@ impl ActuatorLine {
     /// Function to be executed at each time step in the CFD simulation.
     ///
     /// It solves for the circulation strength and computes the simulation result based on the
-    /// current estimate of the control point velocities.
+    /// current estimate of the control point velocities. If the lifting line correction stores a
+    /// wake history, the history is advected before solving, and the new circulation strength is
+    /// added to it afterwards.
     pub fn do_step(&mut self, time: Float, time_step: Float){
         if time >= self.start_time {
+            if let Some(lifting_line_correction) = &mut self.lifting_line_correction {
+                lifting_line_correction.advect_wake_history(
+                    &self.line_force_model,
+                    &self.ctrl_points_velocity,
+                    time_step
+                );
+            }
+
             let solver_result = self.solve(time, time_step);
 
+            if let Some(lifting_line_correction) = &mut self.lifting_line_correction {
+                lifting_line_correction.store_shed_circulation(
+                    &self.line_force_model,
+                    &solver_result.circulation_strength
+                );
+            }
+
             let ctrl_point_acceleration = vec![
                 SpatialVector::default();
                 self.line_force_model.nr_span_lines()
@@ -174,7 +241,9 @@ impl ActuatorLine {
                     &simulation_result,
                     &controller.flow_measurement_settings,
                     &wind_environment,
-                    false
+                    false,
+                    ControllerInput::default_thrust_direction(),
+                    0.0
                 );
 
                 controller.update(time, time_step, &input)
@@ -189,7 +258,12 @@ impl ActuatorLine {
 
                 need_update = true;
 
-                ControllerOutput::write_to_csv_file(&controller_output, "controller_output.csv");
+                ControllerOutput::write_to_csv_file(
+                    &controller_output, 
+                    "controller_output.csv",
+                    &mut self.csv_writer,
+                    self.csv_precision
+                );
             }
 
             need_update
@@ -198,9 +272,48 @@ impl ActuatorLine {
         }
     }
 
+    /// Returns the sum of the integrated forces and moments on all wings from the last simulation
+    /// result, if a result exists.
+    pub fn integrated_force_and_moment(&self) -> Option<(SpatialVector, SpatialVector)> {
+        self.simulation_result.as_ref().map(
+            |result| (result.integrated_forces_sum(), result.integrated_moments_sum())
+        )
+    }
+
+    /// Returns the sum of the integrated moments on all wings from the last simulation result, 
+    /// using the input point as reference point, if a result exists.
+    pub fn integrated_moment_about_point(&self, point: SpatialVector) -> Option<SpatialVector> {
+        self.simulation_result.as_ref().map(
+            |result| result.integrated_moment_about_point(point)
+        )
+    }
+
+    /// Returns the circulation strength from the last simulation result, or zero if no result
+    /// exists.
+    pub fn previous_circulation_strength(&self) -> Vec<Float> {
+        if let Some(result) = &self.simulation_result {
+            result.force_input.circulation_strength.clone()
+        } else {
+            vec![0.0; self.line_force_model.nr_span_lines()]
+        }
+    }
+
     /// Computes a corrected velocity at the control points, based on the sampling settings, and,
     /// if present, the lifting line correction.
     pub fn corrected_ctrl_points_velocity(&self, time: Float) -> Vec<SpatialVector> {
+        self.corrected_ctrl_points_velocity_with_circulation_strength(
+            time,
+            &self.previous_circulation_strength()
+        )
+    }
+
+    /// Same as [`ActuatorLine::corrected_ctrl_points_velocity`], but with the circulation strength
+    /// used in the lifting line correction given as input.
+    pub fn corrected_ctrl_points_velocity_with_circulation_strength(
+        &self,
+        time: Float,
+        circulation_strength: &[Float]
+    ) -> Vec<SpatialVector> {
         let mut corrected_velocity = if self.sampling_settings.remove_span_velocity {
             self.line_force_model.remove_span_velocity(
                 &self.ctrl_points_velocity,
@@ -211,16 +324,10 @@ impl ActuatorLine {
         };
 
         if let Some(lifting_line_correction) = &self.lifting_line_correction {
-            let last_circulation_strength = if let Some(result) = &self.simulation_result {
-                result.force_input.circulation_strength.clone()
-            } else {
-                vec![0.0; self.line_force_model.nr_span_lines()]
-            };
-
             let ll_velocity_correction = lifting_line_correction.velocity_correction(
                 &self.line_force_model,
                 &self.ctrl_points_velocity,
-                &last_circulation_strength,
+                circulation_strength,
                 time - self.start_time
             );
 
@@ -264,74 +371,131 @@ impl ActuatorLine {
 
     /// Takes the estimated velocity on at the control points as input and calculates a simulation
     /// result from the line force model.
+    ///
+    /// The circulation strength is updated with a damped fixed-point iteration, starting from the
+    /// strength in the last simulation result. The reported residual is the average absolute
+    /// residual from the line force model, as for the lifting line solvers. The iterations continue
+    /// until either this residual or the average absolute change in the estimated strength is
+    /// below the tolerance in the solver settings, or the maximum number of iterations is reached.
+    /// The second criterion is needed when the empirical circulation correction is applied, as the
+    /// line force model residual does not include the correction.
+    ///
+    /// With a maximum of one iteration, a single damped update is applied, and the convergence is
+    /// left to the outer CFD loop. A single pass is therefore never reported as converged.
     pub fn solve(&mut self, time: Float, _time_step: Float) -> SolverResult {
-        let corrected_ctrl_points_velocity = self.corrected_ctrl_points_velocity(time);
+        let max_iterations = self.solver_settings.max_iterations.max(1);
 
-        let angles_of_attack = self.line_force_model.angles_of_attack(
-            &corrected_ctrl_points_velocity,
-            CoordinateSystem::Global
-        );
+        let mut circulation_strength = self.previous_circulation_strength();
 
-        let mut new_estimated_circulation_strength = self.line_force_model.circulation_strength(
-            &angles_of_attack,
-            &corrected_ctrl_points_velocity
-        );
+        let mut corrected_ctrl_points_velocity = self.ctrl_points_velocity.clone();
+        let mut residual = Float::INFINITY;
+        let mut iterations = 0;
+        let mut converged = false;
 
-        if let Some(empirical_circulation_correction) = &self.empirical_circulation_correction {
-            let non_dim_span_positions = &self.line_force_model.ctrl_point_spanwise_distance_circulation_model;
+        while iterations < max_iterations {
+            corrected_ctrl_points_velocity = self.corrected_ctrl_points_velocity_with_circulation_strength(
+                time,
+                &circulation_strength
+            );
 
-            for i in 0..new_estimated_circulation_strength.len() {
-                new_estimated_circulation_strength[i] *= empirical_circulation_correction.correction_factor(
-                    non_dim_span_positions[i]
-                );
-            }
-        }
+            let angles_of_attack = self.line_force_model.angles_of_attack(
+                &corrected_ctrl_points_velocity,
+                CoordinateSystem::Global
+            );
 
-        let previous_strength = if let Some(simulation_result) = &self.simulation_result {
-            simulation_result.force_input.circulation_strength.clone()
-        } else {
-            vec![0.0; self.line_force_model.nr_span_lines()]
-        };
+            let new_estimated_circulation_strength = self.estimated_circulation_strength(
+                &angles_of_attack,
+                &corrected_ctrl_points_velocity
+            );
 
-        let mut circulation_strength = Vec::with_capacity(new_estimated_circulation_strength.len());
-        for i in 0..new_estimated_circulation_strength.len() {
-            let strength_difference = new_estimated_circulation_strength[i] - previous_strength[i];
+            let strength_difference: Vec<Float> = (0..circulation_strength.len()).map(
+                |i| new_estimated_circulation_strength[i] - circulation_strength[i]
+            ).collect();
 
-            circulation_strength.push(
-                previous_strength[i] + self.solver_settings.damping_factor * strength_difference
+            let damping_factor = self.relaxation_state.damping_factor(
+                &self.solver_settings,
+                &strength_difference
             );
-        }
 
-        let residual = self.line_force_model.average_residual_absolute(
-            &angles_of_attack,
-            &circulation_strength,
-            &corrected_ctrl_points_velocity
-        );
+            for i in 0..circulation_strength.len() {
+                circulation_strength[i] += damping_factor * strength_difference[i];
+            }
+
+            residual = self.line_force_model.average_residual_absolute(
+                &circulation_strength,
+                &angles_of_attack,
+                &corrected_ctrl_points_velocity
+            );
+
+            let average_strength_difference = strength_difference.iter()
+                .map(|difference| difference.abs())
+                .sum::<Float>() / strength_difference.len() as Float;
+
+            iterations += 1;
+
+            let tolerance = self.solver_settings.tolerance;
+
+            if residual < tolerance || average_strength_difference < tolerance {
+                converged = max_iterations > 1;
+
+                break;
+            }
+        }
 
         SolverResult {
             input_ctrl_points_velocity: self.ctrl_points_velocity.clone(),
             circulation_strength,
             output_ctrl_points_velocity: corrected_ctrl_points_velocity,
-            iterations: 1,
+            iterations,
             residual,
+            converged,
         }
     }
 
+    /// Estimates the circulation strength from the line force model, with the empirical
+    /// circulation correction applied if present.
+    fn estimated_circulation_strength(
+        &self,
+        angles_of_attack: &[Float],
+        ctrl_points_velocity: &[SpatialVector]
+    ) -> Vec<Float> {
+        let mut circulation_strength = self.line_force_model.circulation_strength(
+            angles_of_attack,
+            ctrl_points_velocity
+        );
+
+        if let Some(empirical_circulation_correction) = &self.empirical_circulation_correction {
+            let non_dim_span_positions = &self.line_force_model.ctrl_point_spanwise_distance_circulation_model;
+
+            // The angle of attack is used as the inflow angle, see `PrandtlTipLoss`
+            for i in 0..circulation_strength.len() {
+                circulation_strength[i] *= empirical_circulation_correction.correction_factor(
+                    non_dim_span_positions[i],
+                    angles_of_attack[i]
+                );
+            }
+        }
+
+        circulation_strength
+    }
+
     /// Writes the resulting values from the line force model to a file.
-    pub fn write_results(&self, folder_path: &str) {
+    pub fn write_results(&mut self, folder_path: &str) {
         if let Some(simulation_result) = &self.simulation_result {
             let overall_folder_path = Path::new(folder_path);
 
             io_utils::folder_management::ensure_folder_exists(overall_folder_path).unwrap();
 
-            let (header, data) = simulation_result.as_reduced_flatten_csv_string();
+            let (header, data) = simulation_result.as_reduced_flatten_csv_string(
+                self.csv_precision
+            );
 
             let force_file_path = format!("{}/stormbird_forces.csv", folder_path);
 
-            let _ = io_utils::csv_data::create_or_append_header_and_data_strings_file(
+            let _ = self.csv_writer.write(
                 &force_file_path,
                 &header,
-                &data
+                &data,
             );
 
             if self.current_iteration % self.write_iterations_full_result == 0 {
@@ -348,6 +512,23 @@ impl ActuatorLine {
                     ).as_str(),
                     &json_string
                 ).unwrap();
+
+                if let Some(max_log_length) = self.max_log_length {
+                    let nr_iterations_to_keep = max_log_length * self.write_iterations_full_result;
+
+                    if self.current_iteration >= nr_iterations_to_keep {
+                        let old_file_path = result_folder_path.join(
+                            format!(
+                                "full_results_{}.json",
+                                self.current_iteration - nr_iterations_to_keep
+                            )
+                        );
+
+                        if old_file_path.exists() {
+                            let _ = fs::remove_file(old_file_path);
+                        }
+                    }
+                }
             }
         }
     }
@@ -445,6 +626,64 @@ impl ActuatorLine {
         projection_values
     }
 
+    /// Computes the projection weights for each line element in a set of cells, rescaled so that
+    /// the discrete volume integral of the weights for each line element is exactly one. The output
+    /// is indexed as `[line_index][cell_index]`.
+    ///
+    /// The continuous projection functions integrate to one over an unbounded domain. On a coarse
+    /// mesh, or when the kernel is truncated by a wall or the domain boundary, the discrete integral
+    /// deviates from one, so that the total projected force differs from the sectional force. The
+    /// normalized weights correct for this, and give exact force conservation for each line
+    /// element.
+    ///
+    /// The weights only depend on the geometry of the line force model and the mesh. A CFD coupler
+    /// should therefore call this method once for the cells close to the line elements, for
+    /// instance the cells where the summed projection weights are above the weight limit in the
+    /// projection settings, and store the result. The body force in cell `j` is then the sum over
+    /// the line elements `i` of `weights[i][j] * force_to_project_at_cell(i, velocity)`. The weights
+    /// must be recomputed if the mesh or the line geometry changes, for instance when the local
+    /// wing angles are updated by the controller.
+    ///
+    /// Line elements with a zero integral over the given cells are left with zero weights.
+    pub fn normalized_projection_weights(
+        &self,
+        cell_centers: &[SpatialVector],
+        cell_volumes: &[Float]
+    ) -> Vec<Vec<Float>> {
+        assert_eq!(
+            cell_centers.len(),
+            cell_volumes.len(),
+            "The number of cell centers and cell volumes must be equal"
+        );
+
+        let nr_span_lines = self.line_force_model.nr_span_lines();
+
+        let mut weights = vec![Vec::with_capacity(cell_centers.len()); nr_span_lines];
+
+        for cell_center in cell_centers {
+            let cell_weights = self.line_segments_projection_weights_at_point(*cell_center);
+
+            for line_index in 0..nr_span_lines {
+                weights[line_index].push(cell_weights[line_index]);
+            }
+        }
+
+        for line_weights in weights.iter_mut() {
+            let integral: Float = line_weights.iter()
+                .zip(cell_volumes.iter())
+                .map(|(weight, volume)| weight * volume)
+                .sum();
+
+            if integral > 0.0 {
+                for weight in line_weights.iter_mut() {
+                    *weight /= integral;
+                }
+            }
+        }
+
+        weights
+    }
+
     /// Computes the sum of the projection weights for all line elements at a given point in space.
     pub fn summed_projection_weights_at_point(&self, point: SpatialVector) -> Float {
         self.line_segments_projection_weights_at_point(point).iter().sum()
@@ -453,18 +692,39 @@ impl ActuatorLine {
     /// Checks which line element is dominating at a given point in space by comparing the
     /// projection weights of each line element.
     pub fn dominating_line_element_index_at_point(&self, point: SpatialVector) -> usize {
-        let projection_weights = self.line_segments_projection_weights_at_point(point);
-
-        let mut max_weight = -1.0;
-        let mut max_index = 0;
+        self.dominating_line_elements_at_points(&[point])[0].0
+    }
 
-        for (i, weight) in projection_weights.iter().enumerate() {
-            if *weight > max_weight {
-                max_weight = *weight;
-                max_index = i;
+    /// Batched version of [ActuatorLine::dominating_line_element_index_at_point]. Returns the index
+    /// of the dominating line element at each point, together with the fraction of the summed 
+    /// projection weights that belongs to this element. The fraction is zero for points where all
+    /// weights are zero.
+    pub fn dominating_line_elements_at_points(
+        &self, 
+        points: &[SpatialVector]
+    ) -> Vec<(usize, Float)> {
+        points.iter().map(|point| {
+            let projection_weights = self.line_segments_projection_weights_at_point(*point);
+
+            let mut max_weight = -1.0;
+            let mut max_index = 0;
+
+            for (i, weight) in projection_weights.iter().enumerate() {
+                if *weight > max_weight {
+                    max_weight = *weight;
+                    max_index = i;
+                }
             }
-        }
 
-        max_index
+            let weight_sum: Float = projection_weights.iter().sum();
+
+            let weight_fraction = if weight_sum > 0.0 {
+                max_weight / weight_sum
+            } else {
+                0.0
+            };
+
+            (max_index, weight_fraction)
+        }).collect()
     }
 }
@@ -13,6 +13,10 @@ use crate::line_force_model::span_line::SpanLine;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+/// Planar Gaussian kernel. The force is distributed with a Gaussian in the plane normal to the
+/// span, with independent widths in the chord and thickness directions given as multiples of the
+/// chord length. In the span direction, the distribution is constant over the length of the line
+/// segment and zero outside it, so there is no spanwise spreading beyond each segment.
 pub struct Gaussian {
     pub chord_factor: Float,
     pub thickness_factor: Float,
@@ -60,3 +64,80 @@ impl Gaussian {
         (1.0 / const_denominator ) * exp_factor.exp() * span_factor
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Anisotropic Gaussian kernel, with independent widths in the chord, thickness and span
+/// directions. The chord and thickness widths are multiples of the local chord length, as for the
+/// [`Gaussian`] kernel. In the span direction, the constant distribution over the line segment is
+/// smoothed with a Gaussian with a width equal to the span factor times the length of the line
+/// segment. This avoids the discontinuity at the ends of each line segment. With a span factor of
+/// zero, the kernel is identical to the [`Gaussian`] kernel with the same chord and thickness
+/// factors.
+pub struct AnisotropicGaussian {
+    pub chord_factor: Float,
+    pub thickness_factor: Float,
+    pub span_factor: Float,
+}
+
+impl AnisotropicGaussian {
+    pub fn projection_value_at_point(
+        &self,
+        point: SpatialVector,
+        chord_vector: SpatialVector,
+        span_line: &SpanLine
+    ) -> Float {
+        let chord_length = chord_vector.length();
+        let line_length = span_line.length();
+
+        let point_lc = span_line.line_coordinates(point, chord_vector);
+
+        let e_chord     = self.chord_factor * chord_length;
+        let e_thickness = self.thickness_factor * chord_length;
+        let e_span      = self.span_factor * line_length;
+
+        let chord_term     = (point_lc.chord / e_chord).powi(2);
+        let thickness_term = (point_lc.thickness / e_thickness).powi(2);
+
+        let exp_factor = -chord_term - thickness_term;
+
+        let const_denominator = e_chord * e_thickness * PI;
+
+        let span_factor = if e_span > 0.0 {
+            let upper = (point_lc.span + 0.5 * line_length) / e_span;
+            let lower = (point_lc.span - 0.5 * line_length) / e_span;
+
+            0.5 * (erf(upper) - erf(lower)) / line_length
+        } else {
+            let relative_span = point_lc.span / line_length;
+
+            if relative_span > -0.5 && relative_span < 0.5 {
+                1.0 / line_length
+            } else {
+                0.0
+            }
+        };
+
+        (1.0 / const_denominator ) * exp_factor.exp() * span_factor
+    }
+}
+
+/// Approximation of the error function, from Abramowitz and Stegun, equation 7.1.26. The maximum
+/// absolute error is 1.5e-7.
+fn erf(x: Float) -> Float {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+
+    let polynomial = t * (
+        0.254829592 + t * (
+            -0.284496736 + t * (
+                1.421413741 + t * (
+                    -1.453152027 + t * 1.061405429
+                )
+            )
+        )
+    );
+
+    let value = 1.0 - polynomial * (-x * x).exp();
+
+    if x >= 0.0 { value } else { -value }
+}
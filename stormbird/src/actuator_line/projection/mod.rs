@@ -10,13 +10,67 @@ use crate::line_force_model::span_line::SpanLine;
 
 pub mod gaussian;
 
-use gaussian::Gaussian;
+use gaussian::{Gaussian, AnisotropicGaussian};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+/// The kernel used to project the forces to the CFD domain. The variants are distinguished by
+/// their fields, so that setup files written for the plain [`Gaussian`] kernel are still valid.
+pub enum ProjectionFunction {
+    Gaussian(Gaussian),
+    AnisotropicGaussian(AnisotropicGaussian),
+    /// The planar [`Gaussian`] kernel selected by name, as `{"PlanarGaussian": {...}}`
+    PlanarGaussian {
+        #[serde(rename = "PlanarGaussian")]
+        kernel: Gaussian
+    },
+}
+
+impl Default for ProjectionFunction {
+    fn default() -> Self {
+        Self::Gaussian(Gaussian::default())
+    }
+}
+
+impl ProjectionFunction {
+    /// The width of the kernel in the chord direction, as a multiple of the chord length
+    pub fn chord_factor(&self) -> Float {
+        match self {
+            Self::Gaussian(kernel) | Self::PlanarGaussian { kernel } => kernel.chord_factor,
+            Self::AnisotropicGaussian(kernel) => kernel.chord_factor,
+        }
+    }
+
+    /// The width of the kernel in the thickness direction, as a multiple of the chord length
+    pub fn thickness_factor(&self) -> Float {
+        match self {
+            Self::Gaussian(kernel) | Self::PlanarGaussian { kernel } => kernel.thickness_factor,
+            Self::AnisotropicGaussian(kernel) => kernel.thickness_factor,
+        }
+    }
+
+    pub fn projection_value_at_point(
+        &self,
+        point: SpatialVector,
+        chord_vector: SpatialVector,
+        span_line: &SpanLine
+    ) -> Float {
+        match self {
+            Self::Gaussian(kernel) | Self::PlanarGaussian { kernel } => {
+                kernel.projection_value_at_point(point, chord_vector, span_line)
+            },
+            Self::AnisotropicGaussian(kernel) => kernel.projection_value_at_point(
+                point, chord_vector, span_line
+            ),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ProjectionSettings {
     #[serde(default)]
-    pub projection_function: Gaussian,
+    pub projection_function: ProjectionFunction,
     #[serde(default="ProjectionSettings::default_true")]
     pub realign_sectional_forces: bool,
     #[serde(default)]
@@ -33,7 +87,7 @@ impl Default for ProjectionSettings {
     
     fn default() -> Self {
         Self {
-            projection_function: Gaussian::default(),
+            projection_function: ProjectionFunction::default(),
             realign_sectional_forces: true,
             realign_to_local_velocity_at_each_cell: false,
             weight_limit: Self::default_weight_limit(),
@@ -7,6 +7,19 @@ use serde::{Serialize, Deserialize};
 
 use stormath::type_aliases::Float;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// The length used as reference when computing the width of the Gaussian projection in the span
+/// direction during velocity sampling.
+pub enum SpanSmoothingReference {
+    #[default]
+    /// The smoothing length is proportional to the length of the span line of each strip
+    SpanLineLength,
+    /// The smoothing length is proportional to the local chord length of each strip. This gives a
+    /// narrower projection where the chord is small, for instance towards the tip of a tapered
+    /// wing.
+    LocalChord,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct SamplingSettings {
@@ -15,6 +28,8 @@ pub struct SamplingSettings {
     #[serde(default="SamplingSettings::default_span_projection_factor")]
     pub span_projection_factor: Float,
     #[serde(default)]
+    pub span_smoothing_reference: SpanSmoothingReference,
+    #[serde(default)]
     pub neglect_span_projection: bool,
     #[serde(default="SamplingSettings::default_weight_limit")]
     pub weight_limit: Float,
@@ -37,6 +52,7 @@ impl Default for SamplingSettings {
         Self {
             use_point_sampling: false,
             span_projection_factor: Self::default_span_projection_factor(),
+            span_smoothing_reference: SpanSmoothingReference::default(),
             neglect_span_projection: false,
             weight_limit: Self::default_weight_limit(),
             extrapolate_end_velocities: false,
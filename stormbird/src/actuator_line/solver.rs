@@ -7,21 +7,149 @@ use serde::{Serialize, Deserialize};
 
 use stormath::type_aliases::Float;
 
+use crate::error::Error;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// How the damping of the circulation strength update is determined
+pub enum Relaxation {
+    #[default]
+    /// Constant damping factor, given by the `damping_factor` in the solver settings
+    Fixed,
+    /// Aitken's delta-squared method, where the damping factor is updated based on the change in
+    /// the strength difference between the last two updates. The `damping_factor` in the solver
+    /// settings is used as the initial value.
+    Aitken,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct SolverSettings {
     #[serde(default="SolverSettings::default_damping_factor")]
     pub damping_factor: Float,
+    #[serde(default="SolverSettings::default_max_iterations")]
+    /// Maximum number of iterations of the circulation strength for each call to the solver. The
+    /// default of one gives a single damped update per time step, which is the typical choice when
+    /// the outer CFD loop is responsible for the convergence. The solver result is then never
+    /// reported as converged.
+    pub max_iterations: usize,
+    #[serde(default="SolverSettings::default_tolerance")]
+    /// The iterations stop when the average absolute residual, or the average absolute change in
+    /// the estimated circulation strength, is below this value
+    pub tolerance: Float,
+    #[serde(default)]
+    /// Method used to determine the damping factor
+    pub relaxation: Relaxation,
+    #[serde(default="SolverSettings::default_min_damping_factor")]
+    /// Lower limit on the damping factor when the relaxation is adaptive
+    pub min_damping_factor: Float,
+    #[serde(default="SolverSettings::default_max_damping_factor")]
+    /// Upper limit on the damping factor when the relaxation is adaptive
+    pub max_damping_factor: Float,
 }
 
 impl Default for SolverSettings {
     fn default() -> Self {
         Self {
             damping_factor: Self::default_damping_factor(),
+            max_iterations: Self::default_max_iterations(),
+            tolerance: Self::default_tolerance(),
+            relaxation: Relaxation::default(),
+            min_damping_factor: Self::default_min_damping_factor(),
+            max_damping_factor: Self::default_max_damping_factor(),
         }
     }
 }
 
 impl SolverSettings {
     fn default_damping_factor() -> Float {0.1}
+    fn default_max_iterations() -> usize {1}
+    fn default_tolerance() -> Float {1e-4}
+    fn default_min_damping_factor() -> Float {0.01}
+    fn default_max_damping_factor() -> Float {1.0}
+
+    /// Checks that the limits on the adaptive damping factor are consistent
+    pub fn check(&self) -> Result<(), Error> {
+        if self.min_damping_factor > self.max_damping_factor {
+            return Err(Error::CustomStringError(format!(
+                "The min_damping_factor ({}) must not be larger than the max_damping_factor ({})",
+                self.min_damping_factor,
+                self.max_damping_factor
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Values from the previous update of the circulation strength, used by the adaptive relaxation.
+/// The state is kept between calls to the solver, so that the damping also adapts when only a
+/// single update is done per time step.
+pub struct RelaxationState {
+    /// The damping factor used in the last update
+    pub damping_factor: Option<Float>,
+    /// The difference between the estimated and the current circulation strength in the last
+    /// update
+    pub previous_strength_difference: Option<Vec<Float>>,
+}
+
+impl RelaxationState {
+    /// Returns the damping factor to use for an update with the given strength difference, and
+    /// stores the values needed for the next update.
+    pub fn damping_factor(
+        &mut self,
+        settings: &SolverSettings,
+        strength_difference: &[Float]
+    ) -> Float {
+        match settings.relaxation {
+            Relaxation::Fixed => settings.damping_factor,
+            Relaxation::Aitken => {
+                let previous_factor = self.damping_factor.unwrap_or(settings.damping_factor);
+
+                let factor = match &self.previous_strength_difference {
+                    Some(previous) if previous.len() == strength_difference.len() => {
+                        Self::aitken_factor(previous_factor, previous, strength_difference)
+                    },
+                    _ => previous_factor,
+                }.clamp(settings.min_damping_factor, settings.max_damping_factor);
+
+                self.damping_factor = Some(factor);
+                self.previous_strength_difference = Some(strength_difference.to_vec());
+
+                factor
+            }
+        }
+    }
+
+    /// Aitken's delta-squared update of the damping factor. The previous factor is returned if the
+    /// strength difference has stalled, to avoid division by zero.
+    fn aitken_factor(
+        previous_factor: Float,
+        previous_difference: &[Float],
+        difference: &[Float]
+    ) -> Float {
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        let mut previous_length_squared = 0.0;
+
+        for i in 0..difference.len() {
+            let change = difference[i] - previous_difference[i];
+
+            numerator += previous_difference[i] * change;
+            denominator += change * change;
+            previous_length_squared += previous_difference[i] * previous_difference[i];
+        }
+
+        if denominator <= Float::EPSILON * previous_length_squared {
+            return previous_factor;
+        }
+
+        let factor = -previous_factor * numerator / denominator;
+
+        if factor.is_finite() {
+            factor
+        } else {
+            previous_factor
+        }
+    }
 }
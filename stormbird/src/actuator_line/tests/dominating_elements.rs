@@ -0,0 +1,39 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+
+use stormath::spatial_vector::SpatialVector;
+
+use super::test_setup::StraightWing;
+
+#[test]
+/// Tests that the batched search for the dominating line element returns the line closest to 
+/// each point, with a weight fraction between zero and one.
+fn dominating_line_elements_at_points() {
+    let line_force_model_builder = StraightWing::default().build();
+
+    let actuator_line = ActuatorLineBuilder::new(line_force_model_builder).build().unwrap();
+
+    let line_indices = [0, 4, 9];
+
+    let points: Vec<SpatialVector> = line_indices.iter().map(
+        |&index| actuator_line.line_force_model.ctrl_points_global[index] + 
+            SpatialVector::from([0.05, 0.02, 0.0])
+    ).collect();
+
+    let dominating_elements = actuator_line.dominating_line_elements_at_points(&points);
+
+    assert_eq!(dominating_elements.len(), points.len());
+
+    for (i, (index, weight_fraction)) in dominating_elements.iter().enumerate() {
+        assert_eq!(*index, line_indices[i]);
+        assert_eq!(*index, actuator_line.dominating_line_element_index_at_point(points[i]));
+
+        assert!(
+            *weight_fraction > 0.0 && *weight_fraction <= 1.0,
+            "Weight fraction out of range: {}", weight_fraction
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+use crate::common_utils::prelude::*;
+
+use stormath::spatial_vector::SpatialVector;
+
+use super::test_setup::StraightWing;
+
+#[test]
+/// Tests that the moment about the point where a single force is applied is zero.
+fn moment_about_force_application_point() {
+    let line_force_model_builder = StraightWing {
+        start_point: SpatialVector::from([2.0, 1.0, 3.0]),
+        ..Default::default()
+    }.build();
+
+    let mut actuator_line = ActuatorLineBuilder::new(line_force_model_builder).build().unwrap();
+
+    assert!(actuator_line.integrated_moment_about_point(SpatialVector::default()).is_none());
+
+    let nr_span_lines = actuator_line.line_force_model.nr_span_lines();
+    let force_index = 3;
+
+    let mut total = vec![SpatialVector::default(); nr_span_lines];
+    total[force_index] = SpatialVector::from([10.0, -25.0, 3.0]);
+
+    let sectional_forces = SectionalForces {
+        circulatory: total.clone(),
+        viscous_lift: vec![SpatialVector::default(); nr_span_lines],
+        sectional_drag: vec![SpatialVector::default(); nr_span_lines],
+        added_mass: vec![SpatialVector::default(); nr_span_lines],
+        gyroscopic: vec![SpatialVector::default(); nr_span_lines],
+        total,
+        coordinate_system: CoordinateSystem::Global,
+    };
+
+    let line_force_model = &actuator_line.line_force_model;
+
+    actuator_line.simulation_result = Some(
+        SimulationResult {
+            ctrl_points: line_force_model.ctrl_points_global.clone(),
+            integrated_forces: sectional_forces.integrate_forces(line_force_model),
+            integrated_moments: sectional_forces.integrate_moments(line_force_model),
+            sectional_forces,
+            wing_indices: line_force_model.wing_indices.clone(),
+            ..Default::default()
+        }
+    );
+
+    let force_point = actuator_line.line_force_model.ctrl_points_global[force_index];
+
+    let (_, moment_origin) = actuator_line.integrated_force_and_moment().unwrap();
+    let moment_force_point = actuator_line.integrated_moment_about_point(force_point).unwrap();
+
+    dbg!(moment_origin, moment_force_point);
+
+    assert!(moment_origin.length() > 1.0);
+    assert!(moment_force_point.length() < 1e-9);
+}
@@ -0,0 +1,16 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the actuator line functionality.
+
+pub mod test_setup;
+
+mod integrated_values;
+mod span_smoothing;
+mod dominating_elements;
+mod solver_convergence;
+mod projection_kernels;
+mod projection_normalization;
+mod tip_loss;
+mod wake_history;
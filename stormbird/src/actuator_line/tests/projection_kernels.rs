@@ -0,0 +1,182 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::projection::{ProjectionFunction, ProjectionSettings};
+use crate::actuator_line::projection::gaussian::{Gaussian, AnisotropicGaussian};
+use crate::actuator_line::ActuatorLine;
+use crate::line_force_model::span_line::SpanLine;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+use super::test_setup::StraightWing;
+
+fn test_span_line() -> SpanLine {
+    SpanLine {
+        start_point: SpatialVector::from([0.0, 0.0, 0.0]),
+        end_point: SpatialVector::from([0.0, 0.0, 2.0]),
+    }
+}
+
+#[test]
+/// Tests that setup strings without a span factor give the plain Gaussian kernel, while strings
+/// with a span factor give the anisotropic kernel.
+fn projection_function_from_json() {
+    let gaussian_settings: ProjectionSettings = serde_json::from_str(
+        r#"{"projection_function": {"chord_factor": 0.3, "thickness_factor": 0.1}}"#
+    ).unwrap();
+
+    let anisotropic_settings: ProjectionSettings = serde_json::from_str(
+        r#"{
+            "projection_function": {"chord_factor": 0.3, "thickness_factor": 0.1, "span_factor": 0.2}
+        }"#
+    ).unwrap();
+
+    assert!(matches!(gaussian_settings.projection_function, ProjectionFunction::Gaussian(_)));
+    assert!(
+        matches!(
+            anisotropic_settings.projection_function,
+            ProjectionFunction::AnisotropicGaussian(_)
+        )
+    );
+
+    assert_eq!(anisotropic_settings.projection_function.thickness_factor(), 0.1);
+}
+
+#[test]
+/// Tests that the anisotropic kernel with a span factor of zero is identical to the Gaussian kernel
+fn anisotropic_kernel_reduces_to_gaussian() {
+    let span_line = test_span_line();
+    let chord_vector = SpatialVector::from([1.5, 0.0, 0.0]);
+
+    let gaussian = Gaussian {
+        chord_factor: 0.3,
+        thickness_factor: 0.1,
+    };
+
+    let anisotropic = AnisotropicGaussian {
+        chord_factor: 0.3,
+        thickness_factor: 0.1,
+        span_factor: 0.0,
+    };
+
+    let points = [
+        SpatialVector::from([0.1, 0.05, 1.0]),
+        SpatialVector::from([-0.3, 0.0, 0.2]),
+        SpatialVector::from([0.0, -0.1, 1.9]),
+        SpatialVector::from([0.0, 0.0, 2.5]),
+    ];
+
+    for point in points {
+        let gaussian_value = gaussian.projection_value_at_point(point, chord_vector, &span_line);
+        let anisotropic_value = anisotropic.projection_value_at_point(
+            point, chord_vector, &span_line
+        );
+
+        assert!((gaussian_value - anisotropic_value).abs() <= 1e-12 * gaussian_value.abs().max(1.0));
+    }
+}
+
+#[test]
+/// Tests that the spanwise smoothing of the anisotropic kernel spreads the force past the ends of
+/// the line segment, while keeping the integral along the span equal to the Gaussian kernel, which
+/// is the value of the two-dimensional kernel in the chord and thickness plane.
+fn anisotropic_kernel_span_integral() {
+    let span_line = test_span_line();
+    let chord_vector = SpatialVector::from([1.0, 0.0, 0.0]);
+
+    let gaussian = Gaussian {
+        chord_factor: 0.25,
+        thickness_factor: 0.25,
+    };
+
+    let anisotropic = AnisotropicGaussian {
+        chord_factor: 0.25,
+        thickness_factor: 0.25,
+        span_factor: 0.2,
+    };
+
+    let nr_points = 4000;
+    let span_start = -2.0;
+    let span_end = 4.0;
+    let delta_span = (span_end - span_start) / nr_points as Float;
+
+    let e_chord = gaussian.chord_factor * chord_vector.length();
+    let e_thickness = gaussian.thickness_factor * chord_vector.length();
+
+    let exact_integral = 1.0 / (PI * e_chord * e_thickness);
+
+    let mut anisotropic_integral = 0.0;
+
+    for i in 0..nr_points {
+        let span = span_start + (i as Float + 0.5) * delta_span;
+        let point = SpatialVector::from([0.0, 0.0, span]);
+
+        anisotropic_integral += anisotropic.projection_value_at_point(
+            point, chord_vector, &span_line
+        ) * delta_span;
+    }
+
+    let outside_point = SpatialVector::from([0.0, 0.0, 2.1]);
+
+    assert_eq!(gaussian.projection_value_at_point(outside_point, chord_vector, &span_line), 0.0);
+    assert!(anisotropic.projection_value_at_point(outside_point, chord_vector, &span_line) > 0.0);
+
+    assert!((anisotropic_integral - exact_integral).abs() < 1e-5 * exact_integral);
+}
+
+#[test]
+/// Tests that the planar Gaussian kernel, selected by name through the actuator line builder JSON,
+/// is constant along the span of each line segment and zero outside it.
+fn planar_gaussian_from_builder_json() {
+    let line_force_model_builder = StraightWing {
+        span: 4.0,
+        nr_sections: 4,
+        ..Default::default()
+    }.build();
+
+    let builder_string = format!(
+        r#"{{
+            "line_force_model": {},
+            "projection_settings": {{
+                "projection_function": {{
+                    "PlanarGaussian": {{"chord_factor": 0.25, "thickness_factor": 0.1}}
+                }}
+            }}
+        }}"#,
+        serde_json::to_string(&line_force_model_builder).unwrap()
+    );
+
+    let actuator_line = ActuatorLine::new_from_string(&builder_string).unwrap();
+
+    assert!(
+        matches!(
+            actuator_line.projection_settings.projection_function,
+            ProjectionFunction::PlanarGaussian { .. }
+        )
+    );
+
+    assert_eq!(actuator_line.projection_settings.projection_function.thickness_factor(), 0.1);
+
+    let span_line = actuator_line.line_force_model.span_lines_global[0];
+    let start_span = span_line.start_point[2].min(span_line.end_point[2]);
+    let end_span = span_line.start_point[2].max(span_line.end_point[2]);
+    let line_length = end_span - start_span;
+
+    let weight_at_span = |span: Float| {
+        actuator_line.line_segments_projection_weights_at_point(
+            SpatialVector::from([0.05, 0.01, span])
+        )[0]
+    };
+
+    let weight_near_start = weight_at_span(start_span + 0.1 * line_length);
+    let weight_near_end = weight_at_span(end_span - 0.1 * line_length);
+
+    assert!(weight_near_start > 0.0);
+    assert!((weight_near_start - weight_near_end).abs() < 1e-12 * weight_near_start);
+
+    assert_eq!(weight_at_span(end_span + 0.1 * line_length), 0.0);
+    assert_eq!(weight_at_span(start_span - 0.1 * line_length), 0.0);
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+use super::test_setup::StraightWing;
+
+#[test]
+/// Tests that the normalized projection weights integrate to one for each line element on a coarse
+/// mesh where the kernel is truncated on one side, as would happen close to a wall.
+fn normalized_weights_integrate_to_one() {
+    let line_force_model_builder = StraightWing {
+        span: 4.0,
+        nr_sections: 4,
+        ..Default::default()
+    }.build();
+
+    let actuator_line = ActuatorLineBuilder::new(line_force_model_builder).build().unwrap();
+
+    // Coarse mesh that only covers the positive thickness side of the wing
+    let cell_size = 0.2;
+    let cell_volume = cell_size * cell_size * cell_size;
+
+    let mut cell_centers = Vec::new();
+
+    for i in 0..20 {
+        for j in 0..10 {
+            for k in 0..20 {
+                cell_centers.push(
+                    SpatialVector::from([
+                        -2.0 + (i as Float + 0.5) * cell_size,
+                        (j as Float + 0.5) * cell_size,
+                        (k as Float + 0.5) * cell_size,
+                    ])
+                );
+            }
+        }
+    }
+
+    let cell_volumes = vec![cell_volume; cell_centers.len()];
+
+    let raw_integral: Float = cell_centers.iter().map(
+        |center| actuator_line.line_segments_projection_weights_at_point(*center)[0] * cell_volume
+    ).sum();
+
+    let normalized_weights = actuator_line.normalized_projection_weights(
+        &cell_centers,
+        &cell_volumes
+    );
+
+    dbg!(raw_integral);
+
+    assert!((raw_integral - 1.0).abs() > 0.1);
+
+    assert_eq!(normalized_weights.len(), actuator_line.line_force_model.nr_span_lines());
+
+    for line_weights in &normalized_weights {
+        let integral: Float = line_weights.iter().zip(cell_volumes.iter()).map(
+            |(weight, volume)| weight * volume
+        ).sum();
+
+        assert!((integral - 1.0).abs() < 1e-12);
+    }
+}
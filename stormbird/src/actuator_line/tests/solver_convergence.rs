@@ -0,0 +1,178 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+use crate::actuator_line::ActuatorLine;
+use crate::actuator_line::solver::{Relaxation, RelaxationState, SolverSettings};
+use crate::actuator_line::corrections::empirical_circulation::EmpiricalCirculationCorrection;
+use crate::line_force_model::prelude::*;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+use super::test_setup::StraightWing;
+
+fn simple_wing_actuator_line(solver_settings: SolverSettings) -> ActuatorLine {
+    let line_force_model_builder = StraightWing::default().build();
+
+    let mut builder = ActuatorLineBuilder::new(line_force_model_builder);
+
+    builder.solver_settings = solver_settings;
+
+    let mut actuator_line = builder.build().unwrap();
+
+    let nr_span_lines = actuator_line.line_force_model.nr_span_lines();
+
+    actuator_line.ctrl_points_velocity = vec![SpatialVector::from([10.0, 1.0, 0.0]); nr_span_lines];
+
+    actuator_line
+}
+
+#[test]
+/// Tests that the solver iterates until the residual is below the tolerance, and that a maximum
+/// of one iteration gives a single damped update, as used when coupled to a CFD solver.
+fn solver_iterates_to_tolerance() {
+    let tolerance = 1e-6;
+
+    let mut single_pass = simple_wing_actuator_line(
+        SolverSettings {
+            max_iterations: 1,
+            tolerance,
+            ..Default::default()
+        }
+    );
+    let single_pass_result = single_pass.solve(0.0, 0.1);
+
+    let mut iterated = simple_wing_actuator_line(
+        SolverSettings {
+            max_iterations: 1000,
+            tolerance,
+            ..Default::default()
+        }
+    );
+    let iterated_result = iterated.solve(0.0, 0.1);
+
+    dbg!(single_pass_result.residual, iterated_result.residual, iterated_result.iterations);
+
+    assert_eq!(single_pass_result.iterations, 1);
+    assert!(single_pass_result.residual > tolerance);
+    assert!(!single_pass_result.converged);
+
+    assert!(iterated_result.iterations > 1);
+    assert!(iterated_result.iterations < 1000);
+    assert!(iterated_result.residual < tolerance);
+    assert!(iterated_result.converged);
+
+    let max_circulation = iterated_result.circulation_strength.iter()
+        .fold(0.0, |max: Float, value| max.max(value.abs()));
+
+    assert!(max_circulation > 0.1);
+}
+
+#[test]
+/// Tests that the solver also converges when the empirical circulation correction is applied. The
+/// reported residual is from the line force model without the correction, so the convergence is
+/// detected from the change in the estimated circulation strength instead.
+fn solver_iterates_to_tolerance_with_empirical_correction() {
+    let tolerance = 1e-6;
+
+    let mut actuator_line = simple_wing_actuator_line(
+        SolverSettings {
+            max_iterations: 1000,
+            tolerance,
+            ..Default::default()
+        }
+    );
+
+    actuator_line.empirical_circulation_correction = Some(EmpiricalCirculationCorrection::default());
+
+    let result = actuator_line.solve(0.0, 0.1);
+
+    dbg!(result.residual, result.iterations);
+
+    assert!(result.iterations < 1000);
+    assert!(result.converged);
+    assert!(result.residual > tolerance);
+}
+
+#[test]
+/// Tests that the Aitken relaxation converges in fewer iterations than the fixed damping factor.
+fn aitken_relaxation_converges_faster() {
+    let tolerance = 1e-6;
+
+    let mut fixed = simple_wing_actuator_line(
+        SolverSettings {
+            max_iterations: 1000,
+            tolerance,
+            ..Default::default()
+        }
+    );
+
+    let mut aitken = simple_wing_actuator_line(
+        SolverSettings {
+            max_iterations: 1000,
+            tolerance,
+            relaxation: Relaxation::Aitken,
+            ..Default::default()
+        }
+    );
+
+    let fixed_result = fixed.solve(0.0, 0.1);
+    let aitken_result = aitken.solve(0.0, 0.1);
+
+    dbg!(fixed_result.iterations, aitken_result.iterations);
+
+    assert!(aitken_result.residual < tolerance);
+    assert!(aitken_result.iterations < fixed_result.iterations);
+
+    for i in 0..fixed_result.circulation_strength.len() {
+        let difference = aitken_result.circulation_strength[i] - fixed_result.circulation_strength[i];
+
+        assert!(difference.abs() < 1e-3);
+    }
+}
+
+#[test]
+/// Tests that the adaptive damping factor falls back to the previous value when the strength
+/// difference does not change, and to the fixed value when the adaptation is disabled.
+fn relaxation_with_stalled_strength_difference() {
+    let settings = SolverSettings {
+        relaxation: Relaxation::Aitken,
+        damping_factor: 0.3,
+        ..Default::default()
+    };
+
+    let mut state = RelaxationState::default();
+
+    let strength_difference = vec![0.5, -0.2, 0.1];
+
+    let first_factor = state.damping_factor(&settings, &strength_difference);
+    let second_factor = state.damping_factor(&settings, &strength_difference);
+
+    assert_eq!(first_factor, 0.3);
+    assert_eq!(second_factor, 0.3);
+
+    let fixed_settings = SolverSettings {
+        damping_factor: 0.2,
+        ..Default::default()
+    };
+
+    assert_eq!(state.damping_factor(&fixed_settings, &[1.0, 2.0, 3.0]), 0.2);
+}
+
+#[test]
+/// Tests that the builder rejects damping factor limits where the lower limit is above the upper
+/// limit, as the adaptive relaxation would otherwise panic when clamping the factor.
+fn inconsistent_damping_factor_limits_are_rejected() {
+    let mut builder = ActuatorLineBuilder::new(LineForceModelBuilder::new(10));
+
+    builder.solver_settings = SolverSettings {
+        relaxation: Relaxation::Aitken,
+        min_damping_factor: 0.5,
+        max_damping_factor: 0.2,
+        ..Default::default()
+    };
+
+    assert!(builder.build().is_err());
+}
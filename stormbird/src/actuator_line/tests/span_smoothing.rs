@@ -0,0 +1,79 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+use crate::actuator_line::sampling::SpanSmoothingReference;
+
+use stormath::spatial_vector::SpatialVector;
+
+use super::test_setup::StraightWing;
+
+fn tapered_wing_builder() -> ActuatorLineBuilder {
+    let line_force_model_builder = StraightWing {
+        chord_at_start: 2.0,
+        chord_at_end: 0.5,
+        ..Default::default()
+    }.build();
+
+    ActuatorLineBuilder::new(line_force_model_builder)
+}
+
+#[test]
+/// Tests that the span projection width follows the local chord when requested, and that the 
+/// default behavior still uses the span line length.
+fn span_smoothing_length_follows_local_chord() {
+    let default_model = tapered_wing_builder().build().unwrap();
+
+    let mut builder = tapered_wing_builder();
+    builder.sampling_settings.span_smoothing_reference = SpanSmoothingReference::LocalChord;
+
+    let chord_model = builder.build().unwrap();
+
+    let nr_span_lines = chord_model.line_force_model.nr_span_lines();
+
+    let default_lengths: Vec<_> = (0..nr_span_lines).map(
+        |i| default_model.span_smoothing_length(i)
+    ).collect();
+
+    let chord_lengths: Vec<_> = (0..nr_span_lines).map(
+        |i| chord_model.span_smoothing_length(i)
+    ).collect();
+
+    dbg!(&default_lengths, &chord_lengths);
+
+    for i in 0..nr_span_lines {
+        let span_line_length = default_model.line_force_model.span_lines_global[i].length();
+        let chord_length = chord_model.line_force_model.chord_vectors_global[i].length();
+
+        let factor = default_model.sampling_settings.span_projection_factor;
+
+        assert!((default_lengths[i] - factor * span_line_length).abs() < 1e-9);
+        assert!((chord_lengths[i] - factor * chord_length).abs() < 1e-9);
+    }
+
+    for i in 1..nr_span_lines {
+        assert!(chord_lengths[i] < chord_lengths[i - 1]);
+    }
+
+    // A cell slightly above the control point of the tip strip should get a smaller weight when 
+    // the projection is narrowed by the small tip chord.
+    let tip_index = nr_span_lines - 1;
+    let cell_center = chord_model.line_force_model.ctrl_points_global[tip_index] + 
+        SpatialVector::from([0.0, 0.0, 0.2]);
+
+    let velocity = SpatialVector::from([1.0, 0.0, 0.0]);
+
+    let (_, default_weight) = default_model.get_weighted_velocity_sampling_integral_terms_for_cell(
+        tip_index, velocity, cell_center, 1.0
+    );
+
+    let (_, chord_weight) = chord_model.get_weighted_velocity_sampling_integral_terms_for_cell(
+        tip_index, velocity, cell_center, 1.0
+    );
+
+    dbg!(default_weight, chord_weight);
+
+    assert!(chord_weight > 0.0);
+    assert!(chord_weight < default_weight);
+}
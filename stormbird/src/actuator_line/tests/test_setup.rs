@@ -0,0 +1,56 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::line_force_model::prelude::*;
+use crate::section_models::{SectionModel, foil::Foil};
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone)]
+/// Struct for setting up line force models for a single straight wing along the z-axis, with the
+/// chord along the x-axis and a foil section model
+pub struct StraightWing {
+    pub start_point: SpatialVector,
+    pub span: Float,
+    pub chord_at_start: Float,
+    pub chord_at_end: Float,
+    pub nr_sections: usize,
+}
+
+impl Default for StraightWing {
+    fn default() -> Self {
+        Self {
+            start_point: SpatialVector::from([0.0, 0.0, 0.0]),
+            span: 10.0,
+            chord_at_start: 1.0,
+            chord_at_end: 1.0,
+            nr_sections: 10,
+        }
+    }
+}
+
+impl StraightWing {
+    pub fn build(&self) -> LineForceModelBuilder {
+        let mut line_force_model_builder = LineForceModelBuilder::new(self.nr_sections);
+
+        line_force_model_builder.add_wing(
+            WingBuilder {
+                section_points: vec![
+                    self.start_point,
+                    self.start_point + SpatialVector::from([0.0, 0.0, self.span]),
+                ],
+                chord_vectors: vec![
+                    SpatialVector::from([self.chord_at_start, 0.0, 0.0]),
+                    SpatialVector::from([self.chord_at_end, 0.0, 0.0]),
+                ],
+                section_model: SectionModel::Foil(Foil::default()),
+                non_zero_circulation_at_ends: [false, false],
+                ..Default::default()
+            }
+        );
+
+        line_force_model_builder
+    }
+}
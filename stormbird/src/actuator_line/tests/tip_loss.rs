@@ -0,0 +1,133 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+use crate::actuator_line::corrections::empirical_circulation::{
+    EmpiricalCirculationCorrection,
+    PrandtlTipLoss,
+};
+use crate::common_utils::prelude::CoordinateSystem;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+use super::test_setup::StraightWing;
+
+#[test]
+/// Tests that the Prandtl tip loss gives the classic shape: one at the hub, zero at the tip, and
+/// the analytical value in between.
+fn prandtl_tip_loss_shape() {
+    let nr_blades = 3;
+    let inflow_angle: Float = 0.2;
+
+    let correction: EmpiricalCirculationCorrection = serde_json::from_str(
+        &format!(r#"{{"prandtl_tip_loss": {{"nr_blades": {}}}}}"#, nr_blades)
+    ).unwrap();
+
+    let prandtl_tip_loss = correction.prandtl_tip_loss.as_ref().unwrap();
+
+    assert_eq!(prandtl_tip_loss.nr_blades, nr_blades);
+
+    assert!((correction.correction_factor(0.0, inflow_angle) - 1.0).abs() < 1e-12);
+    assert!(correction.correction_factor(0.5, inflow_angle).abs() < 1e-12);
+    assert!(correction.correction_factor(-0.5, inflow_angle).abs() < 1e-12);
+
+    let span_position: Float = 0.4;
+    let relative_radius = 2.0 * span_position;
+
+    let f = 0.5 * nr_blades as Float * (1.0 - relative_radius) / (relative_radius * inflow_angle.sin());
+    let expected_factor = (2.0 / PI) * (-f).exp().acos();
+
+    assert!((correction.correction_factor(span_position, inflow_angle) - expected_factor).abs() < 1e-12);
+    assert!((correction.correction_factor(-span_position, inflow_angle) - expected_factor).abs() < 1e-12);
+
+    let mut previous_factor = 1.0;
+
+    for i in 1..=10 {
+        let factor = correction.correction_factor(0.05 * i as Float, inflow_angle);
+
+        assert!(factor <= previous_factor);
+
+        previous_factor = factor;
+    }
+
+    // A larger inflow angle gives a smaller exponent, and therefore a larger loss
+    assert!(
+        correction.correction_factor(span_position, 2.0 * inflow_angle) <
+        correction.correction_factor(span_position, inflow_angle)
+    );
+}
+
+#[test]
+/// Tests that the empirical correction is unchanged when no Prandtl tip loss is given, and that
+/// the inflow angle is then not used.
+fn empirical_correction_without_prandtl_tip_loss() {
+    let correction = EmpiricalCirculationCorrection::default();
+
+    let span_position: Float = 0.3;
+    let expected_factor = (2.0 / PI) * (-10.0 * (0.5 - span_position)).exp().acos();
+
+    assert!((correction.correction_factor(span_position, 0.1) - expected_factor).abs() < 1e-12);
+    assert!((correction.correction_factor(span_position, 0.7) - expected_factor).abs() < 1e-12);
+
+    let with_tip_loss = EmpiricalCirculationCorrection {
+        prandtl_tip_loss: Some(PrandtlTipLoss { nr_blades: 2 }),
+        ..Default::default()
+    };
+
+    assert!(with_tip_loss.correction_factor(span_position, 0.5) != correction.correction_factor(span_position, 0.5));
+}
+
+#[test]
+/// Tests that the actuator line uses the local angle of attack as the inflow angle, see
+/// [PrandtlTipLoss].
+fn prandtl_tip_loss_uses_local_inflow_angle() {
+    let line_force_model_builder = StraightWing::default().build();
+
+    let mut builder = ActuatorLineBuilder::new(line_force_model_builder);
+
+    let correction = EmpiricalCirculationCorrection {
+        prandtl_tip_loss: Some(PrandtlTipLoss { nr_blades: 3 }),
+        ..Default::default()
+    };
+
+    builder.empirical_circulation_correction = Some(correction.clone());
+
+    let actuator_line = builder.build().unwrap();
+
+    let line_force_model = &actuator_line.line_force_model;
+    let nr_span_lines = line_force_model.nr_span_lines();
+
+    // Different inflow direction at each control point
+    let ctrl_points_velocity: Vec<SpatialVector> = (0..nr_span_lines).map(
+        |i| SpatialVector::from([10.0, 0.5 + 0.2 * i as Float, 0.0])
+    ).collect();
+
+    let angles_of_attack = line_force_model.angles_of_attack(
+        &ctrl_points_velocity,
+        CoordinateSystem::Global
+    );
+
+    let uncorrected_strength = line_force_model.circulation_strength(
+        &angles_of_attack,
+        &ctrl_points_velocity
+    );
+
+    let corrected_strength = actuator_line.estimated_circulation_strength(
+        &angles_of_attack,
+        &ctrl_points_velocity
+    );
+
+    let span_positions = &line_force_model.ctrl_point_spanwise_distance_circulation_model;
+
+    for i in 0..nr_span_lines {
+        let expected_strength = uncorrected_strength[i] * correction.correction_factor(
+            span_positions[i],
+            angles_of_attack[i]
+        );
+
+        assert!((corrected_strength[i] - expected_strength).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::actuator_line::builder::ActuatorLineBuilder;
+use crate::actuator_line::ActuatorLine;
+use crate::actuator_line::corrections::lifting_line::LiftingLineCorrectionBuilder;
+use crate::lifting_line::singularity_elements::symmetry_condition::SymmetryCondition;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+use super::test_setup::StraightWing;
+
+fn wing_actuator_line_with_wake_length(wake_length: Option<Float>) -> ActuatorLine {
+    let line_force_model_builder = StraightWing {
+        span: 5.0,
+        ..Default::default()
+    }.build();
+
+    let mut builder = ActuatorLineBuilder::new(line_force_model_builder);
+
+    builder.lifting_line_correction = Some(
+        LiftingLineCorrectionBuilder {
+            wake_length_factor: 100.0,
+            symmetry_condition: SymmetryCondition::NoSymmetry,
+            initialization_time: None,
+            wake_length,
+            wake_decay_length: None,
+        }
+    );
+
+    let mut actuator_line = builder.build().unwrap();
+
+    let nr_span_lines = actuator_line.line_force_model.nr_span_lines();
+
+    actuator_line.ctrl_points_velocity = vec![SpatialVector::from([10.0, 1.0, 0.0]); nr_span_lines];
+
+    actuator_line
+}
+
+fn max_difference(a: &[SpatialVector], b: &[SpatialVector]) -> Float {
+    a.iter().zip(b.iter()).fold(0.0, |max, (a, b)| max.max((*a - *b).length()))
+}
+
+#[test]
+/// Tests that the stored wake history has no effect when the circulation is steady, that it
+/// changes the correction when the circulation varies, and that it is removed when it has been
+/// convected further than the wake length.
+fn wake_history_in_velocity_correction() {
+    let mut actuator_line = wing_actuator_line_with_wake_length(Some(2.0));
+    let steady_actuator_line = wing_actuator_line_with_wake_length(None);
+
+    let line_force_model = &actuator_line.line_force_model;
+    let velocity = &actuator_line.ctrl_points_velocity;
+    let nr_span_lines = line_force_model.nr_span_lines();
+
+    let circulation_strength: Vec<Float> = (0..nr_span_lines).map(
+        |i| 1.0 + 0.1 * (i as Float)
+    ).collect();
+    let previous_circulation_strength: Vec<Float> = circulation_strength.iter().map(
+        |strength| 0.5 * strength
+    ).collect();
+
+    let steady_correction = steady_actuator_line.lifting_line_correction.as_ref().unwrap()
+        .velocity_correction(line_force_model, velocity, &circulation_strength, 0.0);
+
+    let correction = actuator_line.lifting_line_correction.as_mut().unwrap();
+
+    // The velocity is just above 10 m/s and the chord is 1 m, so the first entry is more than one 
+    // chord length downstream after 0.11 s, and the next entry is stored separately
+    correction.store_shed_circulation(line_force_model, &circulation_strength);
+    correction.advect_wake_history(line_force_model, velocity, 0.11);
+
+    let correction_with_steady_history = correction.velocity_correction(
+        line_force_model, velocity, &circulation_strength, 0.0
+    );
+
+    assert!(max_difference(&steady_correction, &correction_with_steady_history) < 1e-12);
+
+    correction.store_shed_circulation(line_force_model, &previous_circulation_strength);
+    correction.advect_wake_history(line_force_model, velocity, 0.01);
+
+    let correction_with_unsteady_history = correction.velocity_correction(
+        line_force_model, velocity, &circulation_strength, 0.0
+    );
+
+    dbg!(max_difference(&steady_correction, &correction_with_unsteady_history));
+
+    assert!(max_difference(&steady_correction, &correction_with_unsteady_history) > 1e-6);
+
+    assert_eq!(correction.wake_history.len(), 2);
+
+    // The history is past the wake length of two chord lengths after another 0.2 s
+    correction.advect_wake_history(line_force_model, velocity, 0.2);
+
+    assert!(correction.wake_history.is_empty());
+}
+
+#[test]
+/// Tests that the time steps within one convective chord length are merged into one entry in the
+/// wake history, so that the number of entries is limited by the wake length in chord lengths, and
+/// not by the number of time steps.
+fn wake_history_from_time_steps() {
+    let mut actuator_line = wing_actuator_line_with_wake_length(Some(2.0));
+
+    let time_step = 0.01;
+
+    for i in 0..100 {
+        actuator_line.do_step(i as Float * time_step, time_step);
+    }
+
+    let wake_history = &actuator_line.lifting_line_correction.as_ref().unwrap().wake_history;
+
+    // The wake moves just over 0.1 m per time step, so roughly ten time steps are merged into each
+    // entry, and at most three entries are needed to cover two chord lengths
+    assert!(wake_history.len() <= 3, "Too many entries: {}", wake_history.len());
+
+    for entry in wake_history.iter().skip(1) {
+        assert!(entry.nr_time_steps >= 9, "Too few merged time steps: {}", entry.nr_time_steps);
+        assert!(entry.convected_distances[0] >= 1.0);
+    }
+}
@@ -18,6 +18,20 @@ pub enum CoordinateSystem {
     Body,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Schemes for how the circulatory forces on each line element are calculated.
+pub enum ForceIntegrationScheme {
+    #[default]
+    /// The Kutta-Joukowski theorem is evaluated at the control point of each line element, using
+    /// the circulation strength and velocity at the control point directly.
+    ControlPoints,
+    /// The force per unit span from the Kutta-Joukowski theorem is evaluated at the span points,
+    /// using circulation and velocity values interpolated from the control points, and then
+    /// integrated over each line element with the trapezoidal rule. The circulation is set to zero
+    /// at the wing ends, unless the wing is marked as having non-zero circulation at the ends.
+    SpanPoints,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 /// Integrated values representing either forces or moments.
 pub struct IntegratedValues {
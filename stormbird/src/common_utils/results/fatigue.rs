@@ -0,0 +1,102 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Decomposition of the sail forces into a mean and an oscillatory component over a moving 
+//! window, as input to fatigue assessments.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use stormath::{
+    spatial_vector::SpatialVector,
+    type_aliases::Float
+};
+
+use super::simulation::SimulationResult;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+/// Mean and oscillatory components of the force on a single sail over a window
+pub struct FatigueLoadComponents {
+    /// Mean value of each force component over the window
+    pub mean: SpatialVector,
+    /// Half the difference between the maximum and minimum value of each force component over the
+    /// window
+    pub amplitude: SpatialVector,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Recorder that keeps the total force on each sail for the last time steps, and decomposes it 
+/// into a mean and an oscillatory component. 
+///
+/// The window is given as a number of recorded steps. The recorder can be used with any time 
+/// varying simulation, for instance with a wind condition time series representing turbulence.
+pub struct FatigueLoadRecorder {
+    /// The number of steps in the window
+    pub window_size: usize,
+    /// The forces in the window for each sail, with the most recent step last
+    pub forces: Vec<VecDeque<SpatialVector>>,
+}
+
+impl FatigueLoadRecorder {
+    pub fn new(nr_wings: usize, window_size: usize) -> Self {
+        Self {
+            window_size,
+            forces: vec![VecDeque::with_capacity(window_size); nr_wings],
+        }
+    }
+
+    /// Records the total integrated force on each sail in the result
+    pub fn record(&mut self, result: &SimulationResult) {
+        let forces: Vec<SpatialVector> = result.integrated_forces.iter()
+            .map(|values| values.total)
+            .collect();
+
+        self.record_forces(&forces);
+    }
+
+    /// Records one force vector for each sail. The oldest step is removed when the window is full.
+    pub fn record_forces(&mut self, forces: &[SpatialVector]) {
+        for (window, force) in self.forces.iter_mut().zip(forces.iter()) {
+            if window.len() >= self.window_size {
+                window.pop_front();
+            }
+
+            window.push_back(*force);
+        }
+    }
+
+    /// Returns true when the window is filled with recorded steps for all sails
+    pub fn window_is_full(&self) -> bool {
+        self.forces.iter().all(|window| window.len() >= self.window_size)
+    }
+
+    /// Returns the mean and oscillatory components for each sail, based on the steps currently in
+    /// the window. Sails without any recorded steps get zero values.
+    pub fn load_components(&self) -> Vec<FatigueLoadComponents> {
+        self.forces.iter().map(|window| {
+            if window.is_empty() {
+                return FatigueLoadComponents::default();
+            }
+
+            let mut sum = SpatialVector::default();
+            let mut min_value = window[0];
+            let mut max_value = window[0];
+
+            for force in window.iter() {
+                sum += *force;
+
+                for i in 0..3 {
+                    min_value[i] = min_value[i].min(force[i]);
+                    max_value[i] = max_value[i].max(force[i]);
+                }
+            }
+
+            FatigueLoadComponents {
+                mean: sum / window.len() as Float,
+                amplitude: 0.5 * (max_value - min_value),
+            }
+        }).collect()
+    }
+}
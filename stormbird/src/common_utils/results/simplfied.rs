@@ -12,4 +12,18 @@ pub struct SingleSailResult {
     pub force: SpatialVector,
     pub moment: SpatialVector,
     pub input_power: Float
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Representation of a complete sail system as one equivalent sail.
+pub struct EquivalentSail {
+    /// Combined reference area of all the sails
+    pub reference_area: Float,
+    /// Total force on all the sails
+    pub force: SpatialVector,
+    /// Total moment on all the sails
+    pub moment: SpatialVector,
+    /// Effective center of effort, computed as the average of the control points weighted by the 
+    /// magnitude of the sectional forces
+    pub center_of_effort: SpatialVector,
+}
@@ -9,18 +9,23 @@ use stormath::{
     type_aliases::Float,
     spatial_vector::SpatialVector,
     rigid_body_motion::RigidBodyMotion,
+    interpolation::linear_interpolation,
 };
 use serde::{Serialize, Deserialize};
 
 use crate::error::Error;
+use crate::io_utils::json_data;
+use crate::io_utils::csv_data::CsvPrecision;
 
 use crate::common_utils::forces_and_moments::{
     IntegratedValues,
     SectionalForces,
-    SectionalForcesInput
+    ForceIntegrationScheme,
+    SectionalForcesInput,
+    CoordinateSystem,
 };
 
-use crate::common_utils::results::simplfied::SingleSailResult;
+use crate::common_utils::results::simplfied::{SingleSailResult, EquivalentSail};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 /// Structures used to return results from simulations. 
@@ -36,10 +41,45 @@ pub struct SimulationResult {
     pub iterations: usize,
     pub residual: Float,
     pub wing_indices: Vec<Range<usize>>,
-    pub rigid_body_motion: RigidBodyMotion
+    pub rigid_body_motion: RigidBodyMotion,
+    /// The scheme used to calculate the circulatory forces in this result
+    #[serde(default)]
+    pub force_integration_scheme: ForceIntegrationScheme,
+    /// The area of each strip, computed as the chord length times the span line length
+    #[serde(default)]
+    pub strip_areas: Vec<Float>,
+    /// The local wing angle of each wing when the result was computed
+    #[serde(default)]
+    pub local_wing_angles: Vec<Float>,
+    /// The length of the span line of each strip
+    #[serde(default)]
+    pub span_line_lengths: Vec<Float>,
+    /// True if the time step did not converge, or gave forces that are not finite
+    #[serde(default)]
+    pub non_converged: bool,
 }
 
 impl SimulationResult {
+    /// Sets all the sectional and integrated forces and moments to zero
+    pub fn set_forces_to_zero(&mut self) {
+        let sectional_forces = &mut self.sectional_forces;
+
+        for forces in [
+            &mut sectional_forces.circulatory,
+            &mut sectional_forces.viscous_lift,
+            &mut sectional_forces.sectional_drag,
+            &mut sectional_forces.added_mass,
+            &mut sectional_forces.gyroscopic,
+            &mut sectional_forces.total,
+        ] {
+            forces.fill(SpatialVector::default());
+        }
+
+        for values in self.integrated_forces.iter_mut().chain(self.integrated_moments.iter_mut()) {
+            *values = IntegratedValues::default();
+        }
+    }
+
     pub fn result_history_from_file(file_path: &str) -> Result<Vec<SimulationResult>, Error> {
         let file = std::fs::File::open(file_path)?;
 
@@ -53,9 +93,7 @@ impl SimulationResult {
     pub fn from_json(file_path: &str) -> Result<SimulationResult, Error> {
         let string = std::fs::read_to_string(file_path)?;
         
-        let serde_res = serde_json::from_str(&string)?;
-        
-        Ok(serde_res)
+        json_data::from_json_string(&string)
     }
 
     pub fn integrated_forces_sum(&self) -> SpatialVector {
@@ -78,6 +116,12 @@ impl SimulationResult {
         sum
     }
     
+    /// Returns the sum of the integrated moments, but with the input point as the reference point
+    /// instead of the origin of the coordinate system the moments are computed in.
+    pub fn integrated_moment_about_point(&self, point: SpatialVector) -> SpatialVector {
+        self.integrated_moments_sum() - point.cross(self.integrated_forces_sum())
+    }
+    
     pub fn input_power_sum(&self) -> Float {
         let mut sum = 0.0;
         
@@ -146,7 +190,99 @@ impl SimulationResult {
 
         out
     }
-    
+
+    /// Returns the downwash angle at each control point, for all sails. The downwash angle is
+    /// the angle between the freestream velocity given as input to the solver and the felt
+    /// velocity, which includes the lift-induced velocities. The values are ordered in the same
+    /// way as the control points, so that the values for each sail are found using the wing
+    /// indices.
+    ///
+    /// The angle is always positive, as the sign would depend on the orientation of each sail.
+    pub fn downwash_distribution(&self) -> Vec<Float> {
+        (0..self.nr_span_lines()).map(|i| {
+            let freestream = self.solver_input_ctrl_points_velocity[i];
+            let felt_velocity = self.force_input.velocity[i];
+
+            freestream.cross(felt_velocity).length().atan2(freestream.dot(felt_velocity))
+        }).collect()
+    }
+
+    /// Returns the total sectional force per unit span for the given wing, resampled to the input
+    /// span fractions. A span fraction of zero corresponds to the first span point of the wing, 
+    /// and a span fraction of one corresponds to the last span point.
+    ///
+    /// The force per unit span is computed as the sectional force divided by the span line length
+    /// of each strip, and is linearly interpolated between the control points. Outside the first 
+    /// and last control point, the value at the closest control point is used.
+    pub fn sectional_force_at_span_fractions(
+        &self, 
+        wing_index: usize, 
+        fractions: &[Float]
+    ) -> Vec<SpatialVector> {
+        let indices = self.wing_indices[wing_index].clone();
+
+        let span_line_lengths = &self.span_line_lengths[indices.clone()];
+        let total_span: Float = span_line_lengths.iter().sum();
+
+        let mut ctrl_point_fractions: Vec<Float> = Vec::with_capacity(indices.len());
+        let mut distance_to_start = 0.0;
+
+        for length in span_line_lengths {
+            ctrl_point_fractions.push((distance_to_start + 0.5 * length) / total_span);
+
+            distance_to_start += length;
+        }
+
+        let force_per_unit_span: Vec<SpatialVector> = self.sectional_forces.total[indices].iter()
+            .zip(span_line_lengths.iter())
+            .map(|(force, length)| *force / *length)
+            .collect();
+
+        fractions.iter().map(
+            |fraction| linear_interpolation(*fraction, &ctrl_point_fractions, &force_per_unit_span)
+        ).collect()
+    }
+
+    /// Returns the root point of the given wing, estimated from the first control point and the 
+    /// direction towards the next control point. For wings with a single strip, the control point 
+    /// itself is returned, as the span direction is then not known from the result.
+    pub fn wing_root_point(&self, wing_index: usize) -> SpatialVector {
+        let indices = self.wing_indices[wing_index].clone();
+
+        let first_ctrl_point = self.ctrl_points[indices.start];
+
+        if indices.len() < 2 {
+            return first_ctrl_point;
+        }
+
+        let span_direction = (self.ctrl_points[indices.start + 1] - first_ctrl_point).normalize();
+
+        first_ctrl_point - 0.5 * self.span_line_lengths[indices.start] * span_direction
+    }
+
+    /// Computes the bending moment at the root of the given wing, by integrating the sectional 
+    /// forces times the lever arm from the root point to each control point. The moment is given
+    /// in the same coordinate system as the sectional forces.
+    pub fn root_bending_moment(&self, wing_index: usize) -> SpatialVector {
+        let root_point = self.wing_root_point(wing_index);
+
+        let mut moment = SpatialVector::default();
+
+        for i in self.wing_indices[wing_index].clone() {
+            let lever_arm_global = self.ctrl_points[i] - root_point;
+
+            let lever_arm = match self.sectional_forces.coordinate_system {
+                CoordinateSystem::Global => lever_arm_global,
+                CoordinateSystem::Body => self.rigid_body_motion
+                    .vector_in_body_fixed_coordinate_system(lever_arm_global),
+            };
+
+            moment += lever_arm.cross(self.sectional_forces.total[i]);
+        }
+
+        moment
+    }
+
     pub fn as_simplified(&self) -> Vec<SingleSailResult> {
         let nr_wings = self.nr_of_wings();
         
@@ -165,12 +301,160 @@ impl SimulationResult {
         out
     }
 
-    pub fn as_reduced_flatten_csv_string(&self) -> (String, String) {
+    /// Returns a representation of all the sails as one equivalent sail, with the combined 
+    /// reference area, the total force and moment, and an effective center of effort.
+    pub fn equivalent_single_sail(&self) -> EquivalentSail {
+        let reference_area: Float = self.strip_areas.iter().sum();
+
+        let mut weighted_position_sum = SpatialVector::default();
+        let mut weight_sum = 0.0;
+
+        for i in 0..self.sectional_forces.total.len() {
+            let weight = self.sectional_forces.total[i].length();
+
+            weighted_position_sum += weight * self.ctrl_points[i];
+            weight_sum += weight;
+        }
+
+        let center_of_effort = if weight_sum > 0.0 {
+            weighted_position_sum / weight_sum
+        } else {
+            SpatialVector::default()
+        };
+
+        EquivalentSail {
+            reference_area,
+            force: self.integrated_forces_sum(),
+            moment: self.integrated_moments_sum(),
+            center_of_effort,
+        }
+    }
+
+    /// Returns the input power coefficient for each wing, defined as the input power divided by 
+    /// 0.5 * density * area * velocity^3. The area is the sum of the strip areas on the wing, and 
+    /// the velocity is the mean magnitude of the felt velocity at the control points on the wing. 
+    /// The coefficient is set to zero if the area or the velocity is zero, which is also the case
+    /// when the strip areas or velocities are missing from the result.
+    pub fn input_power_coefficients(&self, density: Float) -> Vec<Float> {
+        (0..self.nr_of_wings()).map(|wing_index| {
+            let indices = self.wing_indices[wing_index].clone();
+            let nr_strips = indices.len() as Float;
+
+            let area: Float = self.strip_areas.get(indices.clone())
+                .map_or(0.0, |areas| areas.iter().sum());
+
+            let velocity = self.force_input.velocity.get(indices)
+                .map_or(0.0, |velocities| {
+                    velocities.iter().map(|velocity| velocity.length()).sum::<Float>() / nr_strips
+                });
+
+            let power_factor = 0.5 * density * area * velocity.powi(3);
+
+            let input_power = self.input_power.get(wing_index).copied().unwrap_or(0.0);
+
+            if power_factor > 0.0 {
+                input_power / power_factor
+            } else {
+                0.0
+            }
+        }).collect()
+    }
+
+    /// Returns the ratio between the thrust power and the input power for each wing. The thrust 
+    /// is the force component along the input thrust direction, and the thrust power is the thrust 
+    /// times the ship speed. The ratio is set to zero for wings without any input power.
+    pub fn thrust_power_efficiencies(
+        &self, 
+        thrust_direction: SpatialVector, 
+        ship_speed: Float
+    ) -> Vec<Float> {
+        let thrust_direction = thrust_direction.normalize();
+
+        (0..self.nr_of_wings()).map(|wing_index| {
+            let thrust = self.integrated_forces[wing_index].total.dot(thrust_direction);
+            let input_power = self.input_power[wing_index];
+
+            if input_power > 0.0 {
+                thrust * ship_speed / input_power
+            } else {
+                0.0
+            }
+        }).collect()
+    }
+
+    /// Returns the side force from the sails, defined as the magnitude of the total force 
+    /// perpendicular to the input forward axis of the ship, together with the point where the side
+    /// force acts. 
+    ///
+    /// The application point is computed as the mean of the control points, weighted by the 
+    /// sectional forces projected onto the direction of the total side force. If there is no side 
+    /// force, the returned point is the origin.
+    pub fn side_force_and_coe(&self, ship_forward_axis: SpatialVector) -> (Float, SpatialVector) {
+        let forward_direction = ship_forward_axis.normalize();
+
+        let total_force = self.integrated_forces_sum();
+
+        let side_force_vector = total_force - total_force.dot(forward_direction) * forward_direction;
+        let side_force = side_force_vector.length();
+
+        if side_force == 0.0 {
+            return (0.0, SpatialVector::default());
+        }
+
+        let side_direction = side_force_vector / side_force;
+
+        let mut weighted_position_sum = SpatialVector::default();
+        let mut weight_sum = 0.0;
+
+        for i in 0..self.sectional_forces.total.len() {
+            let weight = self.sectional_forces.total[i].dot(side_direction);
+
+            weighted_position_sum += weight * self.ctrl_points[i];
+            weight_sum += weight;
+        }
+
+        let center_of_effort = if weight_sum != 0.0 {
+            weighted_position_sum / weight_sum
+        } else {
+            SpatialVector::default()
+        };
+
+        (side_force, center_of_effort)
+    }
+
+    /// Returns the names and values of the time and the integrated forces and moments on each 
+    /// wing. The names are the same as in the header from 
+    /// [SimulationResult::as_reduced_flatten_csv_string].
+    pub fn reduced_flatten_values(&self) -> (Vec<String>, Vec<Float>) {
+        let mut names = vec!["time".to_string()];
+        let mut values = vec![self.time];
+
+        for wing_index in 0..self.nr_of_wings() {
+            let force = self.integrated_forces[wing_index].total;
+            let moment = self.integrated_moments[wing_index].total;
+
+            for (component_index, component) in ["x", "y", "z"].iter().enumerate() {
+                names.push(format!("force_{}.{}", wing_index, component));
+                values.push(force[component_index]);
+            }
+
+            for (component_index, component) in ["x", "y", "z"].iter().enumerate() {
+                names.push(format!("moment_{}.{}", wing_index, component));
+                values.push(moment[component_index]);
+            }
+        }
+
+        (names, values)
+    }
+
+    /// Returns a header and a data line with the time and the integrated forces and moments on 
+    /// each wing, where the values are formatted according to the input precision.
+    pub fn as_reduced_flatten_csv_string(&self, precision: CsvPrecision) -> (String, String) {
         let mut header = String::new();
         let mut data = String::new();
 
         header.push_str("time,");
-        data.push_str(&format!("{}, ", self.time));
+        data.push_str(&format!("{}, ", precision.format_value(self.time)));
 
         for wing_index in 0..self.nr_of_wings() {
             header.push_str(&format!("force_{}.x,", wing_index));
@@ -186,17 +470,20 @@ impl SimulationResult {
                 header.push_str(&format!("moment_{}.z,", wing_index));
             }
 
-            data.push_str(&format!("{},", self.integrated_forces[wing_index].total[0]));
-            data.push_str(&format!("{},", self.integrated_forces[wing_index].total[1]));
-            data.push_str(&format!("{},", self.integrated_forces[wing_index].total[2]));
+            let force = self.integrated_forces[wing_index].total;
+            let moment = self.integrated_moments[wing_index].total;
+
+            data.push_str(&format!("{},", precision.format_value(force[0])));
+            data.push_str(&format!("{},", precision.format_value(force[1])));
+            data.push_str(&format!("{},", precision.format_value(force[2])));
 
-            data.push_str(&format!("{},", self.integrated_moments[wing_index].total[0]));
-            data.push_str(&format!("{},", self.integrated_moments[wing_index].total[1]));
+            data.push_str(&format!("{},", precision.format_value(moment[0])));
+            data.push_str(&format!("{},", precision.format_value(moment[1])));
 
             if wing_index == self.nr_of_wings() - 1 {
-                data.push_str(&format!("{}", self.integrated_moments[wing_index].total[2]));
+                data.push_str(&precision.format_value(moment[2]));
             } else {
-                data.push_str(&format!("{},", self.integrated_moments[wing_index].total[2]));
+                data.push_str(&format!("{},", precision.format_value(moment[2])));
             }
         }
 
@@ -15,4 +15,6 @@ pub struct SolverResult {
     pub output_ctrl_points_velocity: Vec<SpatialVector>,
     pub iterations: usize,
     pub residual: Float,
+    /// Whether the solver reached its convergence criteria. Always true for direct solvers.
+    pub converged: bool,
 }
\ No newline at end of file
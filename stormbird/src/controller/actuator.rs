@@ -0,0 +1,81 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Models of the actuator dynamics between the commanded control values and the values that are
+//! actually applied to the sail.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// First order actuator model with optional saturation of the applied value.
+///
+/// The applied value follows the commanded value with the transfer function 1 / (tau s + 1). The
+/// response is integrated exactly over each time step, assuming the command is constant during
+/// the step. The state of the actuator is the currently applied value, which is given as input to
+/// the controller.
+pub struct FirstOrderActuator {
+    /// Time constant of the actuator, in seconds
+    pub time_constant: Float,
+    #[serde(default)]
+    /// Optional lower limit for the applied value
+    pub min_value: Option<Float>,
+    #[serde(default)]
+    /// Optional upper limit for the applied value
+    pub max_value: Option<Float>,
+}
+
+impl FirstOrderActuator {
+    pub fn applied_value(&self, current_value: Float, command: Float, time_step: Float) -> Float {
+        let response_factor = if self.time_constant > 0.0 {
+            1.0 - (-time_step / self.time_constant).exp()
+        } else {
+            1.0
+        };
+
+        let mut value = current_value + response_factor * (command - current_value);
+
+        if let Some(min_value) = self.min_value {
+            value = value.max(min_value);
+        }
+
+        if let Some(max_value) = self.max_value {
+            value = value.min(max_value);
+        }
+
+        value
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+/// Model of the actuator that applies a control value to the sail.
+pub enum ActuatorModel {
+    #[default]
+    /// The commanded value is applied directly
+    Instantaneous,
+    FirstOrder(FirstOrderActuator),
+}
+
+impl ActuatorModel {
+    /// Returns the value applied to the sail after one time step, given the currently applied
+    /// value and the commanded value.
+    pub fn applied_value(&self, current_value: Float, command: Float, time_step: Float) -> Float {
+        match self {
+            ActuatorModel::Instantaneous => command,
+            ActuatorModel::FirstOrder(actuator) => actuator.applied_value(
+                current_value, command, time_step
+            ),
+        }
+    }
+
+    /// Returns the lower and upper saturation limits of the actuator, if any.
+    pub fn limits(&self) -> (Option<Float>, Option<Float>) {
+        match self {
+            ActuatorModel::Instantaneous => (None, None),
+            ActuatorModel::FirstOrder(actuator) => (actuator.min_value, actuator.max_value),
+        }
+    }
+}
@@ -3,11 +3,13 @@
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html) 
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 
 use serde::{Deserialize, Serialize};
 
 use super::Controller;
 use super::set_points::ControllerSetPoints;
+use super::state::ControllerState;
 use super::measurements::FlowMeasurementSettings;
 
 use stormath::type_aliases::Float;
@@ -31,10 +33,24 @@ pub struct ControllerBuilder {
 impl ControllerBuilder {
     pub fn default_time_steps_between_updates() -> usize {1}
 
-    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
-        let serde_res = serde_json::from_str(json_string)?;
+    /// Creates a builder directly from the set points and flow measurement settings, with default 
+    /// values for the remaining settings.
+    pub fn from_parts(
+        set_points: Vec<ControllerSetPoints>,
+        flow_measurement_settings: FlowMeasurementSettings
+    ) -> Self {
+        Self {
+            set_points,
+            flow_measurement_settings,
+            time_steps_between_updates: Self::default_time_steps_between_updates(),
+            start_time: 0.0,
+            moving_average_window_size: None,
+            use_input_velocity_for_apparent_wind_direction: false,
+        }
+    }
 
-        Ok(serde_res)
+    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(json_string)
     }
 
     pub fn from_json_file(file_path: &str) -> Result<Self, Error> {
@@ -43,14 +59,43 @@ impl ControllerBuilder {
         Self::from_json_string(&json_string)
     }
 
-    pub fn build(&self) -> Controller {
-        Controller {
+    /// Builds the controller, after checking that the set points are consistent. See 
+    /// [ControllerSetPoints::check_data] for the requirements.
+    pub fn build(&self) -> Result<Controller, Error> {
+        for (i, set_point) in self.set_points.iter().enumerate() {
+            if let Err(e) = set_point.check_data() {
+                return Err(Error::CustomStringError(
+                    format!("Invalid controller set points for wing {}: {}", i, e)
+                ));
+            }
+
+            if set_point.has_single_entry() {
+                #[cfg(feature = "logging")]
+                log::warn!(
+                    target: "stormbird::controller",
+                    "The controller set points for wing {} only contain a single apparent wind \
+                    direction. The set points are used as constant values for all apparent wind \
+                    directions.",
+                    i
+                );
+
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    wing = i,
+                    "single-entry controller set points are used as constant values"
+                );
+            }
+        }
+
+        Ok(Controller {
             set_points: self.set_points.clone(),
             flow_measurement_settings: self.flow_measurement_settings.clone(),
             time_steps_between_updates: self.time_steps_between_updates,
             start_time: self.start_time,
             time_step_index: 0,
             use_input_velocity_for_apparent_wind_direction: self.use_input_velocity_for_apparent_wind_direction,
-        }
+            moving_average_window_size: self.moving_average_window_size,
+            state: vec![ControllerState::default(); self.set_points.len()],
+        })
     }
 }
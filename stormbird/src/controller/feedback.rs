@@ -0,0 +1,74 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Closed-loop feedback control of the local wing angle, based on a PID controller acting on the
+//! error between the measured and the target angle of attack.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Gains and limits for a PID controller.
+pub struct PidGains {
+    /// Gain on the error
+    pub proportional: Float,
+    #[serde(default)]
+    /// Gain on the time integral of the error
+    pub integral: Float,
+    #[serde(default)]
+    /// Gain on the time derivative of the error
+    pub derivative: Float,
+    #[serde(default)]
+    /// Optional limit on the magnitude of the integrated error, used as anti-windup
+    pub integral_limit: Option<Float>,
+    #[serde(default)]
+    /// Optional limit on the magnitude of the total correction from the controller
+    pub output_limit: Option<Float>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// Internal state of a PID controller, accumulated over the time steps.
+pub struct PidState {
+    /// Time integral of the error
+    pub integral: Float,
+    /// The error at the previous update, used for the derivative. `None` before the first update.
+    pub previous_error: Option<Float>,
+}
+
+impl PidState {
+    /// Resets the state to the initial value, with no accumulated error.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl PidGains {
+    /// Returns the correction from the controller for the input error, and advances the state 
+    /// with the time step. The derivative term is zero at the first update after a reset.
+    pub fn correction(&self, error: Float, time_step: Float, state: &mut PidState) -> Float {
+        state.integral += error * time_step;
+
+        if let Some(integral_limit) = self.integral_limit {
+            state.integral = state.integral.clamp(-integral_limit, integral_limit);
+        }
+
+        let derivative = match state.previous_error {
+            Some(previous_error) if time_step > 0.0 => (error - previous_error) / time_step,
+            _ => 0.0,
+        };
+
+        state.previous_error = Some(error);
+
+        let correction = self.proportional * error + 
+            self.integral * state.integral + 
+            self.derivative * derivative;
+
+        match self.output_limit {
+            Some(output_limit) => correction.clamp(-output_limit, output_limit),
+            None => correction,
+        }
+    }
+}
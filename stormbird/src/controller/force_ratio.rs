@@ -0,0 +1,64 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Control of the sail trim toward a target ratio between the thrust and the side force, based on
+//! the forces on each sail from the last simulation result.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+fn default_max_loading_factor() -> Float {1.0}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Settings for adjusting the loading of a sail so that the ratio between the thrust and the 
+/// magnitude of the side force approaches a target value.
+///
+/// The controller scales the set points from the tables with a loading factor, which is adjusted 
+/// at every update in proportion to the error in the force ratio. A ratio below the target 
+/// increases the loading factor. This assumes that the ratio increases with the loading, which is
+/// the case for angles of attack below the angle with the maximum lift-to-drag ratio.
+pub struct ForceRatioTarget {
+    /// The target value of the thrust divided by the magnitude of the side force
+    pub target_ratio: Float,
+    /// Change in the loading factor per unit error in the force ratio, for each update
+    pub gain: Float,
+    #[serde(default)]
+    /// Lower limit of the loading factor
+    pub min_loading_factor: Float,
+    #[serde(default="default_max_loading_factor")]
+    /// Upper limit of the loading factor. Also used as the initial value.
+    pub max_loading_factor: Float,
+}
+
+impl ForceRatioTarget {
+    /// Returns the ratio between the thrust and the magnitude of the side force, or `None` if the 
+    /// side force is zero, for instance before any forces have been computed.
+    pub fn force_ratio(thrust: Float, side_force: Float) -> Option<Float> {
+        if side_force.abs() > Float::EPSILON {
+            Some(thrust / side_force.abs())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the updated loading factor based on the measured forces. The current factor is 
+    /// `None` before the first update, in which case the upper limit is used as the initial value.
+    pub fn updated_loading_factor(
+        &self, 
+        current_factor: Option<Float>, 
+        thrust: Float, 
+        side_force: Float
+    ) -> Float {
+        let factor = current_factor.unwrap_or(self.max_loading_factor);
+
+        let new_factor = match Self::force_ratio(thrust, side_force) {
+            Some(ratio) => factor + self.gain * (self.target_ratio - ratio),
+            None => factor,
+        };
+
+        new_factor.clamp(self.min_loading_factor, self.max_loading_factor)
+    }
+}
@@ -13,12 +13,15 @@ use crate::{
 
 use super::measurements::{
     FlowMeasurementSettings,
+    MeasuredQuantity,
     measure_angles_of_attack,
     measure_wind_velocity_magnitude,
     measure_apparent_wind_direction,
     measure_float_values
 };
 
+use super::power_tracking::MaxPowerPointTracking;
+
 use stormath::{spatial_vector::SpatialVector, type_aliases::Float};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -39,12 +42,55 @@ pub struct ControllerInput {
     pub velocity: Float,
     /// Measured apparent wind direction
     pub apparent_wind_direction: Float,
+    #[serde(default)]
+    /// Thrust on the sail from the last simulation result, taken as the force along the thrust 
+    /// direction of the ship. Zero when no simulation result is available.
+    pub thrust: Float,
+    #[serde(default)]
+    /// Side force on the sail from the last simulation result, taken as the force perpendicular to
+    /// the thrust direction, in the plane the wind direction rotates in. Zero when no simulation 
+    /// result is available.
+    pub side_force: Float,
+    #[serde(default)]
+    /// Input power to the sail from the last simulation result. Zero when no simulation result is
+    /// available.
+    pub input_power: Float,
+    #[serde(default)]
+    /// Velocity of the ship, used to compute the thrust power. Zero when the ship velocity is not 
+    /// known.
+    pub ship_velocity: Float,
 }
 
 impl ControllerInput {
+    /// The default thrust direction, which is the negative x-direction. This matches the default 
+    /// wind environment, where the ship velocity is added in the negative direction of the zero 
+    /// direction vector.
+    pub fn default_thrust_direction() -> SpatialVector {SpatialVector::from([-1.0, 0.0, 0.0])}
+
+    /// Returns the net power from the sail, as the thrust power minus the input power, or `None` 
+    /// if the input does not contain the data needed to compute it. This is the case when the ship
+    /// velocity is unknown, or when there are no forces or power from a simulation result yet.
+    pub fn net_power(&self) -> Option<Float> {
+        let forces_available = self.thrust != 0.0 || self.input_power != 0.0;
+
+        if self.ship_velocity > 0.0 && forces_available {
+            Some(MaxPowerPointTracking::net_power(self.thrust, self.ship_velocity, self.input_power))
+        } else {
+            None
+        }
+    }
+
     /// Method that creates input to a controller based on a simulation results structure. This will
     /// then contain information about the lift-induced velocities in the measurements, which might 
     /// be critical for certain controller logic (e.g., effective angle of attack controller). 
+    ///
+    /// The measured velocity and apparent wind direction of each wing can be taken from a sensor 
+    /// wing instead, as defined by the sensor mapping in the measurement settings.
+    ///
+    /// The thrust is the force on each sail along the thrust direction, the side force is the 
+    /// force perpendicular to it, as given by [WindEnvironment::side_direction], and the ship 
+    /// velocity should be zero if it is not known.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_from_simulation_result(
         loading: Float,
         line_force_model: &LineForceModel,
@@ -52,6 +98,8 @@ impl ControllerInput {
         measurement_settings: &FlowMeasurementSettings,
         wind_environment: &WindEnvironment,
         use_input_velocity_for_apparent_wind_direction: bool,
+        thrust_direction: SpatialVector,
+        ship_velocity: Float,
     ) -> Vec<Self> {
         let nr_wings = line_force_model.nr_wings();
         
@@ -62,22 +110,28 @@ impl ControllerInput {
             &measurement_settings.angle_of_attack
         );
         
-        let velocities = measure_wind_velocity_magnitude(
+        let mut velocities = measure_wind_velocity_magnitude(
             simulation_result, 
             &measurement_settings.wind_velocity
         );
         
-        let apparent_wind_directions = measure_apparent_wind_direction(
+        let mut apparent_wind_directions = measure_apparent_wind_direction(
             simulation_result, 
             &measurement_settings.wind_direction, 
             wind_environment, 
             line_force_model,
             use_input_velocity_for_apparent_wind_direction
         );
+
+        measurement_settings.apply_sensor_mapping(&mut velocities, &mut apparent_wind_directions);
         
+        let side_direction = wind_environment.side_direction(thrust_direction);
+
         let mut out: Vec<Self> = Vec::with_capacity(nr_wings);
         
         for i in 0..nr_wings {
+            let force = simulation_result.integrated_forces[i].total;
+
             out.push(
                 Self {
                     loading: loading,
@@ -85,7 +139,11 @@ impl ControllerInput {
                     current_section_model_internal_state: section_models_internal_state[i],
                     angle_of_attack: angles_of_attack[i],
                     velocity: velocities[i],
-                    apparent_wind_direction: apparent_wind_directions[i]
+                    apparent_wind_direction: apparent_wind_directions[i],
+                    thrust: thrust_direction.dot(force),
+                    side_force: side_direction.dot(force),
+                    input_power: simulation_result.input_power.get(i).copied().unwrap_or(0.0),
+                    ship_velocity,
                 }
             )
         }
@@ -96,12 +154,21 @@ impl ControllerInput {
     /// Method for creating controller input based on a supplied velocity vector. The intended use 
     /// case is mostly to be able to create controller input based only on the freestream conditions,
     /// which then do not include induced velocities.
+    ///
+    /// The forces and the input power are taken from the previous simulation result, if given, as 
+    /// the velocity alone does not contain this information. They are zero otherwise. The time is 
+    /// used to compute the measurement noise, if any.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_from_velocity(
         loading: Float,
         line_force_model: &LineForceModel,
         velocity: &[SpatialVector],
+        time: Float,
         measurement_settings: &FlowMeasurementSettings,
         wind_environment: &WindEnvironment,
+        previous_result: Option<&SimulationResult>,
+        thrust_direction: SpatialVector,
+        ship_velocity: Float,
     ) -> Vec<Self> {
         let nr_wings = line_force_model.nr_wings();
         let wing_indices = line_force_model.wing_indices.clone();
@@ -111,16 +178,24 @@ impl ControllerInput {
             velocity, CoordinateSystem::Global
         );
         
-        let angles_of_attack = measure_float_values(
-            &angles_of_attack_all_sections, 
-            wing_indices.clone(), 
-            &measurement_settings.angle_of_attack
+        let angles_of_attack = measurement_settings.angle_of_attack.add_noise(
+            measure_float_values(
+                &angles_of_attack_all_sections, 
+                wing_indices.clone(), 
+                &measurement_settings.angle_of_attack
+            ),
+            MeasuredQuantity::AngleOfAttack,
+            time
         );
         
-        let velocities = measure_float_values(
-            &velocities_all_sections, 
-            wing_indices.clone(), 
-            &measurement_settings.wind_velocity
+        let mut velocities = measurement_settings.wind_velocity.add_noise(
+            measure_float_values(
+                &velocities_all_sections, 
+                wing_indices.clone(), 
+                &measurement_settings.wind_velocity
+            ),
+            MeasuredQuantity::WindVelocity,
+            time
         );
         
         let wind_directions = wind_environment.apparent_wind_direction_from_velocity_and_line_force_model(
@@ -128,18 +203,34 @@ impl ControllerInput {
             line_force_model
         );
         
-        let apparent_wind_directions = measure_float_values(
-            &wind_directions, 
-            wing_indices.clone(), 
-            &measurement_settings.wind_direction
+        let mut apparent_wind_directions = measurement_settings.wind_direction.add_noise(
+            measure_float_values(
+                &wind_directions, 
+                wing_indices.clone(), 
+                &measurement_settings.wind_direction
+            ),
+            MeasuredQuantity::WindDirection,
+            time
         );
+
+        measurement_settings.apply_sensor_mapping(&mut velocities, &mut apparent_wind_directions);
         
         let section_models_internal_state = line_force_model.section_models_internal_state();
 
+        let side_direction = wind_environment.side_direction(thrust_direction);
 
         let mut out: Vec<Self> = Vec::with_capacity(nr_wings);
         
         for i in 0..nr_wings {
+            let force = previous_result
+                .and_then(|result| result.integrated_forces.get(i))
+                .map(|force| force.total)
+                .unwrap_or_default();
+
+            let input_power = previous_result
+                .and_then(|result| result.input_power.get(i).copied())
+                .unwrap_or(0.0);
+
             out.push(
                 Self {
                     loading: loading,
@@ -147,7 +238,11 @@ impl ControllerInput {
                     current_section_model_internal_state: section_models_internal_state[i],
                     angle_of_attack: angles_of_attack[i],
                     velocity: velocities[i],
-                    apparent_wind_direction: apparent_wind_directions[i]
+                    apparent_wind_direction: apparent_wind_directions[i],
+                    thrust: thrust_direction.dot(force),
+                    side_force: side_direction.dot(force),
+                    input_power,
+                    ship_velocity,
                 }
             )
         }
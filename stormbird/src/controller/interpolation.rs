@@ -0,0 +1,143 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Interpolation of the set point tables as a function of the apparent wind direction.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::interpolation::{linear_interpolation, binary_search};
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// How to interpolate between the data points in the set point tables. 
+///
+/// The cubic variants give set points with a continuous derivative, which avoids kinks in the 
+/// commanded values when the apparent wind direction changes. All variants pass through the data 
+/// points, and are held constant outside the range of the data.
+pub enum InterpolationKind {
+    #[default]
+    /// Piecewise linear interpolation
+    Linear,
+    /// Cubic Hermite spline, where the derivative at each data point is the slope between the 
+    /// neighboring points. May overshoot between the data points.
+    CatmullRom,
+    /// Cubic Hermite spline with the derivatives limited so that the interpolant is monotone 
+    /// between the data points, using the method of Fritsch and Carlson. Does not overshoot.
+    MonotoneCubic,
+}
+
+impl InterpolationKind {
+    /// Interpolates the data to the target value. The x data must be sorted in increasing order.
+    pub fn interpolate(&self, x_target: Float, x_data: &[Float], y_data: &[Float]) -> Float {
+        if x_data.len() < 3 {
+            return linear_interpolation(x_target, x_data, y_data);
+        }
+
+        match self {
+            Self::Linear => linear_interpolation(x_target, x_data, y_data),
+            Self::CatmullRom => cubic_hermite_interpolation(
+                x_target, x_data, y_data, &catmull_rom_derivatives(x_data, y_data)
+            ),
+            Self::MonotoneCubic => cubic_hermite_interpolation(
+                x_target, x_data, y_data, &monotone_derivatives(x_data, y_data)
+            ),
+        }
+    }
+}
+
+/// Slopes of the straight lines between each pair of data points
+fn secant_slopes(x_data: &[Float], y_data: &[Float]) -> Vec<Float> {
+    x_data.windows(2).zip(y_data.windows(2)).map(
+        |(x, y)| (y[1] - y[0]) / (x[1] - x[0])
+    ).collect()
+}
+
+/// Derivatives at the data points, taken as the slope between the neighboring points. One-sided
+/// slopes are used at the ends.
+fn catmull_rom_derivatives(x_data: &[Float], y_data: &[Float]) -> Vec<Float> {
+    let n = x_data.len();
+
+    (0..n).map(|i| {
+        let i_before = i.saturating_sub(1);
+        let i_after = (i + 1).min(n - 1);
+
+        (y_data[i_after] - y_data[i_before]) / (x_data[i_after] - x_data[i_before])
+    }).collect()
+}
+
+/// Derivatives at the data points that keep the cubic Hermite spline monotone between the points.
+fn monotone_derivatives(x_data: &[Float], y_data: &[Float]) -> Vec<Float> {
+    let n = x_data.len();
+    let slopes = secant_slopes(x_data, y_data);
+
+    let mut derivatives = vec![0.0; n];
+
+    derivatives[0] = slopes[0];
+    derivatives[n - 1] = slopes[n - 2];
+
+    for i in 1..n - 1 {
+        derivatives[i] = if slopes[i - 1] * slopes[i] <= 0.0 {
+            0.0
+        } else {
+            0.5 * (slopes[i - 1] + slopes[i])
+        };
+    }
+
+    for i in 0..n - 1 {
+        if slopes[i] == 0.0 {
+            derivatives[i] = 0.0;
+            derivatives[i + 1] = 0.0;
+
+            continue;
+        }
+
+        let alpha = derivatives[i] / slopes[i];
+        let beta = derivatives[i + 1] / slopes[i];
+
+        let magnitude = alpha.hypot(beta);
+
+        if magnitude > 3.0 {
+            let tau = 3.0 / magnitude;
+
+            derivatives[i] = tau * alpha * slopes[i];
+            derivatives[i + 1] = tau * beta * slopes[i];
+        }
+    }
+
+    derivatives
+}
+
+/// Evaluates the cubic Hermite spline defined by the values and derivatives at the data points.
+fn cubic_hermite_interpolation(
+    x_target: Float, 
+    x_data: &[Float], 
+    y_data: &[Float], 
+    derivatives: &[Float]
+) -> Float {
+    let n = x_data.len();
+
+    if x_target <= x_data[0] {
+        return y_data[0];
+    }
+
+    if x_target >= x_data[n - 1] {
+        return y_data[n - 1];
+    }
+
+    let i = binary_search(x_target, x_data).min(n - 2);
+
+    let delta_x = x_data[i + 1] - x_data[i];
+    let t = (x_target - x_data[i]) / delta_x;
+
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+
+    h00 * y_data[i] + h10 * delta_x * derivatives[i] + 
+    h01 * y_data[i + 1] + h11 * delta_x * derivatives[i + 1]
+}
@@ -7,11 +7,15 @@ use std::ops::Range;
 
 use serde::{Deserialize, Serialize};
 
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use stormath::consts::PI;
 use stormath::statistics;
 use stormath::type_aliases::Float;
 
 use crate::{
     common_utils::results::simulation::SimulationResult, 
+    common_utils::forces_and_moments::CoordinateSystem,
     line_force_model::LineForceModel, 
     wind::environment::WindEnvironment,
 };
@@ -34,6 +38,112 @@ pub struct MeasurementSettings {
     pub start_index: usize,
     #[serde(default)]
     pub end_offset: usize,
+    #[serde(default)]
+    /// Optional noise added to the measured values, for instance to test the robustness of a
+    /// controller against sensor noise. No noise is added if this is not set.
+    pub noise: Option<MeasurementNoise>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The quantities that can be measured for the controller. Used to give each quantity its own 
+/// noise, so that the noise on different quantities is uncorrelated.
+pub enum MeasuredQuantity {
+    AngleOfAttack,
+    WindVelocity,
+    WindDirection,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Gaussian noise model for a measured quantity.
+///
+/// The noise for each value is generated from a random number generator seeded by the seed, the 
+/// measured quantity, the wing index and the time of the measurement. The same inputs therefore 
+/// always give the same noise, which makes simulations with noise reproducible, while the noise 
+/// still varies between quantities, wings and time steps.
+pub struct MeasurementNoise {
+    /// Standard deviation of the noise, in the same unit as the measured value
+    pub standard_deviation: Float,
+    #[serde(default)]
+    /// Seed for the random number generator
+    pub seed: u64,
+}
+
+impl MeasurementNoise {
+    /// Returns the input values, one per wing, with noise added, where the noise is computed for 
+    /// the given quantity and time.
+    pub fn apply(&self, values: &[Float], quantity: MeasuredQuantity, time: Float) -> Vec<Float> {
+        if self.standard_deviation == 0.0 {
+            return values.to_vec();
+        }
+
+        values.iter().enumerate().map(
+            |(wing_index, value)| {
+                let mut rng = StdRng::seed_from_u64(
+                    self.value_seed(quantity, wing_index, time)
+                );
+
+                value + self.standard_deviation * Self::standard_normal_sample(&mut rng)
+            }
+        ).collect()
+    }
+
+    /// Combines the seed with the quantity, the wing index and the time, so that each combination
+    /// gets an independent random number sequence.
+    fn value_seed(&self, quantity: MeasuredQuantity, wing_index: usize, time: Float) -> u64 {
+        let time_in_microseconds = (time * 1e6).round() as i64 as u64;
+
+        [quantity as u64, wing_index as u64, time_in_microseconds].iter().fold(
+            self.seed,
+            |seed, value| Self::mix(seed ^ Self::mix(*value))
+        )
+    }
+
+    /// Bit mixing function from the SplitMix64 generator.
+    fn mix(value: u64) -> u64 {
+        let mut z = value.wrapping_add(0x9E3779B97F4A7C15);
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+        z ^ (z >> 31)
+    }
+
+    /// Samples a value from the standard normal distribution, using the Box-Muller transform.
+    fn standard_normal_sample(rng: &mut StdRng) -> Float {
+        let u1: Float = 1.0 - rng.random::<Float>();
+        let u2: Float = rng.random::<Float>();
+
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
+
+impl MeasurementSettings {
+    /// Adds noise to the measured values according to the noise model, if there is one.
+    pub fn add_noise(
+        &self, 
+        values: Vec<Float>, 
+        quantity: MeasuredQuantity, 
+        time: Float
+    ) -> Vec<Float> {
+        match &self.noise {
+            Some(noise) => noise.apply(&values, quantity, time),
+            None => values,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Mapping from a wing that is only used as a sensor, e.g., with the 
+/// [EffectiveWindSensor](crate::section_models::SectionModel::EffectiveWindSensor) section model,
+/// to the force-producing wings that should use its measurements.
+pub struct SensorMapping {
+    /// Index of the sensor wing
+    pub sensor_wing: usize,
+    /// Indices of the wings that get the measured velocity and apparent wind direction from the 
+    /// sensor wing
+    pub target_wings: Vec<usize>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -45,6 +155,37 @@ pub struct FlowMeasurementSettings {
     pub wind_direction: MeasurementSettings,
     #[serde(default)]
     pub wind_velocity: MeasurementSettings,
+    #[serde(default)]
+    /// Optional mapping from sensor wings to force-producing wings. Empty by default, which means 
+    /// that all wings use their own measurements.
+    pub sensor_mapping: Vec<SensorMapping>,
+}
+
+impl FlowMeasurementSettings {
+    /// Replaces the measured velocity and apparent wind direction of the target wings with the 
+    /// values from the sensor wing, according to the sensor mapping. Indices outside the number of
+    /// wings are ignored.
+    pub fn apply_sensor_mapping(
+        &self, 
+        velocities: &mut [Float], 
+        apparent_wind_directions: &mut [Float]
+    ) {
+        let nr_wings = velocities.len().min(apparent_wind_directions.len());
+
+        for mapping in &self.sensor_mapping {
+            if mapping.sensor_wing >= nr_wings {
+                continue;
+            }
+
+            let sensor_velocity = velocities[mapping.sensor_wing];
+            let sensor_direction = apparent_wind_directions[mapping.sensor_wing];
+
+            for &target_wing in mapping.target_wings.iter().filter(|&&index| index < nr_wings) {
+                velocities[target_wing] = sensor_velocity;
+                apparent_wind_directions[target_wing] = sensor_direction;
+            }
+        }
+    }
 }
 
 pub fn measure_float_values(
@@ -84,10 +225,39 @@ pub fn measure_angles_of_attack(
     simulation_result: &SimulationResult,
     measurement_settings: &MeasurementSettings,
 ) -> Vec<Float> {
-    measure_float_values(
+    let measured_values = measure_float_values(
         &simulation_result.force_input.angles_of_attack,
         simulation_result.wing_indices.clone(),
         measurement_settings
+    );
+
+    measurement_settings.add_noise(
+        measured_values, MeasuredQuantity::AngleOfAttack, simulation_result.time
+    )
+}
+
+/// Measures the geometric angle of attack, meaning the angle between the chord and the felt 
+/// freestream velocity, without any lift-induced velocities. This is in contrast to 
+/// [measure_angles_of_attack], which measures the effective angle of attack, where the lift-induced
+/// velocities are included.
+pub fn measure_geometric_angles_of_attack(
+    simulation_result: &SimulationResult,
+    line_force_model: &LineForceModel,
+    measurement_settings: &MeasurementSettings,
+) -> Vec<Float> {
+    let angles_of_attack = line_force_model.angles_of_attack(
+        &simulation_result.solver_input_ctrl_points_velocity,
+        CoordinateSystem::Global
+    );
+
+    let measured_values = measure_float_values(
+        &angles_of_attack,
+        simulation_result.wing_indices.clone(),
+        measurement_settings
+    );
+
+    measurement_settings.add_noise(
+        measured_values, MeasuredQuantity::AngleOfAttack, simulation_result.time
     )
 }
 
@@ -100,10 +270,14 @@ pub fn measure_wind_velocity_magnitude(
         .map(|v| v.length())
         .collect();
 
-    measure_float_values(
+    let measured_values = measure_float_values(
         &velocity_magnitude,
         simulation_result.wing_indices.clone(),
         measurement_settings
+    );
+
+    measurement_settings.add_noise(
+        measured_values, MeasuredQuantity::WindVelocity, simulation_result.time
     )
 }
 
@@ -126,9 +300,13 @@ pub fn measure_apparent_wind_direction(
             line_force_model
         );
 
-    measure_float_values(
+    let measured_values = measure_float_values(
         &wind_directions, 
         simulation_result.wing_indices.clone(),
         measurement_settings
+    );
+
+    measurement_settings.add_noise(
+        measured_values, MeasuredQuantity::WindDirection, simulation_result.time
     )
 }
\ No newline at end of file
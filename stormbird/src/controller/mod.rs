@@ -10,16 +10,37 @@ pub mod input;
 pub mod output;
 pub mod measurements;
 pub mod set_points;
+pub mod set_point_diagnostics;
+pub mod actuator;
+pub mod feedback;
+pub mod force_ratio;
+pub mod interpolation;
+pub mod moving_average;
+pub mod power_tracking;
+pub mod state;
 pub mod prelude;
 
+#[cfg(test)]
+mod tests;
+
 use input::ControllerInput;
 use output::ControllerOutput;
 use set_points::ControllerSetPoints;
+use state::ControllerState;
 use measurements::FlowMeasurementSettings;
+use builder::ControllerBuilder;
 
 use stormath::type_aliases::Float;
 
+use crate::error::Error;
+
 #[derive(Debug, Clone)]
+/// A controller that sets the local wing angles and the section models' internal state based on
+/// set points that depend on the apparent wind direction. 
+/// 
+/// The controller only depends on the [ControllerInput] given to the [Controller::update] method,
+/// and can therefore also be used as a standalone component, without any line force model, for 
+/// instance when the aerodynamic forces are computed by some other model.
 pub struct Controller {
     /// Vector containing the set points for all the sails
     pub set_points: Vec<ControllerSetPoints>,
@@ -33,16 +54,90 @@ pub struct Controller {
     pub time_step_index: usize,
     /// Switch to determine which velocity to use when measuring the apparent wind direction
     pub use_input_velocity_for_apparent_wind_direction: bool,
+    /// Optional number of inputs in a moving average of the measured flow conditions. The 
+    /// averaged values are used by the set points instead of the latest measurements.
+    pub moving_average_window_size: Option<usize>,
+    /// Internal state for each sail, kept between updates. One entry for each set point.
+    pub state: Vec<ControllerState>,
 }
 
 impl Controller {
+    /// Creates a new controller directly from the set points and flow measurement settings, 
+    /// without going through JSON. The remaining settings are given default values, as in 
+    /// [ControllerBuilder::from_parts]. Returns an error if the set points are not consistent.
+    pub fn new(
+        set_points: Vec<ControllerSetPoints>,
+        flow_measurement_settings: FlowMeasurementSettings
+    ) -> Result<Self, Error> {
+        ControllerBuilder::from_parts(set_points, flow_measurement_settings).build()
+    }
+
+    /// Creates a new controller from a string that describes a [ControllerBuilder] in a JSON 
+    /// format.
+    pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
+        let builder = ControllerBuilder::from_json_string(setup_string)?;
+
+        builder.build()
+    }
+
+    /// Creates a new controller from a file containing a [ControllerBuilder] in a JSON format.
+    pub fn new_from_file(file_path: &str) -> Result<Self, Error> {
+        let builder = ControllerBuilder::from_json_file(file_path)?;
+
+        builder.build()
+    }
+
+    /// Returns the remaining control authority for each sail, given the current commands. See 
+    /// [set_point_diagnostics::ControlAuthorityMargin] for details.
+    pub fn control_authority_margins(
+        &self, 
+        commands: &[ControllerOutput]
+    ) -> Vec<set_point_diagnostics::ControlAuthorityMargin> {
+        set_point_diagnostics::ControlAuthorityMargin::for_all_sails(&self.set_points, commands)
+    }
+
+    /// Resets the internal state for all sails. The state vector is also resized to match the 
+    /// number of set points, in case these have been changed after the controller was built.
+    pub fn reset_state(&mut self) {
+        self.state = vec![ControllerState::default(); self.set_points.len()];
+    }
+
+    /// Returns new control values for each wing, if it is time to update the controller. The input
+    /// must contain one [ControllerInput] for each set point in the controller.
+    ///
+    /// The method takes a mutable reference, as the controller keeps an internal state for each 
+    /// sail between updates, for instance the integrated error of a PID controller. This state is 
+    /// reset on every call before the start time, so that it is only accumulated from the start 
+    /// time. This includes the moving average of the measurements, if it is used, which is 
+    /// updated on every call from the start time, also when the controller is not updated. Before
+    /// the start time, the latest measurements are used directly. The state is also reset if it 
+    /// does not have one entry for each set point, for instance when the controller is constructed
+    /// directly from its fields or the set points are changed after it was built.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "controller_update", level = "debug", skip(self, time_step, input)
+    ))]
     pub fn update(
-        &self,
+        &mut self,
         time: Float,
         time_step: Float, 
         input: &[ControllerInput],
     ) -> Option<Vec<ControllerOutput>> {
         let initialization_done = time >= self.start_time;
+
+        if !initialization_done || self.state.len() != self.set_points.len() {
+            self.reset_state();
+        }
+
+        let averaged_input: Option<Vec<ControllerInput>> = self.moving_average_window_size
+            .filter(|_| initialization_done)
+            .map(|window_size| input.iter().zip(self.state.iter_mut()).map(
+                |(input_single, state)| {
+                    state.measurement_window.averaged_input(input_single, window_size)
+                }
+            ).collect());
+
+        let input = averaged_input.as_deref().unwrap_or(input);
+
         let time_to_update =  self.time_step_index % self.time_steps_between_updates == 0;
         let first_time_step = self.time_step_index == 1;
         
@@ -52,7 +147,30 @@ impl Controller {
             let mut out = Vec::with_capacity(nr_wings);
             
             for i in 0..nr_wings {
-                let output_single = self.set_points[i].get_new_output(&input[i], time_step);
+                let output_single = self.set_points[i].get_new_output(
+                    &input[i], time_step, &mut self.state[i]
+                );
+
+                #[cfg(feature = "logging")]
+                log::debug!(
+                    target: "stormbird::controller",
+                    "time: {}, wing: {}, apparent wind direction: {}, local wing angle: {}, \
+                    section model internal state: {}",
+                    time, 
+                    i, 
+                    input[i].apparent_wind_direction, 
+                    output_single.local_wing_angle,
+                    output_single.section_model_internal_state
+                );
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    wing = i,
+                    apparent_wind_direction = input[i].apparent_wind_direction,
+                    local_wing_angle = output_single.local_wing_angle,
+                    section_model_internal_state = output_single.section_model_internal_state,
+                    "controller output"
+                );
                 
                 out.push(output_single)
             }
@@ -0,0 +1,71 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Moving average of the measured flow conditions that are used as input to the controller.
+
+use std::collections::VecDeque;
+
+use stormath::type_aliases::Float;
+
+use super::input::ControllerInput;
+
+#[derive(Debug, Clone, Copy, Default)]
+/// The measured values from a single controller input
+pub struct MeasurementSample {
+    pub apparent_wind_direction: Float,
+    pub angle_of_attack: Float,
+    pub velocity: Float,
+}
+
+#[derive(Debug, Clone, Default)]
+/// The most recent measurements for a single sail, used to compute a moving average.
+pub struct MeasurementWindow {
+    pub samples: VecDeque<MeasurementSample>,
+}
+
+impl MeasurementWindow {
+    /// Adds the measurements in the input to the window, removes the oldest samples so that the 
+    /// window contains at most `window_size` samples, and returns a copy of the input where the 
+    /// measurements are replaced by the averages over the window.
+    ///
+    /// The apparent wind direction is averaged as a direction, i.e., by averaging the unit vectors
+    /// in each direction, so that directions on either side of the ±π wraparound are handled 
+    /// correctly.
+    pub fn averaged_input(
+        &mut self, 
+        input: &ControllerInput, 
+        window_size: usize
+    ) -> ControllerInput {
+        self.samples.push_back(MeasurementSample {
+            apparent_wind_direction: input.apparent_wind_direction,
+            angle_of_attack: input.angle_of_attack,
+            velocity: input.velocity,
+        });
+
+        while self.samples.len() > window_size.max(1) {
+            self.samples.pop_front();
+        }
+
+        let nr_samples = self.samples.len() as Float;
+
+        let mut sin_sum = 0.0;
+        let mut cos_sum = 0.0;
+        let mut angle_of_attack_sum = 0.0;
+        let mut velocity_sum = 0.0;
+
+        for sample in &self.samples {
+            sin_sum += sample.apparent_wind_direction.sin();
+            cos_sum += sample.apparent_wind_direction.cos();
+            angle_of_attack_sum += sample.angle_of_attack;
+            velocity_sum += sample.velocity;
+        }
+
+        ControllerInput {
+            apparent_wind_direction: sin_sum.atan2(cos_sum),
+            angle_of_attack: angle_of_attack_sum / nr_samples,
+            velocity: velocity_sum / nr_samples,
+            ..input.clone()
+        }
+    }
+}
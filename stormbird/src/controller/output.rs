@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::io_utils::csv_data;
+use crate::io_utils::csv_data::{CsvPrecision, RotatingCsvWriter};
 use stormath::type_aliases::Float;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -18,7 +18,7 @@ pub struct ControllerOutput {
 
 
 impl ControllerOutput {
-    pub fn as_csv_string(output_to_write: &[Self]) -> (String, String) {
+    pub fn as_csv_string(output_to_write: &[Self], precision: CsvPrecision) -> (String, String) {
         let mut header = String::new();
         let mut data = String::new();
         
@@ -31,7 +31,7 @@ impl ControllerOutput {
             }
             
             header.push_str(&format!("local_wing_angle_{}", i));
-            data.push_str(&format!("{:.6}", output_to_write[i].local_wing_angle));
+            data.push_str(&precision.format_value(output_to_write[i].local_wing_angle));
         }
         
         for i in 0..nr_wings {
@@ -41,16 +41,23 @@ impl ControllerOutput {
             }
             
             header.push_str(&format!("section_model_internal_state_{}", i));
-            data.push_str(&format!("{:.6}", output_to_write[i].section_model_internal_state));
+            data.push_str(&precision.format_value(output_to_write[i].section_model_internal_state));
         }
         
         (header, data)
     }
 
-    pub fn write_to_csv_file(output_to_write: &[Self], file_path: &str) {
-        let (header, data) = Self::as_csv_string(output_to_write);
+    /// Appends the output to a CSV file, using the input writer to rotate the file if it has a 
+    /// limit on the number of lines. The values are formatted according to the input precision.
+    pub fn write_to_csv_file(
+        output_to_write: &[Self], 
+        file_path: &str, 
+        writer: &mut RotatingCsvWriter,
+        precision: CsvPrecision,
+    ) {
+        let (header, data) = Self::as_csv_string(output_to_write, precision);
 
-        let _ = csv_data::create_or_append_header_and_data_strings_file(
+        let _ = writer.write(
             file_path,
             &header,
             &data,
@@ -0,0 +1,131 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tracking of the maximum net power point of a sail, by adjusting the section model internal 
+//! state, such as the spin ratio of a rotor sail, based on the measured power.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+fn default_initial_step() -> Float {0.25}
+fn default_min_step() -> Float {0.01}
+fn default_step_reduction_factor() -> Float {0.5}
+fn default_max_updates() -> usize {50}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Settings for a perturb-and-observe search for the section model internal state that gives the 
+/// maximum net power, defined as the thrust times the ship velocity minus the input power.
+///
+/// The internal state is changed with a fixed step at every update. If the net power decreases 
+/// compared to the previous update, the direction of the step is reversed and the step is reduced.
+/// The search stops when the step is smaller than the minimum step, or after the maximum number of
+/// updates, after which the internal state is held constant. The value from the set point data is 
+/// used as the starting point, in the same units, e.g., as spin ratio for rotor sails.
+pub struct MaxPowerPointTracking {
+    #[serde(default="default_initial_step")]
+    /// Size of the first change in the internal state
+    pub initial_step: Float,
+    #[serde(default="default_min_step")]
+    /// The search is converged when the step is smaller than this value
+    pub min_step: Float,
+    #[serde(default="default_step_reduction_factor")]
+    /// Factor applied to the step each time the net power decreases
+    pub step_reduction_factor: Float,
+    #[serde(default="default_max_updates")]
+    /// Maximum number of updates before the internal state is held constant
+    pub max_updates: usize,
+    #[serde(default)]
+    /// Optional lower limit of the internal state in the search
+    pub min_value: Option<Float>,
+    #[serde(default)]
+    /// Optional upper limit of the internal state in the search
+    pub max_value: Option<Float>,
+}
+
+#[derive(Debug, Clone, Default)]
+/// State of the search for a single sail, kept between updates
+pub struct PowerTrackingState {
+    /// The internal state that was last commanded
+    pub value: Float,
+    /// The next change in the internal state, including the direction
+    pub step: Float,
+    /// Net power measured at the previous update. `None` before the first measurement.
+    pub previous_net_power: Option<Float>,
+    pub nr_updates: usize,
+}
+
+impl MaxPowerPointTracking {
+    /// Net power from the sail, as the thrust power minus the input power
+    pub fn net_power(thrust: Float, ship_velocity: Float, input_power: Float) -> Float {
+        thrust * ship_velocity - input_power
+    }
+
+    /// Returns true if the search has stopped
+    pub fn is_converged(&self, state: &PowerTrackingState) -> bool {
+        state.step.abs() < self.min_step || state.nr_updates >= self.max_updates
+    }
+
+    /// Returns the next internal state to command, based on the net power measured for the last 
+    /// commanded value. The state is `None` before the first update, in which case it is 
+    /// initialized with the initial value, which is returned without any change. If the net power 
+    /// is not available, the search is not advanced and the last commanded value is returned.
+    pub fn updated_value(
+        &self,
+        state: &mut Option<PowerTrackingState>,
+        initial_value: Float,
+        net_power: Option<Float>,
+    ) -> Float {
+        let Some(state) = state else {
+            *state = Some(PowerTrackingState {
+                value: self.limited_value(initial_value),
+                step: self.initial_step,
+                previous_net_power: None,
+                nr_updates: 0,
+            });
+
+            return self.limited_value(initial_value);
+        };
+
+        let Some(net_power) = net_power else {
+            return state.value;
+        };
+
+        if self.is_converged(state) {
+            return state.value;
+        }
+
+        if let Some(previous_net_power) = state.previous_net_power {
+            if net_power < previous_net_power {
+                state.step *= -self.step_reduction_factor;
+            }
+
+            state.nr_updates += 1;
+
+            if self.is_converged(state) {
+                return state.value;
+            }
+        }
+
+        state.previous_net_power = Some(net_power);
+        state.value = self.limited_value(state.value + state.step);
+
+        state.value
+    }
+
+    fn limited_value(&self, value: Float) -> Float {
+        let mut limited_value = value;
+
+        if let Some(min_value) = self.min_value {
+            limited_value = limited_value.max(min_value);
+        }
+
+        if let Some(max_value) = self.max_value {
+            limited_value = limited_value.min(max_value);
+        }
+
+        limited_value
+    }
+}
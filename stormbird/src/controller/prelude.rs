@@ -7,11 +7,21 @@ pub use super::{
     Controller,
     builder::ControllerBuilder,
     set_points::ControllerSetPoints,
+    set_point_diagnostics::{SetPointRangeRecorder, ControlAuthorityMargin},
+    actuator::{ActuatorModel, FirstOrderActuator},
+    feedback::{PidGains, PidState},
+    force_ratio::ForceRatioTarget,
+    interpolation::InterpolationKind,
+    power_tracking::{MaxPowerPointTracking, PowerTrackingState},
+    state::ControllerState,
     input::ControllerInput,
     output::ControllerOutput,
     measurements::{
         MeasurementType,
         MeasurementSettings,
-        FlowMeasurementSettings
+        MeasurementNoise,
+        MeasuredQuantity,
+        FlowMeasurementSettings,
+        SensorMapping,
     }
 };
@@ -0,0 +1,111 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Diagnostics that can be used to evaluate how a controller behaves over a simulation, for 
+//! instance when tuning the set points.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+use super::input::ControllerInput;
+use super::output::ControllerOutput;
+use super::set_points::ControllerSetPoints;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Recorder that keeps track of how often the measured apparent wind direction for each sail is 
+/// outside the range of the apparent wind direction data in the set points. In these cases, the
+/// set points are clamped to the values at the ends of the data, meaning that the sail is operated
+/// outside the region where the set points are known.
+pub struct SetPointRangeRecorder {
+    /// The number of recorded steps
+    pub nr_steps: usize,
+    /// The number of recorded steps where the apparent wind direction was out of range, for each 
+    /// sail
+    pub nr_steps_out_of_range: Vec<usize>,
+}
+
+impl SetPointRangeRecorder {
+    pub fn new(nr_wings: usize) -> Self {
+        Self {
+            nr_steps: 0,
+            nr_steps_out_of_range: vec![0; nr_wings],
+        }
+    }
+
+    /// Records whether the apparent wind direction in the input is out of range of the set points,
+    /// for each sail. The input must contain one value for each set point.
+    pub fn record(&mut self, set_points: &[ControllerSetPoints], input: &[ControllerInput]) {
+        for (i, (set_point, input_single)) in set_points.iter().zip(input.iter()).enumerate() {
+            if set_point.apparent_wind_direction_is_out_of_range(input_single.apparent_wind_direction) {
+                self.nr_steps_out_of_range[i] += 1;
+            }
+        }
+
+        self.nr_steps += 1;
+    }
+
+    /// Returns the fraction of the recorded steps where the apparent wind direction was out of 
+    /// range, for each sail.
+    pub fn out_of_range_fractions(&self) -> Vec<Float> {
+        self.nr_steps_out_of_range.iter().map(
+            |nr_out_of_range| {
+                if self.nr_steps > 0 {
+                    *nr_out_of_range as Float / self.nr_steps as Float
+                } else {
+                    0.0
+                }
+            }
+        ).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+/// How much more the controller can trim a sail before the command saturates. Each margin is the
+/// distance from the current command to the closest limit of the commandable range, and is 
+/// negative if the command is outside the range. A margin is `None` if the value is unlimited.
+pub struct ControlAuthorityMargin {
+    /// Remaining margin for the local wing angle
+    pub local_wing_angle: Option<Float>,
+    /// Remaining margin for the section model internal state, for instance the RPS of a rotor sail
+    pub section_model_internal_state: Option<Float>,
+}
+
+impl ControlAuthorityMargin {
+    /// Computes the margin of the current command relative to the limits in the set points.
+    pub fn new(set_points: &ControllerSetPoints, command: &ControllerOutput) -> Self {
+        Self {
+            local_wing_angle: Self::margin_to_limits(
+                command.local_wing_angle, 
+                set_points.local_wing_angle_limits()
+            ),
+            section_model_internal_state: Self::margin_to_limits(
+                command.section_model_internal_state,
+                set_points.section_model_internal_state_limits()
+            ),
+        }
+    }
+
+    /// Computes the margin for all sails. The commands must contain one value for each set point.
+    pub fn for_all_sails(
+        set_points: &[ControllerSetPoints], 
+        commands: &[ControllerOutput]
+    ) -> Vec<Self> {
+        set_points.iter().zip(commands.iter()).map(
+            |(set_point, command)| Self::new(set_point, command)
+        ).collect()
+    }
+
+    fn margin_to_limits(value: Float, limits: (Option<Float>, Option<Float>)) -> Option<Float> {
+        let margin_to_min = limits.0.map(|min_value| value - min_value);
+        let margin_to_max = limits.1.map(|max_value| max_value - value);
+
+        match (margin_to_min, margin_to_max) {
+            (Some(to_min), Some(to_max)) => Some(to_min.min(to_max)),
+            (Some(to_min), None) => Some(to_min),
+            (None, Some(to_max)) => Some(to_max),
+            (None, None) => None,
+        }
+    }
+}
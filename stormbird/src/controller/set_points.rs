@@ -4,10 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use stormath::interpolation::linear_interpolation;
-
 use super::prelude::*;
 
+use crate::error::Error;
+
 use stormath::type_aliases::Float;
 use stormath::consts::{PI, TAU};
 
@@ -48,7 +48,7 @@ pub fn limit_value(
     let raw_difference = raw_new_value - old_value;
     
     if raw_difference.abs() > max_change {
-        old_value * max_change * raw_difference.signum()
+        old_value + max_change * raw_difference.signum()
     } else {
         raw_new_value
     }
@@ -65,31 +65,105 @@ pub struct ControllerSetPoints {
     pub section_model_internal_state_data: Option<Vec<Float>>,
     #[serde(default)]
     pub internal_state_type: InternalStateType,
+    /// How to interpolate the angle of attack and internal state data between the apparent wind 
+    /// directions
+    #[serde(default)]
+    pub interpolation: InterpolationKind,
+    /// If true, the set point data is interpolated on the circle, so that the gap between the last
+    /// and the first apparent wind direction, across the ±π wraparound, is interpolated along the 
+    /// shortest arc instead of being held constant.
+    #[serde(default)]
+    pub periodic: bool,
     #[serde(default)]
     pub use_effective_angle_of_attack: bool,
     #[serde(default)]
     pub max_local_wing_angle_change_rate: Option<Float>,
+    /// Optional deadband on the local wing angle. A new target angle is only used if it differs 
+    /// from the current angle by more than this value, to prevent chattering when the apparent 
+    /// wind direction varies around a switch in the set points.
+    #[serde(default)]
+    pub deadband: Option<Float>,
+    #[serde(default)]
+    pub max_internal_section_state_change_rate: Option<Float>,
+    /// Dynamics of the actuator that applies the local wing angle
+    #[serde(default)]
+    pub local_wing_angle_actuator: ActuatorModel,
+    /// Dynamics of the actuator that applies the section model internal state
     #[serde(default)]
-    pub max_internal_section_state_change_rate: Option<Float>
+    pub internal_state_actuator: ActuatorModel,
+    /// Optional gains for closed-loop control of the effective angle of attack. Only used when
+    /// `use_effective_angle_of_attack` is true.
+    #[serde(default)]
+    pub feedback_gains: Option<PidGains>,
+    /// Optional target for the ratio between the thrust and the side force on the sail. When 
+    /// given, the loading in the input is scaled with a factor that is adjusted to reach the 
+    /// target, based on the forces in the input.
+    #[serde(default)]
+    pub force_ratio_target: Option<ForceRatioTarget>,
+    /// Optional search for the section model internal state that gives the maximum net power. 
+    /// When given, this replaces the internal state from the set point data, which is then only 
+    /// used as the starting point.
+    #[serde(default)]
+    pub max_power_point_tracking: Option<MaxPowerPointTracking>,
 }
 
 impl ControllerSetPoints {
-    pub fn get_new_output(&self, input: &ControllerInput, time_step: Float) -> ControllerOutput {
+    /// Returns the new control values for the sail. The state contains the values that are 
+    /// remembered between updates, and is advanced by this method.
+    pub fn get_new_output(
+        &self, 
+        input: &ControllerInput, 
+        time_step: Float,
+        state: &mut ControllerState,
+    ) -> ControllerOutput {
+        let scaled_input;
+
+        let input = if self.force_ratio_target.is_some() {
+            scaled_input = self.input_with_force_ratio_loading(input, state);
+
+            &scaled_input
+        } else {
+            input
+        };
+
         let mut local_wing_angle = if self.use_effective_angle_of_attack {
-            self.get_local_wing_angle_effective(input)
+            if self.feedback_gains.is_some() {
+                self.get_local_wing_angle_feedback(input, time_step, state)
+            } else {
+                self.get_local_wing_angle_effective(input)
+            }
         } else {
             self.get_local_wing_angle_geometric(input)
         };
         
+        if let Some(deadband) = self.deadband {
+            local_wing_angle = Self::apply_deadband(
+                input.current_local_wing_angle, 
+                local_wing_angle, 
+                deadband
+            );
+        }
+        
         if self.max_local_wing_angle_change_rate.is_some() {
+            // The change is measured as the smallest angle, so that the limiter does not turn the 
+            // wing the long way around when the target crosses the ±π wraparound
+            let target_angle = input.current_local_wing_angle + 
+                Self::correct_angle_to_be_between_pi_and_negative_pi(
+                    local_wing_angle - input.current_local_wing_angle
+                );
+
             local_wing_angle = limit_value(
                 input.current_local_wing_angle, 
-                local_wing_angle, 
+                target_angle, 
                 self.max_local_wing_angle_change_rate.unwrap() * time_step
             )
         }
 
-        let mut section_model_internal_state = self.get_section_model_internal_state(input);
+        let mut section_model_internal_state = if self.max_power_point_tracking.is_some() {
+            self.get_section_model_internal_state_power_tracking(input, state)
+        } else {
+            self.get_section_model_internal_state(input)
+        };
         
         if self.max_internal_section_state_change_rate.is_some() {
             section_model_internal_state = limit_value(
@@ -99,6 +173,18 @@ impl ControllerSetPoints {
             )
         }
 
+        local_wing_angle = self.local_wing_angle_actuator.applied_value(
+            input.current_local_wing_angle,
+            local_wing_angle,
+            time_step
+        );
+
+        section_model_internal_state = self.internal_state_actuator.applied_value(
+            input.current_section_model_internal_state,
+            section_model_internal_state,
+            time_step
+        );
+
         ControllerOutput {
             local_wing_angle,
             section_model_internal_state,
@@ -139,34 +225,133 @@ impl ControllerSetPoints {
         }
     }
 
+    /// Computes the local wing angle with closed-loop control of the effective angle of attack.
+    ///
+    /// The geometric wing angle, which would give the target angle of attack without any 
+    /// lift-induced velocities, is used as a feedforward term. A PID controller on the error 
+    /// between the measured and the target angle of attack adds a correction to this value. A 
+    /// positive error increases the wing angle, which reduces the angle of attack.
+    pub fn get_local_wing_angle_feedback(
+        &self, 
+        input: &ControllerInput, 
+        time_step: Float,
+        state: &mut ControllerState,
+    ) -> Float {
+        let Some(gains) = &self.feedback_gains else {
+            return self.get_local_wing_angle_effective(input);
+        };
+
+        if self.angle_of_attack_data.is_none() {
+            return 0.0;
+        }
+
+        let set_point = input.loading * self.get_angle_of_attack_set_point(
+            input.apparent_wind_direction
+        );
+
+        let angle_error = Self::correct_angle_to_be_between_pi_and_negative_pi(
+            input.angle_of_attack - set_point
+        );
+
+        let correction = gains.correction(angle_error, time_step, &mut state.feedback);
+
+        Self::correct_angle_to_be_between_pi_and_negative_pi(
+            input.apparent_wind_direction - set_point + correction
+        )
+    }
+
+    /// Updates the loading factor from the force ratio control, and returns a copy of the input 
+    /// where the loading is scaled with this factor.
+    pub fn input_with_force_ratio_loading(
+        &self, 
+        input: &ControllerInput, 
+        state: &mut ControllerState
+    ) -> ControllerInput {
+        let Some(target) = &self.force_ratio_target else {
+            return input.clone();
+        };
+
+        let loading_factor = target.updated_loading_factor(
+            state.force_ratio_loading_factor, 
+            input.thrust, 
+            input.side_force
+        );
+
+        state.force_ratio_loading_factor = Some(loading_factor);
+
+        ControllerInput {
+            loading: input.loading * loading_factor,
+            ..input.clone()
+        }
+    }
+
     pub fn get_section_model_internal_state(&self, input: &ControllerInput) -> Float {
         if self.section_model_internal_state_data.is_some() {
             let internal_state_raw = input.loading * self.get_internal_state_set_point(
                 input.apparent_wind_direction
             );
 
-            let internal_state = match self.internal_state_type {
-                InternalStateType::Generic => {internal_state_raw}
-                InternalStateType::SpinRatio(ref conversion) => {
-                    let velocity = input.velocity;
-
-                    conversion.get_rps_from_spin_ratio(internal_state_raw, velocity)
-                }
-            };
-
-            internal_state
+            self.convert_internal_state(internal_state_raw, input)
         } else {
             0.0
         }
     }
 
+    /// Computes the section model internal state with maximum power point tracking. The search is 
+    /// done on the raw internal state, i.e., the spin ratio for rotor sails, which is converted in
+    /// the same way as the values from the set point data. The search is only advanced when the 
+    /// input contains the data needed to compute the net power, see [ControllerInput::net_power].
+    pub fn get_section_model_internal_state_power_tracking(
+        &self,
+        input: &ControllerInput,
+        state: &mut ControllerState,
+    ) -> Float {
+        let Some(tracking) = &self.max_power_point_tracking else {
+            return self.get_section_model_internal_state(input);
+        };
+
+        let initial_value = input.loading * self.get_internal_state_set_point(
+            input.apparent_wind_direction
+        );
+
+        let internal_state_raw = tracking.updated_value(
+            &mut state.power_tracking, initial_value, input.net_power()
+        );
+
+        self.convert_internal_state(internal_state_raw, input)
+    }
+
+    fn convert_internal_state(&self, internal_state_raw: Float, input: &ControllerInput) -> Float {
+        match self.internal_state_type {
+            InternalStateType::Generic => internal_state_raw,
+            InternalStateType::SpinRatio(ref conversion) => {
+                conversion.get_rps_from_spin_ratio(internal_state_raw, input.velocity)
+            }
+        }
+    }
+
+    /// Checks if the input apparent wind direction is outside the range of the apparent wind 
+    /// direction data, in which case the set points are clamped to the values at the ends. Always 
+    /// false for periodic set points.
+    pub fn apparent_wind_direction_is_out_of_range(&self, apparent_wind_direction: Float) -> bool {
+        if self.periodic {
+            return false;
+        }
+
+        let first = self.apparent_wind_directions_data.first();
+        let last = self.apparent_wind_directions_data.last();
+
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                apparent_wind_direction < *first || apparent_wind_direction > *last
+            },
+            _ => false
+        }
+    }
+
     pub fn get_angle_of_attack_set_point(&self, apparent_wind_direction: Float) -> Float {
         if let Some(angle_data) = &self.angle_of_attack_data {
-            linear_interpolation(
-                apparent_wind_direction,
-                &self.apparent_wind_directions_data,
-                angle_data,
-            )
+            self.interpolate_set_point(apparent_wind_direction, angle_data)
         } else {
             0.0
         }
@@ -174,16 +359,173 @@ impl ControllerSetPoints {
 
     pub fn get_internal_state_set_point(&self, apparent_wind_direction: Float) -> Float {
         if let Some(internal_states_data) = &self.section_model_internal_state_data {
-            linear_interpolation(
-                apparent_wind_direction,
-                &self.apparent_wind_directions_data,
-                internal_states_data,
-            )
+            self.interpolate_set_point(apparent_wind_direction, internal_states_data)
         } else {
             0.0
         }
     }
 
+    /// Returns the lower and upper limits of the commandable local wing angle, given by the 
+    /// saturation of the actuator. A limit is `None` if the angle is unlimited in that direction.
+    pub fn local_wing_angle_limits(&self) -> (Option<Float>, Option<Float>) {
+        self.local_wing_angle_actuator.limits()
+    }
+
+    /// Returns the lower and upper limits of the commandable section model internal state. 
+    ///
+    /// The limits are the strictest of the range of the internal state set point data, the 
+    /// maximum RPS when the internal state is a spin ratio, and the saturation of the actuator. 
+    /// A limit is `None` if the internal state is unlimited in that direction.
+    pub fn section_model_internal_state_limits(&self) -> (Option<Float>, Option<Float>) {
+        let mut lower: Option<Float> = None;
+        let mut upper: Option<Float> = None;
+
+        let mut add_limits = |min_value: Option<Float>, max_value: Option<Float>| {
+            if let Some(min_value) = min_value {
+                lower = Some(lower.map_or(min_value, |value| value.max(min_value)));
+            }
+
+            if let Some(max_value) = max_value {
+                upper = Some(upper.map_or(max_value, |value| value.min(max_value)));
+            }
+        };
+
+        match self.internal_state_type {
+            InternalStateType::Generic => {
+                if let Some(data) = &self.section_model_internal_state_data {
+                    let min_value = data.iter().cloned().reduce(Float::min);
+                    let max_value = data.iter().cloned().reduce(Float::max);
+
+                    add_limits(min_value, max_value);
+                }
+            },
+            InternalStateType::SpinRatio(ref conversion) => {
+                add_limits(Some(-conversion.max_rps), Some(conversion.max_rps));
+            }
+        }
+
+        let (actuator_min, actuator_max) = self.internal_state_actuator.limits();
+
+        add_limits(actuator_min, actuator_max);
+
+        (lower, upper)
+    }
+
+    /// Checks that the set point tables are consistent. There must be at least one apparent wind 
+    /// direction, and each data table must either have the same length as the apparent wind 
+    /// directions, or a single entry, which is then used as a constant value.
+    pub fn check_data(&self) -> Result<(), Error> {
+        let nr_directions = self.apparent_wind_directions_data.len();
+
+        if nr_directions == 0 {
+            return Err(Error::CustomStringError(
+                "The set points must contain at least one apparent wind direction".to_string()
+            ));
+        }
+
+        let tables = [
+            ("angle_of_attack_data", &self.angle_of_attack_data),
+            ("section_model_internal_state_data", &self.section_model_internal_state_data),
+        ];
+
+        for (name, table) in tables {
+            if let Some(data) = table {
+                if data.len() != nr_directions && data.len() != 1 {
+                    return Err(Error::CustomStringError(
+                        format!(
+                            "The {} must have one entry, or the same number of entries as the \
+                            apparent wind directions. Entries: {}, apparent wind directions: {}",
+                            name, data.len(), nr_directions
+                        )
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the set points only contain a single apparent wind direction, which means 
+    /// that the set points are constant for all apparent wind directions.
+    pub fn has_single_entry(&self) -> bool {
+        self.apparent_wind_directions_data.len() == 1
+    }
+
+    /// Interpolates the set point data as a function of the apparent wind direction. Tables with a
+    /// single entry are treated explicitly as a constant value, as the interpolation is not well 
+    /// defined in this case.
+    fn interpolate_set_point(&self, apparent_wind_direction: Float, data: &[Float]) -> Float {
+        if self.has_single_entry() || data.len() == 1 {
+            return data[0];
+        }
+
+        if self.periodic {
+            return self.interpolate_set_point_periodic(apparent_wind_direction, data);
+        }
+
+        self.interpolation.interpolate(
+            apparent_wind_direction,
+            &self.apparent_wind_directions_data,
+            data,
+        )
+    }
+
+    /// Interpolates the set point data on the circle. The apparent wind direction is moved to the 
+    /// range starting at the first data point and spanning one full turn, and the data is extended
+    /// with the last point one turn below the first point and the first point one turn above the 
+    /// last point, unless these are already covered by the data.
+    fn interpolate_set_point_periodic(
+        &self, 
+        apparent_wind_direction: Float, 
+        data: &[Float]
+    ) -> Float {
+        let directions = &self.apparent_wind_directions_data;
+
+        let first_direction = directions[0];
+        let last_direction = directions[directions.len() - 1];
+
+        let mut direction = Self::correct_angle_to_be_between_pi_and_negative_pi(
+            apparent_wind_direction
+        );
+
+        if direction < first_direction {
+            direction += TAU;
+        }
+
+        let mut extended_directions: Vec<Float> = Vec::with_capacity(directions.len() + 2);
+        let mut extended_data: Vec<Float> = Vec::with_capacity(directions.len() + 2);
+
+        if last_direction - TAU < first_direction {
+            extended_directions.push(last_direction - TAU);
+            extended_data.push(data[data.len() - 1]);
+        }
+
+        extended_directions.extend_from_slice(directions);
+        extended_data.extend_from_slice(data);
+
+        if first_direction + TAU > last_direction {
+            extended_directions.push(first_direction + TAU);
+            extended_data.push(data[0]);
+        }
+
+        self.interpolation.interpolate(direction, &extended_directions, &extended_data)
+    }
+
+    /// Returns the current angle if the target is within the deadband, and the target otherwise. 
+    /// The difference is measured as the smallest angle between the two, so that targets on 
+    /// either side of the ±π wraparound are treated as close.
+    pub fn apply_deadband(current_angle: Float, target_angle: Float, deadband: Float) -> Float {
+        let difference = Self::correct_angle_to_be_between_pi_and_negative_pi(
+            target_angle - current_angle
+        );
+
+        if difference.abs() < deadband {
+            current_angle
+        } else {
+            target_angle
+        }
+    }
+
     #[inline(always)]
     fn correct_angle_to_be_between_pi_and_negative_pi(angle: Float) -> Float {
         let mut corrected_angle = angle;
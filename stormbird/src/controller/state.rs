@@ -0,0 +1,32 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Internal state of the controller for a single sail, kept between updates.
+
+use stormath::type_aliases::Float;
+
+use super::feedback::PidState;
+use super::power_tracking::PowerTrackingState;
+use super::moving_average::MeasurementWindow;
+
+#[derive(Debug, Clone, Default)]
+/// State of the controller logic for a single sail that is remembered between updates, such as 
+/// integrated errors. The set points themselves are not changed by the controller.
+pub struct ControllerState {
+    /// State of the feedback controller on the effective angle of attack
+    pub feedback: PidState,
+    /// Loading factor from the force ratio control. `None` before the first update.
+    pub force_ratio_loading_factor: Option<Float>,
+    /// State of the maximum power point tracking. `None` before the first update.
+    pub power_tracking: Option<PowerTrackingState>,
+    /// Recent measurements, used when the controller averages the input
+    pub measurement_window: MeasurementWindow,
+}
+
+impl ControllerState {
+    /// Resets the state to the initial value
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
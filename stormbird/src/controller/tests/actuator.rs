@@ -0,0 +1,74 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the actuator dynamics between the controller command and the applied values.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+
+#[test]
+fn first_order_step_response() {
+    let time_constant = 2.0;
+    let time_step = 0.1;
+    let command = 0.4;
+
+    let setup_string = format!(r#"{{
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-1.0, 1.0],
+                "section_model_internal_state_data": [{command}, {command}],
+                "internal_state_actuator": {{
+                    "FirstOrder": {{
+                        "time_constant": {time_constant}
+                    }}
+                }}
+            }}
+        ]
+    }}"#);
+
+    let mut controller = Controller::new_from_string(&setup_string).unwrap();
+
+    let mut applied_state = 0.0;
+
+    for step in 1..=40 {
+        let input = vec![
+            ControllerInput {
+                loading: 1.0,
+                current_section_model_internal_state: applied_state,
+                ..Default::default()
+            }
+        ];
+
+        let output = controller.update(step as Float * time_step, time_step, &input).unwrap();
+
+        applied_state = output[0].section_model_internal_state;
+
+        let time = step as Float * time_step;
+        let expected_state = command * (1.0 - (-time / time_constant).exp());
+
+        assert!(
+            (applied_state - expected_state).abs() < 1e-12,
+            "Wrong response at t = {}: {} vs {}", time, applied_state, expected_state
+        );
+    }
+
+    dbg!(applied_state);
+
+    // After two time constants, the response should be around 86% of the command
+    assert!((applied_state / command - 0.8647).abs() < 1e-3);
+}
+
+#[test]
+fn first_order_saturation() {
+    let actuator = ActuatorModel::FirstOrder(FirstOrderActuator {
+        time_constant: 0.0,
+        min_value: Some(-0.2),
+        max_value: Some(0.2),
+    });
+
+    assert_eq!(actuator.applied_value(0.0, 1.0, 0.1), 0.2);
+    assert_eq!(actuator.applied_value(0.0, -1.0, 0.1), -0.2);
+    assert_eq!(ActuatorModel::default().applied_value(0.0, 1.0, 0.1), 1.0);
+}
@@ -0,0 +1,74 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the control authority margin, i.e., how far each command is from saturating.
+
+use crate::controller::prelude::*;
+use crate::controller::measurements::FlowMeasurementSettings;
+
+#[test]
+fn margin_near_and_far_from_limits() {
+    let max_rps = 180.0;
+
+    let set_points = ControllerSetPoints {
+        apparent_wind_directions_data: vec![-1.0, 1.0],
+        section_model_internal_state_data: Some(vec![0.0, max_rps]),
+        local_wing_angle_actuator: ActuatorModel::FirstOrder(FirstOrderActuator {
+            time_constant: 1.0,
+            min_value: Some(-0.5),
+            max_value: Some(0.5),
+        }),
+        ..Default::default()
+    };
+
+    let controller = Controller::new(
+        vec![set_points.clone(), set_points], 
+        FlowMeasurementSettings::default()
+    ).unwrap();
+
+    let commands = vec![
+        ControllerOutput {
+            local_wing_angle: 0.45,
+            section_model_internal_state: 175.0,
+        },
+        ControllerOutput {
+            local_wing_angle: 0.0,
+            section_model_internal_state: 90.0,
+        },
+    ];
+
+    let margins = controller.control_authority_margins(&commands);
+
+    dbg!(&margins);
+
+    assert_eq!(margins.len(), 2);
+
+    let near_limit_state = margins[0].section_model_internal_state.unwrap();
+    let mid_range_state = margins[1].section_model_internal_state.unwrap();
+
+    assert!(near_limit_state > 0.0);
+    assert!((near_limit_state - 5.0).abs() < 1e-9);
+    assert!(mid_range_state > near_limit_state);
+    assert!((mid_range_state - 90.0).abs() < 1e-9);
+
+    let near_limit_angle = margins[0].local_wing_angle.unwrap();
+    let mid_range_angle = margins[1].local_wing_angle.unwrap();
+
+    assert!(near_limit_angle > 0.0);
+    assert!(mid_range_angle > near_limit_angle);
+}
+
+#[test]
+fn unlimited_values_have_no_margin() {
+    let set_points = ControllerSetPoints {
+        apparent_wind_directions_data: vec![-1.0, 1.0],
+        angle_of_attack_data: Some(vec![-0.2, 0.2]),
+        ..Default::default()
+    };
+
+    let margin = ControlAuthorityMargin::new(&set_points, &ControllerOutput::default());
+
+    assert!(margin.local_wing_angle.is_none());
+    assert!(margin.section_model_internal_state.is_none());
+}
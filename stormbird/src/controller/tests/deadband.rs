@@ -0,0 +1,83 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the deadband on the local wing angle.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+fn set_points(deadband: Float, max_change_rate: Option<Float>) -> ControllerSetPoints {
+    ControllerSetPoints {
+        apparent_wind_directions_data: vec![-PI, -0.1, 0.1, PI],
+        angle_of_attack_data: Some(vec![-0.2, -0.2, 0.2, 0.2]),
+        deadband: Some(deadband),
+        max_local_wing_angle_change_rate: max_change_rate,
+        ..Default::default()
+    }
+}
+
+fn new_wing_angle(
+    set_points: &ControllerSetPoints, 
+    current_local_wing_angle: Float, 
+    apparent_wind_direction: Float
+) -> Float {
+    let input = ControllerInput {
+        loading: 1.0,
+        current_local_wing_angle,
+        apparent_wind_direction,
+        ..Default::default()
+    };
+
+    set_points.get_new_output(&input, 0.1, &mut ControllerState::default()).local_wing_angle
+}
+
+#[test]
+fn small_changes_are_held() {
+    let set_points = set_points(0.05, None);
+
+    let current = 1.0 - 0.2;
+
+    // Small variations in the wind direction should not change the wing angle
+    assert_eq!(new_wing_angle(&set_points, current, 1.02), current);
+    assert_eq!(new_wing_angle(&set_points, current, 0.97), current);
+
+    // Larger changes are applied directly
+    assert!((new_wing_angle(&set_points, current, 1.1) - (1.1 - 0.2)).abs() < 1e-12);
+}
+
+#[test]
+fn deadband_handles_wraparound() {
+    let set_points = set_points(0.05, None);
+
+    // Following wind, where the current wing angle has turned past π, while the new target is
+    // computed close to -π
+    let current = PI + 0.26;
+    let apparent_wind_direction = -PI + 0.05;
+
+    let target = apparent_wind_direction + 0.2;
+
+    assert!((target - current).abs() > PI);
+    assert_eq!(new_wing_angle(&set_points, current, apparent_wind_direction), current);
+}
+
+#[test]
+fn rate_limiter_takes_shortest_way_around() {
+    let max_change_rate = 0.1;
+    let set_points = set_points(0.01, Some(max_change_rate));
+
+    let current = PI - 0.01;
+    let apparent_wind_direction = -PI + 0.5;
+
+    let new_angle = new_wing_angle(&set_points, current, apparent_wind_direction);
+
+    // The target is outside the deadband, and the wing turns through ±π, limited by the rate
+    assert!((new_angle - (current + max_change_rate * 0.1)).abs() < 1e-12);
+
+    // When the target is inside the deadband, the limiter has nothing to do
+    let held_angle = new_wing_angle(&set_points, current, PI - 0.01 + 0.2 + 0.005);
+
+    assert_eq!(held_angle, current);
+}
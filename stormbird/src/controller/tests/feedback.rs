@@ -0,0 +1,98 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the closed-loop feedback control of the effective angle of attack.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+
+fn feedback_controller(start_time: Float) -> Controller {
+    let setup_string = format!(r#"{{
+        "start_time": {start_time},
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-1.0, 1.0],
+                "angle_of_attack_data": [0.15, 0.15],
+                "use_effective_angle_of_attack": true,
+                "feedback_gains": {{
+                    "proportional": 0.5,
+                    "integral": 4.0,
+                    "integral_limit": 1.0
+                }}
+            }}
+        ]
+    }}"#);
+
+    Controller::new_from_string(&setup_string).unwrap()
+}
+
+#[test]
+fn pid_tracks_effective_angle_of_attack() {
+    let mut controller = feedback_controller(0.0);
+
+    let set_point = 0.15;
+    let apparent_wind_direction = 0.5;
+
+    // Simple model of the sail, where the lift-induced velocities reduce the angle of attack with
+    // a constant offset relative to the geometric angle
+    let induced_angle = 0.04;
+    let angle_of_attack = |wing_angle: Float| apparent_wind_direction - wing_angle - induced_angle;
+
+    let time_step = 0.05;
+
+    let mut wing_angle = 0.0;
+
+    for step in 0..400 {
+        let input = vec![
+            ControllerInput {
+                loading: 1.0,
+                current_local_wing_angle: wing_angle,
+                angle_of_attack: angle_of_attack(wing_angle),
+                apparent_wind_direction,
+                ..Default::default()
+            }
+        ];
+
+        let output = controller.update(step as Float * time_step, time_step, &input).unwrap();
+
+        wing_angle = output[0].local_wing_angle;
+    }
+
+    let final_error = angle_of_attack(wing_angle) - set_point;
+
+    dbg!(wing_angle, final_error);
+
+    let open_loop_error = angle_of_attack(apparent_wind_direction - set_point) - set_point;
+
+    assert!((open_loop_error + induced_angle).abs() < 1e-12);
+    assert!(final_error.abs() < 1e-4);
+}
+
+#[test]
+fn feedback_state_is_reset_before_start_time() {
+    let mut controller = feedback_controller(1.0);
+
+    assert_eq!(controller.state.len(), controller.set_points.len());
+
+    let input = vec![
+        ControllerInput {
+            loading: 1.0,
+            angle_of_attack: 0.5,
+            apparent_wind_direction: 0.5,
+            ..Default::default()
+        }
+    ];
+
+    // Updates before the start time give no output, and should not accumulate any error
+    assert!(controller.update(0.0, 0.1, &input).is_none());
+    assert!(controller.update(0.5, 0.1, &input).is_none());
+
+    assert_eq!(controller.state[0].feedback.integral, 0.0);
+    assert!(controller.state[0].feedback.previous_error.is_none());
+
+    controller.update(1.0, 0.1, &input);
+
+    assert!(controller.state[0].feedback.integral > 0.0);
+}
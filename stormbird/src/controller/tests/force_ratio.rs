@@ -0,0 +1,118 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the control toward a target ratio between the thrust and the side force.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+
+/// Simple model of the sail, which returns the thrust and side force for a given wing angle, 
+/// using a linear lift coefficient and a quadratic drag coefficient.
+fn sail_forces(apparent_wind_direction: Float, wing_angle: Float) -> (Float, Float) {
+    let angle_of_attack = apparent_wind_direction - wing_angle;
+
+    let lift = 5.0 * angle_of_attack;
+    let drag = 0.01 + 0.05 * lift.powi(2);
+
+    let thrust = lift * apparent_wind_direction.sin() - drag * apparent_wind_direction.cos();
+    let side_force = lift * apparent_wind_direction.cos() + drag * apparent_wind_direction.sin();
+
+    (thrust, side_force)
+}
+
+/// Runs the controller together with the sail model until steady state, and returns the final 
+/// forces.
+fn steady_forces(target_ratio: Float) -> (Float, Float) {
+    let setup_string = format!(r#"{{
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-3.2, -0.1, 0.1, 3.2],
+                "angle_of_attack_data": [-0.08, -0.08, 0.08, 0.08],
+                "force_ratio_target": {{
+                    "target_ratio": {target_ratio},
+                    "gain": 0.5
+                }}
+            }}
+        ]
+    }}"#);
+
+    let mut controller = Controller::new_from_string(&setup_string).unwrap();
+
+    let apparent_wind_direction = Float::from(60.0).to_radians();
+    let time_step = 0.1;
+
+    let mut wing_angle = 0.0;
+
+    for step in 0..200 {
+        let (thrust, side_force) = sail_forces(apparent_wind_direction, wing_angle);
+
+        let input = vec![
+            ControllerInput {
+                loading: 1.0,
+                current_local_wing_angle: wing_angle,
+                apparent_wind_direction,
+                thrust,
+                side_force,
+                ..Default::default()
+            }
+        ];
+
+        let output = controller.update(step as Float * time_step, time_step, &input).unwrap();
+
+        wing_angle = output[0].local_wing_angle;
+    }
+
+    sail_forces(apparent_wind_direction, wing_angle)
+}
+
+#[test]
+fn higher_target_ratio_gives_more_thrust_relative_to_side_force() {
+    let low_target = 1.2;
+    let high_target = 1.4;
+
+    let (low_thrust, low_side_force) = steady_forces(low_target);
+    let (high_thrust, high_side_force) = steady_forces(high_target);
+
+    dbg!(low_thrust, low_side_force, high_thrust, high_side_force);
+
+    assert!((low_thrust / low_side_force - low_target).abs() < 1e-3);
+    assert!((high_thrust / high_side_force - high_target).abs() < 1e-3);
+
+    assert!(high_thrust > low_thrust);
+    assert!(high_side_force / high_thrust < low_side_force / low_thrust);
+}
+
+#[test]
+fn loading_factor_is_limited() {
+    // The target is above what the sail can reach, so the loading should stay at the upper limit
+    let set_points: ControllerSetPoints = serde_json::from_str(r#"{
+        "apparent_wind_directions_data": [0.0],
+        "angle_of_attack_data": [0.08],
+        "force_ratio_target": {
+            "target_ratio": 10.0,
+            "gain": 0.5,
+            "max_loading_factor": 0.9
+        }
+    }"#).unwrap();
+
+    let input = ControllerInput {
+        loading: 1.0,
+        apparent_wind_direction: 1.0,
+        thrust: 1.0,
+        side_force: -1.0,
+        ..Default::default()
+    };
+
+    let mut state = ControllerState::default();
+
+    let output = set_points.get_new_output(&input, 0.1, &mut state);
+
+    assert_eq!(state.force_ratio_loading_factor, Some(0.9));
+    assert!((output.local_wing_angle - (1.0 - 0.9 * 0.08)).abs() < 1e-12);
+
+    state.reset();
+
+    assert!(state.force_ratio_loading_factor.is_none());
+}
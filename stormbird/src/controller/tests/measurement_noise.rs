@@ -0,0 +1,174 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the noise model that can be added to the flow measurements.
+
+use crate::controller::prelude::*;
+use crate::controller::measurements::measure_angles_of_attack;
+use crate::common_utils::results::simulation::SimulationResult;
+use crate::line_force_model::prelude::*;
+use crate::line_force_model::input_power::InputPowerModel;
+use crate::section_models::SectionModel;
+use crate::section_models::foil::Foil;
+use crate::wind::environment::WindEnvironment;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+fn example_result(time: Float) -> SimulationResult {
+    let mut result = SimulationResult {
+        time,
+        wing_indices: vec![0..10, 10..20],
+        ..Default::default()
+    };
+
+    result.force_input.angles_of_attack = (0..20).map(|i| 0.01 * i as Float).collect();
+
+    result
+}
+
+fn settings_with_noise(standard_deviation: Float, seed: u64) -> MeasurementSettings {
+    MeasurementSettings {
+        noise: Some(MeasurementNoise { standard_deviation, seed }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn noise_is_reproducible() {
+    let result = example_result(1.5);
+
+    let settings = settings_with_noise(0.05, 42);
+
+    let first = measure_angles_of_attack(&result, &settings);
+    let second = measure_angles_of_attack(&result, &settings);
+
+    let clean = measure_angles_of_attack(&result, &MeasurementSettings::default());
+
+    assert_eq!(first, second, "Same seed and time must give the same noise");
+
+    for i in 0..clean.len() {
+        assert!(
+            first[i] != clean[i],
+            "Noise not added to the measurement for wing {}", i
+        );
+    }
+
+    let other_seed = measure_angles_of_attack(&result, &settings_with_noise(0.05, 43));
+    let other_time = measure_angles_of_attack(&example_result(1.6), &settings);
+
+    assert_ne!(first, other_seed, "Different seeds should give different noise");
+    assert_ne!(first, other_time, "Different times should give different noise");
+}
+
+#[test]
+fn zero_standard_deviation_gives_clean_measurement() {
+    let result = example_result(0.3);
+
+    let clean = measure_angles_of_attack(&result, &MeasurementSettings::default());
+    let with_zero_noise = measure_angles_of_attack(&result, &settings_with_noise(0.0, 7));
+
+    assert_eq!(clean, with_zero_noise);
+}
+
+#[test]
+fn noise_has_expected_standard_deviation() {
+    let standard_deviation = 0.1;
+    let nr_samples = 2000;
+
+    let noise = MeasurementNoise { standard_deviation, seed: 3 };
+
+    let samples = noise.apply(&vec![0.0; nr_samples], MeasuredQuantity::AngleOfAttack, 0.0);
+
+    let mean = samples.iter().sum::<Float>() / nr_samples as Float;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<Float>() / nr_samples as Float;
+
+    assert!(mean.abs() < 0.01, "Mean of the noise too large: {}", mean);
+    assert!(
+        (variance.sqrt() - standard_deviation).abs() < 0.01,
+        "Wrong standard deviation of the noise: {}", variance.sqrt()
+    );
+}
+
+#[test]
+/// Tests that the noise on different quantities and different wings is not the same, as it would 
+/// be if the random number generator was only seeded by the seed and the time.
+fn noise_differs_between_quantities_and_wings() {
+    let noise = MeasurementNoise { standard_deviation: 0.1, seed: 11 };
+
+    let clean_values = vec![0.0; 2];
+
+    let angle_of_attack_noise = noise.apply(&clean_values, MeasuredQuantity::AngleOfAttack, 2.0);
+    let velocity_noise = noise.apply(&clean_values, MeasuredQuantity::WindVelocity, 2.0);
+    let direction_noise = noise.apply(&clean_values, MeasuredQuantity::WindDirection, 2.0);
+
+    assert_ne!(angle_of_attack_noise, velocity_noise);
+    assert_ne!(angle_of_attack_noise, direction_noise);
+    assert_ne!(velocity_noise, direction_noise);
+
+    assert_ne!(angle_of_attack_noise[0], angle_of_attack_noise[1]);
+
+    let single_wing_noise = noise.apply(&clean_values[0..1], MeasuredQuantity::AngleOfAttack, 2.0);
+
+    assert_eq!(
+        single_wing_noise[0], angle_of_attack_noise[0], 
+        "The noise on a wing should not depend on the number of wings"
+    );
+}
+
+#[test]
+/// Tests that the noise is also added when the controller input is computed from a velocity, which
+/// is the path used by the complete sail model.
+fn noise_is_added_to_input_from_velocity() {
+    let mut builder = LineForceModelBuilder::new(5);
+
+    builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector::from([0.0, 0.0, 5.0]),
+                SpatialVector::from([0.0, 0.0, 35.0]),
+            ],
+            chord_vectors: vec![
+                SpatialVector::from([5.0, 0.0, 0.0]),
+                SpatialVector::from([5.0, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::Foil(Foil::default()),
+            non_zero_circulation_at_ends: [false, false],
+            nr_sections: None,
+            input_power_model: InputPowerModel::NoPower,
+        }
+    );
+
+    let line_force_model = builder.build();
+
+    let velocity = vec![SpatialVector::from([8.0, 2.0, 0.0]); line_force_model.nr_span_lines()];
+
+    let clean_settings = FlowMeasurementSettings::default();
+
+    let noisy_settings = FlowMeasurementSettings {
+        angle_of_attack: settings_with_noise(0.05, 1),
+        wind_velocity: settings_with_noise(0.05, 1),
+        wind_direction: settings_with_noise(0.05, 1),
+        ..Default::default()
+    };
+
+    let input = |settings: &FlowMeasurementSettings| ControllerInput::new_from_velocity(
+        1.0,
+        &line_force_model,
+        &velocity,
+        0.5,
+        settings,
+        &WindEnvironment::default(),
+        None,
+        ControllerInput::default_thrust_direction(),
+        0.0
+    )[0].clone();
+
+    let clean_input = input(&clean_settings);
+    let noisy_input = input(&noisy_settings);
+
+    assert_ne!(clean_input.angle_of_attack, noisy_input.angle_of_attack);
+    assert_ne!(clean_input.velocity, noisy_input.velocity);
+    assert_ne!(clean_input.apparent_wind_direction, noisy_input.apparent_wind_direction);
+}
@@ -0,0 +1,20 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the controller functionality.
+
+mod standalone;
+mod set_point_range;
+mod actuator;
+mod measurement_noise;
+mod single_entry_set_point;
+mod control_authority;
+mod feedback;
+mod force_ratio;
+mod deadband;
+mod rate_limit;
+mod power_tracking;
+mod sensor_mapping;
+mod set_point_interpolation;
+mod moving_average;
@@ -0,0 +1,99 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the moving average of the controller input.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+/// Controller with zero angle of attack, so that the local wing angle is equal to the apparent 
+/// wind direction used by the set points.
+fn controller(window_size: usize, start_time: Float) -> Controller {
+    let setup_string = format!(r#"{{
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-3.2, 3.2],
+                "angle_of_attack_data": [0.0, 0.0]
+            }}
+        ],
+        "start_time": {start_time},
+        "moving_average_window_size": {window_size}
+    }}"#);
+
+    Controller::new_from_string(&setup_string).unwrap()
+}
+
+fn input(apparent_wind_direction: Float) -> Vec<ControllerInput> {
+    vec![
+        ControllerInput {
+            loading: 1.0,
+            apparent_wind_direction,
+            ..Default::default()
+        }
+    ]
+}
+
+#[test]
+fn step_input_converges_to_mean_over_window() {
+    let window_size = 5;
+    let time_step = 0.1;
+
+    let mut controller = controller(window_size, 0.0);
+
+    let initial_direction = 0.2;
+    let final_direction = 0.7;
+
+    let mut time = 0.0;
+
+    for _ in 0..10 {
+        controller.update(time, time_step, &input(initial_direction));
+
+        time += time_step;
+    }
+
+    for step in 1..=2 * window_size {
+        let output = controller.update(time, time_step, &input(final_direction)).unwrap();
+
+        let nr_new_samples = step.min(window_size) as Float;
+
+        // Average of the unit vectors, which is close to the arithmetic mean for small angles
+        let (sin_sum, cos_sum) = (
+            (window_size as Float - nr_new_samples) * initial_direction.sin() + 
+                nr_new_samples * final_direction.sin(),
+            (window_size as Float - nr_new_samples) * initial_direction.cos() + 
+                nr_new_samples * final_direction.cos(),
+        );
+
+        let expected_direction = sin_sum.atan2(cos_sum);
+
+        assert!((output[0].local_wing_angle - expected_direction).abs() < 1e-12);
+
+        time += time_step;
+    }
+
+    let output = controller.update(time, time_step, &input(final_direction)).unwrap();
+
+    assert!((output[0].local_wing_angle - final_direction).abs() < 1e-12);
+}
+
+#[test]
+fn average_is_reset_before_start_time_and_wraps_around() {
+    let time_step = 0.1;
+
+    let mut controller = controller(4, 1.0);
+
+    // Measurements before the start time are not remembered
+    controller.update(0.5, time_step, &input(1.0));
+
+    assert!(controller.state[0].measurement_window.samples.is_empty());
+
+    // Directions on both sides of the ±π wraparound average to a direction astern, not ahead
+    controller.update(1.0, time_step, &input(PI - 0.1));
+
+    let output = controller.update(1.1, time_step, &input(-PI + 0.1)).unwrap();
+
+    assert!(output[0].local_wing_angle.abs() > PI - 1e-6);
+}
@@ -0,0 +1,102 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the maximum power point tracking of the section model internal state.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+
+const SHIP_VELOCITY: Float = 5.0;
+
+/// Simple model of a rotor sail, which returns the thrust and input power for a given spin ratio.
+/// The thrust has a maximum at a spin ratio of 5, while the net power has a maximum at a lower 
+/// spin ratio, due to the input power.
+fn rotor_thrust_and_power(spin_ratio: Float) -> (Float, Float) {
+    let thrust = 10.0 * spin_ratio - spin_ratio.powi(2);
+    let input_power = 2.0 * spin_ratio.powi(3);
+
+    (thrust, input_power)
+}
+
+/// Spin ratio where the derivative of the net power, 50 - 10 s - 6 s^2, is zero
+fn optimal_spin_ratio() -> Float {
+    (-10.0 + Float::from(1300.0).sqrt()) / 12.0
+}
+
+fn run_controller(max_updates: usize) -> (Float, Controller) {
+    let setup_string = format!(r#"{{
+        "set_points": [
+            {{
+                "apparent_wind_directions_data": [-3.2, 3.2],
+                "section_model_internal_state_data": [1.0, 1.0],
+                "max_power_point_tracking": {{
+                    "initial_step": 0.25,
+                    "min_step": 0.01,
+                    "max_updates": {max_updates}
+                }}
+            }}
+        ]
+    }}"#);
+
+    let mut controller = Controller::new_from_string(&setup_string).unwrap();
+
+    let time_step = 1.0;
+
+    let mut spin_ratio = 0.0;
+
+    for step in 0..100 {
+        let (thrust, input_power) = rotor_thrust_and_power(spin_ratio);
+
+        let input = vec![
+            ControllerInput {
+                loading: 1.0,
+                current_section_model_internal_state: spin_ratio,
+                apparent_wind_direction: Float::from(90.0).to_radians(),
+                thrust,
+                input_power,
+                ship_velocity: SHIP_VELOCITY,
+                ..Default::default()
+            }
+        ];
+
+        let output = controller.update(step as Float * time_step, time_step, &input).unwrap();
+
+        spin_ratio = output[0].section_model_internal_state;
+    }
+
+    (spin_ratio, controller)
+}
+
+#[test]
+fn tracking_converges_to_maximum_net_power() {
+    let (spin_ratio, controller) = run_controller(50);
+
+    let tracking = controller.set_points[0].max_power_point_tracking.as_ref().unwrap();
+    let state = controller.state[0].power_tracking.as_ref().unwrap();
+
+    assert!(tracking.is_converged(state));
+    assert!(
+        (spin_ratio - optimal_spin_ratio()).abs() < 0.05, 
+        "Spin ratio: {}, optimal: {}", spin_ratio, optimal_spin_ratio()
+    );
+
+    let net_power = |spin_ratio: Float| {
+        let (thrust, input_power) = rotor_thrust_and_power(spin_ratio);
+
+        MaxPowerPointTracking::net_power(thrust, SHIP_VELOCITY, input_power)
+    };
+
+    assert!(net_power(spin_ratio) > net_power(5.0));
+}
+
+#[test]
+fn tracking_stops_after_max_updates() {
+    let (spin_ratio, controller) = run_controller(2);
+
+    let state = controller.state[0].power_tracking.as_ref().unwrap();
+
+    assert_eq!(state.nr_updates, 2);
+    assert!((spin_ratio - 1.5).abs() < 1e-12);
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the rate limits on the controller output.
+
+use crate::controller::prelude::*;
+use crate::controller::set_points::limit_value;
+
+#[test]
+fn limit_value_steps_toward_new_value() {
+    let max_change = 0.5;
+
+    // Positive and negative changes larger than the limit are reduced to the limit
+    assert_eq!(limit_value(2.0, 5.0, max_change), 2.5);
+    assert_eq!(limit_value(2.0, -1.0, max_change), 1.5);
+    assert_eq!(limit_value(-2.0, -5.0, max_change), -2.5);
+
+    // Changes within the limit are applied directly
+    assert_eq!(limit_value(2.0, 2.3, max_change), 2.3);
+    assert_eq!(limit_value(2.0, 1.8, max_change), 1.8);
+    assert_eq!(limit_value(2.0, 2.0, max_change), 2.0);
+}
+
+#[test]
+fn output_change_is_limited_by_rates() {
+    let max_local_wing_angle_change_rate = 0.2;
+    let max_internal_section_state_change_rate = 1.5;
+
+    let set_points = ControllerSetPoints {
+        apparent_wind_directions_data: vec![-3.2, 3.2],
+        angle_of_attack_data: Some(vec![0.1, 0.1]),
+        section_model_internal_state_data: Some(vec![4.0, 4.0]),
+        max_local_wing_angle_change_rate: Some(max_local_wing_angle_change_rate),
+        max_internal_section_state_change_rate: Some(max_internal_section_state_change_rate),
+        ..Default::default()
+    };
+
+    let time_step = 0.5;
+
+    let output = |current_local_wing_angle, current_section_model_internal_state| {
+        let input = ControllerInput {
+            loading: 1.0,
+            current_local_wing_angle,
+            current_section_model_internal_state,
+            apparent_wind_direction: 1.0,
+            ..Default::default()
+        };
+
+        set_points.get_new_output(&input, time_step, &mut ControllerState::default())
+    };
+
+    // Targets are a wing angle of 0.9 and an internal state of 4.0
+    let increasing = output(0.0, 0.0);
+
+    assert!((increasing.local_wing_angle - max_local_wing_angle_change_rate * time_step).abs() < 1e-12);
+    assert!(
+        (increasing.section_model_internal_state - max_internal_section_state_change_rate * time_step).abs() < 1e-12
+    );
+
+    let decreasing = output(2.0, 6.0);
+
+    assert!((decreasing.local_wing_angle - (2.0 - max_local_wing_angle_change_rate * time_step)).abs() < 1e-12);
+    assert!(
+        (decreasing.section_model_internal_state - (6.0 - max_internal_section_state_change_rate * time_step)).abs() < 1e-12
+    );
+
+    let within_limits = output(0.85, 3.5);
+
+    assert!((within_limits.local_wing_angle - 0.9).abs() < 1e-12);
+    assert!((within_limits.section_model_internal_state - 4.0).abs() < 1e-12);
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the mapping of measurements from sensor wings to force-producing wings.
+
+use crate::controller::prelude::*;
+use crate::line_force_model::prelude::*;
+use crate::line_force_model::input_power::InputPowerModel;
+use crate::section_models::SectionModel;
+use crate::section_models::foil::Foil;
+use crate::wind::environment::WindEnvironment;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+/// Two foil wings and one sensor wing in between them
+fn model_with_sensor_wing() -> LineForceModel {
+    let mut builder = LineForceModelBuilder::new(5);
+
+    let section_models = [
+        SectionModel::Foil(Foil::default()),
+        SectionModel::EffectiveWindSensor,
+        SectionModel::Foil(Foil::default()),
+    ];
+
+    for (x_position, section_model) in [-20.0, 0.0, 20.0].into_iter().zip(section_models) {
+        builder.add_wing(
+            WingBuilder {
+                section_points: vec![
+                    SpatialVector::from([x_position, 0.0, 5.0]),
+                    SpatialVector::from([x_position, 0.0, 35.0]),
+                ],
+                chord_vectors: vec![
+                    SpatialVector::from([5.0, 0.0, 0.0]),
+                    SpatialVector::from([5.0, 0.0, 0.0]),
+                ],
+                section_model,
+                non_zero_circulation_at_ends: [false, false],
+                nr_sections: None,
+                input_power_model: InputPowerModel::NoPower,
+            }
+        );
+    }
+
+    builder.build()
+}
+
+#[test]
+fn sensor_wing_measurements_are_used_by_target_wings() {
+    let line_force_model = model_with_sensor_wing();
+
+    // The sensor wing sees a different velocity than the force-producing wings, for instance 
+    // because it is placed outside the disturbed flow around the ship
+    let foil_velocity = SpatialVector::from([8.0, 2.0, 0.0]);
+    let sensor_velocity = SpatialVector::from([10.0, 5.0, 0.0]);
+
+    let mut velocity: Vec<SpatialVector> = Vec::new();
+
+    for (wing_index, wing_indices) in line_force_model.wing_indices.iter().enumerate() {
+        let wing_velocity = if wing_index == 1 { sensor_velocity } else { foil_velocity };
+
+        velocity.extend(vec![wing_velocity; wing_indices.len()]);
+    }
+
+    let wind_environment = WindEnvironment::default();
+
+    let settings_without_mapping = FlowMeasurementSettings::default();
+
+    let settings_with_mapping = FlowMeasurementSettings {
+        sensor_mapping: vec![
+            SensorMapping {
+                sensor_wing: 1,
+                target_wings: vec![0, 2, 10],
+            }
+        ],
+        ..Default::default()
+    };
+
+    let input_without_mapping = ControllerInput::new_from_velocity(
+        1.0, 
+        &line_force_model, 
+        &velocity, 
+        0.0,
+        &settings_without_mapping, 
+        &wind_environment,
+        None,
+        ControllerInput::default_thrust_direction(),
+        0.0
+    );
+
+    let input_with_mapping = ControllerInput::new_from_velocity(
+        1.0, 
+        &line_force_model, 
+        &velocity, 
+        0.0,
+        &settings_with_mapping, 
+        &wind_environment,
+        None,
+        ControllerInput::default_thrust_direction(),
+        0.0
+    );
+
+    let sensor_input = &input_without_mapping[1];
+
+    assert!((input_without_mapping[0].velocity - foil_velocity.length()).abs() < 1e-12);
+    assert!((sensor_input.velocity - sensor_velocity.length()).abs() < 1e-12);
+
+    for wing_index in [0, 2] {
+        let input = &input_with_mapping[wing_index];
+
+        assert_eq!(input.velocity, sensor_input.velocity);
+        assert_eq!(input.apparent_wind_direction, sensor_input.apparent_wind_direction);
+
+        // Other measurements are still taken from the wing itself
+        assert_eq!(
+            input.angle_of_attack, 
+            input_without_mapping[wing_index].angle_of_attack
+        );
+    }
+
+    let direction_difference: Float = (
+        input_without_mapping[0].apparent_wind_direction - sensor_input.apparent_wind_direction
+    ).abs();
+
+    assert!(direction_difference > 0.1);
+}
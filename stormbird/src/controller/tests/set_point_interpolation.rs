@@ -0,0 +1,154 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the interpolation of the set point tables.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+const ALL_KINDS: [InterpolationKind; 3] = [
+    InterpolationKind::Linear,
+    InterpolationKind::CatmullRom,
+    InterpolationKind::MonotoneCubic,
+];
+
+fn set_points(interpolation: InterpolationKind) -> ControllerSetPoints {
+    ControllerSetPoints {
+        apparent_wind_directions_data: vec![-3.2, -1.5, -0.4, -0.1, 0.1, 0.4, 1.5, 3.2],
+        angle_of_attack_data: Some(vec![-0.1, -0.14, -0.12, 0.0, 0.0, 0.12, 0.14, 0.1]),
+        section_model_internal_state_data: Some(vec![1.0, 3.0, 4.0, 4.0, 4.0, 4.0, 3.0, 1.0]),
+        interpolation,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn interpolant_passes_through_data_points() {
+    for kind in ALL_KINDS {
+        let set_points = set_points(kind);
+
+        let angle_data = set_points.angle_of_attack_data.clone().unwrap();
+        let internal_state_data = set_points.section_model_internal_state_data.clone().unwrap();
+
+        for (i, &direction) in set_points.apparent_wind_directions_data.iter().enumerate() {
+            let angle = set_points.get_angle_of_attack_set_point(direction);
+            let internal_state = set_points.get_internal_state_set_point(direction);
+
+            assert!((angle - angle_data[i]).abs() < 1e-12, "{:?}, index {}", kind, i);
+            assert!(
+                (internal_state - internal_state_data[i]).abs() < 1e-12, 
+                "{:?}, index {}", kind, i
+            );
+        }
+
+        // Constant outside the range of the data
+        assert_eq!(set_points.get_angle_of_attack_set_point(-4.0), angle_data[0]);
+        assert_eq!(set_points.get_angle_of_attack_set_point(4.0), angle_data[angle_data.len() - 1]);
+    }
+}
+
+#[test]
+fn cubic_interpolants_have_continuous_derivative() {
+    let step = 1e-6;
+
+    for kind in [InterpolationKind::CatmullRom, InterpolationKind::MonotoneCubic] {
+        let set_points = set_points(kind);
+
+        let interior_directions = &set_points.apparent_wind_directions_data[1..7];
+
+        for &direction in interior_directions {
+            let value = set_points.get_angle_of_attack_set_point(direction);
+
+            let derivative_before = (
+                value - set_points.get_angle_of_attack_set_point(direction - step)
+            ) / step;
+            let derivative_after = (
+                set_points.get_angle_of_attack_set_point(direction + step) - value
+            ) / step;
+
+            assert!(
+                (derivative_after - derivative_before).abs() < 1e-3, 
+                "{:?} at {}: {} vs {}", kind, direction, derivative_before, derivative_after
+            );
+        }
+    }
+}
+
+#[test]
+fn monotone_cubic_does_not_overshoot() {
+    let set_points = set_points(InterpolationKind::MonotoneCubic);
+
+    let directions = &set_points.apparent_wind_directions_data;
+    let internal_state_data = set_points.section_model_internal_state_data.as_ref().unwrap();
+
+    let nr_samples = 20;
+
+    for i in 0..directions.len() - 1 {
+        let min_value = internal_state_data[i].min(internal_state_data[i + 1]);
+        let max_value = internal_state_data[i].max(internal_state_data[i + 1]);
+
+        for j in 0..=nr_samples {
+            let direction = directions[i] + 
+                (directions[i + 1] - directions[i]) * j as Float / nr_samples as Float;
+
+            let value = set_points.get_internal_state_set_point(direction);
+
+            assert!(value >= min_value - 1e-12 && value <= max_value + 1e-12);
+        }
+    }
+}
+
+#[test]
+fn periodic_interpolation_across_stern() {
+    let first_direction = Float::from(-170.0).to_radians();
+    let last_direction = Float::from(170.0).to_radians();
+
+    let periodic_set_points = |interpolation: InterpolationKind| ControllerSetPoints {
+        apparent_wind_directions_data: vec![first_direction, 0.0, last_direction],
+        angle_of_attack_data: Some(vec![-0.1, 0.0, 0.1]),
+        interpolation,
+        periodic: true,
+        ..Default::default()
+    };
+
+    let set_points = periodic_set_points(InterpolationKind::Linear);
+
+    // Directly astern, halfway between the last and the first data point along the shortest arc
+    let astern = set_points.get_angle_of_attack_set_point(PI);
+    let astern_negative = set_points.get_angle_of_attack_set_point(-PI);
+
+    assert!(astern.abs() < 1e-12);
+    assert!(astern_negative.abs() < 1e-12);
+
+    // Continuous across the ±180° boundary
+    let just_below = set_points.get_angle_of_attack_set_point(Float::from(179.9).to_radians());
+    let just_above = set_points.get_angle_of_attack_set_point(Float::from(-179.9).to_radians());
+
+    assert!((just_below - just_above).abs() < 0.01);
+    let quarter_way = set_points.get_angle_of_attack_set_point(Float::from(175.0).to_radians());
+
+    assert!((quarter_way - 0.05).abs() < 1e-12);
+
+    assert!(!set_points.apparent_wind_direction_is_out_of_range(Float::from(179.0).to_radians()));
+
+    // The data points are still reproduced, also with a cubic interpolation
+    for kind in ALL_KINDS {
+        let set_points = periodic_set_points(kind);
+
+        assert!((set_points.get_angle_of_attack_set_point(last_direction) - 0.1).abs() < 1e-12);
+        assert!((set_points.get_angle_of_attack_set_point(first_direction) + 0.1).abs() < 1e-12);
+        assert!(set_points.get_angle_of_attack_set_point(0.0).abs() < 1e-12);
+    }
+
+    // Without the periodic flag, the values are held constant outside the data
+    let non_periodic = ControllerSetPoints {
+        periodic: false,
+        ..periodic_set_points(InterpolationKind::Linear)
+    };
+
+    assert_eq!(non_periodic.get_angle_of_attack_set_point(Float::from(179.9).to_radians()), 0.1);
+    assert_eq!(non_periodic.get_angle_of_attack_set_point(Float::from(-179.9).to_radians()), -0.1);
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the diagnostics for the set point range.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+
+#[test]
+fn out_of_range_fraction() {
+    let set_points = vec![
+        ControllerSetPoints {
+            apparent_wind_directions_data: vec![-1.0, 0.0, 1.0],
+            angle_of_attack_data: Some(vec![-0.2, 0.0, 0.2]),
+            ..Default::default()
+        }
+    ];
+
+    let mut recorder_within = SetPointRangeRecorder::new(set_points.len());
+    let mut recorder_beyond = SetPointRangeRecorder::new(set_points.len());
+
+    let nr_steps = 10;
+
+    for i in 0..nr_steps {
+        let apparent_wind_direction_within = -0.9 + 1.8 * i as Float / (nr_steps - 1) as Float;
+        let apparent_wind_direction_beyond = 0.5 + 0.1 * i as Float;
+
+        recorder_within.record(
+            &set_points,
+            &[ControllerInput {
+                apparent_wind_direction: apparent_wind_direction_within,
+                ..Default::default()
+            }]
+        );
+
+        recorder_beyond.record(
+            &set_points,
+            &[ControllerInput {
+                apparent_wind_direction: apparent_wind_direction_beyond,
+                ..Default::default()
+            }]
+        );
+    }
+
+    let fractions_within = recorder_within.out_of_range_fractions();
+    let fractions_beyond = recorder_beyond.out_of_range_fractions();
+
+    dbg!(&fractions_within, &fractions_beyond);
+
+    assert_eq!(fractions_within[0], 0.0);
+    assert!(fractions_beyond[0] > 0.0);
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of set point tables with only a single entry.
+
+use crate::controller::prelude::*;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+#[test]
+fn single_entry_gives_constant_set_point() {
+    let angle_of_attack = 0.15;
+    let internal_state = 2.5;
+
+    let set_points = ControllerSetPoints {
+        apparent_wind_directions_data: vec![0.5],
+        angle_of_attack_data: Some(vec![angle_of_attack]),
+        section_model_internal_state_data: Some(vec![internal_state]),
+        ..Default::default()
+    };
+
+    assert!(set_points.has_single_entry());
+
+    let nr_directions = 37;
+
+    for i in 0..nr_directions {
+        let apparent_wind_direction = -PI + 2.0 * PI * i as Float / (nr_directions - 1) as Float;
+
+        let angle_set_point = set_points.get_angle_of_attack_set_point(apparent_wind_direction);
+        let state_set_point = set_points.get_internal_state_set_point(apparent_wind_direction);
+
+        assert!(!angle_set_point.is_nan() && !state_set_point.is_nan());
+        assert_eq!(angle_set_point, angle_of_attack);
+        assert_eq!(state_set_point, internal_state);
+    }
+}
+
+#[test]
+/// Tests that set points without any apparent wind directions, or with data tables that do not 
+/// match the directions, are rejected when the controller is built.
+fn inconsistent_set_point_tables_are_rejected() {
+    let without_directions = ControllerSetPoints {
+        apparent_wind_directions_data: vec![],
+        angle_of_attack_data: Some(vec![]),
+        ..Default::default()
+    };
+
+    let mismatched_table = ControllerSetPoints {
+        apparent_wind_directions_data: vec![-1.0, 0.0, 1.0],
+        section_model_internal_state_data: Some(vec![1.0, 2.0]),
+        ..Default::default()
+    };
+
+    let constant_table = ControllerSetPoints {
+        apparent_wind_directions_data: vec![-1.0, 0.0, 1.0],
+        angle_of_attack_data: Some(vec![0.1]),
+        ..Default::default()
+    };
+
+    for set_points in [without_directions, mismatched_table] {
+        assert!(set_points.check_data().is_err());
+        assert!(Controller::new(vec![set_points], FlowMeasurementSettings::default()).is_err());
+    }
+
+    assert!(constant_table.check_data().is_ok());
+
+    let setup_without_directions = r#"{"set_points": [{"apparent_wind_directions_data": []}]}"#;
+
+    assert!(Controller::new_from_string(setup_without_directions).is_err());
+}
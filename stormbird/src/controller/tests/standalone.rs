@@ -0,0 +1,111 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the controller when used without any lifting line simulation.
+
+use crate::controller::prelude::*;
+
+#[test]
+fn set_point_interpolation() {
+    let setup_string = r#"{
+        "set_points": [
+            {
+                "apparent_wind_directions_data": [0.0, 1.0, 2.0],
+                "angle_of_attack_data": [0.1, 0.2, 0.3]
+            }
+        ]
+    }"#;
+
+    let mut controller = Controller::new_from_string(setup_string).unwrap();
+
+    let input = vec![
+        ControllerInput {
+            loading: 1.0,
+            apparent_wind_direction: 0.5,
+            ..Default::default()
+        }
+    ];
+
+    let output = controller.update(0.0, 1.0, &input).unwrap();
+
+    let expected_angle_of_attack = 0.15;
+    let expected_wing_angle = 0.5 - expected_angle_of_attack;
+
+    dbg!(&output);
+
+    assert_eq!(output.len(), 1);
+    assert!((output[0].local_wing_angle - expected_wing_angle).abs() < 1e-12);
+    assert!(output[0].section_model_internal_state.abs() < 1e-12);
+}
+
+#[test]
+fn controller_constructed_in_code() {
+    let set_points = vec![
+        ControllerSetPoints {
+            apparent_wind_directions_data: vec![0.0, 1.0, 2.0],
+            angle_of_attack_data: Some(vec![0.1, 0.2, 0.3]),
+            section_model_internal_state_data: Some(vec![1.0, 2.0, 3.0]),
+            ..Default::default()
+        }
+    ];
+
+    let mut controller = Controller::new(set_points, FlowMeasurementSettings::default()).unwrap();
+
+    assert_eq!(
+        controller.time_steps_between_updates, 
+        ControllerBuilder::default_time_steps_between_updates()
+    );
+
+    let input = vec![
+        ControllerInput {
+            loading: 1.0,
+            apparent_wind_direction: 1.5,
+            ..Default::default()
+        }
+    ];
+
+    let output = controller.update(0.0, 1.0, &input).unwrap();
+
+    let expected_angle_of_attack = 0.25;
+    let expected_wing_angle = 1.5 - expected_angle_of_attack;
+
+    assert_eq!(output.len(), 1);
+    assert!((output[0].local_wing_angle - expected_wing_angle).abs() < 1e-12);
+    assert!((output[0].section_model_internal_state - 2.5).abs() < 1e-12);
+}
+
+#[test]
+/// Tests that a controller constructed directly from its fields, without any internal state, can
+/// be updated with the state sized from the set points.
+fn controller_constructed_without_state() {
+    let mut controller = Controller {
+        set_points: vec![
+            ControllerSetPoints {
+                apparent_wind_directions_data: vec![0.0, 1.0],
+                angle_of_attack_data: Some(vec![0.1, 0.2]),
+                ..Default::default()
+            }
+        ],
+        flow_measurement_settings: FlowMeasurementSettings::default(),
+        time_steps_between_updates: 1,
+        start_time: 0.0,
+        time_step_index: 0,
+        use_input_velocity_for_apparent_wind_direction: false,
+        moving_average_window_size: Some(2),
+        state: Vec::new(),
+    };
+
+    let input = vec![
+        ControllerInput {
+            loading: 1.0,
+            apparent_wind_direction: 0.5,
+            ..Default::default()
+        }
+    ];
+
+    let output = controller.update(1.0, 1.0, &input).unwrap();
+
+    assert_eq!(controller.state.len(), 1);
+    assert!((output[0].local_wing_angle - (0.5 - 0.15)).abs() < 1e-12);
+}
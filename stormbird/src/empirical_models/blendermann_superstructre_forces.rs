@@ -4,12 +4,14 @@
 
 use stormath::{
     spatial_vector::SpatialVector, 
+    spatial_vector::transformations::RotationType,
     type_aliases::Float
 };
 
 use crate::wind::wind_condition::WindCondition;
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 
 use serde::{Serialize, Deserialize};
 
@@ -36,16 +38,77 @@ pub struct BlendermannSuperstructureForces {
     pub density: Float,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Builder for the [BlendermannSuperstructureForces] model. The builder is deserializable, so that
+/// the superstructure can be configured in the same setup files as the sails, and validates the 
+/// input when building the model.
+pub struct BlendermannSuperstructureForcesBuilder {
+    /// Frontal projected area of the superstructure
+    pub frontal_area: Float,
+    /// Lateral projected area of the superstructure
+    pub side_area: Float,
+    /// The point where the forces act, used to compute moments
+    pub center_of_effort: SpatialVector,
+    #[serde(default="BlendermannSuperstructureForces::default_resistance_coefficient")]
+    pub resistance_coefficient: Float,
+    #[serde(default="BlendermannSuperstructureForces::default_side_force_coefficient")]
+    pub side_force_coefficient: Float,
+    #[serde(default="BlendermannSuperstructureForces::default_coupling_factor")]
+    pub coupling_factor: Float,
+    #[serde(default="BlendermannSuperstructureForces::default_air_density")]
+    pub density: Float,
+}
+
+impl BlendermannSuperstructureForcesBuilder {
+    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(json_string)
+    }
+
+    /// Builds the model, after checking that the areas are positive.
+    pub fn build(&self) -> Result<BlendermannSuperstructureForces, Error> {
+        if self.frontal_area <= 0.0 || self.side_area <= 0.0 {
+            return Err(Error::CustomStringError(
+                format!(
+                    "The superstructure areas must be positive. Frontal area: {}, side area: {}",
+                    self.frontal_area, self.side_area
+                )
+            ));
+        }
+
+        Ok(BlendermannSuperstructureForces {
+            frontal_area: self.frontal_area,
+            side_area: self.side_area,
+            center_of_effort: self.center_of_effort,
+            resistance_coefficient: self.resistance_coefficient,
+            side_force_coefficient: self.side_force_coefficient,
+            coupling_factor: self.coupling_factor,
+            density: self.density,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Tabulated superstructure forces and moments for a range of roll and yaw angles, for a single 
+/// wind condition. The forces and moments are given in the body-fixed coordinate system, and 
+/// indexed as `[roll_index][yaw_index]`.
+pub struct SuperstructureAttitudeTable {
+    pub roll: Vec<Float>,
+    pub yaw: Vec<Float>,
+    pub force: Vec<Vec<SpatialVector>>,
+    pub moment: Vec<Vec<SpatialVector>>,
+}
+
 impl BlendermannSuperstructureForces {
-    fn default_resistance_coefficient() -> Float {0.55}
-    fn default_side_force_coefficient() -> Float {0.85}
-    fn default_coupling_factor() -> Float {0.55}
-    fn default_air_density() -> Float {1.225}
+    pub fn default_resistance_coefficient() -> Float {0.55}
+    pub fn default_side_force_coefficient() -> Float {0.85}
+    pub fn default_coupling_factor() -> Float {0.55}
+    pub fn default_air_density() -> Float {1.225}
 
+    /// Creates a new model from a JSON string describing a 
+    /// [BlendermannSuperstructureForcesBuilder], including validation of the input.
     pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
-        let serde_res = serde_json::from_str(json_string)?;
-        
-        Ok(serde_res)
+        BlendermannSuperstructureForcesBuilder::from_json_string(json_string)?.build()
     }
 
     pub fn from_json_file(file_path: &str) -> Result<Self, Error> {
@@ -77,6 +140,70 @@ impl BlendermannSuperstructureForces {
         )
     }
 
+    /// Computes the body-fixed force and moment on the superstructure when the ship has the given 
+    /// roll and yaw angles.
+    ///
+    /// The apparent wind is given in the earth-fixed NED system, and is transformed to the 
+    /// body-fixed system using the yaw and roll angles. Only the velocity components in the deck 
+    /// plane are used in the Blendermann model, so that heel reduces the lateral velocity felt by 
+    /// the superstructure.
+    pub fn force_moment_vs_attitude(
+        &self, 
+        apparent_wind: &WindCondition, 
+        roll: Float, 
+        yaw: Float
+    ) -> (SpatialVector, SpatialVector) {
+        let earth_fixed_velocity = -apparent_wind.velocity * SpatialVector::new(
+            apparent_wind.direction_coming_from.cos(),
+            apparent_wind.direction_coming_from.sin(),
+            0.0
+        );
+
+        let body_fixed_velocity = earth_fixed_velocity.in_rotated_coordinate_system(
+            SpatialVector::new(roll, 0.0, yaw), 
+            RotationType::YawPitchRoll
+        );
+
+        let deck_plane_velocity = SpatialVector::new(
+            body_fixed_velocity[0], 
+            body_fixed_velocity[1], 
+            0.0
+        );
+
+        let force = self.body_fixed_force(deck_plane_velocity);
+        let moment = self.body_fixed_moment(force);
+
+        (force, moment)
+    }
+
+    /// Sweeps [BlendermannSuperstructureForces::force_moment_vs_attitude] over all combinations of
+    /// the input roll and yaw angles.
+    pub fn force_moment_attitude_table(
+        &self,
+        apparent_wind: &WindCondition,
+        roll: &[Float],
+        yaw: &[Float]
+    ) -> SuperstructureAttitudeTable {
+        let mut force = Vec::with_capacity(roll.len());
+        let mut moment = Vec::with_capacity(roll.len());
+
+        for &roll_angle in roll {
+            let (force_row, moment_row): (Vec<SpatialVector>, Vec<SpatialVector>) = yaw.iter()
+                .map(|&yaw_angle| self.force_moment_vs_attitude(apparent_wind, roll_angle, yaw_angle))
+                .unzip();
+
+            force.push(force_row);
+            moment.push(moment_row);
+        }
+
+        SuperstructureAttitudeTable {
+            roll: roll.to_vec(),
+            yaw: yaw.to_vec(),
+            force,
+            moment,
+        }
+    }
+
     pub fn resistance(&self, apparent_wind: &WindCondition) -> Float {
         let denominator = self.coupling_denominator(apparent_wind.direction_coming_from);
 
@@ -103,3 +230,124 @@ impl BlendermannSuperstructureForces {
         1.0 - 0.5 * self.coupling_factor * (1.0 - force_ratio) * (2.0 * wind_direction).sin().powf(2.0)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_builder() -> BlendermannSuperstructureForcesBuilder {
+        BlendermannSuperstructureForcesBuilder::from_json_string(
+            r#"{
+                "frontal_area": 200.0,
+                "side_area": 1000.0,
+                "center_of_effort": [-10.0, 0.0, -15.0]
+            }"#
+        ).unwrap()
+    }
+
+    #[test]
+    fn builder_round_trip() {
+        let builder = example_builder();
+
+        let json_string = serde_json::to_string(&builder).unwrap();
+
+        let model_round_trip = BlendermannSuperstructureForces::from_json_string(&json_string).unwrap();
+        let model = builder.build().unwrap();
+
+        assert_eq!(model_round_trip.frontal_area, model.frontal_area);
+        assert_eq!(model_round_trip.side_area, model.side_area);
+        assert_eq!(model_round_trip.center_of_effort, model.center_of_effort);
+        assert_eq!(model_round_trip.resistance_coefficient, model.resistance_coefficient);
+        assert_eq!(model_round_trip.side_force_coefficient, model.side_force_coefficient);
+        assert_eq!(model_round_trip.coupling_factor, model.coupling_factor);
+        assert_eq!(model_round_trip.density, model.density);
+
+        let invalid_builder = BlendermannSuperstructureForcesBuilder {
+            side_area: -1.0,
+            ..builder
+        };
+
+        assert!(invalid_builder.build().is_err());
+    }
+
+    #[test]
+    fn blendermann_coefficients() {
+        let model = example_builder().build().unwrap();
+
+        let velocity = 10.0;
+        let dynamic_pressure = 0.5 * 1.225 * velocity * velocity;
+
+        let head_wind = WindCondition { velocity, direction_coming_from: 0.0 };
+        let beam_wind = WindCondition { velocity, direction_coming_from: Float::from(90.0).to_radians() };
+        let quartering_wind = WindCondition { velocity, direction_coming_from: Float::from(45.0).to_radians() };
+
+        // No coupling for head and beam wind, so the coefficients equal the input values
+        let resistance_head_wind = 0.55 * 200.0 * dynamic_pressure;
+        let side_force_beam_wind = 0.85 * 1000.0 * dynamic_pressure;
+
+        // At 45 degrees, the coupling denominator is 1 - 0.5 * delta * (1 - CDl AF / (CDt AL))
+        let denominator: Float = 1.0 - 0.5 * 0.55 * (1.0 - (0.55 * 200.0) / (0.85 * 1000.0));
+        let side_force_coefficient_quartering = 0.85 * Float::from(45.0).to_radians().sin() / denominator;
+
+        dbg!(denominator, side_force_coefficient_quartering);
+
+        assert!((model.resistance(&head_wind) - resistance_head_wind).abs() < 1e-9);
+        assert!(model.side_force(&head_wind).abs() < 1e-9);
+        assert!((model.side_force(&beam_wind) - side_force_beam_wind).abs() < 1e-9);
+        assert!((side_force_coefficient_quartering - 0.790231).abs() < 1e-6);
+        assert!(
+            (model.side_force(&quartering_wind) - side_force_coefficient_quartering * 1000.0 * dynamic_pressure).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn yaw_and_roll_change_force_split() {
+        let model = example_builder().build().unwrap();
+
+        let velocity = 10.0;
+
+        // Wind from the north
+        let apparent_wind = WindCondition { velocity, direction_coming_from: 0.0 };
+
+        let yaw_angles: Vec<Float> = [0.0, 30.0, 60.0, 90.0].iter()
+            .map(|angle: &Float| angle.to_radians())
+            .collect();
+
+        let table = model.force_moment_attitude_table(&apparent_wind, &[0.0, 0.3], &yaw_angles);
+
+        let upright_forces = &table.force[0];
+
+        dbg!(upright_forces);
+
+        // Head wind gives only resistance, while the wind is on the port side when heading east
+        assert!(upright_forces[0][0] < 0.0 && upright_forces[0][1].abs() < 1e-9);
+        assert!(upright_forces[3][0].abs() < 1e-9 && upright_forces[3][1] > 0.0);
+
+        // The split follows the Blendermann model for the relative wind direction
+        for (i, yaw) in yaw_angles.iter().enumerate() {
+            let relative_wind = WindCondition { velocity, direction_coming_from: -yaw };
+
+            assert!((upright_forces[i][0] + model.resistance(&relative_wind)).abs() < 1e-6);
+            assert!((upright_forces[i][1] + model.side_force(&relative_wind)).abs() < 1e-6);
+        }
+
+        let lateral_fraction: Vec<Float> = upright_forces.iter()
+            .map(|force| force[1].abs() / (force[0].abs() + force[1].abs()))
+            .collect();
+
+        for i in 1..lateral_fraction.len() {
+            assert!(lateral_fraction[i] > lateral_fraction[i - 1]);
+        }
+
+        // Heel reduces the lateral velocity, and therefore the side force, for beam wind
+        let heeled_side_force = table.force[1][3][1];
+        let expected_side_force = upright_forces[3][1] * (0.3 as Float).cos().powi(2);
+
+        assert!((heeled_side_force - expected_side_force).abs() < 1e-6 * expected_side_force);
+
+        let (_, moment) = model.force_moment_vs_attitude(&apparent_wind, 0.3, yaw_angles[3]);
+
+        assert_eq!(moment, table.moment[1][3]);
+    }
+}
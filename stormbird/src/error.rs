@@ -1,4 +1,3 @@
-
 // Copyright (C) 2024, NTNU
 // Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
@@ -17,10 +16,67 @@ pub enum Error {
     IoError(std::io::Error),
     /// Interface to the Serde JSON error
     SerdeJsonError(serde_json::Error),
+    /// Error from parsing a JSON string, with the location of the error in the input
+    JsonParseError(JsonParseError),
     /// A custom error that can be created from a string
     CustomStringError(String),
 }
 
+#[derive(Debug, Clone)]
+/// Information about a failed JSON parse, pointing to where in the input string the error is.
+pub struct JsonParseError {
+    /// The error message from serde_json, without the location information
+    pub message: String,
+    /// The line in the input where the error occurred, starting at 1
+    pub line: usize,
+    /// The column in the input where the error occurred, starting at 1
+    pub column: usize,
+    /// The offending line from the input, with a marker below the column of the error
+    pub snippet: String,
+}
+
+impl JsonParseError {
+    /// Creates a new parse error based on a serde_json error and the input string that was parsed.
+    pub fn new(error: &serde_json::Error, input: &str) -> Self {
+        let line = error.line();
+        let column = error.column();
+
+        let full_message = error.to_string();
+
+        let message = match full_message.rfind(" at line ") {
+            Some(index) => full_message[..index].to_string(),
+            None => full_message,
+        };
+
+        let snippet = match input.lines().nth(line.saturating_sub(1)) {
+            Some(line_string) => {
+                let marker_offset = line_string.chars()
+                    .take(column.saturating_sub(1))
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+
+                format!("{}\n{}^", line_string, marker_offset)
+            },
+            None => String::new(),
+        };
+
+        Self {
+            message,
+            line,
+            column,
+            snippet,
+        }
+    }
+}
+
+impl Error {
+    /// Converts a serde_json error to an error that also contains the location of the error in the
+    /// input string.
+    pub fn from_json_error_with_input(error: serde_json::Error, input: &str) -> Self {
+        Error::JsonParseError(JsonParseError::new(&error, input))
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         Error::IoError(error)
@@ -44,9 +100,14 @@ impl fmt::Display for Error {
         match self {
             Error::IoError(error) => write!(f, "IO error: {}", error),
             Error::SerdeJsonError(error) => write!(f, "Serde JSON error: {}", error),
+            Error::JsonParseError(error) => write!(
+                f,
+                "JSON parse error at line {}, column {}: {}\n{}",
+                error.line, error.column, error.message, error.snippet
+            ),
             Error::CustomStringError(error) => write!(f, "Custom string error: {}", error),
         }
     }
 }
 
-impl std::error::Error for Error {}
\ No newline at end of file
+impl std::error::Error for Error {}
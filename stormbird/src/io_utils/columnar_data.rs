@@ -0,0 +1,201 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Time series data stored column by column, which is faster to load in analysis tools than 
+//! row-wise CSV files for long simulations. The data is collected in memory during the simulation, 
+//! and can be written to a Parquet file at the end when the `columnar_output` feature is active.
+
+use stormath::type_aliases::Float;
+
+use crate::error::Error;
+use crate::common_utils::results::simulation::SimulationResult;
+
+#[derive(Debug, Clone, Default)]
+/// Accumulates rows of named values, and stores them as one vector per column.
+pub struct ColumnarData {
+    pub column_names: Vec<String>,
+    pub columns: Vec<Vec<Float>>,
+}
+
+impl ColumnarData {
+    /// Collects the reduced time series values from a list of simulation results, for instance the
+    /// output from a maneuver simulation.
+    pub fn from_simulation_results(results: &[SimulationResult]) -> Result<Self, Error> {
+        let mut data = Self::default();
+
+        for result in results {
+            data.add_simulation_result(result)?;
+        }
+
+        Ok(data)
+    }
+
+    /// Adds the reduced time series values from a single simulation result as a new row. See 
+    /// [SimulationResult::reduced_flatten_values] for the columns.
+    pub fn add_simulation_result(&mut self, result: &SimulationResult) -> Result<(), Error> {
+        let (names, values) = result.reduced_flatten_values();
+
+        self.add_row(&names, &values)
+    }
+
+    /// Adds a new row. The column names are set by the first row, and all later rows must have 
+    /// the same names in the same order.
+    pub fn add_row(&mut self, names: &[String], values: &[Float]) -> Result<(), Error> {
+        if names.len() != values.len() {
+            return Err(Error::CustomStringError(format!(
+                "Number of names ({}) and values ({}) in row does not match",
+                names.len(), values.len()
+            )));
+        }
+
+        if self.column_names.is_empty() {
+            self.column_names = names.to_vec();
+            self.columns = vec![Vec::new(); names.len()];
+        } else if self.column_names != names {
+            return Err(Error::CustomStringError(
+                "Column names in row does not match the existing columns".to_string()
+            ));
+        }
+
+        for (column, value) in self.columns.iter_mut().zip(values) {
+            column.push(*value);
+        }
+
+        Ok(())
+    }
+
+    pub fn nr_rows(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    /// Returns the values in the column with the given name, if it exists
+    pub fn column(&self, name: &str) -> Option<&[Float]> {
+        self.column_names.iter()
+            .position(|column_name| column_name == name)
+            .map(|index| self.columns[index].as_slice())
+    }
+
+    /// Writes the data to a Parquet file, with one float column for each column in the data, with 
+    /// the same precision as the floats in the library. The file can be loaded directly with 
+    /// `pandas.read_parquet`.
+    #[cfg(feature = "columnar_output")]
+    pub fn write_to_parquet_file(&self, file_path: &str) -> Result<(), Error> {
+        use std::sync::Arc;
+
+        use arrow_array::{ArrayRef, RecordBatch};
+        use arrow_schema::{Field, Schema};
+        use parquet::arrow::ArrowWriter;
+
+        #[cfg(not(feature = "single_precision"))]
+        use arrow_array::Float64Array as FloatArray;
+        #[cfg(not(feature = "single_precision"))]
+        const DATA_TYPE: arrow_schema::DataType = arrow_schema::DataType::Float64;
+
+        #[cfg(feature = "single_precision")]
+        use arrow_array::Float32Array as FloatArray;
+        #[cfg(feature = "single_precision")]
+        const DATA_TYPE: arrow_schema::DataType = arrow_schema::DataType::Float32;
+
+        if self.column_names.is_empty() {
+            return Err(Error::CustomStringError(
+                "No data to write to the Parquet file".to_string()
+            ));
+        }
+
+        let parquet_error = |error: &dyn std::fmt::Display| Error::CustomStringError(
+            format!("Error when writing Parquet file {}: {}", file_path, error)
+        );
+
+        let fields: Vec<Field> = self.column_names.iter().map(
+            |name| Field::new(name, DATA_TYPE, false)
+        ).collect();
+
+        let arrays: Vec<ArrayRef> = self.columns.iter().map(
+            |column| Arc::new(FloatArray::from(column.clone())) as ArrayRef
+        ).collect();
+
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+            .map_err(|error| parquet_error(&error))?;
+
+        let file = std::fs::File::create(file_path)?;
+
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None)
+            .map_err(|error| parquet_error(&error))?;
+
+        writer.write(&batch).map_err(|error| parquet_error(&error))?;
+        writer.close().map_err(|error| parquet_error(&error))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common_utils::forces_and_moments::IntegratedValues;
+
+    const NR_STEPS: usize = 25;
+    const NR_WINGS: usize = 2;
+
+    fn time_series() -> Vec<SimulationResult> {
+        (0..NR_STEPS).map(|i| {
+            SimulationResult {
+                time: 0.1 * i as Float,
+                wing_indices: (0..NR_WINGS).map(|wing| wing..wing + 1).collect(),
+                integrated_forces: vec![IntegratedValues::default(); NR_WINGS],
+                integrated_moments: vec![IntegratedValues::default(); NR_WINGS],
+                ..Default::default()
+            }
+        }).collect()
+    }
+
+    #[test]
+    fn test_columns_and_rows() {
+        let results = time_series();
+
+        let data = ColumnarData::from_simulation_results(&results).unwrap();
+
+        assert_eq!(data.nr_rows(), NR_STEPS);
+        assert_eq!(data.column_names.len(), 1 + 6 * NR_WINGS);
+        assert_eq!(data.column_names[0], "time");
+
+        for name in ["force_0.x", "force_1.y", "moment_1.z"] {
+            assert!(data.column(name).is_some(), "Missing column {}", name);
+        }
+
+        assert_eq!(data.column("time").unwrap()[NR_STEPS - 1], results[NR_STEPS - 1].time);
+
+        let mut extended_data = data.clone();
+
+        assert!(extended_data.add_row(&["time".to_string()], &[0.0]).is_err());
+    }
+
+    #[cfg(feature = "columnar_output")]
+    #[test]
+    fn test_parquet_file() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let data = ColumnarData::from_simulation_results(&time_series()).unwrap();
+
+        let file_path = std::env::temp_dir().join("stormbird_test_columnar_data.parquet");
+
+        data.write_to_parquet_file(file_path.to_str().unwrap()).unwrap();
+
+        let file = std::fs::File::open(&file_path).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap();
+
+        let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+
+        let nr_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+
+        let schema = batches[0].schema();
+
+        let column_names: Vec<&String> = schema.fields().iter().map(|field| field.name()).collect();
+
+        assert_eq!(nr_rows, NR_STEPS);
+        assert_eq!(column_names, data.column_names.iter().collect::<Vec<&String>>());
+    }
+}
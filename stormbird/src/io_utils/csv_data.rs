@@ -5,9 +5,45 @@
 use std::path::Path;
 use std::fs;
 use std::io::Write;
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+use stormath::type_aliases::Float;
 
 use crate::error::Error;
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// How floating point values are formatted when written to CSV files.
+pub enum CsvPrecision {
+    /// Full precision, using the shortest representation that gives back the exact same value 
+    /// when parsed.
+    Full,
+    /// Fixed number of decimals. The default is six decimals.
+    Decimals(usize),
+    /// Fixed number of significant figures, written in scientific notation. This preserves the
+    /// relative precision of both very small and very large values.
+    SignificantFigures(usize),
+}
+
+impl Default for CsvPrecision {
+    fn default() -> Self {
+        CsvPrecision::Decimals(6)
+    }
+}
+
+impl CsvPrecision {
+    pub fn format_value(&self, value: Float) -> String {
+        match self {
+            CsvPrecision::Full => format!("{}", value),
+            CsvPrecision::Decimals(nr_decimals) => format!("{:.*}", nr_decimals, value),
+            CsvPrecision::SignificantFigures(nr_figures) => format!(
+                "{:.*e}", nr_figures.saturating_sub(1), value
+            ),
+        }
+    }
+}
+
 pub fn create_or_append_header_and_data_strings_file(
     file_path_str: &str,
     header: &str,
@@ -31,3 +67,151 @@ pub fn create_or_append_header_and_data_strings_file(
 
     Ok(())
 }
+
+
+#[derive(Debug, Clone, Default)]
+/// Appends data lines to CSV files, with an optional limit on the number of data lines in each
+/// file. When a file reaches the limit, it is moved to a backup file with the suffix `.1`, which
+/// replaces any previous backup, and a new file is started. The most recent data is therefore
+/// always available in the two files, without the files growing without bounds.
+///
+/// The number of data lines in each file is kept in memory, so that the files are never read back
+/// while writing. The only exception is a file that already exists the first time it is written
+/// to, where the existing lines are counted once.
+pub struct RotatingCsvWriter {
+    pub max_nr_data_lines: Option<usize>,
+    nr_data_lines: HashMap<String, usize>,
+}
+
+impl RotatingCsvWriter {
+    pub fn new(max_nr_data_lines: Option<usize>) -> Self {
+        Self {
+            max_nr_data_lines,
+            nr_data_lines: HashMap::new(),
+        }
+    }
+
+    /// Same as [create_or_append_header_and_data_strings_file], but with the file rotated if the
+    /// limit on the number of data lines is reached.
+    pub fn write(
+        &mut self,
+        file_path_str: &str,
+        header: &str,
+        data: &str,
+    ) -> Result<(), Error> {
+        let max_nr_data_lines = match self.max_nr_data_lines {
+            Some(max_nr_data_lines) => max_nr_data_lines.max(1),
+            None => return create_or_append_header_and_data_strings_file(file_path_str, header, data),
+        };
+
+        let mut nr_data_lines = match self.nr_data_lines.get(file_path_str) {
+            Some(nr_data_lines) => *nr_data_lines,
+            None => Self::count_existing_data_lines(file_path_str)?,
+        };
+
+        if nr_data_lines >= max_nr_data_lines {
+            fs::rename(file_path_str, Self::backup_file_path(file_path_str))?;
+
+            nr_data_lines = 0;
+        }
+
+        create_or_append_header_and_data_strings_file(file_path_str, header, data)?;
+
+        self.nr_data_lines.insert(file_path_str.to_string(), nr_data_lines + 1);
+
+        Ok(())
+    }
+
+    /// The path of the file that holds the previous data lines after a rotation
+    pub fn backup_file_path(file_path_str: &str) -> String {
+        format!("{}.1", file_path_str)
+    }
+
+    fn count_existing_data_lines(file_path_str: &str) -> Result<usize, Error> {
+        if !Path::new(file_path_str).exists() {
+            return Ok(0);
+        }
+
+        let file_content = fs::read_to_string(file_path_str)?;
+
+        Ok(file_content.lines().count().saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precision() {
+        let small_value: Float = 1.234e-9;
+
+        assert_eq!(CsvPrecision::default().format_value(small_value), "0.000000");
+        assert_eq!(CsvPrecision::Full.format_value(small_value), "0.000000001234");
+
+        let value_decimals = CsvPrecision::Decimals(15).format_value(small_value);
+        let value_significant = CsvPrecision::SignificantFigures(4).format_value(small_value);
+
+        assert_eq!(value_significant, "1.234e-9");
+
+        for value_string in [value_decimals, value_significant] {
+            let parsed_value: Float = value_string.parse().unwrap();
+
+            assert!(
+                (parsed_value - small_value).abs() < 1e-12,
+                "Small value not preserved: {}", value_string
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_nr_data_lines() {
+        let file_path = std::env::temp_dir().join("stormbird_test_max_nr_data_lines.csv");
+        let file_path_str = file_path.to_str().unwrap();
+        let backup_file_path_str = RotatingCsvWriter::backup_file_path(file_path_str);
+
+        for path in [file_path_str, backup_file_path_str.as_str()] {
+            if Path::new(path).exists() {
+                fs::remove_file(path).unwrap();
+            }
+        }
+
+        let max_nr_data_lines = 5;
+        let nr_extra_lines = 3;
+
+        let mut writer = RotatingCsvWriter::new(Some(max_nr_data_lines));
+
+        for i in 0..(max_nr_data_lines + nr_extra_lines) {
+            writer.write(
+                file_path_str,
+                "index",
+                &format!("{}", i),
+            ).unwrap();
+        }
+
+        let file_content = fs::read_to_string(&file_path).unwrap();
+        let backup_file_content = fs::read_to_string(&backup_file_path_str).unwrap();
+
+        fs::remove_file(&file_path).unwrap();
+        fs::remove_file(&backup_file_path_str).unwrap();
+
+        let lines: Vec<&str> = file_content.lines().collect();
+        let backup_lines: Vec<&str> = backup_file_content.lines().collect();
+
+        dbg!(&lines, &backup_lines);
+
+        assert_eq!(backup_lines.len(), max_nr_data_lines + 1);
+        assert_eq!(backup_lines[0], "index");
+
+        for i in 0..max_nr_data_lines {
+            assert_eq!(backup_lines[i + 1], format!("{}", i));
+        }
+
+        assert_eq!(lines.len(), nr_extra_lines + 1);
+        assert_eq!(lines[0], "index");
+
+        for i in 0..nr_extra_lines {
+            assert_eq!(lines[i + 1], format!("{}", i + max_nr_data_lines));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+
+/// Parses a JSON string into the output type. If the parsing fails, the returned error contains the
+/// line and column of the problem, together with the offending line from the input.
+pub fn from_json_string<T: DeserializeOwned>(json_string: &str) -> Result<T, Error> {
+    serde_json::from_str(json_string).map_err(
+        |error| Error::from_json_error_with_input(error, json_string)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::line_force_model::builder::LineForceModelBuilder;
+
+    #[test]
+    fn test_error_location() {
+        let json_string = r#"{
+    "wing_builders": [],
+    "nr_sections": 10,
+    "density": 1.225,,
+    "output_coordinate_system": "Global"
+}"#;
+
+        let result: Result<LineForceModelBuilder, Error> = from_json_string(json_string);
+
+        match result {
+            Err(Error::JsonParseError(error)) => {
+                dbg!(&error);
+
+                assert_eq!(error.line, 4);
+                assert_eq!(error.column, 22);
+                assert!(error.snippet.starts_with("    \"density\": 1.225,,"));
+            },
+            _ => panic!("Expected a JSON parse error"),
+        }
+    }
+}
@@ -9,6 +9,8 @@ use std::fs;
 use std::io::Write;
 
 pub mod csv_data;
+pub mod columnar_data;
+pub mod json_data;
 pub mod folder_management;
 
 pub fn write_text_to_file(file_path: &str, text: &str) -> std::io::Result<()> {
@@ -0,0 +1,172 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Functionality for running a batch of steady conditions with the same simulation, for instance
+//! when generating performance polars, and for collecting statistics about the batch run.
+
+use std::time::Instant;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use stormath::type_aliases::Float;
+
+use crate::lifting_line::prelude::*;
+
+use super::simulation::Simulation;
+
+#[derive(Debug, Clone, Default)]
+/// Convergence and timing statistics from a batch run.
+pub struct BatchStats {
+    /// The total number of conditions in the batch
+    pub nr_conditions: usize,
+    /// The sum of solver iterations for all conditions
+    pub total_iterations: usize,
+    /// Indices of the conditions where the solver did not converge
+    pub non_converged_indices: Vec<usize>,
+    /// Wall-clock time for the complete batch, in seconds
+    pub wall_clock_time: Float,
+}
+
+impl BatchStats {
+    /// Average number of solver iterations per condition
+    pub fn average_iterations(&self) -> Float {
+        if self.nr_conditions == 0 {
+            0.0
+        } else {
+            self.total_iterations as Float / self.nr_conditions as Float
+        }
+    }
+
+    pub fn nr_non_converged(&self) -> usize {
+        self.non_converged_indices.len()
+    }
+
+    /// Human readable summary of the statistics
+    pub fn summary(&self) -> String {
+        format!(
+            "Batch run summary:\n\
+            - conditions: {}\n\
+            - average iterations: {:.2}\n\
+            - non-converged conditions: {} {:?}\n\
+            - wall-clock time: {:.3} s",
+            self.nr_conditions,
+            self.average_iterations(),
+            self.nr_non_converged(),
+            self.non_converged_indices,
+            self.wall_clock_time
+        )
+    }
+}
+
+impl Simulation {
+    /// Solves a batch of steady conditions, one after the other, and collects statistics about
+    /// the convergence and the time used.
+    ///
+    /// Each condition is solved as a single time step, where the solution from the previous
+    /// condition is used as the initial guess. A condition is counted as non-converged when an
+    /// iterative solver uses all the allowed iterations.
+    ///
+    /// # Arguments
+    /// * `freestream_velocities` - The freestream velocity for each condition, given at the 
+    ///   points returned by `get_freestream_velocity_points`
+    /// * `print_summary` - If true, a summary of the statistics is printed when the batch is done
+    pub fn solve_batch(
+        &mut self,
+        freestream_velocities: &[Vec<SpatialVector>],
+        print_summary: bool
+    ) -> (Vec<SimulationResult>, BatchStats) {
+        let start_time = Instant::now();
+
+        let mut results = Vec::with_capacity(freestream_velocities.len());
+        let mut stats = BatchStats {
+            nr_conditions: freestream_velocities.len(),
+            ..Default::default()
+        };
+
+        for (index, freestream_velocity) in freestream_velocities.iter().enumerate() {
+            let result = self.do_step(index as Float, 1.0, freestream_velocity);
+
+            stats.total_iterations += result.iterations;
+
+            if result.non_converged {
+                stats.non_converged_indices.push(index);
+            }
+
+            results.push(result);
+        }
+
+        stats.wall_clock_time = start_time.elapsed().as_secs_f64() as Float;
+
+        if print_summary {
+            println!("{}", stats.summary());
+        }
+
+        (results, stats)
+    }
+
+    /// Solves a batch of steady conditions independently of each other, and collects statistics
+    /// about the convergence and the time used.
+    ///
+    /// In contrast to [`Simulation::solve_batch`], each condition is solved on a fresh clone of
+    /// the current simulation, so that no solution is carried over between conditions. This makes
+    /// the result of each condition independent of the order of the batch. When the `parallel`
+    /// feature is enabled, the conditions are distributed over threads. The results are always
+    /// returned in the same order as the input, and are identical to a serial run.
+    ///
+    /// # Arguments
+    /// * `freestream_velocities` - The freestream velocity for each condition, given at the 
+    ///   points returned by `get_freestream_velocity_points`
+    /// * `print_summary` - If true, a summary of the statistics is printed when the batch is done
+    pub fn solve_batch_independent(
+        &self,
+        freestream_velocities: &[Vec<SpatialVector>],
+        print_summary: bool
+    ) -> (Vec<SimulationResult>, BatchStats) {
+        let start_time = Instant::now();
+
+        let results = self.solve_conditions_independently(freestream_velocities);
+
+        let mut stats = BatchStats {
+            nr_conditions: freestream_velocities.len(),
+            ..Default::default()
+        };
+
+        for (index, result) in results.iter().enumerate() {
+            stats.total_iterations += result.iterations;
+
+            if result.non_converged {
+                stats.non_converged_indices.push(index);
+            }
+        }
+
+        stats.wall_clock_time = start_time.elapsed().as_secs_f64() as Float;
+
+        if print_summary {
+            println!("{}", stats.summary());
+        }
+
+        (results, stats)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn solve_conditions_independently(
+        &self,
+        freestream_velocities: &[Vec<SpatialVector>]
+    ) -> Vec<SimulationResult> {
+        freestream_velocities.iter()
+            .map(|freestream_velocity| self.clone().do_step(0.0, 1.0, freestream_velocity))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    fn solve_conditions_independently(
+        &self,
+        freestream_velocities: &[Vec<SpatialVector>]
+    ) -> Vec<SimulationResult> {
+        freestream_velocities.par_iter()
+            .map(|freestream_velocity| self.clone().do_step(0.0, 1.0, freestream_velocity))
+            .collect()
+    }
+}
@@ -11,8 +11,14 @@ use crate::controller::builder::ControllerBuilder;
 //use crate::empirical_models::input_power::InputPower;
 
 use super::CompleteSailModel;
+use super::furling::FurlingModel;
+
+use stormath::type_aliases::Float;
+use stormath::spatial_vector::SpatialVector;
 
 use crate::error::Error;
+use crate::io_utils::json_data;
+use crate::io_utils::columnar_data::ColumnarData;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -20,13 +26,25 @@ pub struct CompleteSailModelBuilder {
     lifting_line_simulation: SimulationBuilder,
     wind_environment: WindEnvironment,
     controller: ControllerBuilder,
+    /// Factor applied to the sail thrust when computing the delivered power, to account for the 
+    /// interaction with the hull. A value of 1.0 means no interaction.
+    #[serde(default="CompleteSailModel::default_thrust_interaction_factor")]
+    thrust_interaction_factor: Float,
+    /// Optional furling schedule that reduces the span of the sails in strong wind
+    #[serde(default)]
+    furling_model: Option<FurlingModel>,
+    /// Direction of the thrust on the ship. Normalized when the model is built.
+    #[serde(default="CompleteSailModel::default_thrust_direction")]
+    thrust_direction: SpatialVector,
+    /// Switch to collect the results from every time step in a columnar format, which can be 
+    /// written to a Parquet file at the end of the simulation.
+    #[serde(default)]
+    record_columnar_output: bool,
 }
 
 impl CompleteSailModelBuilder {
     pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
-        let builder = serde_json::from_str(setup_string)?;
-        
-        Ok(builder)
+        json_data::from_json_string(setup_string)
     }
 
     pub fn new_from_file(file_path: &str) -> Result<Self, Error> {
@@ -35,11 +53,16 @@ impl CompleteSailModelBuilder {
         Self::new_from_string(&string)
     }
 
-    pub fn build(&self) -> CompleteSailModel {
-        CompleteSailModel {
+    pub fn build(&self) -> Result<CompleteSailModel, Error> {
+        Ok(CompleteSailModel {
             lifting_line_simulation: self.lifting_line_simulation.build(),
             wind_environment: self.wind_environment.clone(),
-            controller: self.controller.build()
-        }
+            controller: self.controller.build()?,
+            thrust_interaction_factor: self.thrust_interaction_factor,
+            furling_model: self.furling_model.clone(),
+            thrust_direction: self.thrust_direction.normalize(),
+            last_result: None,
+            columnar_output: self.record_columnar_output.then(ColumnarData::default),
+        })
     }
 }
@@ -0,0 +1,84 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Data used to find the ship speed where the sail thrust balances the hull resistance.
+
+use serde::{Serialize, Deserialize};
+
+use stormath::type_aliases::Float;
+use stormath::interpolation::linear_interpolation;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Tabulated hull resistance as a function of ship velocity. The resistance is linearly 
+/// interpolated between the velocities, and held constant outside the range of the data. The first
+/// velocity should therefore normally be zero.
+pub struct ResistanceCurve {
+    /// Ship velocities, sorted in increasing order
+    pub velocities: Vec<Float>,
+    /// Hull resistance at each velocity
+    pub resistance: Vec<Float>,
+}
+
+impl ResistanceCurve {
+    /// Creates a new resistance curve, after checking that the data is consistent.
+    pub fn new(velocities: Vec<Float>, resistance: Vec<Float>) -> Result<Self, Error> {
+        if velocities.is_empty() || velocities.len() != resistance.len() {
+            return Err(Error::CustomStringError(
+                format!(
+                    "The resistance curve must contain the same, non-zero, number of velocities \
+                    and resistance values. Velocities: {}, resistance values: {}",
+                    velocities.len(), resistance.len()
+                )
+            ));
+        }
+
+        if velocities.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(Error::CustomStringError(
+                "The velocities in the resistance curve must be sorted in increasing order"
+                    .to_string()
+            ));
+        }
+
+        Ok(Self { velocities, resistance })
+    }
+
+    pub fn resistance_at(&self, velocity: Float) -> Float {
+        linear_interpolation(velocity, &self.velocities, &self.resistance)
+    }
+
+    /// The largest velocity in the curve, which is used as the upper bound when searching for the
+    /// equilibrium speed.
+    pub fn max_velocity(&self) -> Float {
+        self.velocities[self.velocities.len() - 1]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Settings for the root finding in 
+/// [CompleteSailModel::solve_equilibrium_speed_with_settings](super::CompleteSailModel::solve_equilibrium_speed_with_settings)
+pub struct EquilibriumSpeedSettings {
+    #[serde(default="EquilibriumSpeedSettings::default_velocity_tolerance")]
+    /// The iterations stop when the change in ship velocity is smaller than this value
+    pub velocity_tolerance: Float,
+    #[serde(default="EquilibriumSpeedSettings::default_max_iterations")]
+    pub max_iterations: usize,
+}
+
+impl EquilibriumSpeedSettings {
+    pub fn default_velocity_tolerance() -> Float {1e-3}
+    pub fn default_max_iterations() -> usize {50}
+}
+
+impl Default for EquilibriumSpeedSettings {
+    fn default() -> Self {
+        Self {
+            velocity_tolerance: Self::default_velocity_tolerance(),
+            max_iterations: Self::default_max_iterations(),
+        }
+    }
+}
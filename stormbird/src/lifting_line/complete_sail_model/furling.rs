@@ -0,0 +1,40 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Model of how a sail is furled to reduce the sail area in strong wind.
+
+use serde::{Serialize, Deserialize};
+
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Furling schedule where the span of the sails is reduced when the apparent wind speed exceeds a 
+/// threshold.
+///
+/// Above the threshold, the span fraction is set to (threshold / apparent_wind_speed)^2. As the 
+/// forces scale with the velocity squared times the area, this keeps the force approximately 
+/// constant above the threshold, until the minimum span fraction is reached.
+pub struct FurlingModel {
+    /// Apparent wind speed where the sails start to furl, in m/s
+    pub wind_speed_threshold: Float,
+    #[serde(default="FurlingModel::default_min_span_fraction")]
+    /// The smallest allowed fraction of the unfurled span
+    pub min_span_fraction: Float,
+}
+
+impl FurlingModel {
+    pub fn default_min_span_fraction() -> Float {0.2}
+
+    /// Returns the fraction of the unfurled span that is active at the given apparent wind speed.
+    pub fn span_fraction(&self, apparent_wind_speed: Float) -> Float {
+        if apparent_wind_speed <= self.wind_speed_threshold {
+            return 1.0;
+        }
+
+        let span_fraction = (self.wind_speed_threshold / apparent_wind_speed).powi(2);
+
+        span_fraction.max(self.min_span_fraction)
+    }
+}
@@ -7,6 +7,13 @@
 /// a generic sail type, where the exact details are not that important.
 
 pub mod builder;
+pub mod equilibrium;
+pub mod furling;
+pub mod polar;
+pub mod savings;
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
 use crate::lifting_line::simulation::Simulation as LiftingLineSimulation;
 
@@ -25,15 +32,25 @@ use crate::common_utils::results::{
     simplfied::SingleSailResult,
 };
 
+use crate::io_utils::columnar_data::ColumnarData;
+
 use builder::CompleteSailModelBuilder;
+use equilibrium::{ResistanceCurve, EquilibriumSpeedSettings};
+use furling::FurlingModel;
+use polar::PolarResult;
+use savings::Savings;
 
 use stormath::{
     type_aliases::Float,
+    consts::{PI, TAU},
     spatial_vector::SpatialVector,
     array_generation,
 };
 use crate::error::Error;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 #[derive(Debug, Clone)]
 /// Collection of the necessary functionality to simulate a *complete sail system* using the lifting
 /// line model. This means combining a lifting line model of the sails with a model of the wind
@@ -43,14 +60,44 @@ pub struct CompleteSailModel {
     pub lifting_line_simulation: LiftingLineSimulation,
     pub wind_environment: WindEnvironment,
     pub controller: Controller,
+    /// Factor applied to the sail thrust when computing the delivered power. Can be used to 
+    /// account for the interaction between the sail thrust and the hull, similar to a thrust 
+    /// deduction factor for a propeller.
+    pub thrust_interaction_factor: Float,
+    /// Optional furling schedule that reduces the span of the sails in strong wind
+    pub furling_model: Option<FurlingModel>,
+    /// Direction of the thrust on the ship, i.e., the direction the ship is moving in
+    pub thrust_direction: SpatialVector,
+    /// The result from the last time step, used as input to the controller at the next time step.
+    /// Cleared when a new condition is simulated.
+    pub last_result: Option<SimulationResult>,
+    /// Optional time series of the results from every time step, stored column by column. Only 
+    /// collected when this is not `None`.
+    pub columnar_output: Option<ColumnarData>,
 }
 
 impl CompleteSailModel {
+    pub fn default_thrust_interaction_factor() -> Float {1.0}
+    /// The ship velocity is added to the wind as `ship_velocity * zero_direction_vector` in 
+    /// [CompleteSailModel::freestream_velocity], which means that the ship moves in the negative 
+    /// direction of the zero direction vector. The default matches the default zero direction 
+    /// vector in the wind environment.
+    pub fn default_thrust_direction() -> SpatialVector {SpatialVector::from([-1.0, 0.0, 0.0])}
+    pub fn default_heading_step() -> Float {Float::from(1.0).to_radians()}
+    pub fn default_zero_thrust_angle_step() -> Float {Float::from(2.0).to_radians()}
+    pub fn default_coarse_wing_angle_step() -> Float {Float::from(5.0).to_radians()}
+    pub fn default_fine_wing_angle_step() -> Float {Float::from(0.25).to_radians()}
+    pub fn default_frequency_response_steps_per_period() -> usize {32}
+    pub fn default_frequency_response_nr_periods() -> usize {4}
+    pub fn default_best_heading_step() -> Float {Float::from(5.0).to_radians()}
+    pub fn default_nr_loadings_to_test() -> usize {10}
+    pub fn default_nr_loading_increments() -> usize {10}
+
     /// Generate a model from an input json string
     pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
         let builder = CompleteSailModelBuilder::new_from_string(setup_string)?;
 
-        Ok(builder.build())
+        builder.build()
     }
     
     /// Query the model for the number of sails
@@ -58,6 +105,59 @@ impl CompleteSailModel {
         self.lifting_line_simulation.line_force_model.nr_wings()
     }
     
+    /// Returns the total thrust from the sails in the simulation result, as the total force 
+    /// projected on the thrust direction.
+    pub fn thrust(&self, result: &SimulationResult) -> Float {
+        self.thrust_direction.dot(result.integrated_forces_sum())
+    }
+
+    /// Returns the total side force from the sails in the simulation result, as the total force 
+    /// projected on the direction perpendicular to the thrust direction, in the plane the wind 
+    /// direction rotates in.
+    pub fn side_force(&self, result: &SimulationResult) -> Float {
+        let side_direction = self.wind_environment.side_direction(self.thrust_direction);
+
+        side_direction.dot(result.integrated_forces_sum())
+    }
+
+    /// Computes the power delivered to the ship from the sails, based on the thrust in the 
+    /// simulation result and the ship velocity. The thrust interaction factor is applied to the
+    /// thrust, while the forces in the result are left unchanged. See [CompleteSailModel::thrust] 
+    /// for the definition of the thrust direction.
+    pub fn delivered_power(&self, result: &SimulationResult, ship_velocity: Float) -> Float {
+        self.thrust_interaction_factor * self.thrust(result) * ship_velocity
+    }
+    
+    /// Estimates the fuel and CO2 savings from the net energy delivered by the sails over an 
+    /// operating profile. See [Savings::from_net_energy] for the units of the input.
+    pub fn estimated_savings(
+        &self,
+        net_energy: Float,
+        specific_fuel_consumption: Float,
+        emission_factor: Float
+    ) -> Savings {
+        Savings::from_net_energy(net_energy, specific_fuel_consumption, emission_factor)
+    }
+
+    /// Checks, in debug builds only, that the power values from a simulation are consistent. The 
+    /// input power must be non-negative, so that the effective power can never exceed the power 
+    /// delivered by the sail thrust, and both values must be finite.
+    fn debug_check_power_consistency(delivered_power: Float, input_power: Float) {
+        debug_assert!(
+            delivered_power.is_finite() && input_power.is_finite(),
+            "Non-finite power values: delivered power {}, input power {}",
+            delivered_power,
+            input_power
+        );
+
+        debug_assert!(
+            input_power >= 0.0,
+            "Negative input power: {}. The effective power would exceed the delivered power {}",
+            input_power,
+            delivered_power
+        );
+    }
+    
     /// Runs multiple `simulate_condition` calls with different loadings, and chooses the best one
     /// based on the maximum delivered power
     pub fn simulate_condition_optimal_controller_loading(
@@ -86,10 +186,10 @@ impl CompleteSailModel {
                 nr_time_steps
             );
             
-            // TODO: must find a way to define what the thrust direction is!
-            let thrust = -result.integrated_forces_sum()[0];
-            let delivered_power = thrust * ship_velocity;
+            let delivered_power = self.delivered_power(&result, ship_velocity);
             let input_power = result.input_power_sum();
+
+            Self::debug_check_power_consistency(delivered_power, input_power);
             
             effective_power.push(delivered_power - input_power);
             
@@ -106,6 +206,55 @@ impl CompleteSailModel {
         results[best_index].clone()
     }
     
+    /// Finds the ship heading that gives the maximum net power from the sails, for a given true 
+    /// wind and ship speed. The net power is the delivered power minus the input power.
+    ///
+    /// The headings in the window, given as the lower and upper bound in radians, are swept with 
+    /// a fixed step, and the optimal controller loading is used for each heading. The heading 
+    /// follows the same sign convention as in [CompleteSailModel::yaw_stiffness].
+    ///
+    /// Returns the best heading and the net power at this heading.
+    pub fn best_heading(
+        &mut self,
+        true_wind: WindCondition,
+        ship_speed: Float,
+        heading_window: (Float, Float),
+    ) -> (Float, Float) {
+        let (min_heading, max_heading) = heading_window;
+
+        let nr_headings = ((max_heading - min_heading) / Self::default_best_heading_step())
+            .ceil() as usize + 1;
+
+        let headings = array_generation::linspace(min_heading, max_heading, nr_headings);
+
+        let mut best_heading = min_heading;
+        let mut max_net_power = Float::NEG_INFINITY;
+
+        for heading in headings {
+            let relative_condition = WindCondition {
+                velocity: true_wind.velocity,
+                direction_coming_from: true_wind.direction_coming_from - heading,
+            };
+
+            let result = self.simulate_condition_optimal_controller_loading(
+                relative_condition,
+                ship_speed,
+                Self::default_nr_loadings_to_test(),
+                1.0,
+                1
+            );
+
+            let net_power = self.delivered_power(&result, ship_speed) - result.input_power_sum();
+
+            if net_power > max_net_power {
+                max_net_power = net_power;
+                best_heading = heading;
+            }
+        }
+
+        (best_heading, max_net_power)
+    }
+
     pub fn simulate_steady_state_condition(
         &mut self,
         wind_condition: WindCondition,
@@ -121,6 +270,319 @@ impl CompleteSailModel {
         )
     }
     
+    /// Finds the ship velocity where the sail thrust is equal to the hull resistance, using the
+    /// default settings in [EquilibriumSpeedSettings].
+    pub fn solve_equilibrium_speed(
+        &mut self,
+        wind_condition: WindCondition,
+        resistance_curve: &ResistanceCurve,
+        controller_loading: Float
+    ) -> Result<(Float, SimulationResult), Error> {
+        self.solve_equilibrium_speed_with_settings(
+            wind_condition,
+            resistance_curve,
+            controller_loading,
+            &EquilibriumSpeedSettings::default()
+        )
+    }
+
+    /// Finds the ship velocity where the sail thrust is equal to the hull resistance, for a given 
+    /// true wind condition and controller loading. The thrust interaction factor is applied to the
+    /// thrust, and the thrust direction is defined as in [CompleteSailModel::thrust].
+    ///
+    /// The root is bracketed between zero and the largest velocity in the resistance curve, and 
+    /// found with the Illinois variant of the false position method. If the thrust is not larger 
+    /// than the resistance at zero velocity, the equilibrium speed is zero. An error is returned if
+    /// the thrust is larger than the resistance at the largest velocity in the curve, or if the 
+    /// method does not converge within the maximum number of iterations.
+    ///
+    /// Returns the equilibrium speed and the simulation result at this speed.
+    pub fn solve_equilibrium_speed_with_settings(
+        &mut self,
+        wind_condition: WindCondition,
+        resistance_curve: &ResistanceCurve,
+        controller_loading: Float,
+        settings: &EquilibriumSpeedSettings
+    ) -> Result<(Float, SimulationResult), Error> {
+        let mut force_balance = |ship_velocity: Float| -> (Float, SimulationResult) {
+            let result = self.simulate_steady_state_condition(
+                wind_condition, ship_velocity, controller_loading
+            );
+
+            let net_force = self.thrust_interaction_factor * self.thrust(&result) - 
+                resistance_curve.resistance_at(ship_velocity);
+
+            (net_force, result)
+        };
+
+        let mut lower_velocity = 0.0;
+        let (mut lower_net_force, lower_result) = force_balance(lower_velocity);
+
+        if lower_net_force <= 0.0 {
+            return Ok((lower_velocity, lower_result));
+        }
+
+        let mut upper_velocity = resistance_curve.max_velocity();
+        let (mut upper_net_force, upper_result) = force_balance(upper_velocity);
+
+        if upper_net_force >= 0.0 {
+            if upper_net_force == 0.0 {
+                return Ok((upper_velocity, upper_result));
+            }
+
+            return Err(Error::CustomStringError(
+                format!(
+                    "The sail thrust is larger than the resistance at the largest velocity in the \
+                    resistance curve, {}. The curve must be extended to find the equilibrium speed",
+                    upper_velocity
+                )
+            ));
+        }
+
+        let mut previous_velocity = lower_velocity;
+        // +1 = last update moved the lower bound, -1 = the upper bound
+        let mut last_moved_side = 0;
+
+        for _ in 0..settings.max_iterations {
+            let velocity = (lower_velocity * upper_net_force - upper_velocity * lower_net_force) / 
+                (upper_net_force - lower_net_force);
+
+            let (net_force, result) = force_balance(velocity);
+
+            let converged = net_force == 0.0 || 
+                (velocity - previous_velocity).abs() < settings.velocity_tolerance ||
+                (upper_velocity - lower_velocity) < settings.velocity_tolerance;
+
+            if converged {
+                return Ok((velocity, result));
+            }
+
+            if net_force > 0.0 {
+                lower_velocity = velocity;
+                lower_net_force = net_force;
+
+                if last_moved_side == 1 {
+                    upper_net_force *= 0.5;
+                }
+
+                last_moved_side = 1;
+            } else {
+                upper_velocity = velocity;
+                upper_net_force = net_force;
+
+                if last_moved_side == -1 {
+                    lower_net_force *= 0.5;
+                }
+
+                last_moved_side = -1;
+            }
+
+            previous_velocity = velocity;
+        }
+
+        Err(Error::CustomStringError(
+            format!(
+                "The equilibrium speed did not converge within {} iterations. Last bracket: [{}, {}]",
+                settings.max_iterations, lower_velocity, upper_velocity
+            )
+        ))
+    }
+
+    /// Simulates steady state conditions for all combinations of the wind velocities and 
+    /// directions, and returns the thrust, side force and power for each combination.
+    ///
+    /// Each condition is simulated on a fresh clone of the model, so that no wake, controller state
+    /// or solution is carried over between conditions. The result is therefore independent of the
+    /// order of the conditions. When the `parallel` feature is enabled, the conditions are 
+    /// distributed over threads, and the result is identical to a serial run.
+    pub fn simulate_polar(
+        &self,
+        wind_velocities: &[Float],
+        wind_directions: &[Float],
+        ship_velocity: Float,
+        controller_loading: Float
+    ) -> PolarResult {
+        let mut polar = PolarResult::new(wind_velocities, wind_directions);
+
+        let wind_conditions: Vec<WindCondition> = wind_velocities.iter().flat_map(
+            |&velocity| wind_directions.iter().map(
+                move |&direction_coming_from| WindCondition { velocity, direction_coming_from }
+            )
+        ).collect();
+
+        let results = self.simulate_polar_conditions(
+            &wind_conditions, ship_velocity, controller_loading
+        );
+
+        for (condition_index, result) in results.iter().enumerate() {
+            let velocity_index = condition_index / wind_directions.len();
+            let direction_index = condition_index % wind_directions.len();
+
+            polar.thrust[velocity_index][direction_index] = self.thrust(result);
+            polar.side_force[velocity_index][direction_index] = self.side_force(result);
+            polar.input_power[velocity_index][direction_index] = result.input_power_sum();
+            polar.delivered_power[velocity_index][direction_index] = 
+                self.delivered_power(result, ship_velocity);
+        }
+
+        polar
+    }
+
+    /// Simulates a single condition in a polar on a fresh clone of the model
+    pub fn simulate_polar_condition(
+        &self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float
+    ) -> SimulationResult {
+        self.clone().simulate_steady_state_condition(
+            wind_condition, ship_velocity, controller_loading
+        )
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn simulate_polar_conditions(
+        &self,
+        wind_conditions: &[WindCondition],
+        ship_velocity: Float,
+        controller_loading: Float
+    ) -> Vec<SimulationResult> {
+        wind_conditions.iter()
+            .map(|&wind_condition| self.simulate_polar_condition(
+                wind_condition, ship_velocity, controller_loading
+            ))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    fn simulate_polar_conditions(
+        &self,
+        wind_conditions: &[WindCondition],
+        ship_velocity: Float,
+        controller_loading: Float
+    ) -> Vec<SimulationResult> {
+        wind_conditions.par_iter()
+            .map(|&wind_condition| self.simulate_polar_condition(
+                wind_condition, ship_velocity, controller_loading
+            ))
+            .collect()
+    }
+
+    /// Same as [CompleteSailModel::simulate_steady_state_condition], but with a separate 
+    /// controller loading for each sail.
+    pub fn simulate_steady_state_condition_with_sail_loadings(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        sail_loadings: &[Float]
+    ) -> SimulationResult {
+        self.lifting_line_simulation.first_time_step_completed = false;
+        self.controller.reset_state();
+
+        self.do_step_with_sail_loadings(0.0, 1.0, wind_condition, ship_velocity, sail_loadings)
+    }
+
+    /// Finds the controller loading for each sail that maximizes the total delivered power, while 
+    /// the total input power stays within the power budget.
+    ///
+    /// The allocation is greedy: starting from zero loading on all sails, the loading is increased
+    /// in fixed increments, each time on the sail with the largest gain in delivered power per 
+    /// added input power. Increments that exceed the budget or reduce the delivered power are not
+    /// used. Sails without input power, such as wing sails, are therefore loaded first.
+    pub fn optimal_loading_within_power_budget(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        power_budget: Float
+    ) -> Vec<Float> {
+        let nr_sails = self.get_number_of_sails();
+        let loading_increment = 1.0 / Self::default_nr_loading_increments() as Float;
+
+        let mut loadings = vec![0.0; nr_sails];
+
+        let result = self.simulate_steady_state_condition_with_sail_loadings(
+            wind_condition, ship_velocity, &loadings
+        );
+
+        let mut delivered_power = self.delivered_power(&result, ship_velocity);
+        let mut input_power = result.input_power_sum();
+
+        loop {
+            let mut best_candidate: Option<(usize, Float, Float, Float)> = None;
+
+            for sail_index in 0..nr_sails {
+                if loadings[sail_index] >= 1.0 {
+                    continue;
+                }
+
+                let mut candidate_loadings = loadings.clone();
+                candidate_loadings[sail_index] = (loadings[sail_index] + loading_increment).min(1.0);
+
+                let result = self.simulate_steady_state_condition_with_sail_loadings(
+                    wind_condition, ship_velocity, &candidate_loadings
+                );
+
+                let candidate_delivered_power = self.delivered_power(&result, ship_velocity);
+                let candidate_input_power = result.input_power_sum();
+
+                let delivered_power_gain = candidate_delivered_power - delivered_power;
+
+                if candidate_input_power > power_budget || delivered_power_gain <= 0.0 {
+                    continue;
+                }
+
+                let input_power_increase = candidate_input_power - input_power;
+
+                let gain_per_input_power = if input_power_increase > 0.0 {
+                    delivered_power_gain / input_power_increase
+                } else {
+                    Float::INFINITY
+                };
+
+                let is_better = match best_candidate {
+                    Some((_, best_gain_per_input_power, _, _)) => {
+                        gain_per_input_power > best_gain_per_input_power
+                    },
+                    None => true
+                };
+
+                if is_better {
+                    best_candidate = Some((
+                        sail_index, 
+                        gain_per_input_power, 
+                        candidate_delivered_power, 
+                        candidate_input_power
+                    ));
+                }
+            }
+
+            match best_candidate {
+                Some((sail_index, _, candidate_delivered_power, candidate_input_power)) => {
+                    loadings[sail_index] = (loadings[sail_index] + loading_increment).min(1.0);
+                    delivered_power = candidate_delivered_power;
+                    input_power = candidate_input_power;
+                },
+                None => break
+            }
+        }
+
+        loadings
+    }
+
+    /// Same as [CompleteSailModel::simulate_steady_state_condition], but with an optional density
+    /// that overrides the density of the model for this call only.
+    pub fn simulate_steady_state_condition_with_density(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float,
+        density: Option<Float>
+    ) -> SimulationResult {
+        self.with_density_override(density, |model| {
+            model.simulate_steady_state_condition(wind_condition, ship_velocity, controller_loading)
+        })
+    }
+
     pub fn simulate_steady_state_condition_simple_output(
         &mut self,
         wind_condition: WindCondition,
@@ -136,6 +598,513 @@ impl CompleteSailModel {
         full_results.as_simplified()
     }
     
+    /// Simulates a steady state condition where the local wing angles are fixed to the input 
+    /// values, rather than given by the controller.
+    pub fn simulate_steady_state_condition_fixed_wing_angles(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        local_wing_angles: &[Float]
+    ) -> SimulationResult {
+        self.apply_furling(wind_condition, ship_velocity, 0.0);
+
+        self.lifting_line_simulation.line_force_model.set_local_wing_angles(local_wing_angles);
+
+        let freestream_velocity = self.freestream_velocity(wind_condition, ship_velocity, 0.0);
+
+        self.lifting_line_simulation.first_time_step_completed = false;
+
+        self.lifting_line_simulation.do_step(0.0, 1.0, &freestream_velocity)
+    }
+
+    /// Returns the expected thrust on each sail, when all sails have the same fixed wing angle,
+    /// over a weighted distribution of wind directions.
+    fn expected_thrust_with_fixed_wing_angle(
+        &mut self,
+        direction_distribution: &[(Float, Float)],
+        wind_speed: Float,
+        ship_velocity: Float,
+        wing_angle: Float
+    ) -> Vec<Float> {
+        let nr_sails = self.get_number_of_sails();
+        let wing_angles = vec![wing_angle; nr_sails];
+
+        let weight_sum: Float = direction_distribution.iter().map(|(_, weight)| weight).sum();
+
+        let mut expected_thrust = vec![0.0; nr_sails];
+
+        for (direction, weight) in direction_distribution {
+            let wind_condition = WindCondition {
+                velocity: wind_speed,
+                direction_coming_from: *direction,
+            };
+
+            let result = self.simulate_steady_state_condition_fixed_wing_angles(
+                wind_condition,
+                ship_velocity,
+                &wing_angles
+            );
+
+            for (thrust_sum, force) in expected_thrust.iter_mut().zip(&result.integrated_forces) {
+                let thrust = self.thrust_direction.dot(force.total);
+
+                *thrust_sum += weight * thrust / weight_sum;
+            }
+        }
+
+        expected_thrust
+    }
+
+    /// Finds the fixed wing angle for each sail that maximizes the expected thrust over a 
+    /// distribution of wind directions. This is intended for sails that are not actively 
+    /// controlled, but set to a constant angle.
+    ///
+    /// The distribution is given as pairs of wind directions and weights, where the directions are
+    /// the direction the wind is coming from, in radians, and the weights do not need to be 
+    /// normalized. The wind speed and ship velocity are used to compute the apparent wind for 
+    /// each direction. With zero ship velocity, the directions are equal to the apparent wind
+    /// directions.
+    ///
+    /// The wing angles are first searched on a coarse grid over all angles, and then refined on a 
+    /// fine grid around the best coarse angle for each sail. All sails are given the same wing 
+    /// angle in each evaluation, so that each angle only needs to be simulated once, even when it
+    /// is part of the search for several sails.
+    pub fn best_fixed_wing_angle(
+        &mut self,
+        direction_distribution: &[(Float, Float)],
+        wind_speed: Float,
+        ship_velocity: Float
+    ) -> Vec<Float> {
+        let coarse_step = Self::default_coarse_wing_angle_step();
+        let fine_step = Self::default_fine_wing_angle_step();
+
+        let nr_coarse_angles = (TAU / coarse_step).round() as i64;
+        let nr_fine_steps_per_coarse_step = (coarse_step / fine_step).round() as i64;
+
+        // The angles are indexed on the fine grid, so that the results from the coarse search are
+        // reused in the refinement
+        let angle_from_index = |index: i64| -PI + index as Float * fine_step;
+
+        let mut expected_thrust: HashMap<i64, Vec<Float>> = HashMap::new();
+
+        let coarse_indices: Vec<i64> = (0..nr_coarse_angles)
+            .map(|i| i * nr_fine_steps_per_coarse_step)
+            .collect();
+
+        for &index in &coarse_indices {
+            expected_thrust.insert(index, self.expected_thrust_with_fixed_wing_angle(
+                direction_distribution, wind_speed, ship_velocity, angle_from_index(index)
+            ));
+        }
+
+        let best_coarse_indices = Self::best_wing_angle_indices(&expected_thrust, &coarse_indices);
+
+        let fine_indices: Vec<Vec<i64>> = best_coarse_indices.iter().map(|coarse_index| {
+            (-nr_fine_steps_per_coarse_step..=nr_fine_steps_per_coarse_step)
+                .map(|i| coarse_index + i)
+                .collect()
+        }).collect();
+
+        for &index in fine_indices.iter().flatten() {
+            if let Entry::Vacant(entry) = expected_thrust.entry(index) {
+                entry.insert(self.expected_thrust_with_fixed_wing_angle(
+                    direction_distribution, wind_speed, ship_velocity, angle_from_index(index)
+                ));
+            }
+        }
+
+        fine_indices.iter().enumerate().map(|(sail_index, indices)| {
+            angle_from_index(Self::best_wing_angle_indices(&expected_thrust, indices)[sail_index])
+        }).collect()
+    }
+
+    /// Returns the candidate index with the highest expected thrust for each sail. The first 
+    /// candidate is chosen if several give the same thrust.
+    fn best_wing_angle_indices(
+        expected_thrust: &HashMap<i64, Vec<Float>>,
+        candidate_indices: &[i64]
+    ) -> Vec<i64> {
+        let nr_sails = expected_thrust[&candidate_indices[0]].len();
+
+        let mut best_indices = vec![candidate_indices[0]; nr_sails];
+        let mut best_thrust = vec![Float::NEG_INFINITY; nr_sails];
+
+        for index in candidate_indices {
+            for (sail_index, &thrust) in expected_thrust[index].iter().enumerate() {
+                if thrust > best_thrust[sail_index] {
+                    best_thrust[sail_index] = thrust;
+                    best_indices[sail_index] = *index;
+                }
+            }
+        }
+
+        best_indices
+    }
+
+    /// Computes the gust load factor for each sail, defined as the ratio between the peak force 
+    /// magnitude during a simulated time window and the force magnitude in the steady condition.
+    ///
+    /// The wind during the time window is taken from the wind condition time series in the wind 
+    /// environment, including the optional lag filter, and falls back to the steady condition if 
+    /// no time series is given. The filter state is reset before the window is simulated. The 
+    /// load factor is None for sails without any force in the steady condition.
+    pub fn gust_load_factors(
+        &mut self,
+        steady_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float,
+        time_step: Float,
+        nr_time_steps: usize,
+    ) -> Vec<Option<Float>> {
+        let steady_result = self.simulate_steady_state_condition(
+            steady_condition,
+            ship_velocity,
+            controller_loading
+        );
+
+        let steady_forces: Vec<Float> = steady_result.integrated_forces.iter().map(
+            |force| force.total.length()
+        ).collect();
+
+        let mut peak_forces: Vec<Float> = vec![0.0; steady_forces.len()];
+
+        self.lifting_line_simulation.first_time_step_completed = false;
+        self.controller.reset_state();
+        self.wind_environment.lagged_wind_condition = None;
+
+        for time_index in 0..nr_time_steps {
+            let current_time = (time_index as Float) * time_step;
+
+            let result = self.do_step_with_environment_wind(
+                current_time,
+                time_step,
+                steady_condition,
+                ship_velocity,
+                controller_loading
+            );
+
+            for (peak_force, force) in peak_forces.iter_mut().zip(result.integrated_forces.iter()) {
+                *peak_force = peak_force.max(force.total.length());
+            }
+        }
+
+        peak_forces.iter().zip(steady_forces.iter()).map(
+            |(peak_force, steady_force)| if *steady_force > 0.0 {
+                Some(peak_force / steady_force)
+            } else {
+                None
+            }
+        ).collect()
+    }
+
+    /// Simulates a maneuver where the ship heading changes over time, for instance a tack or a
+    /// gybe, and returns the result from every time step.
+    ///
+    /// The heading schedule contains pairs of time and heading, with one entry per time step. The
+    /// heading follows the same sign convention as in [CompleteSailModel::yaw_stiffness], so that
+    /// the wind relative to the ship comes from the direction in the wind condition minus the
+    /// heading. The controller is applied with full loading at every time step, which means that
+    /// any rate limits or actuator dynamics in the controller are part of the simulated transient.
+    pub fn simulate_maneuver(
+        &mut self,
+        heading_schedule: &[(Float, Float)],
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+    ) -> Vec<SimulationResult> {
+        let nr_time_steps = heading_schedule.len();
+
+        let mut results: Vec<SimulationResult> = Vec::with_capacity(nr_time_steps);
+
+        self.lifting_line_simulation.first_time_step_completed = false;
+        self.controller.reset_state();
+
+        for time_index in 0..nr_time_steps {
+            let (current_time, heading) = heading_schedule[time_index];
+
+            let time_step = if time_index > 0 {
+                current_time - heading_schedule[time_index - 1].0
+            } else if nr_time_steps > 1 {
+                heading_schedule[1].0 - current_time
+            } else {
+                1.0
+            };
+
+            let relative_condition = WindCondition {
+                velocity: wind_condition.velocity,
+                direction_coming_from: wind_condition.direction_coming_from - heading,
+            };
+
+            results.push(
+                self.do_step(current_time, time_step, relative_condition, ship_velocity, 1.0)
+            );
+        }
+
+        results
+    }
+
+    /// Computes the frequency response of the total force magnitude on the sails to a sinusoidal 
+    /// variation in the wind velocity.
+    ///
+    /// For each frequency, in Hz, the wind velocity is varied as the velocity in the mean condition
+    /// plus the amplitude times a sine function, while the direction is kept constant. The 
+    /// simulation is run for a number of periods, and the first half is discarded to remove the 
+    /// start-up transient. A sine and cosine are fitted to the remaining force signal, which gives
+    /// the gain, as the force amplitude divided by the wind velocity amplitude, and the phase of 
+    /// the force relative to the wind, in radians. A negative phase means that the force lags 
+    /// behind the wind.
+    ///
+    /// Returns a vector with (frequency, gain, phase) for each input frequency.
+    pub fn frequency_response(
+        &mut self,
+        frequencies: &[Float],
+        amplitude: Float,
+        mean_condition: WindCondition,
+        ship_velocity: Float,
+    ) -> Vec<(Float, Float, Float)> {
+        let nr_steps_per_period = Self::default_frequency_response_steps_per_period();
+        let nr_periods = Self::default_frequency_response_nr_periods();
+
+        let nr_time_steps = nr_steps_per_period * nr_periods;
+        let first_fit_index = nr_time_steps - nr_steps_per_period * (nr_periods / 2);
+
+        frequencies.iter().map(|&frequency| {
+            let period = 1.0 / frequency;
+            let time_step = period / nr_steps_per_period as Float;
+            let angular_frequency = TAU * frequency;
+
+            self.lifting_line_simulation.first_time_step_completed = false;
+            self.controller.reset_state();
+
+            let mut time_values: Vec<Float> = Vec::with_capacity(nr_time_steps);
+            let mut force_values: Vec<Float> = Vec::with_capacity(nr_time_steps);
+
+            for time_index in 0..nr_time_steps {
+                let current_time = (time_index as Float) * time_step;
+
+                let wind_condition = WindCondition {
+                    velocity: mean_condition.velocity + 
+                        amplitude * (angular_frequency * current_time).sin(),
+                    direction_coming_from: mean_condition.direction_coming_from,
+                };
+
+                let result = self.do_step(
+                    current_time, time_step, wind_condition, ship_velocity, 1.0
+                );
+
+                time_values.push(current_time);
+                force_values.push(result.integrated_forces_sum().length());
+            }
+
+            let fit_time = &time_values[first_fit_index..];
+            let fit_force = &force_values[first_fit_index..];
+
+            let nr_fit_values = fit_force.len() as Float;
+
+            let mean_force = fit_force.iter().sum::<Float>() / nr_fit_values;
+
+            let mut sine_coefficient = 0.0;
+            let mut cosine_coefficient = 0.0;
+
+            for (time, force) in fit_time.iter().zip(fit_force.iter()) {
+                let phase_angle = angular_frequency * time;
+
+                sine_coefficient += 2.0 * (force - mean_force) * phase_angle.sin() / nr_fit_values;
+                cosine_coefficient += 2.0 * (force - mean_force) * phase_angle.cos() / nr_fit_values;
+            }
+
+            let gain = sine_coefficient.hypot(cosine_coefficient) / amplitude;
+            let phase = cosine_coefficient.atan2(sine_coefficient);
+
+            (frequency, gain, phase)
+        }).collect()
+    }
+
+    /// Computes the derivative of the yaw moment with respect to the ship heading, using central
+    /// differences, to indicate whether the rig tends to turn the ship into the wind or not.
+    ///
+    /// Both the heading and the yaw moment are defined as positive around the wind rotation axis in
+    /// the wind environment. A positive change in heading is therefore equivalent to a negative 
+    /// change in the direction the wind is coming from, relative to the ship. A negative value 
+    /// means that the yaw moment is restoring, i.e., the rig makes the ship weathervane.
+    pub fn yaw_stiffness(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float
+    ) -> Float {
+        let heading_step = Self::default_heading_step();
+        let yaw_axis = self.wind_environment.wind_rotation_axis.normalize();
+
+        let mut yaw_moment_at_heading = |heading: Float| -> Float {
+            let relative_condition = WindCondition {
+                velocity: wind_condition.velocity,
+                direction_coming_from: wind_condition.direction_coming_from - heading,
+            };
+
+            let result = self.simulate_steady_state_condition(
+                relative_condition,
+                ship_velocity,
+                controller_loading
+            );
+
+            result.integrated_moments_sum().dot(yaw_axis)
+        };
+
+        let yaw_moment_positive = yaw_moment_at_heading(heading_step);
+        let yaw_moment_negative = yaw_moment_at_heading(-heading_step);
+
+        (yaw_moment_positive - yaw_moment_negative) / (2.0 * heading_step)
+    }
+
+    /// Computes the thrust from each sail for a given apparent wind condition, with the ship at 
+    /// rest so that the wind condition is equal to the apparent wind.
+    fn thrust_per_sail(&mut self, apparent_wind_condition: WindCondition) -> Vec<Float> {
+        let result = self.simulate_steady_state_condition(apparent_wind_condition, 0.0, 1.0);
+
+        result.integrated_forces.iter().map(|force| self.thrust_direction.dot(force.total)).collect()
+    }
+
+    /// Finds the apparent wind angles that bound the region where each sail is not able to deliver
+    /// any thrust, i.e., the no-go zone around head wind.
+    ///
+    /// The apparent wind direction is swept outwards from head wind, in both directions, with the
+    /// controller at full loading. The bounds are found where the thrust of each sail changes 
+    /// sign, using linear interpolation between the sampled directions. If the thrust is already 
+    /// positive at head wind, the bound on that side is zero.
+    ///
+    /// Returns a vector with the lower and upper bound for each sail. The value is `None` for sails
+    /// where the thrust never becomes positive on at least one of the sides, as the no-go zone is
+    /// then not bounded.
+    pub fn zero_thrust_angles(
+        &mut self, 
+        apparent_wind_speed: Float
+    ) -> Vec<Option<(Float, Float)>> {
+        let angle_step = Self::default_zero_thrust_angle_step();
+        let nr_steps_each_side = (PI / angle_step).ceil() as usize;
+
+        let angles: Vec<Float> = (0..=nr_steps_each_side).map(
+            |i| (i as Float * angle_step).min(PI)
+        ).collect();
+
+        let mut thrust_at_angle = |angle: Float| -> Vec<Float> {
+            self.thrust_per_sail(
+                WindCondition {
+                    velocity: apparent_wind_speed,
+                    direction_coming_from: angle,
+                }
+            )
+        };
+
+        let thrust_positive: Vec<Vec<Float>> = angles.iter().map(
+            |&angle| thrust_at_angle(angle)
+        ).collect();
+
+        let thrust_negative: Vec<Vec<Float>> = angles.iter().map(
+            |&angle| thrust_at_angle(-angle)
+        ).collect();
+
+        let nr_sails = self.get_number_of_sails();
+
+        (0..nr_sails).map(|sail_index| {
+            let upper = Self::first_thrust_crossing(&angles, &thrust_positive, sail_index)?;
+            let lower = Self::first_thrust_crossing(&angles, &thrust_negative, sail_index)?;
+
+            Some((-lower, upper))
+        }).collect()
+    }
+
+    /// Returns the first angle where the thrust of the given sail changes from non-positive to 
+    /// positive, interpolated linearly between the sampled angles. Returns the first angle if the
+    /// thrust is positive already there, and `None` if the thrust never becomes positive.
+    fn first_thrust_crossing(
+        angles: &[Float], 
+        thrust: &[Vec<Float>], 
+        sail_index: usize
+    ) -> Option<Float> {
+        if thrust.first()?[sail_index] > 0.0 {
+            return Some(angles[0]);
+        }
+
+        for i in 1..angles.len() {
+            let thrust_previous = thrust[i - 1][sail_index];
+            let thrust_current = thrust[i][sail_index];
+
+            if thrust_previous <= 0.0 && thrust_current > 0.0 {
+                let fraction = -thrust_previous / (thrust_current - thrust_previous);
+
+                return Some(angles[i - 1] + fraction * (angles[i] - angles[i - 1]));
+            }
+        }
+
+        None
+    }
+
+    /// Computes the total sail area needed to deliver a target thrust in a design condition.
+    ///
+    /// The forces are evaluated for the current geometry, with the controller at full loading, and
+    /// the total projected area is scaled with the ratio between the target and the computed 
+    /// thrust. This assumes that the geometry is scaled uniformly, so that the shape of the sails,
+    /// and therefore the force coefficients, are unchanged. If the sails are not able to deliver 
+    /// any thrust in the condition, the required area is infinite.
+    pub fn required_area_for_thrust(
+        &mut self,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        target_thrust: Float
+    ) -> Float {
+        let result = self.simulate_steady_state_condition(wind_condition, ship_velocity, 1.0);
+
+        let thrust = self.thrust(&result);
+
+        if thrust <= 0.0 {
+            return Float::INFINITY;
+        }
+
+        let current_area = self.lifting_line_simulation.line_force_model.total_projected_area();
+
+        current_area * target_thrust / thrust
+    }
+
+    /// Returns the ratio between the apparent and the true wind speed for each sail.
+    ///
+    /// The apparent wind speed is averaged over the control points of each sail, and includes the
+    /// ship motion, the height variation of the wind and any inflow corrections. The true wind 
+    /// speed is the velocity in the input wind condition, i.e., at the reference height. The ratio
+    /// is infinite if the true wind speed is zero. A gust model in the wind environment is 
+    /// evaluated at time zero.
+    pub fn apparent_to_true_speed_ratio(
+        &self,
+        wind_condition: WindCondition,
+        ship_velocity: Float
+    ) -> Vec<Float> {
+        let line_force_model = &self.lifting_line_simulation.line_force_model;
+
+        let linear_velocity = ship_velocity * self.wind_environment.zero_direction_vector;
+
+        let apparent_velocity = self.wind_environment
+            .apparent_wind_velocity_vectors_at_ctrl_points_with_corrections_applied(
+                wind_condition,
+                &line_force_model.ctrl_points_global,
+                linear_velocity,
+                &line_force_model.wing_indices,
+                0.0
+            );
+
+        line_force_model.wing_indices.iter().map(|wing_indices| {
+            let nr_points = wing_indices.len() as Float;
+
+            let average_apparent_speed = apparent_velocity[wing_indices.clone()].iter()
+                .map(|velocity| velocity.length())
+                .sum::<Float>() / nr_points;
+
+            if wind_condition.velocity > 0.0 {
+                average_apparent_speed / wind_condition.velocity
+            } else {
+                Float::INFINITY
+            }
+        }).collect()
+    }
+
     /// Simulate a condition for the sail, specified by a wind condition, ship velocity, 
     /// and controller loading
     pub fn simulate_condition(
@@ -149,6 +1118,8 @@ impl CompleteSailModel {
         let mut result = SimulationResult::default();
 
         self.lifting_line_simulation.first_time_step_completed = false; // Make sure the wake is re-initialized
+        self.controller.reset_state(); // Make sure no controller state is carried over from previous conditions
+        self.last_result = None;
 
         for time_index in 0..nr_time_steps {
             let current_time = (time_index as Float) * time_step;
@@ -174,29 +1145,165 @@ impl CompleteSailModel {
         ship_velocity: Float,
         controller_loading: Float,
     ) -> SimulationResult {
+        let sail_loadings = vec![controller_loading; self.get_number_of_sails()];
+
+        self.do_step_with_sail_loadings(
+            current_time,
+            time_step,
+            wind_condition,
+            ship_velocity,
+            &sail_loadings
+        )
+    }
+    
+    /// Same as [CompleteSailModel::do_step], but with a separate controller loading for each sail
+    fn do_step_with_sail_loadings(
+        &mut self,
+        current_time: Float,
+        time_step: Float,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        sail_loadings: &[Float],
+    ) -> SimulationResult {
+        self.apply_furling(wind_condition, ship_velocity, current_time);
+
         let freestream_velocity = self.freestream_velocity(
             wind_condition,
+            ship_velocity,
+            current_time
+        );
+
+        self.apply_controller_with_sail_loadings(
+            current_time,
+            time_step,
+            sail_loadings,
+            &freestream_velocity,
             ship_velocity
         );
 
-        self.apply_controller_based_on_freestream(
+        let result = self.lifting_line_simulation.do_step(
             current_time,
             time_step,
-            controller_loading,
             &freestream_velocity
         );
 
-        self.lifting_line_simulation.do_step(
+        if let Some(columnar_output) = &mut self.columnar_output {
+            columnar_output.add_simulation_result(&result)
+                .expect("The number of wings should not change during a simulation");
+        }
+
+        self.last_result = Some(result.clone());
+
+        result
+    }
+
+    /// Same as [CompleteSailModel::do_step], but with the wind condition taken from the time series
+    /// in the wind environment at the current time. The default condition is used if the wind 
+    /// environment has no time series.
+    pub fn do_step_with_environment_wind(
+        &mut self,
+        current_time: Float,
+        time_step: Float,
+        default_wind_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float,
+    ) -> SimulationResult {
+        let wind_condition = self.wind_environment.wind_condition_at_time(current_time)
+            .unwrap_or(default_wind_condition);
+
+        self.do_step(
             current_time,
             time_step,
-            &freestream_velocity
+            wind_condition,
+            ship_velocity,
+            controller_loading
         )
     }
-    
+
+    /// Same as [CompleteSailModel::do_step], but with an optional density that overrides the 
+    /// density of the model for this time step only.
+    pub fn do_step_with_density(
+        &mut self,
+        current_time: Float,
+        time_step: Float,
+        wind_condition: WindCondition,
+        ship_velocity: Float,
+        controller_loading: Float,
+        density: Option<Float>,
+    ) -> SimulationResult {
+        self.with_density_override(density, |model| {
+            model.do_step(
+                current_time, 
+                time_step, 
+                wind_condition, 
+                ship_velocity, 
+                controller_loading
+            )
+        })
+    }
+
+    /// Runs the input function with the density of the line force model temporarily set to the 
+    /// input density, if given. The original density is restored afterwards.
+    fn with_density_override<T>(
+        &mut self, 
+        density: Option<Float>, 
+        function: impl FnOnce(&mut Self) -> T
+    ) -> T {
+        let Some(density) = density else {
+            return function(self);
+        };
+
+        let original_density = self.lifting_line_simulation.line_force_model.density;
+
+        self.lifting_line_simulation.line_force_model.density = density;
+
+        let out = function(self);
+
+        self.lifting_line_simulation.line_force_model.density = original_density;
+
+        out
+    }
+
+    /// Furls the sails according to the furling model, based on the apparent wind speed at the 
+    /// reference height of the wind environment and the input time. Does nothing if there is no 
+    /// furling model.
+    pub fn apply_furling(
+        &mut self, 
+        wind_condition: WindCondition, 
+        ship_velocity: Float, 
+        time: Float
+    ) {
+        if let Some(furling_model) = &self.furling_model {
+            let reference_height = self.wind_environment.reference_height();
+
+            let linear_velocity = ship_velocity * self.wind_environment.zero_direction_vector;
+
+            let reference_location = reference_height * self.wind_environment.up_direction;
+
+            let apparent_wind_speed = self.wind_environment.apparent_wind_velocity_vector_at_location(
+                wind_condition,
+                reference_location,
+                linear_velocity,
+                time
+            ).length();
+
+            let span_fraction = furling_model.span_fraction(apparent_wind_speed);
+
+            let nr_sails = self.get_number_of_sails();
+
+            self.lifting_line_simulation.line_force_model.set_furled_span_fractions(
+                &vec![span_fraction; nr_sails]
+            );
+        }
+    }
+
+    /// Returns the freestream velocity at the freestream velocity points of the simulation, with the
+    /// wind evaluated at the input time.
     pub fn freestream_velocity(
         &self,
         wind_condition: WindCondition,
-        ship_velocity: Float
+        ship_velocity: Float,
+        time: Float
     ) -> Vec<SpatialVector> {
         let freestream_velocity_points = self.lifting_line_simulation
             .get_freestream_velocity_points();
@@ -206,16 +1313,18 @@ impl CompleteSailModel {
         let mut freestream_velocity = self.wind_environment.apparent_wind_velocity_vectors_at_locations(
             wind_condition, 
             &freestream_velocity_points, 
-            linear_velocity
+            linear_velocity,
+            time
         );
         
-        let reference_height = 10.0;
+        let reference_height = self.wind_environment.reference_height();
         
         let apparent_wind_direction = self.wind_environment
             .apparent_wind_direction_from_condition_and_linear_velocity(
                 wind_condition,
                 linear_velocity,
-                reference_height
+                reference_height,
+                time
             );
         
         self.wind_environment.apply_inflow_corrections(
@@ -228,21 +1337,54 @@ impl CompleteSailModel {
         freestream_velocity
     }
 
+    /// Applies the controller based on the freestream velocity. The forces and input power in the 
+    /// controller input are taken from the result of the last time step, if any, and the thrust is
+    /// measured along the thrust direction of the model.
     pub fn apply_controller_based_on_freestream(
         &mut self,
         current_time: Float,
         time_step: Float,
         loading: Float,
-        freestream_velocity: &[SpatialVector]
+        freestream_velocity: &[SpatialVector],
+        ship_velocity: Float,
     ) {
-        let controller_input = ControllerInput::new_from_velocity(
-            loading,
+        let sail_loadings = vec![loading; self.get_number_of_sails()];
+
+        self.apply_controller_with_sail_loadings(
+            current_time, 
+            time_step, 
+            &sail_loadings, 
+            freestream_velocity,
+            ship_velocity
+        );
+    }
+
+    /// Same as [CompleteSailModel::apply_controller_based_on_freestream], but with a separate 
+    /// loading for each sail
+    pub fn apply_controller_with_sail_loadings(
+        &mut self,
+        current_time: Float,
+        time_step: Float,
+        sail_loadings: &[Float],
+        freestream_velocity: &[SpatialVector],
+        ship_velocity: Float,
+    ) {
+        let mut controller_input = ControllerInput::new_from_velocity(
+            1.0,
             &self.lifting_line_simulation.line_force_model,
             freestream_velocity,
+            current_time,
             &self.controller.flow_measurement_settings,
             &self.wind_environment,
+            self.last_result.as_ref(),
+            self.thrust_direction,
+            ship_velocity,
         );
 
+        for (input, loading) in controller_input.iter_mut().zip(sail_loadings.iter()) {
+            input.loading = *loading;
+        }
+
         let controller_output = self.controller.update(
             current_time,
             time_step,
@@ -0,0 +1,54 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tables of sail performance over a grid of wind velocities and directions.
+
+use serde::{Serialize, Deserialize};
+
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/// Sail performance for each combination of wind velocity and direction in a sweep. The tables are
+/// indexed as `[velocity_index][direction_index]`.
+pub struct PolarResult {
+    /// Wind velocities in the sweep
+    pub wind_velocities: Vec<Float>,
+    /// Directions the wind is coming from in the sweep, in radians
+    pub wind_directions: Vec<Float>,
+    /// Total thrust from all sails
+    pub thrust: Vec<Vec<Float>>,
+    /// Total side force from all sails, perpendicular to the thrust direction
+    pub side_force: Vec<Vec<Float>>,
+    /// Total input power to all sails
+    pub input_power: Vec<Vec<Float>>,
+    /// Power delivered to the ship from the sail thrust
+    pub delivered_power: Vec<Vec<Float>>,
+}
+
+impl PolarResult {
+    /// Creates a result with all values set to zero, for the given velocities and directions.
+    pub fn new(wind_velocities: &[Float], wind_directions: &[Float]) -> Self {
+        let table = vec![vec![0.0; wind_directions.len()]; wind_velocities.len()];
+
+        Self {
+            wind_velocities: wind_velocities.to_vec(),
+            wind_directions: wind_directions.to_vec(),
+            thrust: table.clone(),
+            side_force: table.clone(),
+            input_power: table.clone(),
+            delivered_power: table,
+        }
+    }
+
+    /// Net power, as the delivered power minus the input power, for each cell
+    pub fn net_power(&self) -> Vec<Vec<Float>> {
+        self.delivered_power.iter().zip(self.input_power.iter()).map(
+            |(delivered_row, input_row)| {
+                delivered_row.iter().zip(input_row.iter()).map(
+                    |(delivered, input)| delivered - input
+                ).collect()
+            }
+        ).collect()
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Estimates of the fuel and emission savings from the energy delivered by the sails.
+
+use serde::{Serialize, Deserialize};
+
+use stormath::type_aliases::Float;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+/// Fuel and CO2 savings, both given in kg.
+pub struct Savings {
+    /// Mass of fuel saved, in kg
+    pub fuel_mass: Float,
+    /// Mass of CO2 saved, in kg
+    pub co2_mass: Float,
+}
+
+impl Savings {
+    /// Computes the savings from the net energy delivered by the sails.
+    ///
+    /// The net energy is given in kWh, the specific fuel consumption of the main engine in g/kWh, 
+    /// and the emission factor in kg CO2 per kg fuel.
+    pub fn from_net_energy(
+        net_energy: Float,
+        specific_fuel_consumption: Float,
+        emission_factor: Float
+    ) -> Self {
+        let fuel_mass = net_energy * specific_fuel_consumption / 1000.0;
+
+        Self {
+            fuel_mass,
+            co2_mass: fuel_mass * emission_factor,
+        }
+    }
+}
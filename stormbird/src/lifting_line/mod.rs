@@ -13,6 +13,8 @@ pub mod solvers;
 pub mod singularity_elements;
 pub mod simulation_builder;
 pub mod simulation;
+pub mod batch;
+pub mod resolution_study;
 pub mod complete_sail_model;
 
 /// Typical imports when using the lifting line functionality
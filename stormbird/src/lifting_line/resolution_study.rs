@@ -0,0 +1,87 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Functionality for finding a suitable number of span strips for a given wing setup, by 
+//! comparing the integrated lift at increasing resolutions.
+
+use stormath::type_aliases::Float;
+
+use crate::lifting_line::prelude::*;
+
+use super::simulation_builder::SimulationBuilder;
+
+#[derive(Debug, Clone, Default)]
+/// Result from a resolution study
+pub struct ResolutionStudyResult {
+    /// The number of sections per wing that was tested
+    pub nr_sections: Vec<usize>,
+    /// The magnitude of the integrated lift for each tested resolution
+    pub lift: Vec<Float>,
+    /// The first tested resolution where the relative change in lift to the next refinement is
+    /// below the tolerance. None if the tolerance was not reached for any of the resolutions.
+    pub recommended_nr_sections: Option<usize>,
+}
+
+impl SimulationBuilder {
+    /// Runs the simulation with the number of sections given as input, in increasing order, and
+    /// reports the lowest number of sections where the relative change in the integrated lift to
+    /// the next refinement is below the tolerance.
+    ///
+    /// The lift is taken as the part of the total force that is normal to the freestream velocity.
+    /// Each resolution is solved as a single step with a uniform freestream velocity.
+    ///
+    /// # Arguments
+    /// * `freestream_velocity` - Uniform freestream velocity used in all the simulations
+    /// * `candidate_nr_sections` - The number of sections per wing to test
+    /// * `relative_tolerance` - Maximum relative change in lift between two refinements
+    pub fn resolution_study(
+        &self,
+        freestream_velocity: SpatialVector,
+        candidate_nr_sections: &[usize],
+        relative_tolerance: Float
+    ) -> ResolutionStudyResult {
+        let mut nr_sections = candidate_nr_sections.to_vec();
+        nr_sections.sort();
+        nr_sections.dedup();
+
+        let velocity_direction = freestream_velocity.normalize();
+
+        let lift: Vec<Float> = nr_sections.iter().map(|&nr| {
+            let mut builder = self.clone();
+            builder.line_force_model.nr_sections = nr;
+
+            let mut simulation = builder.build();
+
+            let nr_points = simulation.get_freestream_velocity_points().len();
+
+            let result = simulation.do_step(
+                0.0, 
+                1.0, 
+                &vec![freestream_velocity; nr_points]
+            );
+
+            let force = result.integrated_forces_sum();
+
+            (force - force.dot(velocity_direction) * velocity_direction).length()
+        }).collect();
+
+        let mut recommended_nr_sections = None;
+
+        for i in 0..lift.len().saturating_sub(1) {
+            let relative_change = (lift[i + 1] - lift[i]).abs() / lift[i + 1].abs().max(Float::MIN_POSITIVE);
+
+            if relative_change < relative_tolerance {
+                recommended_nr_sections = Some(nr_sections[i]);
+
+                break;
+            }
+        }
+
+        ResolutionStudyResult {
+            nr_sections,
+            lift,
+            recommended_nr_sections,
+        }
+    }
+}
@@ -7,6 +7,8 @@
 //! To support different use cases, both quasi-steady and true dynamic simulations are supported.
 //! However, the interface is designed to be as unified as possible.
 
+use serde::{Serialize, Deserialize};
+
 use stormath::type_aliases::Float;
 
 use crate::lifting_line::prelude::*;
@@ -15,6 +17,24 @@ use super::simulation_builder::SimulationBuilder;
 
 use crate::error::Error;
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+/// Policy for what to return from a time step where the solver does not converge, or where the 
+/// forces are not finite. The result is always marked as non-converged.
+pub enum NonConvergencePolicy {
+    #[default]
+    /// Return the result from the solver as it is
+    Ignore,
+    /// Return the forces from the last converged time step. Falls back to zero forces if no time 
+    /// step has converged yet.
+    HoldLast,
+    /// Return zero forces
+    Zero,
+    /// Return the result as it is from [Simulation::do_step], but an error from 
+    /// [Simulation::try_do_step]. Interfaces that only use [Simulation::do_step], such as the FMU,
+    /// report the non-converged step as an error message instead.
+    Error,
+}
+
 #[derive(Debug, Clone)]
 /// Struct that contains the data needed to run a lifting line simulation.
 pub struct Simulation {
@@ -25,6 +45,12 @@ pub struct Simulation {
     pub solver: Solver,
     pub previous_circulation_strength: Vec<Float>,
     pub first_time_step_completed: bool,
+    /// If true, the circulation strength from the previous time step is kept as the initial guess
+    /// for the solver when the simulation is re-initialized. Reset after each initialization.
+    pub warm_start_next_initialization: bool,
+    pub on_nonconvergence: NonConvergencePolicy,
+    /// The last converged result, only stored when the policy is to hold the last result
+    pub last_converged_result: Option<SimulationResult>,
 }
 
 impl Simulation {
@@ -88,9 +114,25 @@ impl Simulation {
         );
 
         self.first_time_step_completed = true;
-        
 
-        self.previous_circulation_strength = vec![0.0; self.line_force_model.nr_span_lines()];
+        let nr_span_lines = self.line_force_model.nr_span_lines();
+
+        let keep_circulation = self.warm_start_next_initialization && 
+            self.previous_circulation_strength.len() == nr_span_lines;
+
+        if !keep_circulation {
+            self.previous_circulation_strength = vec![0.0; nr_span_lines];
+        }
+
+        self.warm_start_next_initialization = false;
+    }
+
+    /// Makes sure the simulation is re-initialized at the next time step, but with the circulation
+    /// strength from the last time step as the initial guess for the solver. Useful when a series
+    /// of similar steady state conditions are simulated after each other.
+    pub fn reinitialize_with_warm_start(&mut self) {
+        self.first_time_step_completed = false;
+        self.warm_start_next_initialization = true;
     }
 
     /// Steps the simulation forward in time by one time step.
@@ -104,12 +146,100 @@ impl Simulation {
     /// - `time_step`: The time step to use for the simulation.
     /// - `freestream_velocity`: The freestream velocity at the points returned by
     /// `get_freestream_velocity_points`
+    ///
+    /// If the step does not converge, the returned result is given by the 
+    /// [NonConvergencePolicy] of the simulation.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "do_step", level = "debug", skip(self, freestream_velocity)
+    ))]
     pub fn do_step(
         &mut self,
         time: Float,
         time_step: Float,
         freestream_velocity: &[SpatialVector],
     ) -> SimulationResult {
+        let mut result = self.do_step_unchecked(time, time_step, freestream_velocity);
+
+        result.non_converged = self.result_is_non_converged(&result);
+
+        if !result.non_converged {
+            if self.on_nonconvergence == NonConvergencePolicy::HoldLast {
+                self.last_converged_result = Some(result.clone());
+            }
+
+            return result;
+        }
+
+        match self.on_nonconvergence {
+            NonConvergencePolicy::Ignore | NonConvergencePolicy::Error => result,
+            NonConvergencePolicy::HoldLast => {
+                if let Some(last_result) = &self.last_converged_result {
+                    SimulationResult {
+                        time,
+                        iterations: result.iterations,
+                        residual: result.residual,
+                        non_converged: true,
+                        ..last_result.clone()
+                    }
+                } else {
+                    result.set_forces_to_zero();
+
+                    result
+                }
+            },
+            NonConvergencePolicy::Zero => {
+                result.set_forces_to_zero();
+
+                result
+            }
+        }
+    }
+
+    /// Same as [Simulation::do_step], but returns an error if the step does not converge and the
+    /// policy is [NonConvergencePolicy::Error].
+    pub fn try_do_step(
+        &mut self,
+        time: Float,
+        time_step: Float,
+        freestream_velocity: &[SpatialVector],
+    ) -> Result<SimulationResult, Error> {
+        let result = self.do_step(time, time_step, freestream_velocity);
+
+        if result.non_converged && self.on_nonconvergence == NonConvergencePolicy::Error {
+            return Err(Error::CustomStringError(format!(
+                "Time step at time {} did not converge. Iterations: {}, residual: {}",
+                time, result.iterations, result.residual
+            )));
+        }
+
+        Ok(result)
+    }
+
+    /// A result is regarded as non-converged if the solver did not reach its convergence criteria,
+    /// or if any of the integrated forces are not finite.
+    fn result_is_non_converged(&self, result: &SimulationResult) -> bool {
+        let forces_are_finite = result.integrated_forces.iter().chain(
+            result.integrated_moments.iter()
+        ).all(
+            |value| value.total.0.iter().all(|component| component.is_finite())
+        );
+
+        result.non_converged || !forces_are_finite
+    }
+
+    /// Steps the simulation forward in time by one time step, without checking the convergence.
+    fn do_step_unchecked(
+        &mut self,
+        time: Float,
+        time_step: Float,
+        freestream_velocity: &[SpatialVector],
+    ) -> SimulationResult {
+        #[cfg(feature = "logging")]
+        log::trace!(
+            target: "stormbird::simulation",
+            "do_step, time: {}, time step: {}", time, time_step
+        );
+
         self.line_force_model.update_global_data_representations();
 
         let ctrl_points_freestream_range = 0..self.line_force_model.nr_span_lines();
@@ -173,6 +303,20 @@ impl Simulation {
             }
         };
 
+        #[cfg(feature = "logging")]
+        log::debug!(
+            target: "stormbird::solver",
+            "time: {}, iterations: {}, residual: {}",
+            time, solver_result.iterations, solver_result.residual
+        );
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            iterations = solver_result.iterations, 
+            residual = solver_result.residual, 
+            "solver finished"
+        );
+
         if let WakeData::Dynamic(wake) = &mut self.wake_data {
             // Update the wake model after solving
             wake.update_after_solving(
@@ -14,9 +14,10 @@ use crate::lifting_line::wake::{
 
 use crate::lifting_line::wake::settings::QuasiSteadyWakeSettings;
 
-use super::simulation::Simulation;
+use super::simulation::{Simulation, NonConvergencePolicy};
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(deny_unknown_fields)]
@@ -26,6 +27,8 @@ pub struct QuasiSteadySettings {
     pub solver: QuasiSteadySolverBuilder,
     #[serde(default)]
     pub wake: QuasiSteadyWakeSettings,
+    #[serde(default)]
+    pub on_nonconvergence: NonConvergencePolicy,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -36,6 +39,8 @@ pub struct DynamicSettings {
     pub solver: Solver,
     #[serde(default)]
     pub wake: DynamicWakeBuilder,
+    #[serde(default)]
+    pub on_nonconvergence: NonConvergencePolicy,
 }
 
 
@@ -56,6 +61,16 @@ impl Default for SimulationSettings {
     }
 }
 
+impl SimulationSettings {
+    /// Returns the policy for time steps that do not converge
+    pub fn on_nonconvergence(&self) -> NonConvergencePolicy {
+        match self {
+            SimulationSettings::QuasiSteady(settings) => settings.on_nonconvergence,
+            SimulationSettings::Dynamic(settings) => settings.on_nonconvergence,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 /// Builder for the [Simulation] struct.
@@ -79,9 +94,7 @@ impl SimulationBuilder {
     /// Creates a new simulation builder by parsing the the string as a JSON object. The parsing is
     /// done using the serde_json library.
     pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
-        let builder = serde_json::from_str(setup_string)?;
-        
-        Ok(builder)
+        json_data::from_json_string(setup_string)
     }
 
     /// Creates a new simulation builder by reading the file at the given path and parsing the 
@@ -139,6 +152,9 @@ impl SimulationBuilder {
             solver,
             previous_circulation_strength,
             first_time_step_completed: false,
+            warm_start_next_initialization: false,
+            on_nonconvergence: self.simulation_settings.on_nonconvergence(),
+            last_converged_result: None,
         }
     }
 }
\ No newline at end of file
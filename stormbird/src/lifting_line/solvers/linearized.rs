@@ -51,6 +51,9 @@ impl Linearized {
     /// Solves for the circulation strength assuming that there is a linear relationship between the
     /// induced velocity and the circulation strength, as was the case for the original lifting line
     /// theory.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "linearized_solve", level = "debug", skip_all
+    ))]
     pub fn solve(
         &self,
         line_force_model: &LineForceModel,
@@ -212,7 +215,8 @@ impl Linearized {
             circulation_strength,
             output_ctrl_points_velocity: ctrl_points_velocity,
             iterations: 1,
-            residual
+            residual,
+            converged: true,
         }
     }
 
@@ -251,7 +255,8 @@ impl Linearized {
                     circulation_strength: vec![0.0; nr_unknowns],
                     output_ctrl_points_velocity: ctrl_points_velocity.to_vec(),
                     iterations: 0,
-                    residual: 0.0
+                    residual: 0.0,
+                    converged: true,
                 }
             )
         } else {
@@ -29,6 +29,17 @@ impl Default for Solver {
     }
 }
 
+impl Solver {
+    /// Returns the maximum number of iterations the solver is allowed to use in a single time step,
+    /// if the solver is iterative. A solve that reaches this limit is regarded as not converged.
+    pub fn max_iterations(&self) -> Option<usize> {
+        match self {
+            Solver::SimpleIterative(solver) => Some(solver.max_iterations_per_time_step),
+            Solver::Linearized(_) => None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum QuasiSteadySolverBuilder {
     SimpleIterative(QuasiSteadySimpleIterativeBuilder),
@@ -83,6 +83,9 @@ impl SimpleIterative {
     pub fn default_strength_difference_tolerance() -> Float {1e-6}
     pub fn default_one() -> Float {1.0}
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "simple_iterative_solve", level = "debug", skip_all
+    ))]
     pub fn solve(
         &self,
         line_force_model: &LineForceModel,
@@ -172,6 +175,15 @@ impl SimpleIterative {
                 &ctrl_points_velocity
             );
 
+            #[cfg(feature = "logging")]
+            log::trace!(
+                target: "stormbird::solver", 
+                "iteration: {}, residual: {}", iterations, residual
+            );
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(iteration = iterations, residual, "solver iteration");
+
             if residual < self.residual_tolerance_absolute {
                 converged = true;
             }
@@ -207,7 +219,8 @@ impl SimpleIterative {
             circulation_strength,
             output_ctrl_points_velocity: ctrl_points_velocity,
             iterations,
-            residual
+            residual,
+            converged,
         }
     }
 }
@@ -0,0 +1,62 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the difference between geometric and effective angle of attack measurements.
+
+use crate::controller::measurements::{
+    MeasurementSettings,
+    measure_angles_of_attack,
+    measure_geometric_angles_of_attack
+};
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+use super::test_setup::RectangularWing;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+/// Returns the geometric and effective angle of attack for a rectangular wing at the input angle
+fn geometric_and_effective_angle_of_attack(angle_of_attack: Float) -> (Float, Float) {
+    let mut sim = SimulationBuilder {
+        line_force_model: RectangularWing {
+            angle_of_attack,
+            cl_zero_angle: 0.0,
+            ..Default::default()
+        }.build(),
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let nr_points = sim.get_freestream_velocity_points().len();
+
+    let result = sim.do_step(0.0, 1.0, &vec![SpatialVector::from([8.0, 0.0, 0.0]); nr_points]);
+
+    let settings = MeasurementSettings::default();
+
+    let geometric = measure_geometric_angles_of_attack(&result, &sim.line_force_model, &settings);
+    let effective = measure_angles_of_attack(&result, &settings);
+
+    (geometric[0], effective[0])
+}
+
+#[test]
+fn geometric_and_effective_angle_of_attack_measurements() {
+    let (geometric_unloaded, effective_unloaded) = geometric_and_effective_angle_of_attack(0.0);
+
+    dbg!(geometric_unloaded, effective_unloaded);
+
+    assert!((geometric_unloaded - effective_unloaded).abs() < 1e-9);
+
+    let angle_of_attack = Float::from(6.0).to_radians();
+
+    let (geometric_loaded, effective_loaded) = geometric_and_effective_angle_of_attack(
+        angle_of_attack
+    );
+
+    dbg!(geometric_loaded, effective_loaded);
+
+    assert!((geometric_loaded.abs() - angle_of_attack).abs() < 1e-6);
+    assert!(effective_loaded.abs() < geometric_loaded.abs() - 1e-3);
+}
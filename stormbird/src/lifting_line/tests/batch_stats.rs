@@ -0,0 +1,81 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the statistics collected during batch runs
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+    QuasiSteadySettings
+};
+use crate::lifting_line::solvers::simple_iterative::QuasiSteadySimpleIterativeBuilder;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWing;
+
+#[test]
+fn batch_stats() {
+    let max_iterations = 200;
+
+    let line_force_model_builder = RectangularWing {
+        cl_zero_angle: 0.0,
+        ..Default::default()
+    }.build();
+
+    let mut sim = SimulationBuilder {
+        line_force_model: line_force_model_builder,
+        simulation_settings: SimulationSettings::QuasiSteady(QuasiSteadySettings {
+            solver: QuasiSteadySolverBuilder::SimpleIterative(QuasiSteadySimpleIterativeBuilder {
+                max_iterations_per_time_step: max_iterations,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }.build();
+
+    let nr_points = sim.get_freestream_velocity_points().len();
+
+    let velocity_magnitudes: [Float; 2] = [8.0, 12.0];
+    let angles_of_attack_deg: [Float; 3] = [0.0, 2.0, 4.0];
+
+    let mut freestream_velocities: Vec<Vec<SpatialVector>> = Vec::new();
+
+    for velocity in velocity_magnitudes {
+        for angle in angles_of_attack_deg {
+            let freestream = SpatialVector::from([velocity, 0.0, 0.0]).rotate_around_axis(
+                angle.to_radians(), SpatialVector::unit_z()
+            );
+
+            freestream_velocities.push(vec![freestream; nr_points]);
+        }
+    }
+
+    // Deliberately hard condition: an extreme velocity at an angle of attack beyond stall, which 
+    // makes the residual tolerance impossible to reach within the iteration limit
+    let hard_condition_index = freestream_velocities.len();
+
+    freestream_velocities.push(
+        vec![
+            SpatialVector::from([200000.0, 0.0, 0.0]).rotate_around_axis(
+                Float::from(50.0).to_radians(), SpatialVector::unit_z()
+            ); 
+            nr_points
+        ]
+    );
+
+    let (results, stats) = sim.solve_batch(&freestream_velocities, true);
+
+    for result in &results {
+        dbg!(result.iterations, result.residual);
+    }
+
+    assert_eq!(results.len(), freestream_velocities.len());
+    assert_eq!(stats.nr_conditions, freestream_velocities.len());
+    assert_eq!(stats.nr_non_converged(), 1);
+    assert_eq!(stats.non_converged_indices[0], hard_condition_index);
+    assert!(stats.average_iterations() > 0.0);
+    assert!(stats.wall_clock_time >= 0.0);
+}
@@ -0,0 +1,41 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the search for the ship heading with maximum net power
+
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn best_heading_is_reaching() {
+    let mut model = RectangularWingSailModel {
+        angle_of_attack_set_point: Float::from(10.0).to_radians(),
+        ..Default::default()
+    }.build();
+
+    let true_wind = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: 0.0,
+    };
+
+    let ship_speed = 5.0;
+
+    let (best_heading, net_power) = model.best_heading(true_wind, ship_speed, (-PI, 0.0));
+
+    dbg!(best_heading.to_degrees(), net_power);
+
+    assert!(net_power > 0.0, "No positive net power at the best heading: {}", net_power);
+
+    let true_wind_angle = best_heading.abs();
+
+    assert!(
+        true_wind_angle > Float::from(45.0).to_radians() && 
+        true_wind_angle < Float::from(150.0).to_radians(),
+        "Best heading is not a reaching angle: {}", best_heading.to_degrees()
+    );
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the columnar time series output from the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn columnar_output_has_one_row_per_time_step() {
+    let mut setup = RectangularWingSailModel::default().setup();
+
+    setup["record_columnar_output"] = serde_json::json!(true);
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let wind_condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let nr_time_steps = 12;
+
+    model.simulate_condition(wind_condition, 5.0, 1.0, 0.5, nr_time_steps);
+
+    let data = model.columnar_output.as_ref().unwrap();
+
+    let expected_names = [
+        "time", 
+        "force_0.x", "force_0.y", "force_0.z", 
+        "moment_0.x", "moment_0.y", "moment_0.z"
+    ];
+
+    assert_eq!(data.column_names, expected_names);
+    assert_eq!(data.nr_rows(), nr_time_steps);
+    assert_eq!(data.column("time").unwrap()[nr_time_steps - 1], 0.5 * (nr_time_steps - 1) as Float);
+
+    #[cfg(feature = "columnar_output")]
+    {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let file_path = std::env::temp_dir().join("stormbird_test_complete_sail_model.parquet");
+
+        data.write_to_parquet_file(file_path.to_str().unwrap()).unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&file_path).unwrap()).unwrap();
+
+        let metadata = reader.metadata().file_metadata();
+
+        assert_eq!(metadata.num_rows() as usize, nr_time_steps);
+        assert_eq!(metadata.schema_descr().num_columns(), expected_names.len());
+    }
+}
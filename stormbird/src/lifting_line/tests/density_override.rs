@@ -0,0 +1,49 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for overriding the density in a single call to the complete sail model
+
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn density_override_scales_forces() {
+    let mut model = RectangularWingSailModel::default().build();
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let default_density = model.lifting_line_simulation.line_force_model.density;
+    let density_factor = 1.1;
+
+    let default_result = model.simulate_steady_state_condition_with_density(
+        wind_condition, ship_velocity, 1.0, None
+    );
+
+    let dense_result = model.simulate_steady_state_condition_with_density(
+        wind_condition, ship_velocity, 1.0, Some(density_factor * default_density)
+    );
+
+    let default_force = default_result.integrated_forces_sum();
+    let dense_force = dense_result.integrated_forces_sum();
+
+    dbg!(default_force, dense_force);
+
+    assert_eq!(model.lifting_line_simulation.line_force_model.density, default_density);
+
+    for i in 0..2 {
+        assert!(
+            (dense_force[i] - density_factor * default_force[i]).abs() < 1e-6 * default_force[i].abs(),
+            "Force component {} does not scale with density: {} vs {}", 
+            i, dense_force[i], default_force[i]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the downwash distribution, compared against elliptic wing theory.
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+use stormath::statistics;
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+    QuasiSteadySettings,
+};
+
+use super::test_setup::RectangularWing;
+
+/// Returns a wing with an elliptic planform, which gives an elliptic circulation distribution
+fn elliptic_wing(span: Float, root_chord: Float, angle_of_attack: Float) -> LineForceModelBuilder {
+    let nr_section_points = 81;
+
+    let chord_direction = SpatialVector::from([1.0, 0.0, 0.0]).rotate_around_axis(
+        -angle_of_attack, SpatialVector::unit_z()
+    );
+
+    let mut section_points = Vec::with_capacity(nr_section_points);
+    let mut chord_vectors = Vec::with_capacity(nr_section_points);
+
+    for i in 0..nr_section_points {
+        let relative_position = -1.0 + 2.0 * i as Float / (nr_section_points - 1) as Float;
+
+        let chord_length = root_chord * (1.0 - relative_position.powi(2)).max(1e-6).sqrt();
+
+        section_points.push(SpatialVector::from([0.0, 0.0, 0.5 * span * relative_position]));
+        chord_vectors.push(chord_length * chord_direction);
+    }
+
+    let mut builder = LineForceModelBuilder::new(64);
+
+    builder.add_wing(WingBuilder {
+        section_points,
+        chord_vectors,
+        section_model: SectionModel::Foil(Foil {
+            cl_zero_angle: 0.0,
+            mean_positive_stall_angle: Float::from(45.0).to_radians(),
+            mean_negative_stall_angle: Float::from(45.0).to_radians(),
+            ..Default::default()
+        }),
+        non_zero_circulation_at_ends: [false, false],
+        ..Default::default()
+    });
+
+    builder
+}
+
+/// Returns the downwash distribution along the inner part of the span, together with the lift
+/// coefficient of the wing
+fn inner_downwash(builder: LineForceModelBuilder) -> (Vec<Float>, Float) {
+    let velocity = SpatialVector::from([1.2, 0.0, 0.0]);
+
+    let mut sim = SimulationBuilder::new(
+        builder,
+        SimulationSettings::QuasiSteady(QuasiSteadySettings::default())
+    ).build();
+
+    let freestream_velocity = vec![velocity; sim.get_freestream_velocity_points().len()];
+
+    let result = sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    let cl = result.integrated_forces_sum()[1] /
+        sim.line_force_model.total_force_factor(velocity.length());
+
+    let downwash = result.downwash_distribution();
+
+    let nr_strips = downwash.len();
+
+    (downwash[nr_strips / 8..nr_strips - nr_strips / 8].to_vec(), cl)
+}
+
+fn relative_variation(values: &[Float]) -> Float {
+    (statistics::max(values) - statistics::min(values)) / statistics::mean(values)
+}
+
+#[test]
+fn elliptic_loading_gives_constant_downwash() {
+    let span: Float = 5.0;
+    let root_chord = 1.0;
+    let angle_of_attack = Float::from(4.0).to_radians();
+
+    let aspect_ratio = span.powi(2) / (0.25 * PI * span * root_chord);
+
+    let (elliptic_downwash, elliptic_cl) = inner_downwash(
+        elliptic_wing(span, root_chord, angle_of_attack)
+    );
+
+    let (rectangular_downwash, _) = inner_downwash(
+        RectangularWing {
+            aspect_ratio: span,
+            cl_zero_angle: 0.0,
+            angle_of_attack,
+            nr_strips: 64,
+            ..Default::default()
+        }.build()
+    );
+
+    let elliptic_variation = relative_variation(&elliptic_downwash);
+    let rectangular_variation = relative_variation(&rectangular_downwash);
+
+    dbg!(elliptic_variation, rectangular_variation);
+
+    assert!(
+        elliptic_variation < 0.05,
+        "Downwash not constant for elliptic loading. Variation: {}", elliptic_variation
+    );
+
+    assert!(
+        rectangular_variation > 4.0 * elliptic_variation,
+        "Downwash for rectangular loading does not vary. Variation: {}", rectangular_variation
+    );
+
+    // Classical theory: the downwash angle is CL / (pi * AR) along the entire span
+    let downwash_theory = elliptic_cl.abs() / (PI * aspect_ratio);
+    let downwash_mean = statistics::mean(&elliptic_downwash);
+
+    dbg!(downwash_mean, downwash_theory);
+
+    let error = (downwash_mean - downwash_theory).abs() / downwash_theory;
+
+    assert!(error < 0.05, "Downwash differs from theory. Relative error: {}", error);
+}
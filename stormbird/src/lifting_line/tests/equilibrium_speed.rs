@@ -0,0 +1,93 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the ship speed where the sail thrust balances the hull resistance
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::lifting_line::complete_sail_model::equilibrium::ResistanceCurve;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+fn wing_sail_model() -> CompleteSailModel {
+    RectangularWingSailModel::default().build()
+}
+
+/// Quadratic resistance curve, scaled so that the resistance is equal to the reference resistance
+/// at the reference velocity.
+fn quadratic_resistance_curve(
+    reference_velocity: Float, 
+    reference_resistance: Float, 
+    max_velocity: Float
+) -> ResistanceCurve {
+    let nr_points = 41;
+
+    let velocities: Vec<Float> = (0..nr_points)
+        .map(|i| max_velocity * i as Float / (nr_points - 1) as Float)
+        .collect();
+
+    let resistance = velocities.iter()
+        .map(|velocity| reference_resistance * (velocity / reference_velocity).powi(2))
+        .collect();
+
+    ResistanceCurve::new(velocities, resistance).unwrap()
+}
+
+#[test]
+fn thrust_balances_resistance_at_equilibrium_speed() {
+    let mut model = wing_sail_model();
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let reference_result = model.simulate_steady_state_condition(wind_condition, 4.0, 1.0);
+    let reference_thrust = model.thrust(&reference_result);
+
+    assert!(reference_thrust > 0.0);
+
+    let resistance_curve = quadratic_resistance_curve(4.0, reference_thrust, 20.0);
+
+    let (speed, result) = model.solve_equilibrium_speed(
+        wind_condition, &resistance_curve, 1.0
+    ).unwrap();
+
+    let thrust = model.thrust(&result);
+    let resistance = resistance_curve.resistance_at(speed);
+
+    assert!((speed - 4.0).abs() < 0.05, "Equilibrium speed: {}", speed);
+    assert!((thrust - resistance).abs() < 0.01 * resistance);
+}
+
+#[test]
+fn equilibrium_speed_outside_resistance_curve() {
+    let mut model = wing_sail_model();
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let resistance_curve = ResistanceCurve::new(vec![0.0, 1.0], vec![0.0, 1e-6]).unwrap();
+
+    assert!(model.solve_equilibrium_speed(wind_condition, &resistance_curve, 1.0).is_err());
+
+    // Head wind gives no thrust at zero speed, so the ship should not move
+    let head_wind = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: 0.0,
+    };
+
+    let resistance_curve = quadratic_resistance_curve(4.0, 1.0, 20.0);
+
+    let (speed, _) = model.solve_equilibrium_speed(head_wind, &resistance_curve, 1.0).unwrap();
+
+    assert_eq!(speed, 0.0);
+
+    assert!(ResistanceCurve::new(vec![1.0, 0.0], vec![0.0, 1.0]).is_err());
+    assert!(ResistanceCurve::new(vec![0.0, 1.0], vec![0.0]).is_err());
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the equivalent single sail representation of a simulation result
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWing;
+
+#[test]
+fn equivalent_sail_for_co_located_sails() {
+    let single_wing_builder = RectangularWing {
+        angle_of_attack: Float::from(5.0).to_radians(),
+        ..Default::default()
+    }.build();
+
+    let mut line_force_model_builder = single_wing_builder.clone();
+
+    line_force_model_builder.add_wing(single_wing_builder.wing_builders[0].clone());
+
+    let mut sim_single = SimulationBuilder {
+        line_force_model: single_wing_builder,
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let mut sim = SimulationBuilder {
+        line_force_model: line_force_model_builder,
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let freestream_velocity = SpatialVector::from([8.0, 0.0, 0.0]);
+
+    let single_result = sim_single.do_step(
+        0.0, 1.0, &vec![freestream_velocity; sim_single.get_freestream_velocity_points().len()]
+    );
+
+    let result = sim.do_step(
+        0.0, 1.0, &vec![freestream_velocity; sim.get_freestream_velocity_points().len()]
+    );
+
+    let single_sail_area = sim_single.line_force_model.total_projected_area();
+    let single_sail_center_of_effort = single_result.equivalent_single_sail().center_of_effort;
+
+    let equivalent_sail = result.equivalent_single_sail();
+
+    dbg!(&equivalent_sail, single_sail_center_of_effort);
+
+    assert!((equivalent_sail.reference_area - 2.0 * single_sail_area).abs() < 1e-9);
+    assert!(equivalent_sail.force.length() > 0.0);
+    assert!((equivalent_sail.center_of_effort - single_sail_center_of_effort).length() < 1e-9);
+    assert!((equivalent_sail.force - result.integrated_forces_sum()).length() < 1e-12);
+}
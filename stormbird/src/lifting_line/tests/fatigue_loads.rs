@@ -0,0 +1,70 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the decomposition of the sail forces into mean and oscillatory components
+
+use crate::common_utils::results::fatigue::FatigueLoadRecorder;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+use stormath::consts::TAU;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn steady_load_has_no_oscillatory_amplitude() {
+    let mut model = RectangularWingSailModel::default().build();
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let mut recorder = FatigueLoadRecorder::new(1, 4);
+
+    for _ in 0..4 {
+        let result = model.simulate_steady_state_condition(wind_condition, 5.0, 1.0);
+
+        recorder.record(&result);
+    }
+
+    assert!(recorder.window_is_full());
+
+    let components = recorder.load_components();
+
+    dbg!(&components);
+
+    assert!(components[0].mean.length() > 0.0);
+    assert!(components[0].amplitude.length() < 1e-9 * components[0].mean.length());
+}
+
+#[test]
+fn sinusoidal_load_gives_mean_and_amplitude() {
+    let mean = SpatialVector::new(-1000.0, 5000.0, 0.0);
+    let amplitude = SpatialVector::new(200.0, 800.0, 0.0);
+
+    let period_steps = 40;
+
+    let mut recorder = FatigueLoadRecorder::new(2, period_steps);
+
+    // Record more than one period, so that the oldest steps are removed from the window
+    for step in 0..(3 * period_steps / 2) {
+        let phase = TAU * step as Float / period_steps as Float;
+
+        recorder.record_forces(&[mean + phase.sin() * amplitude, mean]);
+    }
+
+    let components = recorder.load_components();
+
+    dbg!(&components);
+
+    for i in 0..3 {
+        assert!((components[0].mean[i] - mean[i]).abs() < 1e-9 * mean.length());
+        assert!((components[0].amplitude[i] - amplitude[i]).abs() < 1e-9 * amplitude.length());
+    }
+
+    assert_eq!(components[1].mean, mean);
+    assert_eq!(components[1].amplitude, SpatialVector::default());
+}
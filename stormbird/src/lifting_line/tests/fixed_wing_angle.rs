@@ -0,0 +1,69 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the search for the best fixed wing angle in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+/// For a narrow distribution around beam wind, the best fixed wing angle should be close to the
+/// optimal wing angle for the peak direction alone, found from a dense sweep.
+fn best_fixed_angle_for_narrow_beam_wind_distribution() {
+    let setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            cl_zero_angle: 0.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    }.setup_with_fixed_wing_angles();
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let peak_direction = Float::from(90.0).to_radians();
+    let direction_spread = Float::from(2.0).to_radians();
+    let wind_speed = 8.0;
+
+    let direction_distribution = vec![
+        (peak_direction - direction_spread, 0.25),
+        (peak_direction, 0.5),
+        (peak_direction + direction_spread, 0.25),
+    ];
+
+    let best_fixed_angles = model.best_fixed_wing_angle(&direction_distribution, wind_speed, 0.0);
+
+    let peak_condition = WindCondition {
+        velocity: wind_speed,
+        direction_coming_from: peak_direction,
+    };
+
+    let nr_sweep_angles = 720;
+    let mut optimal_angle = 0.0;
+    let mut max_thrust = Float::NEG_INFINITY;
+
+    for i in 0..nr_sweep_angles {
+        let angle = (-180.0 + 0.5 * i as Float).to_radians();
+
+        let result = model.simulate_steady_state_condition_fixed_wing_angles(
+            peak_condition, 0.0, &[angle]
+        );
+
+        let thrust = -result.integrated_forces_sum()[0];
+
+        if thrust > max_thrust {
+            max_thrust = thrust;
+            optimal_angle = angle;
+        }
+    }
+
+    dbg!(best_fixed_angles[0].to_degrees(), optimal_angle.to_degrees(), max_thrust);
+
+    assert_eq!(best_fixed_angles.len(), 1);
+    assert!(max_thrust > 0.0);
+    assert!((best_fixed_angles[0] - optimal_angle).abs() < Float::from(1.5).to_radians());
+}
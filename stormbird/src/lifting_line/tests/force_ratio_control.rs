@@ -0,0 +1,54 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the force ratio control in the controller of the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+use crate::common_utils::results::simulation::SimulationResult;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+fn force_ratio(model: &CompleteSailModel, result: &SimulationResult) -> Float {
+    let force = result.integrated_forces_sum();
+    let side_direction = model.wind_environment.side_direction(model.thrust_direction);
+
+    model.thrust_direction.dot(force) / side_direction.dot(force).abs()
+}
+
+#[test]
+/// The wind is coming from behind the beam, where the ratio between the thrust and the side force
+/// increases with the loading. The target is below the ratio at full loading, so the controller 
+/// must reduce the loading to reach it.
+fn force_ratio_target_is_reached() {
+    let mut sail_model = RectangularWingSailModel::default();
+    sail_model.wing.cl_zero_angle = 0.0;
+
+    let mut setup = sail_model.setup();
+
+    let target_ratio = 1.83;
+
+    setup["controller"]["set_points"][0]["force_ratio_target"] = serde_json::json!({
+        "target_ratio": target_ratio,
+        "gain": 2.0
+    });
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(120.0).to_radians(),
+    };
+
+    let result = model.simulate_condition(condition, 0.0, 1.0, 1.0, 50);
+
+    let loading_factor = model.controller.state[0].force_ratio_loading_factor.unwrap();
+
+    dbg!(force_ratio(&model, &result), loading_factor);
+
+    assert!((force_ratio(&model, &result) - target_ratio).abs() < 1e-3);
+    assert!(loading_factor < 1.0);
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the frequency response of the complete sail model to wind variations
+
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn low_frequency_response_is_quasi_steady() {
+    let mut model = RectangularWingSailModel::default().build();
+
+    let mean_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let amplitude = 0.2;
+
+    let mut steady_force = |velocity: Float| -> Float {
+        model.simulate_steady_state_condition(
+            WindCondition {
+                velocity,
+                ..mean_condition
+            },
+            0.0,
+            1.0
+        ).integrated_forces_sum().length()
+    };
+
+    let quasi_steady_gain = (
+        steady_force(mean_condition.velocity + amplitude) - 
+        steady_force(mean_condition.velocity - amplitude)
+    ) / (2.0 * amplitude);
+
+    let response = model.frequency_response(&[0.001], amplitude, mean_condition, 0.0);
+
+    let (frequency, gain, phase) = response[0];
+
+    dbg!(frequency, gain, phase, quasi_steady_gain);
+
+    assert_eq!(frequency, 0.001);
+
+    assert!(
+        (gain - quasi_steady_gain).abs() < 0.02 * quasi_steady_gain,
+        "Gain differs from the quasi-steady value: {} vs {}", gain, quasi_steady_gain
+    );
+
+    assert!(phase.abs() < Float::from(2.0).to_radians(), "Phase not close to zero: {}", phase);
+}
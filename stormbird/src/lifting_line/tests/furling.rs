@@ -0,0 +1,72 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the furling schedule in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+fn furling_caps_the_force_in_strong_wind() {
+    let wind_speed_threshold = 10.0;
+
+    let mut setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            angle_of_attack: Float::from(5.0).to_radians(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }.setup_with_fixed_wing_angles();
+
+    setup["furling_model"] = serde_json::json!({
+        "wind_speed_threshold": wind_speed_threshold
+    });
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let mut areas: Vec<Float> = Vec::new();
+    let mut forces: Vec<Float> = Vec::new();
+
+    let wind_speeds = [8.0, 15.0, 20.0];
+
+    for wind_speed in wind_speeds {
+        let wind_condition = WindCondition {
+            velocity: wind_speed,
+            direction_coming_from: 0.0,
+        };
+
+        let result = model.simulate_steady_state_condition(wind_condition, 0.0, 1.0);
+
+        areas.push(result.strip_areas.iter().sum());
+        forces.push(result.integrated_forces_sum().length());
+    }
+
+    dbg!(&areas, &forces);
+
+    let unfurled_area = model.lifting_line_simulation.line_force_model.span_lines_local_unfurled
+        .iter()
+        .zip(model.lifting_line_simulation.line_force_model.chord_lengths.iter())
+        .map(|(line, chord)| line.length() * chord)
+        .sum::<Float>();
+
+    assert!((areas[0] - unfurled_area).abs() < 1e-9);
+    assert!(areas[1] < areas[0]);
+    assert!(areas[2] < areas[1]);
+
+    // Without furling, the force would grow with the velocity squared
+    let unfurled_force_ratio = (wind_speeds[2] / wind_speeds[1]).powi(2);
+    let force_ratio = forces[2] / forces[1];
+
+    assert!(
+        force_ratio < 0.5 * (1.0 + unfurled_force_ratio), 
+        "Force is not capped by furling. Ratio: {}", force_ratio
+    );
+
+    // The force may drop somewhat, as the aspect ratio is also reduced by the furling
+    assert!(force_ratio < 1.1);
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the gust load factor in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::environment::WindEnvironment;
+use crate::wind::wind_condition::{WindCondition, WindConditionTimeSeries};
+
+use stormath::type_aliases::Float;
+use stormath::consts::TAU;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+/// With a quasi-steady simulation, the forces scale with the velocity squared. A sinusoidal gust 
+/// with relative amplitude a should therefore give a load factor of (1 + a)^2.
+fn sinusoidal_gust_gives_quasi_steady_load_factor() {
+    let mean_velocity = 10.0;
+    let gust_amplitude = 0.3;
+    let gust_period = 4.0;
+
+    let time_step = 0.25;
+    let nr_time_steps = 17;
+
+    let time: Vec<Float> = (0..nr_time_steps).map(|i| i as Float * time_step).collect();
+
+    let wind_condition_time_series = WindConditionTimeSeries {
+        velocity: time.iter().map(
+            |t| mean_velocity * (1.0 + gust_amplitude * (TAU * t / gust_period).sin())
+        ).collect(),
+        direction_coming_from: vec![0.0; nr_time_steps],
+        direction_time: None,
+        time,
+    };
+
+    let wind_environment = WindEnvironment {
+        wind_condition_time_series: Some(wind_condition_time_series),
+        ..Default::default()
+    };
+
+    let setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            angle_of_attack: Float::from(5.0).to_radians(),
+            ..Default::default()
+        },
+        wind_environment: serde_json::json!(wind_environment),
+        ..Default::default()
+    }.setup_with_fixed_wing_angles();
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let steady_condition = WindCondition {
+        velocity: mean_velocity,
+        direction_coming_from: 0.0,
+    };
+
+    let load_factors = model.gust_load_factors(
+        steady_condition, 0.0, 1.0, time_step, nr_time_steps
+    );
+
+    let expected_load_factor = (1.0 + gust_amplitude).powi(2);
+
+    dbg!(&load_factors, expected_load_factor);
+
+    assert_eq!(load_factors.len(), 1);
+    let load_factor = load_factors[0].expect("The sail should have a steady force");
+
+    assert!((load_factor - expected_load_factor).abs() / expected_load_factor < 1e-3);
+}
+
+#[test]
+/// Without wind or ship motion there is no steady force to compare the peak force with, and the
+/// load factor should be undefined rather than a division by zero.
+fn no_steady_force_gives_no_load_factor() {
+    let setup = RectangularWingSailModel::default().setup_with_fixed_wing_angles();
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let steady_condition = WindCondition {
+        velocity: 0.0,
+        direction_coming_from: 0.0,
+    };
+
+    let load_factors = model.gust_load_factors(steady_condition, 0.0, 1.0, 0.25, 4);
+
+    assert_eq!(load_factors, vec![None]);
+}
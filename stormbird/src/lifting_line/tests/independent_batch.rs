@@ -0,0 +1,56 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests that a batch of independent conditions gives the same results, in the same order, as 
+//! solving each condition on its own. With the `parallel` feature enabled, this compares the 
+//! threaded batch against a serial reference.
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+};
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWing;
+
+#[test]
+fn independent_batch_matches_serial() {
+    let sim = SimulationBuilder {
+        line_force_model: RectangularWing::default().build(),
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let nr_points = sim.get_freestream_velocity_points().len();
+
+    let angles_of_attack_deg: [Float; 5] = [-4.0, 0.0, 2.0, 6.0, 10.0];
+
+    let freestream_velocities: Vec<Vec<SpatialVector>> = angles_of_attack_deg.iter()
+        .map(|angle| {
+            let freestream = SpatialVector::from([8.0, 0.0, 0.0]).rotate_around_axis(
+                angle.to_radians(), SpatialVector::unit_z()
+            );
+
+            vec![freestream; nr_points]
+        })
+        .collect();
+
+    let (batch_results, stats) = sim.solve_batch_independent(&freestream_velocities, false);
+
+    assert_eq!(batch_results.len(), freestream_velocities.len());
+    assert_eq!(stats.nr_conditions, freestream_velocities.len());
+
+    for (index, freestream_velocity) in freestream_velocities.iter().enumerate() {
+        let reference = sim.clone().do_step(0.0, 1.0, freestream_velocity);
+
+        let batch_force = batch_results[index].integrated_forces_sum();
+        let reference_force = reference.integrated_forces_sum();
+
+        assert_eq!(batch_results[index].iterations, reference.iterations);
+        assert_eq!(batch_force[0], reference_force[0]);
+        assert_eq!(batch_force[1], reference_force[1]);
+        assert_eq!(batch_force[2], reference_force[2]);
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests that independent conditions simulated with the complete sail model do not depend on the
+//! order they are simulated in.
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+/// The controller averages the measurements over several time steps. The state of the controller
+/// must therefore be reset between conditions, so that a condition gives the same result when it
+/// is simulated before and after a different condition.
+fn condition_is_independent_of_previous_condition() {
+    let mut setup = RectangularWingSailModel::default().setup();
+
+    setup["controller"]["moving_average_window_size"] = serde_json::json!(10);
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let other_condition = WindCondition {
+        velocity: 12.0,
+        direction_coming_from: Float::from(-45.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let first_result = model.simulate_steady_state_condition(condition, ship_velocity, 1.0);
+
+    model.simulate_steady_state_condition(other_condition, ship_velocity, 1.0);
+
+    let second_result = model.simulate_steady_state_condition(condition, ship_velocity, 1.0);
+
+    let first_force = first_result.integrated_forces_sum();
+    let second_force = second_result.integrated_forces_sum();
+
+    dbg!(first_force, second_force);
+
+    assert!((first_force - second_force).length() < 1e-6 * first_force.length());
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the input power coefficient and thrust power efficiency of rotor sails
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+use crate::line_force_model::input_power::{InputPowerModel, InputPowerData};
+
+use crate::common_utils::results::simulation::SimulationResult;
+use crate::common_utils::forces_and_moments::IntegratedValues;
+
+use stormath::type_aliases::Float;
+
+
+#[test]
+fn rotor_sail_power_coefficient_and_efficiency() {
+    let diameter = 1.0;
+    let span = 5.0;
+    let velocity = 8.0;
+    let ship_speed = 5.0;
+
+    let mut line_force_model_builder = LineForceModelBuilder::new(10);
+
+    line_force_model_builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector::from([0.0, 0.0, 0.0]),
+                SpatialVector::from([0.0, 0.0, span]),
+            ],
+            chord_vectors: vec![
+                SpatialVector::from([diameter, 0.0, 0.0]),
+                SpatialVector::from([diameter, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::RotatingCylinder(
+                RotatingCylinder {
+                    spin_ratio_data: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+                    cd_data: vec![0.554, 0.674, 1.497, 2.877, 3.556, 3.816],
+                    cl_data: vec![0.0, 1.889, 4.638, 6.794, 7.680, 7.950],
+                    wake_angle_data: Some(vec![
+                        0.0, Float::from(20.0).to_radians(), Float::from(25.0).to_radians(), 
+                        Float::from(35.0).to_radians(), Float::from(45.0).to_radians(), 
+                        Float::from(60.0).to_radians()
+                    ]),
+                    ..Default::default()
+                }
+            ),
+            non_zero_circulation_at_ends: [false, false],
+            input_power_model: InputPowerModel::InterpolateFromInternalStateOnly(
+                InputPowerData {
+                    section_models_internal_state_data: vec![0.0, 10.0],
+                    input_power_coefficient_data: vec![0.0, 300.0],
+                }
+            ),
+            ..Default::default()
+        }
+    );
+
+    let mut sim = SimulationBuilder {
+        line_force_model: line_force_model_builder,
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let density = sim.line_force_model.density;
+
+    // Beam wind, so that the lift from the rotor is directed along the x-axis
+    let freestream_velocity = vec![
+        SpatialVector::from([0.0, velocity, 0.0]); 
+        sim.get_freestream_velocity_points().len()
+    ];
+
+    let thrust_direction = SpatialVector::from([-1.0, 0.0, 0.0]);
+
+    sim.line_force_model.set_section_models_internal_state(&[0.0]);
+
+    let result_zero_rps = sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    let power_coefficients_zero_rps = result_zero_rps.input_power_coefficients(density);
+    let efficiencies_zero_rps = result_zero_rps.thrust_power_efficiencies(
+        thrust_direction, ship_speed
+    );
+
+    dbg!(&result_zero_rps.input_power, &power_coefficients_zero_rps, &efficiencies_zero_rps);
+
+    assert_eq!(result_zero_rps.input_power[0], 0.0);
+    assert_eq!(power_coefficients_zero_rps[0], 0.0);
+    assert_eq!(efficiencies_zero_rps[0], 0.0);
+
+    let revolutions_per_second = -RotatingCylinder::revolutions_per_second_from_spin_ratio(
+        3.0, diameter, velocity
+    );
+
+    sim.line_force_model.set_section_models_internal_state(&[revolutions_per_second]);
+
+    let result_loaded = sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    let power_coefficients_loaded = result_loaded.input_power_coefficients(density);
+    let efficiencies_loaded = result_loaded.thrust_power_efficiencies(
+        thrust_direction, ship_speed
+    );
+
+    dbg!(&result_loaded.input_power, &power_coefficients_loaded, &efficiencies_loaded);
+
+    assert!(result_loaded.input_power[0] > 0.0);
+    assert!(power_coefficients_loaded[0] > 0.0 && power_coefficients_loaded[0].is_finite());
+    assert!(
+        efficiencies_loaded[0] > 0.0 && efficiencies_loaded[0] < 50.0,
+        "Unexpected efficiency: {}", efficiencies_loaded[0]
+    );
+}
+
+#[test]
+/// Tests that a result without strip areas, for instance one written before the areas were part of
+/// the result, gives zero power coefficients instead of panicking.
+fn power_coefficient_without_strip_areas() {
+    let result = SimulationResult {
+        wing_indices: vec![0..5, 5..10],
+        integrated_forces: vec![IntegratedValues::default(); 2],
+        input_power: vec![100.0, 200.0],
+        ..Default::default()
+    };
+
+    assert_eq!(result.input_power_coefficients(1.225), vec![0.0, 0.0]);
+}
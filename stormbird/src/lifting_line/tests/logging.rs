@@ -0,0 +1,71 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the log events emitted by the solver, when the `logging` feature is active.
+
+#![cfg(feature = "logging")]
+
+use std::sync::Mutex;
+
+use log::{Log, Metadata, Record, LevelFilter};
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+};
+
+use super::test_setup::RectangularWing;
+
+/// Logger that stores the target and message of every event
+struct CapturingLogger {
+    events: Mutex<Vec<(String, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.events.lock().unwrap().push(
+            (record.target().to_string(), record.args().to_string())
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    events: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn solver_residual_is_logged() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let mut sim = SimulationBuilder {
+        line_force_model: RectangularWing::default().build(),
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let freestream_velocity = vec![
+        SpatialVector::from([1.0, 0.0, 0.0]); 
+        sim.get_freestream_velocity_points().len()
+    ];
+
+    sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    let events = LOGGER.events.lock().unwrap();
+
+    dbg!(&events);
+
+    assert!(
+        events.iter().any(
+            |(target, message)| target == "stormbird::solver" && message.contains("residual")
+        ),
+        "No residual event emitted by the solver"
+    );
+}
@@ -0,0 +1,101 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the simulation of maneuvers with the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+/// A tack where the apparent wind crosses the bow. The thrust should drop while the wind is
+/// close to head wind and recover when the sail is re-trimmed on the new side, and the wing angle
+/// should never change faster than the rate limit in the controller.
+fn tack_gives_thrust_dip_and_recovery() {
+    let max_wing_angle_change_rate = Float::from(10.0).to_radians();
+
+    let mut setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            cl_zero_angle: 0.0,
+            ..Default::default()
+        },
+        angle_of_attack_set_point: Float::from(10.0).to_radians(),
+        ..Default::default()
+    }.setup();
+
+    setup["controller"]["set_points"][0]["max_local_wing_angle_change_rate"] = 
+        serde_json::json!(max_wing_angle_change_rate);
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let wind_condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(70.0).to_radians(),
+    };
+
+    let time_step = 0.5;
+    let start_turn_time = 20.0;
+    let turn_duration = 10.0;
+    let end_time = 60.0;
+    let heading_change = Float::from(140.0).to_radians();
+
+    let nr_time_steps = (end_time / time_step) as usize + 1;
+
+    let heading_schedule: Vec<(Float, Float)> = (0..nr_time_steps).map(|i| {
+        let time = i as Float * time_step;
+
+        let turn_fraction = ((time - start_turn_time) / turn_duration).clamp(0.0, 1.0);
+
+        (time, turn_fraction * heading_change)
+    }).collect();
+
+    let results = model.simulate_maneuver(&heading_schedule, wind_condition, 0.0);
+
+    assert_eq!(results.len(), nr_time_steps);
+
+    let thrust: Vec<Float> = results.iter().map(
+        |result| -result.integrated_forces_sum()[0]
+    ).collect();
+
+    let wing_angles: Vec<Float> = results.iter().map(
+        |result| result.local_wing_angles[0]
+    ).collect();
+
+    let thrust_before = thrust[(start_turn_time / time_step) as usize - 1];
+    let thrust_after = thrust[nr_time_steps - 1];
+    let min_thrust = thrust.iter().cloned().fold(Float::INFINITY, Float::min);
+
+    dbg!(thrust_before, thrust_after, min_thrust);
+
+    assert!(thrust_before > 0.0, "No thrust before the maneuver: {}", thrust_before);
+    assert!(
+        min_thrust < 0.5 * thrust_before,
+        "No dip in thrust during the maneuver: {}", min_thrust
+    );
+    assert!(
+        (thrust_after - thrust_before).abs() < 0.05 * thrust_before,
+        "Thrust not recovered after the maneuver: {} vs {}", thrust_after, thrust_before
+    );
+
+    let max_wing_angle_change = max_wing_angle_change_rate * time_step;
+
+    for i in 1..nr_time_steps {
+        let wing_angle_change = (wing_angles[i] - wing_angles[i - 1]).abs();
+
+        assert!(
+            wing_angle_change <= max_wing_angle_change + 1e-9,
+            "Wing angle changes too fast at time {}: {}", heading_schedule[i].0, wing_angle_change
+        );
+    }
+
+    let total_wing_angle_change = (wing_angles[nr_time_steps - 1] - wing_angles[0]).abs();
+
+    assert!(
+        total_wing_angle_change > 10.0 * max_wing_angle_change,
+        "Wing angle does not sweep during the maneuver: {}", total_wing_angle_change
+    );
+}
@@ -0,0 +1,137 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the maximum power point tracking in the controller of the complete sail model
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::line_force_model::input_power::{InputPowerModel, InputPowerData};
+use crate::wind::environment::WindEnvironment;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+/// A single rotor sail, where the internal state is the number of revolutions per second. The 
+/// set point data is used as a constant internal state, and the tracking settings, if given, are 
+/// added to the set points.
+fn rotor_sail_model(
+    revolutions_per_second: Float, 
+    tracking: Option<serde_json::Value>
+) -> CompleteSailModel {
+    let diameter = 1.0;
+    let span = 5.0;
+
+    let mut line_force_model_builder = LineForceModelBuilder::new(10);
+
+    line_force_model_builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector::from([0.0, 0.0, 0.0]),
+                SpatialVector::from([0.0, 0.0, span]),
+            ],
+            chord_vectors: vec![
+                SpatialVector::from([diameter, 0.0, 0.0]),
+                SpatialVector::from([diameter, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::RotatingCylinder(
+                RotatingCylinder {
+                    spin_ratio_data: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+                    cd_data: vec![0.554, 0.674, 1.497, 2.877, 3.556, 3.816],
+                    cl_data: vec![0.0, 1.889, 4.638, 6.794, 7.680, 7.950],
+                    ..Default::default()
+                }
+            ),
+            non_zero_circulation_at_ends: [false, false],
+            input_power_model: InputPowerModel::InterpolateFromInternalStateOnly(
+                InputPowerData {
+                    section_models_internal_state_data: vec![0.0, 10.0],
+                    input_power_coefficient_data: vec![0.0, 300.0],
+                }
+            ),
+            ..Default::default()
+        }
+    );
+
+    let simulation_builder = SimulationBuilder {
+        line_force_model: line_force_model_builder,
+        simulation_settings: SimulationSettings::default()
+    };
+
+    let mut set_point = serde_json::json!({
+        "apparent_wind_directions_data": [-3.2, 3.2],
+        "section_model_internal_state_data": [revolutions_per_second, revolutions_per_second]
+    });
+
+    if let Some(tracking) = tracking {
+        set_point["max_power_point_tracking"] = tracking;
+    }
+
+    let setup = serde_json::json!({
+        "lifting_line_simulation": simulation_builder,
+        "wind_environment": WindEnvironment::default(),
+        "controller": {
+            "set_points": [set_point]
+        }
+    });
+
+    CompleteSailModel::new_from_string(&setup.to_string()).unwrap()
+}
+
+fn net_power(model: &CompleteSailModel, result: &SimulationResult, ship_velocity: Float) -> Float {
+    model.delivered_power(result, ship_velocity) - result.input_power_sum()
+}
+
+#[test]
+/// The tracking starts far below the optimal rotational speed, and must find the speed with the 
+/// maximum net power, which is found with a sweep over constant speeds as reference.
+fn tracking_converges_to_maximum_net_power() {
+    let wind_condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let sweep: Vec<(Float, Float)> = (0..=40).map(|i| {
+        let revolutions_per_second = 0.5 * i as Float;
+
+        let mut model = rotor_sail_model(revolutions_per_second, None);
+
+        let result = model.simulate_steady_state_condition(wind_condition, ship_velocity, 1.0);
+
+        (revolutions_per_second, net_power(&model, &result, ship_velocity))
+    }).collect();
+
+    let (optimal_revolutions_per_second, max_net_power) = sweep.iter().cloned().fold(
+        (0.0, Float::NEG_INFINITY), |best, current| if current.1 > best.1 { current } else { best }
+    );
+
+    dbg!(optimal_revolutions_per_second, max_net_power);
+
+    let mut model = rotor_sail_model(
+        1.0, 
+        Some(serde_json::json!({
+            "initial_step": 1.0,
+            "min_step": 0.05,
+            "min_value": 0.0,
+            "max_value": 20.0
+        }))
+    );
+
+    let result = model.simulate_condition(wind_condition, ship_velocity, 1.0, 1.0, 50);
+
+    let revolutions_per_second = model.lifting_line_simulation.line_force_model
+        .section_models_internal_state()[0];
+
+    let tracking_net_power = net_power(&model, &result, ship_velocity);
+
+    dbg!(revolutions_per_second, tracking_net_power);
+
+    assert!((revolutions_per_second - optimal_revolutions_per_second).abs() < 0.5);
+    assert!(tracking_net_power > 0.99 * max_net_power);
+}
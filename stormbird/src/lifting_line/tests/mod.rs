@@ -13,3 +13,39 @@ mod compare_models;
 mod no_self_induced_velocity;
 mod coordinate_systems;
 mod wing_sails_speed_test;
+mod batch_stats;
+mod resolution_study;
+mod thrust_interaction;
+mod angle_of_attack_definitions;
+mod equivalent_sail;
+mod yaw_stiffness;
+mod zero_thrust_angles;
+mod side_force;
+mod furling;
+mod gust_load_factor;
+mod fixed_wing_angle;
+mod input_power_coefficient;
+mod downwash;
+mod maneuver;
+mod span_resampling;
+mod required_area;
+mod frequency_response;
+mod logging;
+mod tracing_spans;
+mod best_heading;
+mod savings;
+mod nonconvergence;
+mod root_bending_moment;
+mod independent_batch;
+mod speed_ratio;
+mod density_override;
+mod fatigue_loads;
+mod wind_time_series;
+mod power_budget;
+mod polar;
+mod equilibrium_speed;
+mod thrust_direction;
+mod independent_conditions;
+mod max_power_point_tracking;
+mod force_ratio_control;
+mod columnar_output;
@@ -0,0 +1,132 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the policies for time steps that do not converge
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation::{Simulation, NonConvergencePolicy};
+use crate::lifting_line::solvers::simple_iterative::SimpleIterative;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+    QuasiSteadySettings,
+};
+
+use super::test_setup::RectangularWing;
+
+fn simulation_with_policy(on_nonconvergence: NonConvergencePolicy) -> Simulation {
+    SimulationBuilder::new(
+        RectangularWing::default().build(),
+        SimulationSettings::QuasiSteady(QuasiSteadySettings {
+            on_nonconvergence,
+            ..Default::default()
+        })
+    ).build()
+}
+
+/// Runs one converged step, and then a step where the solver is only allowed a single iteration, 
+/// which makes it non-converged.
+fn converged_and_failing_step(sim: &mut Simulation) -> (SimulationResult, SimulationResult) {
+    let nr_freestream_points = sim.get_freestream_velocity_points().len();
+
+    let converged_result = sim.do_step(
+        0.0, 1.0, &vec![SpatialVector::from([1.0, 0.0, 0.0]); nr_freestream_points]
+    );
+
+    sim.solver = Solver::SimpleIterative(SimpleIterative {
+        max_iterations_per_time_step: 1,
+        ..Default::default()
+    });
+
+    let failing_result = sim.do_step(
+        1.0, 1.0, &vec![SpatialVector::from([2.0, 0.0, 0.0]); nr_freestream_points]
+    );
+
+    (converged_result, failing_result)
+}
+
+#[test]
+fn hold_last_reports_previous_forces() {
+    let mut sim = simulation_with_policy(NonConvergencePolicy::HoldLast);
+
+    let (converged_result, failing_result) = converged_and_failing_step(&mut sim);
+
+    assert!(!converged_result.non_converged);
+    assert!(failing_result.non_converged);
+
+    assert_eq!(failing_result.time, 1.0);
+    assert_eq!(
+        failing_result.integrated_forces_sum(), 
+        converged_result.integrated_forces_sum()
+    );
+}
+
+#[test]
+fn zero_and_ignore_policies() {
+    let mut sim_zero = simulation_with_policy(NonConvergencePolicy::Zero);
+    let mut sim_ignore = simulation_with_policy(NonConvergencePolicy::Ignore);
+
+    let (_, zero_result) = converged_and_failing_step(&mut sim_zero);
+    let (converged_result, ignore_result) = converged_and_failing_step(&mut sim_ignore);
+
+    assert!(zero_result.non_converged && ignore_result.non_converged);
+
+    assert_eq!(zero_result.integrated_forces_sum(), SpatialVector::default());
+    assert!(
+        ignore_result.integrated_forces_sum().length() > 
+        converged_result.integrated_forces_sum().length()
+    );
+}
+
+#[test]
+fn error_policy_returns_error() {
+    let mut sim = simulation_with_policy(NonConvergencePolicy::Error);
+
+    let nr_freestream_points = sim.get_freestream_velocity_points().len();
+
+    sim.solver = Solver::SimpleIterative(SimpleIterative {
+        max_iterations_per_time_step: 1,
+        ..Default::default()
+    });
+
+    let result = sim.try_do_step(
+        0.0, 1.0, &vec![SpatialVector::from([1.0, 0.0, 0.0]); nr_freestream_points]
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+/// Tests that a step where the solver converges on the last allowed iteration is not marked as 
+/// non-converged, as the convergence is taken from the solver and not from the iteration count.
+fn convergence_on_last_iteration() {
+    let mut reference_sim = simulation_with_policy(NonConvergencePolicy::Ignore);
+
+    reference_sim.solver = Solver::SimpleIterative(SimpleIterative {
+        max_iterations_per_time_step: 1000,
+        ..Default::default()
+    });
+
+    let mut limited_sim = reference_sim.clone();
+
+    let freestream_velocity = vec![
+        SpatialVector::from([1.0, 0.0, 0.0]); 
+        reference_sim.get_freestream_velocity_points().len()
+    ];
+
+    let reference_result = reference_sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    assert!(!reference_result.non_converged);
+    assert!(reference_result.iterations > 1);
+
+    limited_sim.solver = Solver::SimpleIterative(SimpleIterative {
+        max_iterations_per_time_step: reference_result.iterations,
+        ..Default::default()
+    });
+
+    let limited_result = limited_sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    assert_eq!(limited_result.iterations, reference_result.iterations);
+    assert!(!limited_result.non_converged);
+}
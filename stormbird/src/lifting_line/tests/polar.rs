@@ -0,0 +1,140 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the sweep over wind velocities and directions in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+fn wing_sail_model() -> CompleteSailModel {
+    RectangularWingSailModel::default().build()
+}
+
+#[test]
+fn polar_matches_single_conditions() {
+    let model = wing_sail_model();
+    let mut reference_model = model.clone();
+
+    let wind_velocities = [6.0, 10.0];
+    let wind_directions: Vec<Float> = [60.0, 75.0, 90.0, 120.0].iter()
+        .map(|direction: &Float| direction.to_radians())
+        .collect();
+
+    let ship_velocity = 5.0;
+
+    let polar = model.simulate_polar(&wind_velocities, &wind_directions, ship_velocity, 1.0);
+
+    assert_eq!(polar.thrust.len(), wind_velocities.len());
+    assert_eq!(polar.thrust[0].len(), wind_directions.len());
+
+    let net_power = polar.net_power();
+
+    for (velocity_index, &velocity) in wind_velocities.iter().enumerate() {
+        for (direction_index, &direction) in wind_directions.iter().enumerate() {
+            let result = reference_model.simulate_steady_state_condition(
+                WindCondition { velocity, direction_coming_from: direction },
+                ship_velocity,
+                1.0
+            );
+
+            let force = result.integrated_forces_sum();
+
+            let thrust = polar.thrust[velocity_index][direction_index];
+            let side_force = polar.side_force[velocity_index][direction_index];
+
+            assert!(thrust > 0.0);
+            assert!((thrust + force[0]).abs() < 1e-3 * force.length());
+            assert!((side_force - force[1]).abs() < 1e-3 * force.length());
+
+            assert_eq!(
+                net_power[velocity_index][direction_index],
+                polar.delivered_power[velocity_index][direction_index] - 
+                polar.input_power[velocity_index][direction_index]
+            );
+        }
+    }
+}
+
+#[test]
+/// With a thrust direction that is not along the x-axis, the side force should still be the force
+/// perpendicular to the thrust direction, so that the thrust and side force together make up the
+/// horizontal force.
+fn polar_side_force_is_perpendicular_to_thrust_direction() {
+    let mut setup = RectangularWingSailModel::default().setup();
+
+    setup["thrust_direction"] = serde_json::json!([0.0, 1.0, 0.0]);
+
+    let model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+    let mut reference_model = model.clone();
+
+    let wind_direction = Float::from(90.0).to_radians();
+
+    let polar = model.simulate_polar(&[8.0], &[wind_direction], 0.0, 1.0);
+
+    let result = reference_model.simulate_steady_state_condition(
+        WindCondition { velocity: 8.0, direction_coming_from: wind_direction },
+        0.0,
+        1.0
+    );
+
+    let force = result.integrated_forces_sum();
+
+    let thrust = polar.thrust[0][0];
+    let side_force = polar.side_force[0][0];
+
+    let horizontal_force = (force[0].powi(2) + force[1].powi(2)).sqrt();
+
+    dbg!(thrust, side_force, force);
+
+    assert!((thrust - force[1]).abs() < 1e-3 * force.length());
+    assert!((side_force.abs() - force[0].abs()).abs() < 1e-3 * force.length());
+    assert!(
+        ((thrust.powi(2) + side_force.powi(2)).sqrt() - horizontal_force).abs() < 
+        1e-3 * force.length()
+    );
+}
+
+#[test]
+/// Tests that the polar, which runs on several threads when the `parallel` feature is enabled, is
+/// identical to simulating the conditions one by one in order.
+fn polar_equals_serial_simulation() {
+    let model = wing_sail_model();
+
+    let wind_velocities = [6.0, 10.0, 14.0];
+    let wind_directions: Vec<Float> = [45.0, 90.0, 135.0, 180.0].iter()
+        .map(|direction: &Float| direction.to_radians())
+        .collect();
+
+    let ship_velocity = 5.0;
+
+    let polar = model.simulate_polar(&wind_velocities, &wind_directions, ship_velocity, 1.0);
+
+    for (velocity_index, &velocity) in wind_velocities.iter().enumerate() {
+        for (direction_index, &direction) in wind_directions.iter().enumerate() {
+            let result = model.simulate_polar_condition(
+                WindCondition { velocity, direction_coming_from: direction },
+                ship_velocity,
+                1.0
+            );
+
+            assert_eq!(polar.thrust[velocity_index][direction_index], model.thrust(&result));
+            assert_eq!(
+                polar.side_force[velocity_index][direction_index], 
+                model.side_force(&result)
+            );
+            assert_eq!(
+                polar.input_power[velocity_index][direction_index], 
+                result.input_power_sum()
+            );
+            assert_eq!(
+                polar.delivered_power[velocity_index][direction_index],
+                model.delivered_power(&result, ship_velocity)
+            );
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the allocation of controller loading between sails within an input power budget
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::line_force_model::input_power::{InputPowerModel, InputPowerData};
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+/// Two rotor sails, where the second sail needs three times as much input power for the same 
+/// rotational speed, and is therefore less efficient.
+fn rotor_sail_model() -> CompleteSailModel {
+    let diameter = 1.0;
+    let span = 5.0;
+
+    let mut line_force_model_builder = LineForceModelBuilder::new(10);
+
+    for (x_position, power_coefficient) in [(-10.0, 300.0), (10.0, 900.0)] {
+        line_force_model_builder.add_wing(
+            WingBuilder {
+                section_points: vec![
+                    SpatialVector::from([x_position, 0.0, 0.0]),
+                    SpatialVector::from([x_position, 0.0, span]),
+                ],
+                chord_vectors: vec![
+                    SpatialVector::from([diameter, 0.0, 0.0]),
+                    SpatialVector::from([diameter, 0.0, 0.0]),
+                ],
+                section_model: SectionModel::RotatingCylinder(
+                    RotatingCylinder {
+                        spin_ratio_data: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+                        cd_data: vec![0.554, 0.674, 1.497, 2.877, 3.556, 3.816],
+                        cl_data: vec![0.0, 1.889, 4.638, 6.794, 7.680, 7.950],
+                        ..Default::default()
+                    }
+                ),
+                non_zero_circulation_at_ends: [false, false],
+                input_power_model: InputPowerModel::InterpolateFromInternalStateOnly(
+                    InputPowerData {
+                        section_models_internal_state_data: vec![0.0, 10.0],
+                        input_power_coefficient_data: vec![0.0, power_coefficient],
+                    }
+                ),
+                ..Default::default()
+            }
+        );
+    }
+
+    let revolutions_per_second = 6.0;
+
+    let set_point = serde_json::json!({
+        "apparent_wind_directions_data": [-3.2, 3.2],
+        "section_model_internal_state_data": [revolutions_per_second, revolutions_per_second]
+    });
+
+    let mut setup = RectangularWingSailModel::default().setup();
+
+    setup["lifting_line_simulation"]["line_force_model"] = serde_json::json!(
+        line_force_model_builder
+    );
+    setup["controller"]["set_points"] = serde_json::json!([set_point.clone(), set_point]);
+
+    CompleteSailModel::new_from_string(&setup.to_string()).unwrap()
+}
+
+#[test]
+fn power_budget_prioritizes_efficient_sails() {
+    let mut model = rotor_sail_model();
+
+    let wind_condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let full_result = model.simulate_steady_state_condition_with_sail_loadings(
+        wind_condition, ship_velocity, &[1.0, 1.0]
+    );
+
+    dbg!(full_result.integrated_forces_sum(), &full_result.input_power);
+
+    let unconstrained_power = full_result.input_power_sum();
+    let power_budget = 0.5 * unconstrained_power;
+
+    let loadings = model.optimal_loading_within_power_budget(
+        wind_condition, ship_velocity, power_budget
+    );
+
+    let result = model.simulate_steady_state_condition_with_sail_loadings(
+        wind_condition, ship_velocity, &loadings
+    );
+
+    dbg!(&loadings, result.input_power_sum(), power_budget);
+
+    assert!(result.input_power_sum() <= power_budget);
+    assert!(loadings[0] > loadings[1]);
+    assert!(model.delivered_power(&result, ship_velocity) > 0.0);
+}
@@ -0,0 +1,73 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the sizing of the sail area based on a target thrust
+
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn required_area_scales_with_target_thrust() {
+    let mut model = RectangularWingSailModel::default().build();
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let current_area = model.lifting_line_simulation.line_force_model.total_projected_area();
+
+    let current_thrust = -model.simulate_steady_state_condition(
+        wind_condition, ship_velocity, 1.0
+    ).integrated_forces_sum()[0];
+
+    assert!(current_thrust > 0.0, "No thrust in the design condition: {}", current_thrust);
+
+    let area_current_thrust = model.required_area_for_thrust(
+        wind_condition, ship_velocity, current_thrust
+    );
+
+    let area_single = model.required_area_for_thrust(
+        wind_condition, ship_velocity, 1000.0
+    );
+
+    let area_double = model.required_area_for_thrust(
+        wind_condition, ship_velocity, 2000.0
+    );
+
+    dbg!(current_area, area_current_thrust, area_single, area_double);
+
+    assert!(
+        (area_current_thrust - current_area).abs() < 1e-3 * current_area,
+        "Current thrust should give the current area: {} vs {}", area_current_thrust, current_area
+    );
+
+    assert!(
+        (area_double / area_single - 2.0).abs() < 0.01,
+        "Doubling the target thrust does not double the area: {} vs {}", area_double, area_single
+    );
+}
+
+#[test]
+fn required_area_is_infinite_without_thrust() {
+    let mut model = RectangularWingSailModel {
+        angle_of_attack_set_point: 0.0,
+        ..Default::default()
+    }.build();
+
+    // Head wind, where the sail can only give drag
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: 0.0,
+    };
+
+    let area = model.required_area_for_thrust(wind_condition, 0.0, 1000.0);
+
+    assert!(area.is_infinite());
+}
@@ -0,0 +1,56 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the resolution study functionality
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWing;
+
+#[test]
+fn recommended_nr_sections() {
+    let tolerance = 0.01;
+
+    let builder = SimulationBuilder {
+        line_force_model: RectangularWing {
+            angle_of_attack: Float::from(4.0).to_radians(),
+            ..Default::default()
+        }.build(),
+        simulation_settings: SimulationSettings::default()
+    };
+
+    let freestream_velocity = SpatialVector::from([8.0, 0.0, 0.0]);
+
+    let study = builder.resolution_study(
+        freestream_velocity,
+        &[4, 8, 16, 32, 64],
+        tolerance
+    );
+
+    dbg!(&study);
+
+    let recommended = study.recommended_nr_sections.expect("No recommended resolution found");
+
+    assert!(recommended < 64);
+
+    let refined = builder.resolution_study(
+        freestream_velocity,
+        &[recommended, 2 * recommended, 4 * recommended],
+        tolerance
+    );
+
+    dbg!(&refined);
+
+    for i in 1..refined.lift.len() {
+        let relative_change = (refined.lift[i] - refined.lift[i - 1]).abs() / refined.lift[i];
+
+        assert!(relative_change < tolerance, "Relative change too large: {}", relative_change);
+    }
+}
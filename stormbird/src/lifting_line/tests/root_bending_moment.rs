@@ -0,0 +1,50 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the root bending moment computed from the sectional forces
+
+use std::ops::Range;
+
+use crate::lifting_line::prelude::*;
+
+use stormath::type_aliases::Float;
+
+#[test]
+fn uniform_load_gives_half_span_lever_arm() {
+    let nr_strips = 20;
+    let span = 30.0;
+    let root_height = 4.0;
+    let side_force_per_strip = 150.0;
+
+    let strip_length = span / nr_strips as Float;
+
+    let ctrl_points: Vec<SpatialVector> = (0..nr_strips).map(
+        |i| SpatialVector::from([0.0, 0.0, root_height + (i as Float + 0.5) * strip_length])
+    ).collect();
+
+    let mut result = SimulationResult {
+        ctrl_points,
+        wing_indices: vec![Range { start: 0, end: nr_strips }],
+        span_line_lengths: vec![strip_length; nr_strips],
+        ..Default::default()
+    };
+
+    result.sectional_forces.total = vec![
+        SpatialVector::from([0.0, side_force_per_strip, 0.0]); nr_strips
+    ];
+
+    let root_point = result.wing_root_point(0);
+
+    assert!(root_point.distance(SpatialVector::from([0.0, 0.0, root_height])) < 1e-12);
+
+    let moment = result.root_bending_moment(0);
+
+    let total_side_force = side_force_per_strip * nr_strips as Float;
+    let expected_moment = total_side_force * 0.5 * span;
+
+    dbg!(moment, expected_moment);
+
+    assert!((moment[0] + expected_moment).abs() < 1e-9 * expected_moment);
+    assert!(moment[1].abs() < 1e-9 && moment[2].abs() < 1e-9);
+}
@@ -0,0 +1,32 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the estimated fuel and emission savings
+
+use crate::lifting_line::complete_sail_model::savings::Savings;
+
+#[test]
+fn savings_scale_with_net_energy() {
+    let specific_fuel_consumption = 180.0;
+    let emission_factor = 3.206;
+
+    let net_energy = 5000.0;
+
+    let savings = Savings::from_net_energy(net_energy, specific_fuel_consumption, emission_factor);
+    let savings_double = Savings::from_net_energy(
+        2.0 * net_energy, specific_fuel_consumption, emission_factor
+    );
+    let savings_zero = Savings::from_net_energy(0.0, specific_fuel_consumption, emission_factor);
+
+    dbg!(savings, savings_double);
+
+    assert!((savings.fuel_mass - 900.0).abs() < 1e-9);
+    assert!((savings.co2_mass - 900.0 * emission_factor).abs() < 1e-9);
+
+    assert!((savings_double.fuel_mass - 2.0 * savings.fuel_mass).abs() < 1e-9);
+    assert!((savings_double.co2_mass - 2.0 * savings.co2_mass).abs() < 1e-9);
+
+    assert_eq!(savings_zero.fuel_mass, 0.0);
+    assert_eq!(savings_zero.co2_mass, 0.0);
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the side force and center of effort from a simulation result
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWing;
+
+#[test]
+/// Sail on a ship sailing close-hauled, with the apparent wind 30 degrees off the bow. The lift is
+/// then mostly directed to the side of the ship.
+fn side_force_and_center_of_effort_close_hauled() {
+    let aspect_ratio = 5.0;
+
+    let line_force_model = RectangularWing {
+        aspect_ratio,
+        angle_of_attack: Float::from(8.0).to_radians(),
+        ..Default::default()
+    }.build();
+
+    let mut sim = SimulationBuilder {
+        line_force_model,
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let freestream_velocity = SpatialVector::from([8.0, 0.0, 0.0]);
+
+    let result = sim.do_step(
+        0.0, 1.0, &vec![freestream_velocity; sim.get_freestream_velocity_points().len()]
+    );
+
+    let total_force = result.integrated_forces_sum();
+
+    // The ship moves against the wind, rotated so that the lift contributes to the thrust
+    let apparent_wind_angle = Float::from(30.0).to_radians();
+    let ship_forward_axis = SpatialVector::from([
+        -apparent_wind_angle.cos(),
+        total_force[1].signum() * apparent_wind_angle.sin(),
+        0.0
+    ]);
+
+    let (side_force, center_of_effort) = result.side_force_and_coe(ship_forward_axis);
+
+    let thrust = total_force.dot(ship_forward_axis);
+
+    dbg!(total_force, thrust, side_force, center_of_effort);
+
+    assert!(thrust > 0.0);
+    assert!(side_force > thrust.abs(), "Side force is not the dominant component");
+    assert!((side_force.powi(2) + thrust.powi(2) - total_force.length_squared()).abs() < 1e-6);
+
+    let relative_height = center_of_effort[2] / aspect_ratio;
+
+    assert!(
+        relative_height > 0.35 && relative_height < 0.65, 
+        "Unexpected height of the center of effort: {}", relative_height
+    );
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the resampling of the sectional forces to arbitrary spanwise stations
+
+use stormath::type_aliases::Float;
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+
+use super::test_setup::RectangularWing;
+
+#[test]
+fn resampled_sectional_force() {
+    let nr_strips = 32;
+
+    let line_force_model = RectangularWing {
+        aspect_ratio: 5.0,
+        angle_of_attack: Float::from(4.0).to_radians(),
+        nr_strips,
+        ..Default::default()
+    }.build();
+
+    let mut sim = SimulationBuilder {
+        line_force_model,
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let velocity = SpatialVector::from([1.2, 0.0, 0.0]);
+    let freestream_velocity = vec![velocity; sim.get_freestream_velocity_points().len()];
+
+    let result = sim.do_step(0.0, 1.0, &freestream_velocity);
+
+    let strip_length = result.span_line_lengths[0];
+
+    let strip_center_fractions: Vec<Float> = (0..nr_strips).map(
+        |i| (i as Float + 0.5) / nr_strips as Float
+    ).collect();
+
+    let forces_at_centers = result.sectional_force_at_span_fractions(0, &strip_center_fractions);
+
+    for (i, force_at_center) in forces_at_centers.iter().enumerate() {
+        let strip_force = *force_at_center * strip_length;
+
+        assert!(
+            (strip_force - result.sectional_forces.total[i]).length() < 1e-9,
+            "Strip force not reproduced at strip {}", i
+        );
+    }
+
+    let nr_stations = 1001;
+    let station_fractions: Vec<Float> = (0..nr_stations).map(
+        |i| i as Float / (nr_stations - 1) as Float
+    ).collect();
+
+    let resampled_forces = result.sectional_force_at_span_fractions(0, &station_fractions);
+
+    let span: Float = result.span_line_lengths.iter().sum();
+    let station_spacing = span / (nr_stations - 1) as Float;
+
+    let mut integrated_force = SpatialVector::default();
+
+    for i in 1..nr_stations {
+        integrated_force += 0.5 * station_spacing * (resampled_forces[i] + resampled_forces[i - 1]);
+    }
+
+    let total_force = result.integrated_forces[0].total;
+
+    let relative_error = (integrated_force - total_force).length() / total_force.length();
+
+    dbg!(integrated_force, total_force, relative_error);
+
+    assert!(relative_error < 0.02, "Integrated resampled force differs: {}", relative_error);
+}
@@ -0,0 +1,51 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the ratio between the apparent and the true wind speed at the sails
+
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+use stormath::consts::PI;
+
+use super::test_setup::RectangularWingSailModel;
+
+#[test]
+fn apparent_to_true_speed_ratio_follows_wind_triangle() {
+    let model = RectangularWingSailModel {
+        angle_of_attack_set_point: 0.0,
+        ..Default::default()
+    }.build();
+
+    let wind_velocity = 10.0;
+    let ship_velocity = 4.0;
+
+    let headwind = WindCondition {
+        velocity: wind_velocity,
+        direction_coming_from: 0.0,
+    };
+
+    let tailwind = WindCondition {
+        velocity: wind_velocity,
+        direction_coming_from: PI,
+    };
+
+    let headwind_ratio = model.apparent_to_true_speed_ratio(headwind, ship_velocity);
+    let tailwind_ratio = model.apparent_to_true_speed_ratio(tailwind, ship_velocity);
+
+    dbg!(&headwind_ratio, &tailwind_ratio);
+
+    assert_eq!(headwind_ratio.len(), model.get_number_of_sails());
+    assert_eq!(tailwind_ratio.len(), model.get_number_of_sails());
+
+    let headwind_expected = (wind_velocity + ship_velocity) / wind_velocity;
+    let tailwind_expected = (wind_velocity - ship_velocity) / wind_velocity;
+
+    let allowable_error: Float = 1e-6;
+
+    assert!(headwind_ratio[0] > 1.0);
+    assert!(tailwind_ratio[0] < 1.0);
+    assert!((headwind_ratio[0] - headwind_expected).abs() < allowable_error);
+    assert!((tailwind_ratio[0] - tailwind_expected).abs() < allowable_error);
+}
@@ -3,6 +3,11 @@
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
 use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings
+};
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
 
 use stormath::type_aliases::Float;
 
@@ -74,4 +79,73 @@ impl RectangularWing {
 
         line_force_model_builder
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+/// Struct for setting up a complete sail model with a single [RectangularWing], where the 
+/// controller keeps a constant angle of attack with the sign following the apparent wind direction
+pub struct RectangularWingSailModel {
+    pub wing: RectangularWing,
+    pub angle_of_attack_set_point: Float,
+    pub wind_environment: serde_json::Value,
+}
+
+impl Default for RectangularWingSailModel {
+    fn default() -> Self {
+        Self {
+            wing: RectangularWing::default(),
+            angle_of_attack_set_point: Float::from(8.0).to_radians(),
+            wind_environment: serde_json::json!({}),
+        }
+    }
+}
+
+impl RectangularWingSailModel {
+    /// Returns the JSON setup for the model, which can be modified before the model is built
+    pub fn setup(&self) -> serde_json::Value {
+        let simulation_builder = SimulationBuilder {
+            line_force_model: self.wing.build(),
+            simulation_settings: SimulationSettings::default()
+        };
+
+        let angle_of_attack_set_point = self.angle_of_attack_set_point;
+
+        serde_json::json!({
+            "lifting_line_simulation": simulation_builder,
+            "wind_environment": self.wind_environment,
+            "controller": {
+                "set_points": [
+                    {
+                        "apparent_wind_directions_data": [-3.2, -0.1, 0.1, 3.2],
+                        "angle_of_attack_data": [
+                            -angle_of_attack_set_point,
+                            -angle_of_attack_set_point,
+                            angle_of_attack_set_point,
+                            angle_of_attack_set_point
+                        ]
+                    }
+                ]
+            }
+        })
+    }
+
+    /// Returns the JSON setup for the model with a controller that keeps the local wing angles 
+    /// fixed, so that the angle of attack is given by the wing geometry and the wind direction
+    pub fn setup_with_fixed_wing_angles(&self) -> serde_json::Value {
+        let mut setup = self.setup();
+
+        setup["controller"] = serde_json::json!({
+            "set_points": [
+                {
+                    "apparent_wind_directions_data": [-3.2, 3.2]
+                }
+            ]
+        });
+
+        setup
+    }
+
+    pub fn build(&self) -> CompleteSailModel {
+        CompleteSailModel::new_from_string(&self.setup().to_string()).unwrap()
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the thrust direction in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+use stormath::spatial_vector::SpatialVector;
+
+use super::test_setup::RectangularWingSailModel;
+
+fn wing_sail_model(thrust_direction: Option<[Float; 3]>) -> CompleteSailModel {
+    let mut setup = RectangularWingSailModel::default().setup();
+
+    if let Some(direction) = thrust_direction {
+        setup["thrust_direction"] = serde_json::json!(direction);
+    }
+
+    CompleteSailModel::new_from_string(&setup.to_string()).unwrap()
+}
+
+#[test]
+fn delivered_power_follows_thrust_direction() {
+    let mut default_model = wing_sail_model(None);
+    let mut side_model = wing_sail_model(Some([0.0, 2.0, 0.0]));
+
+    assert_eq!(default_model.thrust_direction, SpatialVector::from([-1.0, 0.0, 0.0]));
+    assert_eq!(side_model.thrust_direction, SpatialVector::from([0.0, 1.0, 0.0]));
+
+    let wind_condition = WindCondition {
+        velocity: 10.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let default_result = default_model.simulate_steady_state_condition(
+        wind_condition, ship_velocity, 1.0
+    );
+    let side_result = side_model.simulate_steady_state_condition(
+        wind_condition, ship_velocity, 1.0
+    );
+
+    let default_force = default_result.integrated_forces_sum();
+    let side_force = side_result.integrated_forces_sum();
+
+    assert_eq!(
+        default_model.delivered_power(&default_result, ship_velocity), 
+        -default_force[0] * ship_velocity
+    );
+    assert_eq!(
+        side_model.delivered_power(&side_result, ship_velocity), 
+        side_force[1] * ship_velocity
+    );
+}
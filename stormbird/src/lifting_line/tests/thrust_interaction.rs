@@ -0,0 +1,68 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the thrust interaction factor in the complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+fn complete_sail_model_setup_string(thrust_interaction_factor: Option<Float>) -> String {
+    let mut setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            angle_of_attack: Float::from(10.0).to_radians(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }.setup_with_fixed_wing_angles();
+
+    if let Some(factor) = thrust_interaction_factor {
+        setup["thrust_interaction_factor"] = serde_json::json!(factor);
+    }
+
+    setup.to_string()
+}
+
+#[test]
+fn thrust_interaction_factor_scales_delivered_power() {
+    let mut model_default = CompleteSailModel::new_from_string(
+        &complete_sail_model_setup_string(None)
+    ).unwrap();
+
+    let mut model_interaction = CompleteSailModel::new_from_string(
+        &complete_sail_model_setup_string(Some(0.9))
+    ).unwrap();
+
+    assert_eq!(model_default.thrust_interaction_factor, 1.0);
+
+    let wind_condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: Float::from(90.0).to_radians(),
+    };
+
+    let ship_velocity = 5.0;
+
+    let result_default = model_default.simulate_steady_state_condition(
+        wind_condition, ship_velocity, 1.0
+    );
+
+    let result_interaction = model_interaction.simulate_steady_state_condition(
+        wind_condition, ship_velocity, 1.0
+    );
+
+    let force_default = result_default.integrated_forces_sum();
+    let force_interaction = result_interaction.integrated_forces_sum();
+
+    let power_default = model_default.delivered_power(&result_default, ship_velocity);
+    let power_interaction = model_interaction.delivered_power(&result_interaction, ship_velocity);
+
+    dbg!(force_default, force_interaction, power_default, power_interaction);
+
+    assert!(power_default.abs() > 0.0);
+    assert!((force_default - force_interaction).length() < 1e-9 * force_default.length());
+    assert!((power_interaction - 0.9 * power_default).abs() < 1e-9 * power_default.abs());
+}
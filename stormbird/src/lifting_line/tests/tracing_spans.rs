@@ -0,0 +1,129 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests of the tracing spans and events emitted by the simulation, when the `tracing` feature is 
+//! active.
+
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use tracing::{span, Event, Metadata, Subscriber};
+use tracing::field::{Field, Visit};
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::simulation_builder::{
+    SimulationBuilder,
+    SimulationSettings,
+};
+
+use super::test_setup::RectangularWing;
+
+#[derive(Debug, Clone)]
+/// An event, with the names of the fields and the names of the spans it was emitted in
+struct CapturedEvent {
+    spans: Vec<&'static str>,
+    fields: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct CapturedData {
+    span_names: Vec<&'static str>,
+    entered_spans: Vec<u64>,
+    events: Vec<CapturedEvent>,
+}
+
+/// Subscriber that stores every event together with the spans that are entered when the event is
+/// emitted
+struct CapturingSubscriber {
+    data: Arc<Mutex<CapturedData>>,
+}
+
+#[derive(Default)]
+struct FieldNames(Vec<String>);
+
+impl Visit for FieldNames {
+    fn record_debug(&mut self, field: &Field, _value: &dyn std::fmt::Debug) {
+        self.0.push(field.name().to_string());
+    }
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut data = self.data.lock().unwrap();
+
+        data.span_names.push(attributes.metadata().name());
+
+        span::Id::from_u64(data.span_names.len() as u64)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut field_names = FieldNames::default();
+
+        event.record(&mut field_names);
+
+        let mut data = self.data.lock().unwrap();
+
+        let spans = data.entered_spans.iter()
+            .map(|id| data.span_names[*id as usize - 1])
+            .collect();
+
+        data.events.push(CapturedEvent { spans, fields: field_names.0 });
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.data.lock().unwrap().entered_spans.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &span::Id) {
+        self.data.lock().unwrap().entered_spans.pop();
+    }
+}
+
+#[test]
+fn solver_residual_event_is_emitted_in_step_span() {
+    let mut sim = SimulationBuilder {
+        line_force_model: RectangularWing::default().build(),
+        simulation_settings: SimulationSettings::default()
+    }.build();
+
+    let freestream_velocity = vec![
+        SpatialVector::from([1.0, 0.0, 0.0]); 
+        sim.get_freestream_velocity_points().len()
+    ];
+
+    let data = Arc::new(Mutex::new(CapturedData::default()));
+
+    let subscriber = CapturingSubscriber { data: data.clone() };
+
+    tracing::subscriber::with_default(subscriber, || {
+        sim.do_step(0.0, 1.0, &freestream_velocity);
+    });
+
+    let data = data.lock().unwrap();
+
+    dbg!(&data.span_names, &data.events);
+
+    assert!(data.span_names.contains(&"do_step"));
+    assert!(
+        data.span_names.iter().any(|name| name.ends_with("_solve")),
+        "No solver span"
+    );
+
+    assert!(
+        data.events.iter().any(
+            |event| event.spans.contains(&"do_step") && 
+                event.fields.iter().any(|field| field == "residual")
+        ),
+        "No residual event emitted in the time step"
+    );
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for time steps where the wind condition is taken from the wind environment
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::environment::WindEnvironment;
+use crate::wind::wind_condition::WindCondition;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::RectangularWingSailModel;
+
+fn model_with_wind_environment(wind_environment: serde_json::Value) -> CompleteSailModel {
+    RectangularWingSailModel {
+        wind_environment,
+        ..Default::default()
+    }.build()
+}
+
+#[test]
+fn wind_condition_follows_time_series() {
+    let mut model = model_with_wind_environment(serde_json::json!({
+        "wind_condition_time_series": {
+            "time": [0.0, 2.0],
+            "velocity": [8.0, 12.0],
+            "direction_coming_from": [1.2, 1.6]
+        }
+    }));
+
+    let mut reference_model = model.clone();
+
+    let default_condition = WindCondition {
+        velocity: 5.0,
+        direction_coming_from: 0.5,
+    };
+
+    let ship_velocity = 5.0;
+    let time_step = 0.5;
+
+    for time_index in 0..4 {
+        let current_time = time_index as Float * time_step;
+
+        let expected_condition = WindCondition {
+            velocity: 8.0 + 2.0 * current_time,
+            direction_coming_from: 1.2 + 0.2 * current_time,
+        };
+
+        let result = model.do_step_with_environment_wind(
+            current_time, time_step, default_condition, ship_velocity, 1.0
+        );
+
+        let reference_result = reference_model.do_step(
+            current_time, time_step, expected_condition, ship_velocity, 1.0
+        );
+
+        let force = result.integrated_forces_sum();
+        let reference_force = reference_result.integrated_forces_sum();
+
+        assert!((force - reference_force).length() < 1e-6 * reference_force.length());
+    }
+}
+
+#[test]
+fn default_condition_is_used_without_time_series() {
+    let mut model = model_with_wind_environment(
+        serde_json::to_value(WindEnvironment::default()).unwrap()
+    );
+
+    assert!(model.wind_environment.wind_condition_time_series.is_none());
+
+    let mut reference_model = model.clone();
+
+    let condition = WindCondition {
+        velocity: 9.0,
+        direction_coming_from: 1.4,
+    };
+
+    let result = model.do_step_with_environment_wind(0.0, 0.5, condition, 5.0, 1.0);
+    let reference_result = reference_model.do_step(0.0, 0.5, condition, 5.0, 1.0);
+
+    assert_eq!(result.integrated_forces_sum(), reference_result.integrated_forces_sum());
+}
@@ -0,0 +1,43 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the yaw stiffness of a complete sail model
+
+use crate::lifting_line::prelude::*;
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+use crate::wind::wind_condition::WindCondition;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+fn sail_behind_pivot_point_is_weathervane_stable() {
+    // Controller without any set points for the angle of attack, meaning the sail is fixed with 
+    // the chord along the ship
+    let mut setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            cl_zero_angle: 0.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    }.setup_with_fixed_wing_angles();
+
+    // The ship moves in the negative x-direction in the complete sail model, so a positive 
+    // translation places the sail behind the pivot point
+    setup["lifting_line_simulation"]["line_force_model"]["translation"] = 
+        serde_json::json!(SpatialVector::from([20.0, 0.0, 0.0]));
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let head_wind = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: 0.0,
+    };
+
+    let yaw_stiffness = model.yaw_stiffness(head_wind, 0.0, 1.0);
+
+    dbg!(yaw_stiffness);
+
+    assert!(yaw_stiffness < 0.0, "Expected restoring yaw moment, got stiffness {}", yaw_stiffness);
+    assert!(yaw_stiffness.is_finite());
+}
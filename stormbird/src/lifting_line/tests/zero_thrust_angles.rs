@@ -0,0 +1,44 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the no-go zone of a complete sail model
+
+use crate::lifting_line::complete_sail_model::CompleteSailModel;
+
+use stormath::type_aliases::Float;
+
+use super::test_setup::{RectangularWing, RectangularWingSailModel};
+
+#[test]
+fn no_go_zone_is_symmetric_around_head_wind() {
+    // Symmetric set points, where the sign of the angle of attack follows the apparent wind 
+    // direction
+    let angle_of_attack = 0.2;
+
+    let mut setup = RectangularWingSailModel {
+        wing: RectangularWing {
+            cl_zero_angle: 0.0,
+            ..Default::default()
+        },
+        angle_of_attack_set_point: angle_of_attack,
+        ..Default::default()
+    }.setup();
+
+    setup["controller"]["set_points"][0]["apparent_wind_directions_data"] = 
+        serde_json::json!([-3.2, -0.01, 0.01, 3.2]);
+
+    let mut model = CompleteSailModel::new_from_string(&setup.to_string()).unwrap();
+
+    let zero_thrust_angles = model.zero_thrust_angles(8.0);
+
+    dbg!(&zero_thrust_angles);
+
+    assert_eq!(zero_thrust_angles.len(), 1);
+
+    let (lower, upper) = zero_thrust_angles[0].expect("The no-go zone is not bounded");
+
+    assert!(lower < 0.0 && upper > 0.0, "No-go zone does not contain head wind");
+    assert!(upper < Float::from(90.0).to_radians(), "No-go zone is unexpectedly wide");
+    assert!((lower + upper).abs() < 1e-3, "No-go zone is not symmetric");
+}
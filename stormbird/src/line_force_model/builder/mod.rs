@@ -5,7 +5,6 @@
 //! Structures and logic for building a [line force model](crate::line_force_model::LineForceModel).
 
 use serde::{Serialize, Deserialize};
-use serde_json;
 
 pub mod single_wing;
 
@@ -14,6 +13,7 @@ use super::*;
 use single_wing::WingBuilder;
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 
 use corrections::circulation::CirculationCorrectionBuilder;
 
@@ -33,9 +33,19 @@ pub struct LineForceModelBuilder {
     #[serde(default)]
     pub output_coordinate_system: CoordinateSystem,
     #[serde(default)]
+    pub force_integration_scheme: ForceIntegrationScheme,
+    #[serde(default)]
     pub rotation_type: RotationType,
     #[serde(default)]
     pub local_wing_angles: Vec<Float>,
+    /// Optional lean angle for each wing, in radians. The wings are rotated around the lean axis, 
+    /// with the first section point of each wing as the center of rotation. This can be used to 
+    /// model sails where the span axis is tilted from the vertical.
+    #[serde(default)]
+    pub lean_angles: Vec<Float>,
+    /// The axis used when applying the lean angles. 
+    #[serde(default="LineForceModelBuilder::default_lean_axis")]
+    pub lean_axis: SpatialVector,
     #[serde(default)]
     pub rotation: SpatialVector,
     #[serde(default)]
@@ -51,17 +61,22 @@ impl LineForceModelBuilder {
             circulation_correction: Default::default(),
             angle_of_attack_correction: Default::default(),
             output_coordinate_system: CoordinateSystem::Global,
+            force_integration_scheme: ForceIntegrationScheme::default(),
             rotation_type: RotationType::XYZ,
             local_wing_angles: Vec::new(),
+            lean_angles: Vec::new(),
+            lean_axis: Self::default_lean_axis(),
             rotation: SpatialVector::from([0.0, 0.0, 0.0]),
             translation: SpatialVector::from([0.0, 0.0, 0.0]),
         }
     }
 
-    pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
-        let serde_res = serde_json::from_str(setup_string)?;
+    pub fn default_lean_axis() -> SpatialVector {
+        SpatialVector::unit_x()
+    }
 
-        Ok(serde_res)
+    pub fn new_from_string(setup_string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(setup_string)
     }
 
     pub fn add_wing(&mut self, wing_builder: WingBuilder) {
@@ -75,8 +90,16 @@ impl LineForceModelBuilder {
     pub fn build_with_nr_sections(&self, nr_sections: usize) -> LineForceModel {
         let mut line_force_model = LineForceModel::new(self.density);
 
-        for wing_builder in &self.wing_builders {
-            let wing = wing_builder.build(nr_sections);
+        if !self.lean_angles.is_empty() && self.lean_angles.len() != self.wing_builders.len() {
+            panic!("The number of lean angles does not match the number of wings.");
+        }
+
+        for (wing_index, wing_builder) in self.wing_builders.iter().enumerate() {
+            let wing = if !self.lean_angles.is_empty() {
+                wing_builder.leaned(self.lean_angles[wing_index], self.lean_axis).build(nr_sections)
+            } else {
+                wing_builder.build(nr_sections)
+            };
 
             line_force_model.add_wing(&wing);
         }
@@ -89,6 +112,7 @@ impl LineForceModelBuilder {
         line_force_model.angle_of_attack_correction = self.angle_of_attack_correction.clone();
         
         line_force_model.output_coordinate_system = self.output_coordinate_system;
+        line_force_model.force_integration_scheme = self.force_integration_scheme;
 
         if self.local_wing_angles.len() > 0 {
             if self.local_wing_angles.len() != line_force_model.nr_wings() {
@@ -11,6 +11,7 @@ use stormath::{
     type_aliases::Float
 };
 
+use crate::error::Error;
 use crate::section_models::SectionModel;
 use crate::line_force_model::span_line::SpanLine;
 use crate::line_force_model::input_power::InputPowerModel;
@@ -42,6 +43,52 @@ pub struct WingBuilder {
 }
 
 impl WingBuilder {
+    /// Creates a wing builder from points along the leading and trailing edge of the wing, as is
+    /// typically exported from CAD tools. The two lists must contain the same number of points, 
+    /// where each pair of points defines one section.
+    ///
+    /// The chord vectors point from the leading edge to the trailing edge, and the section points 
+    /// are placed at the quarter chord.
+    pub fn from_edge_points(
+        leading_edge_points: &[SpatialVector],
+        trailing_edge_points: &[SpatialVector],
+        section_model: SectionModel,
+        non_zero_circulation_at_ends: [bool; 2],
+    ) -> Result<Self, Error> {
+        if leading_edge_points.len() != trailing_edge_points.len() {
+            return Err(Error::CustomStringError(format!(
+                "The number of leading edge points ({}) does not match the number of trailing \
+                edge points ({})",
+                leading_edge_points.len(), trailing_edge_points.len()
+            )));
+        }
+
+        if leading_edge_points.len() < 2 {
+            return Err(Error::CustomStringError(
+                "At least two leading and trailing edge points are needed to define a wing"
+                    .to_string()
+            ));
+        }
+
+        let chord_vectors: Vec<SpatialVector> = leading_edge_points.iter()
+            .zip(trailing_edge_points.iter())
+            .map(|(leading_edge, trailing_edge)| *trailing_edge - *leading_edge)
+            .collect();
+
+        let section_points = leading_edge_points.iter()
+            .zip(chord_vectors.iter())
+            .map(|(leading_edge, chord_vector)| *leading_edge + 0.25 * *chord_vector)
+            .collect();
+
+        Ok(Self {
+            section_points,
+            chord_vectors,
+            section_model,
+            non_zero_circulation_at_ends,
+            ..Default::default()
+        })
+    }
+
     pub fn span_distance(&self) -> Vec<Float> {
         let mut span_distance: Vec<Float> = Vec::new();
 
@@ -61,6 +108,27 @@ impl WingBuilder {
         span_distance
     }
 
+    /// Returns a copy of the wing builder where the section points and chord vectors are rotated 
+    /// with the input lean angle around the lean axis. The first section point is used as the 
+    /// center of rotation, so that the root of the wing stays in place.
+    pub fn leaned(&self, lean_angle: Float, lean_axis: SpatialVector) -> Self {
+        let root_point = self.section_points[0];
+
+        let section_points = self.section_points.iter().map(
+            |point| root_point + (*point - root_point).rotate_around_axis(lean_angle, lean_axis)
+        ).collect();
+
+        let chord_vectors = self.chord_vectors.iter().map(
+            |chord_vector| chord_vector.rotate_around_axis(lean_angle, lean_axis)
+        ).collect();
+
+        Self {
+            section_points,
+            chord_vectors,
+            ..self.clone()
+        }
+    }
+
     pub fn build(&self, default_nr_sections: usize) -> SingleWing {
         // TODO: add functionality to handle varying foil models across the span!
 
@@ -97,6 +165,7 @@ impl WingBuilder {
             SectionModel::Foil(foil) => SectionModel::Foil(foil.clone()),
             SectionModel::VaryingFoil(foils) => SectionModel::VaryingFoil(foils.clone()),
             SectionModel::RotatingCylinder(cylinder) => SectionModel::RotatingCylinder(cylinder.clone()),
+            SectionModel::CamberedFoilTable(table) => SectionModel::CamberedFoilTable(table.clone()),
             SectionModel::EffectiveWindSensor => SectionModel::EffectiveWindSensor
         };
 
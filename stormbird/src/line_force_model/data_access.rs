@@ -6,6 +6,22 @@
 
 use super::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+/// Geometric metrics describing the planform of a single wing.
+pub struct PlanformMetrics {
+    /// The length of the span line of the wing
+    pub span: Float,
+    /// The projected area divided by the span
+    pub mean_chord: Float,
+    /// The projected area of the wing
+    pub area: Float,
+    /// The span squared divided by the projected area
+    pub aspect_ratio: Float,
+    /// The chord length at the tip divided by the chord length at the root, where the root is the
+    /// start of the wing
+    pub taper_ratio: Float,
+}
+
 impl LineForceModel {
     /// Short hand for querying for the number of wings in the model
     pub fn nr_wings(&self) -> usize {
@@ -55,6 +71,19 @@ impl LineForceModel {
         areas
     }
 
+    /// Returns the chord length of each strip in the model
+    pub fn chord_lengths(&self) -> Vec<Float> {
+        self.chord_lengths.clone()
+    }
+
+    /// Returns the area of each strip in the model, computed as the chord length times the length
+    /// of the span line.
+    pub fn strip_areas(&self) -> Vec<Float> {
+        (0..self.nr_span_lines()).map(
+            |i| self.chord_lengths[i] * self.span_lines_local[i].length()
+        ).collect()
+    }
+
     /// returns the span length of each wing in the model
     pub fn span_lengths(&self) -> Vec<Float> {
         let mut span_length = vec![0.0; self.nr_wings()];
@@ -85,6 +114,36 @@ impl LineForceModel {
         aspect_ratios
     }
 
+    /// Returns the span, mean chord, area, aspect ratio and taper ratio of each wing in the model.
+    ///
+    /// The root and tip chords used for the taper ratio are the chord lengths of the first and
+    /// last strip of each wing.
+    pub fn wing_planform_metrics(&self) -> Vec<PlanformMetrics> {
+        let areas = self.projected_areas();
+        let span_lengths = self.span_lengths();
+        let aspect_ratios = self.aspect_ratios();
+
+        (0..self.nr_wings()).map(|wing_index| {
+            let span = span_lengths[wing_index];
+            let area = areas[wing_index];
+
+            let mean_chord = if span > 0.0 { area / span } else { 0.0 };
+
+            let root_chord = self.chord_lengths[self.wing_indices[wing_index].start];
+            let tip_chord = self.chord_lengths[self.wing_indices[wing_index].end - 1];
+
+            let taper_ratio = if root_chord > 0.0 { tip_chord / root_chord } else { 0.0 };
+
+            PlanformMetrics {
+                span,
+                mean_chord,
+                area,
+                aspect_ratio: aspect_ratios[wing_index],
+                taper_ratio,
+            }
+        }).collect()
+    }
+
     /// Integrates the chord length along the span of all wings in the model to return the total
     /// projected area of the wing.
     pub fn total_projected_area(&self) -> Float {
@@ -116,5 +175,35 @@ impl LineForceModel {
         internal_state
     }
 
-    
-}
\ No newline at end of file
+    /// Returns the current translation and rotation of the model, as given by the rigid body 
+    /// motion.
+    pub fn current_transform(&self) -> (SpatialVector, SpatialVector) {
+        (self.rigid_body_motion.translation, self.rigid_body_motion.rotation)
+    }
+
+    /// Maps a point from the body fixed coordinate system to the global coordinate system, using 
+    /// the current rigid body motion.
+    pub fn body_to_global_point(&self, point: SpatialVector) -> SpatialVector {
+        self.rigid_body_motion.transform_point(point)
+    }
+
+    /// Maps a point from the global coordinate system to the body fixed coordinate system. This is
+    /// the inverse of [LineForceModel::body_to_global_point].
+    pub fn global_to_body_point(&self, point: SpatialVector) -> SpatialVector {
+        self.rigid_body_motion.vector_in_body_fixed_coordinate_system(
+            self.rigid_body_motion.point_relative_to_body_center(point)
+        )
+    }
+
+    /// Maps a vector from the body fixed coordinate system to the global coordinate system. Only 
+    /// the rotation is applied.
+    pub fn body_to_global_vector(&self, vector: SpatialVector) -> SpatialVector {
+        self.rigid_body_motion.transform_vector(vector)
+    }
+
+    /// Maps a vector from the global coordinate system to the body fixed coordinate system. This 
+    /// is the inverse of [LineForceModel::body_to_global_vector].
+    pub fn global_to_body_vector(&self, vector: SpatialVector) -> SpatialVector {
+        self.rigid_body_motion.vector_in_body_fixed_coordinate_system(vector)
+    }
+}
@@ -142,10 +142,36 @@ impl LineForceModel {
             ).collect();
     }
 
+    /// Furls each wing by scaling the span towards the start of the wing, so that the span of
+    /// wing `i` becomes `span_fractions[i]` times the unfurled span. The number of strips is kept 
+    /// constant, while the length of each strip is reduced. A fraction of 1.0 gives the original
+    /// geometry.
+    pub fn set_furled_span_fractions(&mut self, span_fractions: &[Float]) {
+        for (wing_index, wing_indices) in self.wing_indices.iter().enumerate() {
+            let span_fraction = span_fractions[wing_index].clamp(0.0, 1.0);
+
+            let foot_point = self.span_lines_local_unfurled[wing_indices.start].start_point;
+
+            for i in wing_indices.clone() {
+                let unfurled_line = &self.span_lines_local_unfurled[i];
+
+                self.span_lines_local[i] = SpanLine {
+                    start_point: foot_point + 
+                        span_fraction * (unfurled_line.start_point - foot_point),
+                    end_point: foot_point + 
+                        span_fraction * (unfurled_line.end_point - foot_point),
+                };
+            }
+        }
+
+        self.update_calculated_values_after_create();
+    }
+
     pub fn set_section_models_internal_state(&mut self, internal_state: &[Float]) {
         for wing_index in 0..self.nr_wings() {
             match self.section_models[wing_index] {
                 SectionModel::Foil(_) => {}
+                SectionModel::CamberedFoilTable(_) => {}
                 SectionModel::VaryingFoil(ref mut foil) => {
                     foil.current_internal_state = internal_state[wing_index];
                 }
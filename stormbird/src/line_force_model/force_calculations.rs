@@ -20,6 +20,7 @@
 //! - [`sectional_force_input`](LineForceModel::sectional_force_input)
 //! - [`sectional_forces`](LineForceModel::sectional_forces)
 //! - [`sectional_circulatory_forces`](LineForceModel::sectional_circulatory_forces)
+//! - [`sectional_circulatory_forces_span_points`](LineForceModel::sectional_circulatory_forces_span_points)
 //! - [`sectional_drag_forces`](LineForceModel::sectional_drag_forces)
 //! - [`sectional_added_mass_force`](LineForceModel::sectional_added_mass_force)
 //! - [`sectional_gyroscopic_force`](LineForceModel::sectional_gyroscopic_force)
@@ -114,6 +115,8 @@ impl LineForceModel {
                         foil.lift_coefficient(angles_of_attack[index]),
                     SectionModel::VaryingFoil(foil) =>
                         foil.lift_coefficient(angles_of_attack[index]),
+                    SectionModel::CamberedFoilTable(table) =>
+                        table.lift_coefficient(angles_of_attack[index]),
                     SectionModel::RotatingCylinder(cylinder) =>
                         cylinder.lift_coefficient(
                             self.chord_lengths[index], velocity[index].length()
@@ -138,6 +141,8 @@ impl LineForceModel {
                         foil.lift_coefficient_pre_stall_with_stall_drop_off(angles_of_attack[index]),
                     SectionModel::VaryingFoil(foil) =>
                         foil.lift_coefficient_pre_stall_with_stall_drop_off(angles_of_attack[index]),
+                    SectionModel::CamberedFoilTable(table) =>
+                        table.lift_coefficient(angles_of_attack[index]),
                     SectionModel::RotatingCylinder(cylinder) =>
                         cylinder.lift_coefficient(
                             self.chord_lengths[index], velocity[index].length()
@@ -162,6 +167,7 @@ impl LineForceModel {
                     SectionModel::VaryingFoil(foil) =>
                         foil.lift_coefficient_post_stall_with_stall_weight(angles_of_attack[index]),
                     SectionModel::RotatingCylinder(_) => 0.0,
+                    SectionModel::CamberedFoilTable(_) => 0.0,
                     SectionModel::EffectiveWindSensor => 0.0
                 }
             }
@@ -187,6 +193,8 @@ impl LineForceModel {
                         foil.lift_coefficient_linear(angles_of_attack[index]),
                     SectionModel::VaryingFoil(foil) =>
                         foil.lift_coefficient_linear(angles_of_attack[index]),
+                    SectionModel::CamberedFoilTable(table) =>
+                        table.lift_coefficient_linear(angles_of_attack[index]),
                     SectionModel::RotatingCylinder(cylinder) =>
                         cylinder.lift_coefficient(
                             self.chord_lengths[index], velocity[index].length()
@@ -209,6 +217,7 @@ impl LineForceModel {
 
                         foil.cl_initial_slope
                     },
+                    SectionModel::CamberedFoilTable(table) => table.cl_initial_slope(),
                     SectionModel::RotatingCylinder(_) => 0.0,
                     SectionModel::EffectiveWindSensor => 0.0
                 }
@@ -282,6 +291,8 @@ impl LineForceModel {
                         foil.drag_coefficient(angles_of_attack[index]),
                     SectionModel::VaryingFoil(foil) =>
                         foil.drag_coefficient(angles_of_attack[index]),
+                    SectionModel::CamberedFoilTable(table) =>
+                        table.drag_coefficient(angles_of_attack[index]),
                     SectionModel::RotatingCylinder(cylinder) =>
                         cylinder.drag_coefficient(self.chord_lengths[index], velocity[index].length()),
                     SectionModel::EffectiveWindSensor => 0.0
@@ -333,10 +344,16 @@ impl LineForceModel {
      /// Calculates the forces on each line element.
      pub fn sectional_forces(&self, input: &SectionalForcesInput) -> SectionalForces {
         let mut sectional_forces = SectionalForces {
-            circulatory: self.sectional_circulatory_forces(
-                &input.circulation_strength, 
-                &input.velocity
-            ),
+            circulatory: match self.force_integration_scheme {
+                ForceIntegrationScheme::ControlPoints => self.sectional_circulatory_forces(
+                    &input.circulation_strength, 
+                    &input.velocity
+                ),
+                ForceIntegrationScheme::SpanPoints => self.sectional_circulatory_forces_span_points(
+                    &input.circulation_strength, 
+                    &input.velocity
+                ),
+            },
             viscous_lift: self.viscous_lift_forces(&input.angles_of_attack, &input.velocity),
             sectional_drag: self.sectional_drag_forces(&input.angles_of_attack, &input.velocity),
             added_mass: self.sectional_added_mass_force(&input.acceleration),
@@ -370,6 +387,60 @@ impl LineForceModel {
         ).collect()
     }
 
+    /// Calculates the circulatory forces on each line element by evaluating the Kutta-Joukowski 
+    /// theorem at the span points, and then integrating the force per unit span over each line 
+    /// element using the trapezoidal rule.
+    /// 
+    /// The circulation strength and velocity at the span points are linearly interpolated from the
+    /// control point values. At the ends of each wing, the circulation is set to zero, unless the 
+    /// wing is marked as having non-zero circulation at that end.
+    pub fn sectional_circulatory_forces_span_points(
+        &self, 
+        strength: &[Float], 
+        velocity: &[SpatialVector]
+    ) -> Vec<SpatialVector> {
+        let span_lines = match self.output_coordinate_system {
+            CoordinateSystem::Global => &self.span_lines_global,
+            CoordinateSystem::Body => &self.span_lines_local,
+        };
+
+        let mut span_point_strength = self.span_point_values_from_ctrl_point_values(strength, false);
+        let span_point_velocity = self.span_point_values_from_ctrl_point_values(velocity, false);
+
+        for (wing_index, wing_indices) in self.wing_indices.iter().enumerate() {
+            let first_span_point = wing_indices.start + wing_index;
+            let last_span_point = wing_indices.end + wing_index;
+
+            if !self.non_zero_circulation_at_ends[wing_index][0] {
+                span_point_strength[first_span_point] = 0.0;
+            }
+
+            if !self.non_zero_circulation_at_ends[wing_index][1] {
+                span_point_strength[last_span_point] = 0.0;
+            }
+        }
+
+        (0..self.nr_span_lines()).map(
+            |index| {
+                if velocity[index].length() == 0.0 {
+                    return SpatialVector::default();
+                }
+
+                let wing_index = self.wing_index_from_global(index);
+
+                let start_index = index + wing_index;
+                let end_index = start_index + 1;
+
+                let force_per_span_start = span_point_strength[start_index] * span_point_velocity[start_index];
+                let force_per_span_end = span_point_strength[end_index] * span_point_velocity[end_index];
+
+                0.5 * (force_per_span_start + force_per_span_end).cross(
+                    span_lines[index].relative_vector()
+                ) * self.density
+            }
+        ).collect()
+    }
+
     pub fn viscous_lift_forces(&self, angles_of_attack: &[Float], velocity: &[SpatialVector]) -> Vec<SpatialVector> {
         let cl_viscous = self.lift_coefficients_post_stall_with_stall_weight(
             angles_of_attack
@@ -440,7 +511,7 @@ impl LineForceModel {
                 relevant_acceleration -= relevant_acceleration.project(span_lines[index].direction());
 
                 match self.section_models[wing_index] {
-                    SectionModel::Foil(_) | SectionModel::VaryingFoil(_) => {
+                    SectionModel::Foil(_) | SectionModel::VaryingFoil(_) | SectionModel::CamberedFoilTable(_) => {
                         relevant_acceleration -= relevant_acceleration.project(chord_vectors[index]);
                     },
                     _ => {}
@@ -459,6 +530,9 @@ impl LineForceModel {
                         SectionModel::RotatingCylinder(cylinder) => {
                             cylinder.added_mass_coefficient(relevant_acceleration.length())
                         },
+                        SectionModel::CamberedFoilTable(table) => {
+                            table.added_mass_coefficient(relevant_acceleration.length())
+                        },
                         SectionModel::EffectiveWindSensor => 0.0
                     };
                     
@@ -487,7 +561,7 @@ impl LineForceModel {
                 let wing_index = self.wing_index_from_global(index);
 
                 match &self.section_models[wing_index] {
-                    SectionModel::Foil(_) | SectionModel::VaryingFoil(_) | SectionModel::EffectiveWindSensor => SpatialVector::default(),
+                    SectionModel::Foil(_) | SectionModel::VaryingFoil(_) | SectionModel::CamberedFoilTable(_) | SectionModel::EffectiveWindSensor => SpatialVector::default(),
                     SectionModel::RotatingCylinder(cylinder) => {
                         let i_zz = cylinder.moment_of_inertia_2d * span_lines[index].length(); // TODO: does this depend on position?
 
@@ -640,7 +714,12 @@ impl LineForceModel {
             iterations: solver_result.iterations,
             residual: solver_result.residual,
             wing_indices: self.wing_indices.clone(),
-            rigid_body_motion: self.rigid_body_motion.clone()
+            rigid_body_motion: self.rigid_body_motion.clone(),
+            force_integration_scheme: self.force_integration_scheme,
+            strip_areas: self.strip_areas(),
+            local_wing_angles: self.local_wing_angles.clone(),
+            span_line_lengths: self.span_lines_local.iter().map(|line| line.length()).collect(),
+            non_converged: !solver_result.converged,
         }
     }
 }
@@ -49,7 +49,7 @@ impl Default for InputPowerModel {
 
 impl InputPowerModel {
     pub fn input_power_coefficient(&self, section_model_internal_state: Float) -> Float {
-        match self {
+        let power_coefficient = match self {
             InputPowerModel::NoPower => 0.0,
             InputPowerModel::InternalStateAsPowerCoefficient => {
                 section_model_internal_state.abs()
@@ -68,8 +68,17 @@ impl InputPowerModel {
                     &data.input_power_coefficient_data,
                 )
             },
-            
-        }
+        };
+
+        debug_assert!(
+            power_coefficient.is_finite() && power_coefficient >= 0.0,
+            "Inconsistent input power coefficient: {} for internal state {}. The input power must \
+            be non-negative.",
+            power_coefficient,
+            section_model_internal_state
+        );
+
+        power_coefficient
     }
 
     /// The input power on a given strip, represented by a span lien and chord length.
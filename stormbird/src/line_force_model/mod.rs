@@ -53,6 +53,9 @@ pub struct LineForceModel {
     /// Vector of line segments that defines the span geometry of the wings. Each have its own start
     /// and end point, to allow for uncoupled analysis
     pub span_lines_local: Vec<SpanLine>,
+    /// The span lines as originally defined, before any furling is applied. The local span lines
+    /// are equal to these when the wings are fully unfurled.
+    pub span_lines_local_unfurled: Vec<SpanLine>,
     /// Vectors representing both the chord length and the direction of the chord for each span line
     pub chord_vectors_local_not_rotated: Vec<SpatialVector>,
     /// The length of the chord vectors, stored as it is needed for several calculations
@@ -78,6 +81,8 @@ pub struct LineForceModel {
     pub angle_of_attack_correction: AngleOfAttackCorrection,
     /// The coordinate system to generate the output in. Variants consists of Global and Body.
     pub output_coordinate_system: CoordinateSystem,
+    /// The scheme used to calculate the circulatory forces on each line element
+    pub force_integration_scheme: ForceIntegrationScheme,
     /// Rigid body motion of the line force model
     pub rigid_body_motion: RigidBodyMotion,
     /// Vector used to store local angles for each wing. This can be used to rotate the wing along
@@ -124,6 +129,7 @@ impl LineForceModel {
     pub fn new(density: Float) -> LineForceModel {
         Self {
             span_lines_local: Vec::new(),
+            span_lines_local_unfurled: Vec::new(),
             chord_vectors_local_not_rotated: Vec::new(),
             chord_lengths: Vec::new(),
             section_models: Vec::new(),
@@ -135,6 +141,7 @@ impl LineForceModel {
             circulation_correction: Default::default(),
             angle_of_attack_correction: Default::default(),
             output_coordinate_system: CoordinateSystem::Global,
+            force_integration_scheme: ForceIntegrationScheme::default(),
             chord_vectors_local: Vec::new(),
             chord_vectors_global: Vec::new(),
             chord_vectors_global_at_span_points: Vec::new(),
@@ -167,6 +174,7 @@ impl LineForceModel {
 
         for line in &wing.span_lines_local {
             self.span_lines_local.push(line.clone());
+            self.span_lines_local_unfurled.push(*line);
             self.span_lines_global.push(line.clone());
         }
 
@@ -248,6 +256,7 @@ impl LineForceModel {
                 match &self.section_models[wing_index] {
                     SectionModel::Foil(_) => 0.0,
                     SectionModel::VaryingFoil(_) => 0.0,
+                    SectionModel::CamberedFoilTable(_) => 0.0,
                     SectionModel::RotatingCylinder(cylinder) => cylinder.wake_angle(
                         self.chord_lengths[index],
                         velocity[index].length(),
@@ -10,4 +10,5 @@ pub use super::builder::{
         WingBuilder
     }
 };
-pub use super::span_line::SpanLine;
\ No newline at end of file
+pub use super::span_line::SpanLine;
+pub use super::data_access::PlanformMetrics;
\ No newline at end of file
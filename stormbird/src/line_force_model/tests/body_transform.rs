@@ -0,0 +1,55 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use super::*;
+
+use stormath::type_aliases::Float;
+
+#[test]
+/// Tests that the mapping between the global and body fixed coordinate systems round-trips, and 
+/// that the body to global mapping is consistent with the global geometry of the model.
+fn global_body_round_trip() {
+    let mut line_force_model = get_example_model();
+
+    let translation = SpatialVector::from([1.3, -2.7, 0.4]);
+    let rotation = SpatialVector::from([
+        Float::from(12.0).to_radians(),
+        Float::from(-7.0).to_radians(),
+        Float::from(35.0).to_radians(),
+    ]);
+
+    line_force_model.set_translation_and_rotation(translation, rotation);
+
+    let (current_translation, current_rotation) = line_force_model.current_transform();
+
+    assert_eq!(current_translation, translation);
+    assert_eq!(current_rotation, rotation);
+
+    let global_point = SpatialVector::from([4.1, 9.2, -3.3]);
+
+    let body_point = line_force_model.global_to_body_point(global_point);
+    let round_trip_point = line_force_model.body_to_global_point(body_point);
+
+    assert!((body_point - global_point).length() > 1.0);
+    assert!(
+        (round_trip_point - global_point).length() < 1e-9,
+        "Point round trip failed: {} vs {}", round_trip_point, global_point
+    );
+
+    let global_vector = SpatialVector::from([0.3, -1.1, 2.0]);
+
+    let round_trip_vector = line_force_model.body_to_global_vector(
+        line_force_model.global_to_body_vector(global_vector)
+    );
+
+    assert!((round_trip_vector - global_vector).length() < 1e-9);
+
+    let first_span_line = line_force_model.span_lines_local[0];
+
+    let mapped_start_point = line_force_model.body_to_global_point(first_span_line.start_point);
+
+    assert!(
+        (mapped_start_point - line_force_model.span_lines_global[0].start_point).length() < 1e-9
+    );
+}
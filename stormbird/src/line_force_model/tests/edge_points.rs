@@ -0,0 +1,80 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for wings defined by leading and trailing edge points
+
+use super::*;
+
+#[test]
+fn edge_points_reproduce_section_points_and_chords() {
+    let chord = 2.0;
+    let span = 8.0;
+
+    let section_model = SectionModel::Foil(Foil::default());
+
+    let mut builder_reference = LineForceModelBuilder::new(10);
+
+    builder_reference.add_wing(WingBuilder {
+        section_points: vec![
+            SpatialVector::from([0.0, 0.0, 0.0]),
+            SpatialVector::from([0.0, 0.0, span]),
+        ],
+        chord_vectors: vec![
+            SpatialVector::from([chord, 0.0, 0.0]),
+            SpatialVector::from([chord, 0.0, 0.0]),
+        ],
+        section_model: section_model.clone(),
+        non_zero_circulation_at_ends: [false, false],
+        ..Default::default()
+    });
+
+    let mut builder_edges = LineForceModelBuilder::new(10);
+
+    builder_edges.add_wing(
+        WingBuilder::from_edge_points(
+            &[
+                SpatialVector::from([-0.25 * chord, 0.0, 0.0]),
+                SpatialVector::from([-0.25 * chord, 0.0, span]),
+            ],
+            &[
+                SpatialVector::from([0.75 * chord, 0.0, 0.0]),
+                SpatialVector::from([0.75 * chord, 0.0, span]),
+            ],
+            section_model.clone(),
+            [false, false]
+        ).unwrap()
+    );
+
+    let model_reference = builder_reference.build();
+    let model_edges = builder_edges.build();
+
+    assert_eq!(model_reference.nr_span_lines(), model_edges.nr_span_lines());
+
+    for i in 0..model_reference.nr_span_lines() {
+        let span_line_reference = model_reference.span_lines_local[i];
+        let span_line_edges = model_edges.span_lines_local[i];
+
+        assert!(span_line_reference.start_point.distance(span_line_edges.start_point) < 1e-12);
+        assert!(span_line_reference.end_point.distance(span_line_edges.end_point) < 1e-12);
+
+        assert!(
+            model_reference.chord_vectors_local[i].distance(model_edges.chord_vectors_local[i]) <
+            1e-12
+        );
+
+        assert!(
+            model_reference.ctrl_points_global[i].distance(model_edges.ctrl_points_global[i]) < 
+            1e-12
+        );
+    }
+
+    let mismatched = WingBuilder::from_edge_points(
+        &[SpatialVector::default(); 3],
+        &[SpatialVector::default(); 2],
+        section_model,
+        [false, false]
+    );
+
+    assert!(mismatched.is_err());
+}
@@ -0,0 +1,86 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use super::*;
+
+use crate::common_utils::forces_and_moments::{
+    ForceIntegrationScheme,
+    SectionalForcesInput,
+};
+
+use stormath::type_aliases::Float;
+
+#[test]
+/// Compares the control point and span point force integration schemes on a wing with an elliptic
+/// circulation distribution. The total lift should be the same, while the sectional distribution
+/// should differ close to the tips.
+fn compare_force_integration_schemes() {
+    let mut builder = LineForceModelBuilder::new(40);
+
+    builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector::from([0.0, 0.0, 0.0]),
+                SpatialVector::from([0.0, 0.0, 10.0]),
+            ],
+            chord_vectors: vec![
+                SpatialVector::from([1.0, 0.0, 0.0]),
+                SpatialVector::from([1.0, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::Foil(Foil::default()),
+            non_zero_circulation_at_ends: [false, false],
+            nr_sections: None,
+            input_power_model: InputPowerModel::NoPower,
+        }
+    );
+
+    builder.force_integration_scheme = ForceIntegrationScheme::ControlPoints;
+    let model_ctrl_points = builder.build();
+
+    builder.force_integration_scheme = ForceIntegrationScheme::SpanPoints;
+    let model_span_points = builder.build();
+
+    let nr_span_lines = model_ctrl_points.nr_span_lines();
+
+    let circulation_strength: Vec<Float> = model_ctrl_points.ctrl_point_spanwise_distance_non_dimensional
+        .iter()
+        .map(|s| (1.0 - 4.0 * s.powi(2)).sqrt())
+        .collect();
+
+    let input = SectionalForcesInput {
+        circulation_strength,
+        velocity: vec![SpatialVector::from([1.0, 0.0, 0.0]); nr_span_lines],
+        angles_of_attack: vec![0.0; nr_span_lines],
+        acceleration: vec![SpatialVector::default(); nr_span_lines],
+        ..Default::default()
+    };
+
+    let forces_ctrl_points = model_ctrl_points.sectional_forces(&input).circulatory;
+    let forces_span_points = model_span_points.sectional_forces(&input).circulatory;
+
+    let total_ctrl_points: Float = forces_ctrl_points.iter().map(|f| f[1]).sum();
+    let total_span_points: Float = forces_span_points.iter().map(|f| f[1]).sum();
+
+    let total_difference = (total_ctrl_points - total_span_points).abs() / total_ctrl_points.abs();
+
+    dbg!(total_ctrl_points, total_span_points, total_difference);
+
+    assert!(total_difference < 0.02, "Total lift differs too much: {}", total_difference);
+
+    for tip_index in [0, nr_span_lines - 1] {
+        let tip_difference = (forces_ctrl_points[tip_index][1] - forces_span_points[tip_index][1]).abs() / 
+            forces_ctrl_points[tip_index][1].abs();
+
+        dbg!(tip_index, tip_difference);
+
+        assert!(tip_difference > 0.1, "Tip forces are too similar: {}", tip_difference);
+    }
+
+    let mid_index = nr_span_lines / 2;
+
+    let mid_difference = (forces_ctrl_points[mid_index][1] - forces_span_points[mid_index][1]).abs() / 
+        forces_ctrl_points[mid_index][1].abs();
+
+    assert!(mid_difference < 0.01, "Mid span forces differ too much: {}", mid_difference);
+}
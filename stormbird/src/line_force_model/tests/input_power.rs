@@ -0,0 +1,55 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the consistency checks in the input power model
+
+use crate::line_force_model::input_power::{InputPowerModel, InputPowerData};
+use crate::line_force_model::span_line::SpanLine;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+fn power_model_from_coefficients(input_power_coefficient_data: Vec<Float>) -> InputPowerModel {
+    InputPowerModel::InterpolatePowerCoefficientFromInternalState(
+        InputPowerData {
+            section_models_internal_state_data: vec![0.0, 1.0, 2.0],
+            input_power_coefficient_data,
+        }
+    )
+}
+
+fn strip_power(power_model: &InputPowerModel, internal_state: Float) -> Float {
+    let span_line = SpanLine {
+        start_point: SpatialVector::from([0.0, 0.0, 0.0]),
+        end_point: SpatialVector::from([0.0, 0.0, 1.0]),
+    };
+
+    power_model.input_power_for_strip(
+        internal_state,
+        span_line,
+        2.0,
+        1.225,
+        SpatialVector::from([8.0, 0.0, 0.0])
+    )
+}
+
+#[test]
+fn consistent_power_table_gives_positive_power() {
+    let power_model = power_model_from_coefficients(vec![0.0, 0.1, 0.3]);
+
+    let power = strip_power(&power_model, 1.5);
+
+    dbg!(power);
+
+    assert!(power > 0.0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Inconsistent input power coefficient")]
+fn negative_power_table_trips_debug_check() {
+    let power_model = power_model_from_coefficients(vec![0.0, -0.1, -0.3]);
+
+    strip_power(&power_model, 1.5);
+}
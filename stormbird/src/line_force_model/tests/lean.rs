@@ -0,0 +1,82 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use super::*;
+
+use crate::wind::{
+    environment::WindEnvironment,
+    height_variation::{HeightVariationModel, PowerModel},
+    wind_condition::WindCondition,
+};
+
+use stormath::type_aliases::Float;
+
+#[test]
+/// Tests that a leaned sail ends up with a reduced tip height, and that the wind velocity at each
+/// control point follows the height of the leaned geometry.
+fn leaned_sail_height() {
+    let span = 10.0;
+    let lean_angle = Float::from(30.0).to_radians();
+
+    let mut builder = LineForceModelBuilder::new(10);
+
+    builder.add_wing(
+        WingBuilder {
+            section_points: vec![
+                SpatialVector::from([0.0, 0.0, 0.0]),
+                SpatialVector::from([0.0, 0.0, span]),
+            ],
+            chord_vectors: vec![
+                SpatialVector::from([1.0, 0.0, 0.0]),
+                SpatialVector::from([1.0, 0.0, 0.0]),
+            ],
+            section_model: SectionModel::Foil(Foil::default()),
+            non_zero_circulation_at_ends: [false, false],
+            nr_sections: None,
+            input_power_model: InputPowerModel::NoPower,
+        }
+    );
+
+    let upright_model = builder.build();
+
+    builder.lean_angles = vec![lean_angle];
+
+    let leaned_model = builder.build();
+
+    let tip_height = leaned_model.span_lines_global.last().unwrap().end_point[2];
+    let expected_tip_height = span * lean_angle.cos();
+
+    dbg!(tip_height, expected_tip_height);
+
+    assert!((tip_height - expected_tip_height).abs() < 1e-6);
+
+    let wind_environment = WindEnvironment {
+        height_variation_model: Some(HeightVariationModel::PowerModel(PowerModel::default())),
+        ..Default::default()
+    };
+
+    let condition = WindCondition {
+        velocity: 8.0,
+        direction_coming_from: 0.0
+    };
+
+    let upright_velocity = wind_environment.true_wind_velocity_vectors_at_locations(
+        condition, &upright_model.ctrl_points_global, 0.0
+    );
+
+    let leaned_velocity = wind_environment.true_wind_velocity_vectors_at_locations(
+        condition, &leaned_model.ctrl_points_global, 0.0
+    );
+
+    for i in 0..leaned_model.nr_span_lines() {
+        let expected_height = upright_model.ctrl_points_global[i][2] * lean_angle.cos();
+
+        let expected_velocity = wind_environment.true_wind_velocity_at_height(
+            condition, expected_height, 0.0
+        );
+
+        assert!((leaned_velocity[i].length() - expected_velocity).abs() < 1e-6);
+        assert!(leaned_velocity[i].length() < upright_velocity[i].length());
+    }
+}
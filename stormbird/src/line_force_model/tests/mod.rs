@@ -6,6 +6,14 @@
 
 
 pub mod motion;
+mod force_integration;
+mod lean;
+mod strip_geometry;
+mod input_power;
+mod body_transform;
+mod edge_points;
+mod planform_metrics;
+mod ship_relative_wind;
 
 use crate::line_force_model::builder::single_wing::WingBuilder;
 use crate::line_force_model::builder::LineForceModelBuilder;
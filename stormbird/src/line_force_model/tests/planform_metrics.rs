@@ -0,0 +1,70 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the planform metrics of each wing
+
+use super::*;
+
+use stormath::type_aliases::Float;
+
+fn single_wing_model(root_chord: Float, tip_chord: Float, span: Float) -> LineForceModel {
+    let mut builder = LineForceModelBuilder::new(10);
+
+    builder.add_wing(WingBuilder {
+        section_points: vec![
+            SpatialVector::from([0.0, 0.0, 0.0]),
+            SpatialVector::from([0.0, 0.0, span]),
+        ],
+        chord_vectors: vec![
+            SpatialVector::from([root_chord, 0.0, 0.0]),
+            SpatialVector::from([tip_chord, 0.0, 0.0]),
+        ],
+        section_model: SectionModel::Foil(Foil::default()),
+        non_zero_circulation_at_ends: [false, false],
+        nr_sections: None,
+        input_power_model: InputPowerModel::NoPower,
+    });
+
+    builder.build()
+}
+
+#[test]
+fn rectangular_wing_planform() {
+    let chord = 5.0;
+    let span = 25.0;
+
+    let metrics = single_wing_model(chord, chord, span).wing_planform_metrics();
+
+    dbg!(&metrics);
+
+    assert_eq!(metrics.len(), 1);
+
+    let metrics = metrics[0];
+
+    assert!((metrics.span - span).abs() < 1e-9 * span);
+    assert!((metrics.area - chord * span).abs() < 1e-9 * chord * span);
+    assert!((metrics.mean_chord - chord).abs() < 1e-9 * chord);
+    assert!((metrics.aspect_ratio - metrics.span.powi(2) / metrics.area).abs() < 1e-12);
+    assert!((metrics.taper_ratio - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn tapered_wing_planform() {
+    let metrics = single_wing_model(10.0, 4.0, 30.0).wing_planform_metrics()[0];
+
+    dbg!(&metrics);
+
+    assert!(metrics.taper_ratio < 1.0);
+    assert!(metrics.taper_ratio > 0.0);
+    assert!((metrics.aspect_ratio - metrics.span.powi(2) / metrics.area).abs() < 1e-12);
+}
+
+#[test]
+fn planform_metrics_for_each_wing() {
+    let line_force_model = get_example_model();
+
+    let metrics = line_force_model.wing_planform_metrics();
+
+    assert_eq!(metrics.len(), line_force_model.nr_wings());
+}
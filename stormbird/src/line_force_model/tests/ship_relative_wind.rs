@@ -0,0 +1,79 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use super::*;
+
+use crate::wind::{
+    environment::WindEnvironment,
+    wind_condition::WindCondition,
+};
+
+use stormath::type_aliases::Float;
+
+#[test]
+/// Tests that the apparent wind direction relative to the ship is independent of the ship heading,
+/// the mounting position of the sails and the local wing angles.
+fn apparent_wind_direction_off_the_bow() {
+    let mut builder = LineForceModelBuilder::new(10);
+
+    for x_position in [-20.0, 0.0, 25.0] {
+        builder.add_wing(
+            WingBuilder {
+                section_points: vec![
+                    SpatialVector::from([x_position, 0.0, 5.0]),
+                    SpatialVector::from([x_position, 0.0, 35.0]),
+                ],
+                chord_vectors: vec![
+                    SpatialVector::from([5.0, 0.0, 0.0]),
+                    SpatialVector::from([5.0, 0.0, 0.0]),
+                ],
+                section_model: SectionModel::Foil(Foil::default()),
+                non_zero_circulation_at_ends: [false, false],
+                nr_sections: None,
+                input_power_model: InputPowerModel::NoPower,
+            }
+        );
+    }
+
+    let mut line_force_model = builder.build();
+
+    let heading = Float::from(40.0).to_radians();
+
+    line_force_model.set_rotation_only(SpatialVector::new(0.0, 0.0, heading));
+    line_force_model.set_local_wing_angles(&[0.3, -0.2, 0.5]);
+
+    let wind_environment = WindEnvironment::default();
+
+    let forward_axis = line_force_model.rigid_body_motion.transform_vector(
+        wind_environment.zero_direction_vector
+    );
+
+    let apparent_directions = |direction_coming_from: Float, ship_velocity: Float| {
+        let condition = WindCondition {
+            velocity: 8.0,
+            direction_coming_from,
+        };
+
+        let velocity = wind_environment.apparent_wind_velocity_vectors_at_locations(
+            condition,
+            &line_force_model.ctrl_points_global,
+            ship_velocity * forward_axis,
+            0.0
+        );
+
+        wind_environment.apparent_wind_directions_relative_to_ship(&velocity, &line_force_model)
+    };
+
+    let head_wind = apparent_directions(-heading, 5.0);
+    let beam_wind = apparent_directions(Float::from(90.0).to_radians() - heading, 0.0);
+
+    dbg!(&head_wind, &beam_wind);
+
+    assert_eq!(head_wind.len(), 3);
+
+    for i in 0..3 {
+        assert!(head_wind[i].to_degrees().abs() < 1e-6);
+        assert!((beam_wind[i].to_degrees() - 90.0).abs() < 1e-6);
+    }
+}
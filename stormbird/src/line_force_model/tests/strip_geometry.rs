@@ -0,0 +1,48 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Tests for the per-strip geometry accessors
+
+use super::*;
+
+use stormath::type_aliases::Float;
+
+#[test]
+fn strip_areas_sum_to_reference_area() {
+    let root_chord = 10.0;
+    let tip_chord = 6.0;
+    let span = 30.0;
+
+    let mut builder = LineForceModelBuilder::new(12);
+
+    builder.add_wing(WingBuilder {
+        section_points: vec![
+            SpatialVector::from([0.0, 0.0, 0.0]),
+            SpatialVector::from([0.0, 0.0, span]),
+        ],
+        chord_vectors: vec![
+            SpatialVector::from([root_chord, 0.0, 0.0]),
+            SpatialVector::from([tip_chord, 0.0, 0.0]),
+        ],
+        section_model: SectionModel::Foil(Foil::default()),
+        non_zero_circulation_at_ends: [false, false],
+        nr_sections: None,
+        input_power_model: InputPowerModel::NoPower,
+    });
+
+    let line_force_model = builder.build();
+
+    let chord_lengths = line_force_model.chord_lengths();
+    let strip_areas = line_force_model.strip_areas();
+
+    let reference_area = 0.5 * (root_chord + tip_chord) * span;
+    let strip_area_sum: Float = strip_areas.iter().sum();
+
+    dbg!(&chord_lengths, strip_area_sum, reference_area);
+
+    assert_eq!(chord_lengths.len(), line_force_model.nr_span_lines());
+    assert_eq!(strip_areas.len(), line_force_model.nr_span_lines());
+    assert!(chord_lengths[0] > chord_lengths[chord_lengths.len() - 1]);
+    assert!((strip_area_sum - reference_area).abs() < 1e-9 * reference_area);
+}
@@ -0,0 +1,92 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use stormath::interpolation::linear_interpolation;
+
+use super::*;
+
+use crate::error::Error;
+use crate::io_utils::json_data;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Table based model of a foil profile, where the lift and drag coefficients are given as
+/// functions of both the angle of attack and the camber of the section.
+///
+/// The camber is a fixed model parameter, set when the model is built. This makes it possible to
+/// run parametric studies on the section camber by only changing a single value in the setup,
+/// rather than re-tabulating the coefficients for each camber value.
+///
+/// The coefficient tables are structured so that the first index is the camber index and the
+/// second index is the angle of attack index. Values between the data points are found using
+/// bilinear interpolation. Values outside the data range are taken from the closest data point.
+pub struct CamberedFoilTable {
+    /// Angles of attack, in radians, for the columns in the coefficient tables
+    pub angles_of_attack_data: Vec<Float>,
+    /// Camber values for the rows in the coefficient tables
+    pub camber_data: Vec<Float>,
+    /// Lift coefficients, indexed as [camber index][angle of attack index]
+    pub cl_data: Vec<Vec<Float>>,
+    /// Drag coefficients, indexed as [camber index][angle of attack index]
+    pub cd_data: Vec<Vec<Float>>,
+    #[serde(default)]
+    /// The camber of the section. Must be given in the same unit as the camber data.
+    pub camber: Float,
+    #[serde(default)]
+    /// Factor to linearly scale the added mass
+    pub added_mass_factor: Float,
+}
+
+impl CamberedFoilTable {
+    /// Step size used when computing the lift slope with finite differences
+    const ANGLE_DELTA: Float = 1e-4;
+
+    pub fn new_from_string(string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(string)
+    }
+
+    /// Interpolates the value in a coefficient table at the given angle of attack and the current
+    /// camber.
+    fn table_value(&self, table: &[Vec<Float>], angle_of_attack: Float) -> Float {
+        let values_at_angle: Vec<Float> = table.iter().map(
+            |row| linear_interpolation(angle_of_attack, &self.angles_of_attack_data, row)
+        ).collect();
+
+        linear_interpolation(self.camber, &self.camber_data, &values_at_angle)
+    }
+
+    /// Calculates the lift coefficient for a given angle of attack.
+    ///
+    /// # Arguments
+    /// * `angle_of_attack` - Angle of attack in radians.
+    pub fn lift_coefficient(&self, angle_of_attack: Float) -> Float {
+        self.table_value(&self.cl_data, angle_of_attack)
+    }
+
+    /// Calculates the drag coefficient for a given angle of attack.
+    ///
+    /// # Arguments
+    /// * `angle_of_attack` - Angle of attack in radians.
+    pub fn drag_coefficient(&self, angle_of_attack: Float) -> Float {
+        self.table_value(&self.cd_data, angle_of_attack)
+    }
+
+    /// The slope of the lift coefficient at zero angle of attack, computed with central
+    /// differences.
+    pub fn cl_initial_slope(&self) -> Float {
+        let cl_plus  = self.lift_coefficient(Self::ANGLE_DELTA);
+        let cl_minus = self.lift_coefficient(-Self::ANGLE_DELTA);
+
+        (cl_plus - cl_minus) / (2.0 * Self::ANGLE_DELTA)
+    }
+
+    /// Linearized lift coefficient around zero angle of attack.
+    pub fn lift_coefficient_linear(&self, angle_of_attack: Float) -> Float {
+        self.lift_coefficient(0.0) + self.cl_initial_slope() * angle_of_attack
+    }
+
+    pub fn added_mass_coefficient(&self, heave_acceleration: Float) -> Float {
+        self.added_mass_factor * heave_acceleration
+    }
+}
@@ -5,11 +5,12 @@
 use super::*;
 use stormath::{
     type_aliases::Float,
-    consts::{PI, TAU},
+    consts::{PI, TAU, MIN_POSITIVE},
     special_functions
 };
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -114,6 +115,15 @@ pub struct Foil {
     #[serde(default)]
     /// Factor to model added mass due to accelerating flow around the foil. Set to zero by default.
     pub added_mass_factor: Float,
+    #[serde(default)]
+    /// Moment coefficient about the quarter chord for angles of attack below stall. Set to zero by
+    /// default, which corresponds to a symmetric foil profile.
+    pub cm_zero_angle: Float,
+    #[serde(default="Foil::default_center_of_pressure_after_stall")]
+    /// The chordwise position of the center of pressure after stall, given as a fraction of the 
+    /// chord length from the leading edge. The default value is 0.5, which corresponds to a flat 
+    /// plate in fully separated flow.
+    pub center_of_pressure_after_stall: Float,
 }
 
 fn get_stall_angle(angle_of_attack: Float) -> Float {
@@ -134,11 +144,10 @@ impl Foil {
     pub fn default_mean_stall_angle()     -> Float {Float::from(20.0).to_radians()}
     pub fn default_stall_range()          -> Float {Float::from(6.0).to_radians()}
     pub fn default_cd_power_after_stall() -> Float {1.6}
+    pub fn default_center_of_pressure_after_stall() -> Float {0.5}
 
     pub fn new_from_string(string: &str) -> Result<Self, Error> {
-        let serde_res = serde_json::from_str(string)?;
-
-        Ok(serde_res)
+        json_data::from_json_string(string)
     }
 
     pub fn to_string(&self) -> String {
@@ -234,6 +243,53 @@ impl Foil {
         }
     }
 
+    /// Calculates the moment coefficient about the quarter chord for a given angle of attack, with
+    /// positive values for moments that increases the angle of attack.
+    /// 
+    /// Before stall, the moment coefficient is assumed to be constant. After stall, the moment is
+    /// assumed to come from the normal force acting at the center of pressure after stall. The 
+    /// transition between the two is done with the same sigmoid function as for the lift and drag.
+    ///
+    /// # Arguments
+    /// * `angle_of_attack` - Angle of attack in radians.
+    pub fn moment_coefficient(&self, angle_of_attack: Float) -> Float {
+        let stall_angle = get_stall_angle(angle_of_attack);
+
+        let cl_post_stall = self.lift_coefficient_post_stall_raw(angle_of_attack);
+        let cd_post_stall = self.cd_max_after_stall * stall_angle.sin().abs().powf(self.cd_power_after_stall);
+
+        let cn_post_stall = cl_post_stall * angle_of_attack.cos() + cd_post_stall * angle_of_attack.sin();
+
+        let cm_post_stall = -(self.center_of_pressure_after_stall - 0.25) * cn_post_stall;
+
+        self.combine_pre_and_post_stall(angle_of_attack, self.cm_zero_angle, cm_post_stall)
+    }
+
+    /// Calculates the chordwise position of the aerodynamic center for a given angle of attack, 
+    /// given as a fraction of the chord length from the leading edge. 
+    /// 
+    /// The position is computed from the derivative of the moment coefficient with respect to the
+    /// lift coefficient, i.e., x_ac = 0.25 - dCm/dCl, using central finite differences. For a foil 
+    /// with a constant moment coefficient, the aerodynamic center is at the quarter chord.
+    ///
+    /// # Arguments
+    /// * `angle_of_attack` - Angle of attack in radians.
+    pub fn aerodynamic_center(&self, angle_of_attack: Float) -> Float {
+        let delta_angle = 1e-4;
+
+        let delta_cm = self.moment_coefficient(angle_of_attack + delta_angle) - 
+            self.moment_coefficient(angle_of_attack - delta_angle);
+
+        let delta_cl = self.lift_coefficient(angle_of_attack + delta_angle) - 
+            self.lift_coefficient(angle_of_attack - delta_angle);
+
+        if delta_cl.abs() < MIN_POSITIVE {
+            return 0.25;
+        }
+
+        0.25 - delta_cm / delta_cl
+    }
+
     fn additional_cd_during_stall(&self, angle_of_attack: Float) -> Float {
         let amount_of_stall = self.amount_of_stall(angle_of_attack);
 
@@ -296,7 +352,9 @@ impl Default for Foil {
             stall_range:            Self::default_stall_range(),
             cd_stall_angle_offset:  0.0,
             cd_bump_during_stall:   0.0,
-            added_mass_factor:      0.0
+            added_mass_factor:      0.0,
+            cm_zero_angle:          0.0,
+            center_of_pressure_after_stall: Self::default_center_of_pressure_after_stall(),
         }
     }
 }
@@ -11,6 +11,10 @@ pub mod foil;
 pub mod varying_foil;
 /// Section model of a rotating cylinder, for instance to be used when modelling rotor sails.
 pub mod rotating_cylinder;
+/// Table based section model of a foil profile, where the coefficients depend on the camber
+pub mod camber_table;
+/// Diagnostics for section models that are evaluated outside their tabulated data
+pub mod table_range;
 
 use serde::{Serialize, Deserialize};
 use stormath::type_aliases::Float;
@@ -18,6 +22,7 @@ use stormath::type_aliases::Float;
 use foil::Foil;
 use varying_foil::VaryingFoil;
 use rotating_cylinder::RotatingCylinder;
+use camber_table::CamberedFoilTable;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Sectional model for a wing, that can be of multiple variants
@@ -25,6 +30,7 @@ pub enum SectionModel {
     Foil(Foil),
     VaryingFoil(VaryingFoil),
     RotatingCylinder(RotatingCylinder),
+    CamberedFoilTable(CamberedFoilTable),
     EffectiveWindSensor,
 }
 
@@ -34,6 +40,58 @@ impl SectionModel {
             SectionModel::Foil(foil) => foil.amount_of_stall(angle_of_attack),
             SectionModel::VaryingFoil(varying_foil) => varying_foil.amount_of_stall(angle_of_attack),
             SectionModel::RotatingCylinder(_) => 1.0,
+            SectionModel::CamberedFoilTable(_) => 0.0,
+            SectionModel::EffectiveWindSensor => 0.0
+        }
+    }
+
+    /// Returns the chordwise position of the aerodynamic center, as a fraction of the chord length
+    /// from the leading edge, for the section models where this is defined.
+    pub fn aerodynamic_center(&self, angle_of_attack: Float) -> Option<Float> {
+        match self {
+            SectionModel::Foil(foil) => Some(foil.aerodynamic_center(angle_of_attack)),
+            SectionModel::VaryingFoil(varying_foil) => Some(varying_foil.aerodynamic_center(angle_of_attack)),
+            SectionModel::RotatingCylinder(_) => None,
+            SectionModel::CamberedFoilTable(_) => None,
+            SectionModel::EffectiveWindSensor => None
+        }
+    }
+
+    /// Returns how far outside the tabulated data range the section model is evaluated, for the
+    /// section models that are based on tables. Values outside the tables are taken from the 
+    /// closest data point, so any positive value means that the coefficients are extrapolated. 
+    /// The value is zero when within the range, and for models that are not table based.
+    ///
+    /// The excursion is measured in the unit of the table variable: the angle of attack and the
+    /// camber for a [CamberedFoilTable], the magnitude of the spin ratio for a [RotatingCylinder],
+    /// and the internal state for a [VaryingFoil].
+    pub fn table_range_excursion(
+        &self, 
+        angle_of_attack: Float, 
+        chord_length: Float, 
+        velocity: Float
+    ) -> Float {
+        match self {
+            SectionModel::CamberedFoilTable(table) => {
+                table_range::distance_outside_range(angle_of_attack, &table.angles_of_attack_data)
+                    .max(table_range::distance_outside_range(table.camber, &table.camber_data))
+            },
+            SectionModel::RotatingCylinder(cylinder) => {
+                let spin_ratio = cylinder.spin_ratio(chord_length, velocity);
+
+                if spin_ratio.is_finite() {
+                    table_range::distance_outside_range(spin_ratio.abs(), &cylinder.spin_ratio_data)
+                } else {
+                    0.0
+                }
+            },
+            SectionModel::VaryingFoil(varying_foil) => {
+                table_range::distance_outside_range(
+                    varying_foil.current_internal_state, 
+                    &varying_foil.internal_state_data
+                )
+            },
+            SectionModel::Foil(_) => 0.0,
             SectionModel::EffectiveWindSensor => 0.0
         }
     }
@@ -0,0 +1,125 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Functionality to keep track of sails where the section models are evaluated outside the range
+//! of their tabulated data during a run, meaning that the coefficients are extrapolated.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+
+use crate::line_force_model::LineForceModel;
+use crate::common_utils::results::simulation::SimulationResult;
+
+/// Returns how far the value is outside the range of the data, or zero if it is inside. The data 
+/// is assumed to be sorted in increasing order.
+pub fn distance_outside_range(value: Float, data: &[Float]) -> Float {
+    match (data.first(), data.last()) {
+        (Some(first), Some(last)) => {
+            if value < *first {
+                first - value
+            } else if value > *last {
+                value - last
+            } else {
+                0.0
+            }
+        },
+        _ => 0.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// The largest excursion outside the table range for a single sail
+pub struct TableRangeExcursion {
+    /// The index of the recorded step where the excursion occurred
+    pub step_index: usize,
+    /// The global index of the strip with the largest excursion
+    pub strip_index: usize,
+    /// How far outside the table range the section model was evaluated
+    pub excursion: Float,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Recorder that keeps track of the worst excursion outside the tabulated data of the section 
+/// models for each sail, over all the recorded steps. See 
+/// [SectionModel::table_range_excursion](super::SectionModel::table_range_excursion) for how the
+/// excursion is measured for the different section models.
+pub struct TableRangeRecorder {
+    /// The number of recorded steps
+    pub nr_steps: usize,
+    /// The worst excursion for each sail, or `None` if the sail stayed within the tables
+    pub worst_excursions: Vec<Option<TableRangeExcursion>>,
+}
+
+impl TableRangeRecorder {
+    pub fn new(nr_wings: usize) -> Self {
+        Self {
+            nr_steps: 0,
+            worst_excursions: vec![None; nr_wings],
+        }
+    }
+
+    /// Records the excursions outside the table range in the result, using the angles of attack
+    /// and velocities in the force input, and the section models in the line force model.
+    pub fn record(&mut self, line_force_model: &LineForceModel, result: &SimulationResult) {
+        let chord_lengths = line_force_model.chord_lengths();
+
+        let angles_of_attack = &result.force_input.angles_of_attack;
+        let velocity = &result.force_input.velocity;
+
+        for strip_index in 0..angles_of_attack.len() {
+            let wing_index = line_force_model.wing_index_from_global(strip_index);
+
+            let excursion = line_force_model.section_models[wing_index].table_range_excursion(
+                angles_of_attack[strip_index],
+                chord_lengths[strip_index],
+                velocity[strip_index].length()
+            );
+
+            if excursion <= 0.0 {
+                continue;
+            }
+
+            let is_worst = match self.worst_excursions[wing_index] {
+                Some(worst) => excursion > worst.excursion,
+                None => true,
+            };
+
+            if is_worst {
+                self.worst_excursions[wing_index] = Some(TableRangeExcursion {
+                    step_index: self.nr_steps,
+                    strip_index,
+                    excursion,
+                });
+            }
+        }
+
+        self.nr_steps += 1;
+    }
+
+    /// Returns true if any sail was evaluated outside its tables during the recorded steps
+    pub fn has_violations(&self) -> bool {
+        self.worst_excursions.iter().any(|excursion| excursion.is_some())
+    }
+
+    /// Human readable report of the sails that were evaluated outside their tables
+    pub fn report(&self) -> String {
+        let mut report = format!("Table range report for {} steps:", self.nr_steps);
+
+        if !self.has_violations() {
+            report.push_str("\n- all sails within the table range");
+        }
+
+        for (wing_index, excursion) in self.worst_excursions.iter().enumerate() {
+            if let Some(excursion) = excursion {
+                report.push_str(&format!(
+                    "\n- sail {}: worst excursion {:.4} at step {}, strip {}",
+                    wing_index, excursion.excursion, excursion.step_index, excursion.strip_index
+                ));
+            }
+        }
+
+        report
+    }
+}
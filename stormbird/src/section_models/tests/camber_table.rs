@@ -0,0 +1,64 @@
+// Copyright (C) 2024, NTNU 
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see seperate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::section_models::camber_table::CamberedFoilTable;
+use crate::section_models::SectionModel;
+
+use stormath::type_aliases::Float;
+
+fn table_setup_string(camber: Float) -> String {
+    format!(
+        r#"{{
+            "CamberedFoilTable": {{
+                "angles_of_attack_data": [-0.2, 0.0, 0.2],
+                "camber_data": [0.0, 0.05, 0.1],
+                "cl_data": [
+                    [-1.2, 0.0, 1.2],
+                    [-0.8, 0.4, 1.6],
+                    [-0.4, 0.8, 2.0]
+                ],
+                "cd_data": [
+                    [0.02, 0.01, 0.02],
+                    [0.02, 0.012, 0.025],
+                    [0.025, 0.015, 0.03]
+                ],
+                "camber": {}
+            }}
+        }}"#,
+        camber
+    )
+}
+
+fn table_from_camber(camber: Float) -> CamberedFoilTable {
+    match SectionModel::from_string(&table_setup_string(camber)).unwrap() {
+        SectionModel::CamberedFoilTable(table) => table,
+        _ => panic!("Wrong section model variant"),
+    }
+}
+
+#[test]
+fn camber_increases_zero_angle_lift() {
+    let cambers: [Float; 4] = [0.0, 0.025, 0.05, 0.1];
+    let expected_cl_zero: [Float; 4] = [0.0, 0.2, 0.4, 0.8];
+
+    let mut previous_cl_zero = Float::NEG_INFINITY;
+
+    for (camber, expected) in cambers.iter().zip(expected_cl_zero.iter()) {
+        let table = table_from_camber(*camber);
+
+        let cl_zero = table.lift_coefficient(0.0);
+
+        dbg!(camber, cl_zero);
+
+        assert!((cl_zero - expected).abs() < 1e-9);
+        assert!(cl_zero > previous_cl_zero);
+
+        previous_cl_zero = cl_zero;
+    }
+
+    let table = table_from_camber(0.05);
+
+    assert!((table.cl_initial_slope() - 6.0).abs() < 1e-6);
+    assert!((table.drag_coefficient(0.0) - 0.012).abs() < 1e-9);
+}
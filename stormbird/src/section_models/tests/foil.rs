@@ -33,4 +33,20 @@ fn default_lift_coefficent() {
     dbg!(&cl_error);
 
     assert!(cl_error < 1e-5);
-}
\ No newline at end of file
+}
+
+#[test]
+fn aerodynamic_center() {
+    let foil = Foil::default();
+
+    let x_ac_linear = foil.aerodynamic_center(Float::from(2.0).to_radians());
+
+    let x_ac_close_to_stall = foil.aerodynamic_center(Float::from(14.0).to_radians());
+    let x_ac_closer_to_stall = foil.aerodynamic_center(Float::from(16.0).to_radians());
+
+    dbg!(x_ac_linear, x_ac_close_to_stall, x_ac_closer_to_stall);
+
+    assert!((x_ac_linear - 0.25).abs() < 1e-3);
+    assert!(x_ac_close_to_stall > 0.25 + 5e-3);
+    assert!(x_ac_closer_to_stall > x_ac_close_to_stall);
+}
@@ -3,4 +3,8 @@
 // License: GPL v3.0 (see seperate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
 #[cfg(test)]
-mod foil;
\ No newline at end of file
+mod foil;
+#[cfg(test)]
+mod camber_table;
+#[cfg(test)]
+mod table_range;
@@ -0,0 +1,93 @@
+// Copyright (C) 2024, NTNU 
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see seperate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+use crate::section_models::SectionModel;
+use crate::section_models::table_range::TableRangeRecorder;
+use crate::line_force_model::prelude::*;
+use crate::line_force_model::input_power::InputPowerModel;
+use crate::common_utils::results::simulation::SimulationResult;
+
+use stormath::spatial_vector::SpatialVector;
+use stormath::type_aliases::Float;
+
+fn table_line_force_model() -> LineForceModel {
+    let section_model = SectionModel::from_string(
+        r#"{
+            "CamberedFoilTable": {
+                "angles_of_attack_data": [-0.2, 0.0, 0.2],
+                "camber_data": [0.0],
+                "cl_data": [[-1.2, 0.0, 1.2]],
+                "cd_data": [[0.02, 0.01, 0.02]]
+            }
+        }"#
+    ).unwrap();
+
+    let mut builder = LineForceModelBuilder::new(4);
+
+    builder.add_wing(WingBuilder {
+        section_points: vec![
+            SpatialVector::from([0.0, 0.0, 0.0]),
+            SpatialVector::from([0.0, 0.0, 10.0]),
+        ],
+        chord_vectors: vec![
+            SpatialVector::from([2.0, 0.0, 0.0]),
+            SpatialVector::from([2.0, 0.0, 0.0]),
+        ],
+        section_model,
+        non_zero_circulation_at_ends: [false, false],
+        nr_sections: None,
+        input_power_model: InputPowerModel::NoPower,
+    });
+
+    builder.build()
+}
+
+fn result_with_angle_of_attack(nr_strips: usize, angle_of_attack: Float) -> SimulationResult {
+    let mut result = SimulationResult::default();
+
+    result.force_input.angles_of_attack = vec![angle_of_attack; nr_strips];
+    result.force_input.velocity = vec![SpatialVector::from([10.0, 0.0, 0.0]); nr_strips];
+
+    result
+}
+
+#[test]
+fn no_violations_within_tables() {
+    let line_force_model = table_line_force_model();
+    let nr_strips = line_force_model.nr_span_lines();
+
+    let mut recorder = TableRangeRecorder::new(line_force_model.nr_wings());
+
+    for angle in [-0.15, 0.0, 0.1, 0.2] {
+        recorder.record(&line_force_model, &result_with_angle_of_attack(nr_strips, angle));
+    }
+
+    println!("{}", recorder.report());
+
+    assert_eq!(recorder.nr_steps, 4);
+    assert!(!recorder.has_violations());
+}
+
+#[test]
+fn high_angle_of_attack_is_reported() {
+    let line_force_model = table_line_force_model();
+    let nr_strips = line_force_model.nr_span_lines();
+
+    let mut recorder = TableRangeRecorder::new(line_force_model.nr_wings());
+
+    let angles = [0.0, 0.25, 0.1, 0.35, 0.3];
+
+    for angle in angles {
+        recorder.record(&line_force_model, &result_with_angle_of_attack(nr_strips, angle));
+    }
+
+    println!("{}", recorder.report());
+
+    assert!(recorder.has_violations());
+
+    let worst = recorder.worst_excursions[0].unwrap();
+
+    assert_eq!(worst.step_index, 3);
+    assert!((worst.excursion - 0.15).abs() < 1e-12);
+}
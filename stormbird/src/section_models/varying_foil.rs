@@ -54,6 +54,9 @@ impl VaryingFoil {
 
         let added_mass_factor_data: Vec<Float> = self.foils_data.iter().map(|x| x.added_mass_factor).collect();
 
+        let cm_zero_angle_data: Vec<Float> = self.foils_data.iter().map(|x| x.cm_zero_angle).collect();
+        let center_of_pressure_after_stall_data: Vec<Float> = self.foils_data.iter().map(|x| x.center_of_pressure_after_stall).collect();
+
         let x = self.current_internal_state;
         let x_data = &self.internal_state_data;
 
@@ -75,7 +78,9 @@ impl VaryingFoil {
             stall_range:               linear_interpolation(x, x_data, &stall_range_data),
             cd_stall_angle_offset:     linear_interpolation(x, x_data, &cd_stall_angle_offset_data),
             cd_bump_during_stall:      linear_interpolation(x, x_data, &cd_bump_during_stall_data),
-            added_mass_factor:         linear_interpolation(x, x_data, &added_mass_factor_data)
+            added_mass_factor:         linear_interpolation(x, x_data, &added_mass_factor_data),
+            cm_zero_angle:             linear_interpolation(x, x_data, &cm_zero_angle_data),
+            center_of_pressure_after_stall: linear_interpolation(x, x_data, &center_of_pressure_after_stall_data),
         }
     }
 
@@ -119,4 +124,12 @@ impl VaryingFoil {
     pub fn amount_of_stall(&self, angle_of_attack: Float) -> Float {
         self.get_foil().amount_of_stall(angle_of_attack)
     }
+
+    pub fn moment_coefficient(&self, angle_of_attack: Float) -> Float {
+        self.get_foil().moment_coefficient(angle_of_attack)
+    }
+
+    pub fn aerodynamic_center(&self, angle_of_attack: Float) -> Float {
+        self.get_foil().aerodynamic_center(angle_of_attack)
+    }
 }
\ No newline at end of file
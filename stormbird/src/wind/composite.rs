@@ -0,0 +1,246 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Functionality to superimpose localized disturbances, such as gust cells, on a background wind
+//! environment.
+
+use stormath::{
+    type_aliases::Float,
+    consts::PI,
+    spatial_vector::SpatialVector
+};
+use serde::{Serialize, Deserialize};
+
+use crate::error::Error;
+use crate::io_utils::json_data;
+
+use super::environment::WindEnvironment;
+use super::wind_condition::WindCondition;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// A spherical region with added wind velocity, which can move through the domain and be active 
+/// only for a limited time. 
+///
+/// The added velocity is largest at the center, and decreases smoothly to zero at the radius with
+/// a cosine shape.
+pub struct GustPatch {
+    /// The added velocity vector at the center of the patch
+    pub velocity: SpatialVector,
+    /// The location of the center at time zero
+    pub center: SpatialVector,
+    /// The radius of the patch. The added velocity is zero outside this distance from the center.
+    pub radius: Float,
+    /// Velocity of the center of the patch, for instance to convect the patch with the wind
+    #[serde(default)]
+    pub center_velocity: SpatialVector,
+    /// Optional time when the patch becomes active
+    #[serde(default)]
+    pub start_time: Option<Float>,
+    /// Optional time when the patch is no longer active
+    #[serde(default)]
+    pub end_time: Option<Float>,
+}
+
+impl GustPatch {
+    /// Returns true if the patch is active at the given time
+    pub fn is_active(&self, time: Float) -> bool {
+        let started = self.start_time.is_none_or(|start_time| time >= start_time);
+        let ended = self.end_time.is_some_and(|end_time| time > end_time);
+
+        started && !ended
+    }
+
+    /// Returns the added velocity at the location and time
+    pub fn velocity_at_location(&self, location: SpatialVector, time: Float) -> SpatialVector {
+        if !self.is_active(time) || self.radius <= 0.0 {
+            return SpatialVector::default();
+        }
+
+        let center = self.center + time * self.center_velocity;
+
+        let distance = location.distance(center);
+
+        if distance >= self.radius {
+            return SpatialVector::default();
+        }
+
+        let shape_factor = 0.5 * (1.0 + (PI * distance / self.radius).cos());
+
+        shape_factor * self.velocity
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Disturbances that can be added to a background wind environment
+pub enum WindDisturbance {
+    GustPatch(GustPatch),
+}
+
+impl WindDisturbance {
+    /// Returns the velocity added by the disturbance at the location and time
+    pub fn velocity_at_location(&self, location: SpatialVector, time: Float) -> SpatialVector {
+        match self {
+            WindDisturbance::GustPatch(patch) => patch.velocity_at_location(location, time),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// A wind environment consisting of a background environment, with a sum of disturbances on top.
+/// Without any disturbances, the composite environment gives the same velocities as the 
+/// background.
+pub struct CompositeWindEnvironment {
+    pub background: WindEnvironment,
+    #[serde(default)]
+    pub disturbances: Vec<WindDisturbance>,
+}
+
+impl From<WindEnvironment> for CompositeWindEnvironment {
+    fn from(background: WindEnvironment) -> Self {
+        Self {
+            background,
+            disturbances: Vec::new(),
+        }
+    }
+}
+
+impl CompositeWindEnvironment {
+    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(json_string)
+    }
+
+    pub fn from_json_file(file_path: &str) -> Result<Self, Error> {
+        let json_string = std::fs::read_to_string(file_path)?;
+
+        Self::from_json_string(&json_string)
+    }
+
+    /// Returns the sum of the velocities from all the disturbances at the location and time
+    pub fn disturbance_velocity_at_location(
+        &self, 
+        location: SpatialVector, 
+        time: Float
+    ) -> SpatialVector {
+        let mut velocity = SpatialVector::default();
+
+        for disturbance in self.disturbances.iter() {
+            velocity += disturbance.velocity_at_location(location, time);
+        }
+
+        velocity
+    }
+
+    /// Returns the true wind vector at the location, as the sum of the background wind and the 
+    /// disturbances
+    pub fn true_wind_velocity_vector_at_location(
+        &self,
+        condition: WindCondition,
+        location: SpatialVector,
+        time: Float
+    ) -> SpatialVector {
+        self.background.true_wind_velocity_vector_at_location(condition, location, time) + 
+            self.disturbance_velocity_at_location(location, time)
+    }
+
+    pub fn true_wind_velocity_vectors_at_locations(
+        &self,
+        condition: WindCondition,
+        locations: &[SpatialVector],
+        time: Float
+    ) -> Vec<SpatialVector> {
+        locations.iter().map(
+            |&location| self.true_wind_velocity_vector_at_location(condition, location, time)
+        ).collect()
+    }
+
+    /// Returns the apparent wind velocity vectors at the input locations, given a wind condition 
+    /// for the background environment and a linear velocity of the body
+    pub fn apparent_wind_velocity_vectors_at_locations(
+        &self,
+        condition: WindCondition,
+        locations: &[SpatialVector],
+        linear_velocity: SpatialVector,
+        time: Float
+    ) -> Vec<SpatialVector> {
+        locations.iter().map(
+            |&location| {
+                self.true_wind_velocity_vector_at_location(condition, location, time) + 
+                    linear_velocity
+            }
+        ).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::wind::height_variation::{HeightVariationModel, PowerModel};
+
+    #[test]
+    fn gust_patch_on_background() {
+        let background = WindEnvironment {
+            height_variation_model: Some(HeightVariationModel::PowerModel(PowerModel::default())),
+            ..Default::default()
+        };
+
+        let gust_velocity = SpatialVector::new(0.0, 3.0, 0.0);
+
+        let composite = CompositeWindEnvironment {
+            background: background.clone(),
+            disturbances: vec![
+                WindDisturbance::GustPatch(GustPatch {
+                    velocity: gust_velocity,
+                    center: SpatialVector::new(0.0, 0.0, 20.0),
+                    radius: 10.0,
+                    center_velocity: SpatialVector::new(5.0, 0.0, 0.0),
+                    start_time: Some(1.0),
+                    end_time: None,
+                })
+            ]
+        };
+
+        let condition = WindCondition {
+            velocity: 8.0,
+            direction_coming_from: Float::from(45.0).to_radians(),
+        };
+
+        let time = 2.0;
+
+        let center_location = SpatialVector::new(10.0, 0.0, 20.0);
+        let far_location = SpatialVector::new(-50.0, 0.0, 20.0);
+
+        let composite_velocities = composite.true_wind_velocity_vectors_at_locations(
+            condition, &[center_location, far_location], time
+        );
+
+        let background_velocities = background.true_wind_velocity_vectors_at_locations(
+            condition, &[center_location, far_location], time
+        );
+
+        dbg!(&composite_velocities, &background_velocities);
+
+        assert_eq!(composite_velocities[1], background_velocities[1]);
+        assert!(
+            (composite_velocities[0] - (background_velocities[0] + gust_velocity)).length() < 1e-9
+        );
+
+        // Before the start time, the patch has no effect
+        let early_velocity = composite.true_wind_velocity_vector_at_location(
+            condition, SpatialVector::new(0.0, 0.0, 20.0), 0.5
+        );
+
+        assert_eq!(early_velocity, background_velocities[0]);
+
+        // Without disturbances, the composite is the same as the background
+        let trivial = CompositeWindEnvironment::from(background.clone());
+
+        assert_eq!(
+            trivial.true_wind_velocity_vector_at_location(condition, center_location, time),
+            background_velocities[0]
+        );
+    }
+}
@@ -8,17 +8,20 @@ use std::ops::Range;
 
 use stormath::{
     type_aliases::Float,
+    consts::{PI, TAU},
     spatial_vector::SpatialVector
 };
 use serde::{Serialize, Deserialize};
-use serde_json;
 
 use crate::error::Error;
+use crate::io_utils::json_data;
 use crate::line_force_model::LineForceModel;
 
 use super::height_variation::HeightVariationModel;
+use super::gust::GustModel;
+use super::veer::VeerModel;
 use super::inflow_corrections::InflowCorrections;
-use super::wind_condition::WindCondition;
+use super::wind_condition::{WindCondition, WindConditionTimeSeries};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -27,6 +30,9 @@ use super::wind_condition::WindCondition;
 pub struct WindEnvironment {
     #[serde(default)]
     pub height_variation_model: Option<HeightVariationModel>,
+    /// Optional model for the change in wind direction with height
+    #[serde(default)]
+    pub veer_model: Option<VeerModel>,
     #[serde(default="WindEnvironment::default_up_direction")]
     pub up_direction: SpatialVector,
     #[serde(default="WindEnvironment::default_wind_rotation_axis")]
@@ -37,17 +43,38 @@ pub struct WindEnvironment {
     pub water_plane_height: Float,
     #[serde(default)]
     pub inflow_corrections: Option<InflowCorrections>,
+    /// Optional time series of wind conditions, used by [WindEnvironment::wind_condition_at_time]
+    #[serde(default)]
+    pub wind_condition_time_series: Option<WindConditionTimeSeries>,
+    /// Optional time constant, in seconds, for a first order lag on the wind conditions returned
+    /// by [WindEnvironment::wind_condition_at_time]. Can be used to model the response of an 
+    /// anemometer. No lag is applied by default.
+    #[serde(default)]
+    pub wind_lag_time_constant: Option<Float>,
+    /// Internal state of the wind lag filter, as the time and the filtered condition at the last 
+    /// query.
+    #[serde(skip)]
+    pub lagged_wind_condition: Option<(Float, WindCondition)>,
+    /// Optional gust model, which modulates the wind velocity over time. Evaluated at the time 
+    /// given to the velocity functions.
+    #[serde(default)]
+    pub gust_model: Option<GustModel>,
 }
 
 impl Default for WindEnvironment {
     fn default() -> Self {
         Self {
             height_variation_model: None,
+            veer_model: None,
             up_direction: Self::default_up_direction(),
             wind_rotation_axis: Self::default_wind_rotation_axis(),
             zero_direction_vector: Self::default_zero_direction_vector(),
             water_plane_height: 0.0,
-            inflow_corrections: None
+            inflow_corrections: None,
+            wind_condition_time_series: None,
+            wind_lag_time_constant: None,
+            lagged_wind_condition: None,
+            gust_model: None,
         }
     }
 }
@@ -56,11 +83,10 @@ impl WindEnvironment {
     pub fn default_zero_direction_vector() -> SpatialVector {SpatialVector::from([1.0, 0.0, 0.0])}
     pub fn default_up_direction() -> SpatialVector {SpatialVector::from([0.0, 0.0, 1.0])}
     pub fn default_wind_rotation_axis() -> SpatialVector {SpatialVector::from([0.0, 0.0, -1.0])}
+    pub fn default_reference_height() -> Float {10.0}
 
     pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
-        let serde_res = serde_json::from_str(json_string)?;
-
-        Ok(serde_res)
+        json_data::from_json_string(json_string)
     }
 
     pub fn from_json_file(file_path: &str) -> Result<Self, Error> {
@@ -69,9 +95,76 @@ impl WindEnvironment {
         Self::from_json_string(&json_string)
     }
 
-    /// Computes the true wind velocity magnitude based on the input height
-    pub fn true_wind_velocity_at_height(&self, condition: WindCondition, height: Float) -> Float {
-        let increase_factor = if let Some(model) = self.height_variation_model {
+    /// The height the wind condition refers to. Taken from the height variation model if present,
+    /// and otherwise the default of 10 meters.
+    pub fn reference_height(&self) -> Float {
+        self.height_variation_model.as_ref().map_or(
+            Self::default_reference_height(),
+            |model| model.reference_height()
+        )
+    }
+
+    /// Returns the wind condition at the given time, based on the wind condition time series. 
+    /// 
+    /// If a lag time constant is set, the returned condition is low-pass filtered with a first
+    /// order lag, where the filter state is advanced from the time of the previous query. The 
+    /// filter is initialized with the unfiltered condition at the first query.
+    pub fn wind_condition_at_time(&mut self, time: Float) -> Option<WindCondition> {
+        let raw_condition = self.wind_condition_time_series.as_ref()?.wind_condition_at_time(time);
+
+        let time_constant = match self.wind_lag_time_constant {
+            Some(time_constant) if time_constant > 0.0 => time_constant,
+            _ => return Some(raw_condition),
+        };
+
+        let filtered_condition = match self.lagged_wind_condition {
+            Some((previous_time, previous_condition)) => {
+                let time_step = (time - previous_time).max(0.0);
+
+                let response_factor = 1.0 - (-time_step / time_constant).exp();
+
+                let mut direction_difference = raw_condition.direction_coming_from - 
+                    previous_condition.direction_coming_from;
+
+                while direction_difference > PI {
+                    direction_difference -= TAU;
+                }
+                while direction_difference < -PI {
+                    direction_difference += TAU;
+                }
+
+                WindCondition {
+                    velocity: previous_condition.velocity + 
+                        response_factor * (raw_condition.velocity - previous_condition.velocity),
+                    direction_coming_from: previous_condition.direction_coming_from + 
+                        response_factor * direction_difference,
+                }
+            },
+            None => raw_condition
+        };
+
+        self.lagged_wind_condition = Some((time, filtered_condition));
+
+        Some(filtered_condition)
+    }
+
+    /// Returns the direction perpendicular to the input direction in the plane the wind direction 
+    /// rotates in. Used to find the side force on a sail when the input is the thrust direction. 
+    /// With the default environment and thrust in the negative x-direction, this is the positive 
+    /// y-direction.
+    pub fn side_direction(&self, thrust_direction: SpatialVector) -> SpatialVector {
+        self.wind_rotation_axis.cross(thrust_direction).normalize()
+    }
+
+    /// Computes the true wind velocity magnitude based on the input height, including the gust 
+    /// model at the input time, if given.
+    pub fn true_wind_velocity_at_height(
+        &self, 
+        condition: WindCondition, 
+        height: Float, 
+        time: Float
+    ) -> Float {
+        let increase_factor = if let Some(model) = &self.height_variation_model {
             if height > 0.0 {
                 model.velocity_increase_factor(height)
             } else {
@@ -81,7 +174,18 @@ impl WindEnvironment {
             1.0
         };
 
-        increase_factor * condition.velocity
+        let gust_factor = if let Some(gust_model) = self.gust_model {
+            gust_model.velocity_factor_at(time, height)
+        } else {
+            1.0
+        };
+
+        gust_factor * increase_factor * condition.velocity
+    }
+
+    /// Returns the height of the input location above the water plane
+    pub fn height_of_location(&self, location: SpatialVector) -> Float {
+        (location.dot(self.up_direction) - self.water_plane_height).max(0.0)
     }
 
     /// Computes the height of the input location and then the true wind velocity at this height
@@ -89,24 +193,33 @@ impl WindEnvironment {
         &self,
         condition: WindCondition,
         location: SpatialVector,
+        time: Float,
     ) -> Float {
-        let height = (
-            location.dot(self.up_direction) - self.water_plane_height
-        ).max(0.0);
+        let height = self.height_of_location(location);
+
+        self.true_wind_velocity_at_height(condition, height, time)
+    }
 
-        self.true_wind_velocity_at_height(condition, height)
+    /// Returns the direction the wind is coming from at the given height, which differs from the 
+    /// direction in the wind condition if a veer model is given.
+    pub fn wind_direction_at_height(&self, condition: WindCondition, height: Float) -> Float {
+        match &self.veer_model {
+            Some(veer_model) => condition.direction_coming_from + veer_model.direction_offset(height),
+            None => condition.direction_coming_from
+        }
     }
 
     /// Returns the true wind vector at the location given as input
     pub fn true_wind_velocity_vector_at_location(
         &self,
         condition: WindCondition,
-        location: SpatialVector
+        location: SpatialVector,
+        time: Float
     ) -> SpatialVector {
-        let velocity = self.true_wind_velocity_at_location(condition, location);
+        let velocity = self.true_wind_velocity_at_location(condition, location, time);
 
         let direction_vector = self.zero_direction_vector.rotate_around_axis(
-            condition.direction_coming_from,
+            self.wind_direction_at_height(condition, self.height_of_location(location)),
             self.wind_rotation_axis
         );
 
@@ -117,9 +230,10 @@ impl WindEnvironment {
         &self,
         condition: WindCondition,
         location: SpatialVector,
-        linear_velocity: SpatialVector
+        linear_velocity: SpatialVector,
+        time: Float
     ) -> SpatialVector {
-        let true_wind = self.true_wind_velocity_vector_at_location(condition, location);
+        let true_wind = self.true_wind_velocity_vector_at_location(condition, location, time);
         
         true_wind + linear_velocity
     }
@@ -127,10 +241,11 @@ impl WindEnvironment {
     pub fn true_wind_velocity_vectors_at_locations(
         &self,
         condition: WindCondition,
-        locations: &[SpatialVector]
+        locations: &[SpatialVector],
+        time: Float
     ) -> Vec<SpatialVector> {
         locations.iter().map(
-            |&location| self.true_wind_velocity_vector_at_location(condition, location)
+            |&location| self.true_wind_velocity_vector_at_location(condition, location, time)
         ).collect()
     }
 
@@ -141,12 +256,14 @@ impl WindEnvironment {
         condition: WindCondition,
         locations: &[SpatialVector],
         linear_velocity: SpatialVector,
+        time: Float,
     ) -> Vec<SpatialVector> {
         locations.iter().map(
             |&location| self.apparent_wind_velocity_vector_at_location(
                 condition, 
                 location, 
-                linear_velocity
+                linear_velocity,
+                time
             )
         ).collect()
     }
@@ -156,12 +273,14 @@ impl WindEnvironment {
         condition: WindCondition,
         ctrl_points: &[SpatialVector],
         linear_velocity: SpatialVector,
-        wing_indices: &[Range<usize>]
+        wing_indices: &[Range<usize>],
+        time: Float
     ) -> Vec<SpatialVector> {
         let mut wind_velocity = self.apparent_wind_velocity_vectors_at_locations(
             condition,
             ctrl_points,
-            linear_velocity
+            linear_velocity,
+            time
         );
         
         let mut average_height = 0.0;
@@ -173,7 +292,7 @@ impl WindEnvironment {
         average_height /= ctrl_points.len() as Float;
         
         let apparent_wind_direction = self.apparent_wind_direction_from_condition_and_linear_velocity_and_height(
-            condition, linear_velocity, average_height
+            condition, linear_velocity, average_height, time
         );
         
         self.apply_inflow_corrections(
@@ -210,6 +329,10 @@ impl WindEnvironment {
             
             for wing_index in 0..nr_wings {
                 for i in wing_indices[wing_index].start..wing_indices[wing_index].end {
+                    if !corrections.applies_at_height(height_values[i]) {
+                        continue;
+                    }
+
                     freestream_velocity[i] = corrections.correct_velocity_single_sail(
                         wing_index,
                         apparent_wind_direction,
@@ -226,12 +349,13 @@ impl WindEnvironment {
         &self,
         condition: WindCondition,
         linear_velocity: SpatialVector,
-        height: Float
+        height: Float,
+        time: Float
     ) -> Float {
-        let true_wind_velocity = self.true_wind_velocity_at_height(condition, height);
+        let true_wind_velocity = self.true_wind_velocity_at_height(condition, height, time);
         
         let true_wind_vector = true_wind_velocity * self.zero_direction_vector.rotate_around_axis(
-            condition.direction_coming_from,
+            self.wind_direction_at_height(condition, height),
             self.wind_rotation_axis
         );
 
@@ -247,12 +371,13 @@ impl WindEnvironment {
         &self,
         condition: WindCondition,
         linear_velocity: SpatialVector,
-        height: Float
+        height: Float,
+        time: Float
     ) -> Float {
-        let true_wind_velocity = self.true_wind_velocity_at_height(condition, height);
+        let true_wind_velocity = self.true_wind_velocity_at_height(condition, height, time);
         
         let true_wind_vector = true_wind_velocity * self.zero_direction_vector.rotate_around_axis(
-            condition.direction_coming_from,
+            self.wind_direction_at_height(condition, height),
             self.wind_rotation_axis
         );
 
@@ -278,6 +403,39 @@ impl WindEnvironment {
         }).collect()
     }
 
+    /// Measures the apparent wind direction for each wing relative to the forward axis of the ship,
+    /// i.e., the angle off the bow. The velocity vectors are averaged over the control points of 
+    /// each wing, so that the local variation at the mounting position of the sail is included. 
+    /// 
+    /// The ship axes are given by the zero direction vector and the wind rotation axis, rotated 
+    /// with the rigid body motion of the line force model. A direction of zero means head wind, 
+    /// independent of the ship heading and the local wing angles.
+    pub fn apparent_wind_directions_relative_to_ship(
+        &self,
+        velocity: &[SpatialVector],
+        line_force_model: &LineForceModel
+    ) -> Vec<Float> {
+        let forward_axis = line_force_model.rigid_body_motion.transform_vector(
+            self.zero_direction_vector
+        );
+
+        let rotation_axis = line_force_model.rigid_body_motion.transform_vector(
+            self.wind_rotation_axis
+        );
+
+        line_force_model.wing_indices.iter().map(|wing_indices| {
+            let mut mean_velocity = SpatialVector::default();
+
+            for i in wing_indices.clone() {
+                mean_velocity += velocity[i];
+            }
+
+            mean_velocity /= wing_indices.len() as Float;
+
+            forward_axis.signed_angle_between(mean_velocity, rotation_axis)
+        }).collect()
+    }
+
     /// Measures the apparent wind direction based on the input velocity vectors, where the sign is
     /// defined by the local, non-rotated, chord vector and rotation-axis of each wing in the line
     /// force model. This, then, gives the wind direction relative to the local coordinate system
@@ -319,6 +477,8 @@ impl WindEnvironment {
 mod tests {
     use super::*;
 
+    use crate::wind::inflow_corrections::InflowCorrectionsSingleDirection;
+
     #[test]
     fn test_true_wind_velocity_vectors() {
         let wind_environment = WindEnvironment::default();
@@ -347,19 +507,19 @@ mod tests {
         };
 
         let north_vector = wind_environment.true_wind_velocity_vector_at_location(
-            north_wind_condition, location
+            north_wind_condition, location, 0.0
         );
 
         let east_vector = wind_environment.true_wind_velocity_vector_at_location(
-            east_wind_condition, location
+            east_wind_condition, location, 0.0
         );
 
         let west_vector = wind_environment.true_wind_velocity_vector_at_location(
-            west_wind_condition, location
+            west_wind_condition, location, 0.0
         );
 
         let south_vector = wind_environment.true_wind_velocity_vector_at_location(
-            south_wind_condition, location
+            south_wind_condition, location, 0.0
         );
 
         assert!(north_vector[0] > 0.0);
@@ -372,4 +532,198 @@ mod tests {
         dbg!(west_vector);
         dbg!(south_vector);
     }
+
+    #[test]
+    fn inflow_corrections_minimum_height() {
+        let minimum_height = 5.0;
+        let magnitude_correction = 1.2;
+
+        let ctrl_points: Vec<SpatialVector> = (0..10).map(
+            |i| SpatialVector::new(0.0, 0.0, 0.5 + i as Float)
+        ).collect();
+
+        let wing_indices = vec![Range { start: 0, end: ctrl_points.len() }];
+
+        let single_direction = InflowCorrectionsSingleDirection {
+            height_values: ctrl_points.iter().map(|point| point[2]).collect(),
+            magnitude_corrections: vec![magnitude_correction; ctrl_points.len()],
+            angle_corrections: vec![0.0; ctrl_points.len()],
+            wing_indices: wing_indices.clone(),
+        };
+
+        let wind_environment = WindEnvironment {
+            inflow_corrections: Some(InflowCorrections {
+                apparent_wind_directions: vec![-PI, PI],
+                corrections: vec![single_direction.clone(), single_direction],
+                minimum_height: Some(minimum_height),
+            }),
+            ..Default::default()
+        };
+
+        let velocity = SpatialVector::new(8.0, 1.0, 0.0);
+
+        let mut freestream_velocity = vec![velocity; ctrl_points.len()];
+
+        wind_environment.apply_inflow_corrections(
+            0.3, &mut freestream_velocity, &ctrl_points, &wing_indices
+        );
+
+        for (point, corrected_velocity) in ctrl_points.iter().zip(freestream_velocity.iter()) {
+            let expected_velocity = if point[2] < minimum_height {
+                velocity
+            } else {
+                magnitude_correction * velocity
+            };
+
+            assert!(
+                corrected_velocity.distance(expected_velocity) < 1e-12,
+                "Wrong velocity at height {}: {:?}", point[2], corrected_velocity
+            );
+        }
+    }
+
+    #[test]
+    fn lagged_wind_condition_step_response() {
+        let time_constant = 2.0;
+        let step_time = 1.0;
+        let velocity_before = 5.0;
+        let velocity_after = 10.0;
+
+        let mut wind_environment = WindEnvironment {
+            wind_condition_time_series: Some(WindConditionTimeSeries {
+                time: vec![0.0, step_time, step_time + 1e-9, 100.0],
+                velocity: vec![velocity_before, velocity_before, velocity_after, velocity_after],
+                direction_coming_from: vec![0.0; 4],
+                direction_time: None,
+            }),
+            wind_lag_time_constant: Some(time_constant),
+            ..Default::default()
+        };
+
+        let time_step = 0.001;
+        let nr_steps = ((step_time + time_constant) / time_step).round() as usize;
+
+        let mut condition = wind_environment.wind_condition_at_time(0.0).unwrap();
+
+        for i in 1..=nr_steps {
+            condition = wind_environment.wind_condition_at_time(i as Float * time_step).unwrap();
+        }
+
+        let expected_velocity = velocity_before + 
+            (velocity_after - velocity_before) * (1.0 - Float::exp(-1.0));
+
+        dbg!(condition.velocity, expected_velocity);
+
+        assert!((condition.velocity - expected_velocity).abs() < 0.01);
+
+        let mut unfiltered_environment = WindEnvironment {
+            wind_lag_time_constant: None,
+            ..wind_environment.clone()
+        };
+
+        let unfiltered = unfiltered_environment.wind_condition_at_time(step_time + time_constant).unwrap();
+
+        assert_eq!(unfiltered.velocity, velocity_after);
+    }
+
+    #[test]
+    fn reference_height_from_height_variation_model() {
+        use crate::wind::height_variation::{HeightVariationModel, PowerModel};
+
+        let environment = WindEnvironment {
+            height_variation_model: Some(HeightVariationModel::PowerModel(PowerModel {
+                reference_height: 20.0,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        assert_eq!(environment.reference_height(), 20.0);
+        assert_eq!(
+            WindEnvironment::default().reference_height(), 
+            WindEnvironment::default_reference_height()
+        );
+    }
+
+    #[test]
+    fn veer_rotates_wind_with_height() {
+        use crate::wind::veer::{VeerModel, LinearVeer};
+
+        let veer_rate = Float::from(0.2).to_radians();
+
+        let veered_environment = WindEnvironment {
+            veer_model: Some(VeerModel::Linear(LinearVeer {
+                reference_height: 10.0,
+                veer_rate,
+            })),
+            ..Default::default()
+        };
+
+        let uniform_environment = WindEnvironment::default();
+
+        let condition = WindCondition {
+            velocity: 8.0,
+            direction_coming_from: Float::from(60.0).to_radians()
+        };
+
+        let reference_location = SpatialVector::new(0.0, 0.0, 10.0);
+        let top_location = SpatialVector::new(0.0, 0.0, 40.0);
+
+        assert_eq!(
+            veered_environment.true_wind_velocity_vector_at_location(
+                condition, reference_location, 0.0
+            ),
+            uniform_environment.true_wind_velocity_vector_at_location(
+                condition, reference_location, 0.0
+            )
+        );
+
+        let veered_top = veered_environment.true_wind_velocity_vector_at_location(
+            condition, top_location, 0.0
+        );
+
+        let uniform_top = uniform_environment.true_wind_velocity_vector_at_location(
+            condition, top_location, 0.0
+        );
+
+        let angle = uniform_top.signed_angle_between(
+            veered_top, veered_environment.wind_rotation_axis
+        );
+
+        assert!((angle - 30.0 * veer_rate).abs() < 1e-9);
+        assert!((veered_top.length() - uniform_top.length()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gust_is_evaluated_at_input_time() {
+        use crate::wind::gust::GustModelBuilder;
+
+        let wind_environment = WindEnvironment {
+            gust_model: Some(GustModelBuilder::MexicanHat {
+                center_time: 10.0,
+                width: 2.0,
+                relative_amplitude: 0.3
+            }.build().unwrap()),
+            ..Default::default()
+        };
+
+        let condition = WindCondition {
+            velocity: 8.0,
+            direction_coming_from: 0.0
+        };
+
+        let height = 10.0;
+
+        let peak_velocity = wind_environment.true_wind_velocity_at_height(condition, height, 10.0);
+        let calm_velocity = wind_environment.true_wind_velocity_at_height(condition, height, 100.0);
+
+        assert!((peak_velocity - 1.3 * condition.velocity).abs() < 1e-9);
+        assert!((calm_velocity - condition.velocity).abs() < 1e-6);
+
+        // The same time always gives the same velocity, independent of earlier evaluations
+        assert_eq!(
+            wind_environment.true_wind_velocity_at_height(condition, height, 10.0),
+            peak_velocity
+        );
+    }
 }
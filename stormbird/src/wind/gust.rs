@@ -0,0 +1,200 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Deterministic gust models, which modulate the magnitude of the wind velocity over time. Used 
+//! together with the [HeightVariationModel](super::height_variation::HeightVariationModel) to 
+//! compute transient loads.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+use stormath::consts::{PI, TAU};
+
+use crate::error::Error;
+use crate::io_utils::json_data;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum GustModel {
+    ExtremeOperatingGust(ExtremeOperatingGust),
+    MexicanHat(MexicanHatGust),
+}
+
+impl GustModel {
+    /// Returns the factor that the mean wind velocity should be multiplied with at the given time
+    /// and height. The current models are uniform in height, but the height is part of the 
+    /// interface so that the gusts can be used in the same way as the height variation models.
+    pub fn velocity_factor_at(&self, time: Float, height: Float) -> Float {
+        match self {
+            GustModel::ExtremeOperatingGust(model) => model.velocity_factor_at(time, height),
+            GustModel::MexicanHat(model) => model.velocity_factor_at(time, height),
+        }
+    }
+
+    /// Creates a new model from a JSON string describing a [GustModelBuilder], including 
+    /// validation of the input.
+    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
+        GustModelBuilder::from_json_string(json_string)?.build()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Extreme operating gust, with the shape from IEC 61400-1. The velocity first drops, then rises
+/// to a peak, and drops again before it returns to the mean value at the end of the gust.
+pub struct ExtremeOperatingGust {
+    /// The time when the gust starts
+    pub start_time: Float,
+    /// The duration of the gust
+    pub duration: Float,
+    /// The gust velocity relative to the mean velocity. The peak velocity is approximately 
+    /// 1 + 0.74 times this value.
+    pub relative_amplitude: Float,
+}
+
+impl ExtremeOperatingGust {
+    pub fn default_duration() -> Float {10.5}
+
+    pub fn velocity_factor_at(&self, time: Float, _height: Float) -> Float {
+        let gust_time = time - self.start_time;
+
+        if gust_time < 0.0 || gust_time > self.duration {
+            return 1.0;
+        }
+
+        let phase = gust_time / self.duration;
+
+        1.0 - 0.37 * self.relative_amplitude * (3.0 * PI * phase).sin() * (1.0 - (TAU * phase).cos())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Discrete gust with the shape of a Mexican hat wavelet, i.e., the second derivative of a 
+/// Gaussian. The peak is at the center time, with lulls on each side.
+pub struct MexicanHatGust {
+    /// The time of the peak of the gust
+    pub center_time: Float,
+    /// The time from the center to the zero crossings of the gust
+    pub width: Float,
+    /// The increase of the velocity at the peak, relative to the mean velocity
+    pub relative_amplitude: Float,
+}
+
+impl MexicanHatGust {
+    pub fn velocity_factor_at(&self, time: Float, _height: Float) -> Float {
+        let normalized_time_squared = ((time - self.center_time) / self.width).powi(2);
+
+        1.0 + self.relative_amplitude * (1.0 - normalized_time_squared) * 
+            (-0.5 * normalized_time_squared).exp()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Builder for the [GustModel], which validates the input when building the model.
+pub enum GustModelBuilder {
+    ExtremeOperatingGust {
+        start_time: Float,
+        #[serde(default="ExtremeOperatingGust::default_duration")]
+        duration: Float,
+        relative_amplitude: Float,
+    },
+    MexicanHat {
+        center_time: Float,
+        width: Float,
+        relative_amplitude: Float,
+    },
+}
+
+impl GustModelBuilder {
+    pub fn from_json_string(json_string: &str) -> Result<Self, Error> {
+        json_data::from_json_string(json_string)
+    }
+
+    /// Builds the model, after checking that the duration or width of the gust is positive.
+    pub fn build(&self) -> Result<GustModel, Error> {
+        match *self {
+            GustModelBuilder::ExtremeOperatingGust { start_time, duration, relative_amplitude } => {
+                if duration <= 0.0 {
+                    return Err(Error::CustomStringError(
+                        format!("The gust duration must be positive. Duration: {}", duration)
+                    ));
+                }
+
+                Ok(GustModel::ExtremeOperatingGust(ExtremeOperatingGust {
+                    start_time,
+                    duration,
+                    relative_amplitude,
+                }))
+            },
+            GustModelBuilder::MexicanHat { center_time, width, relative_amplitude } => {
+                if width <= 0.0 {
+                    return Err(Error::CustomStringError(
+                        format!("The gust width must be positive. Width: {}", width)
+                    ));
+                }
+
+                Ok(GustModel::MexicanHat(MexicanHatGust {
+                    center_time,
+                    width,
+                    relative_amplitude,
+                }))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extreme_operating_gust_returns_to_baseline() {
+        let gust = GustModel::from_json_string(
+            r#"{
+                "ExtremeOperatingGust": {
+                    "start_time": 5.0,
+                    "relative_amplitude": 0.4
+                }
+            }"#
+        ).unwrap();
+
+        let duration = ExtremeOperatingGust::default_duration();
+        let height = 20.0;
+
+        assert_eq!(gust.velocity_factor_at(0.0, height), 1.0);
+        assert_eq!(gust.velocity_factor_at(5.0 + duration + 1.0, height), 1.0);
+        assert_eq!(gust.velocity_factor_at(100.0, height), 1.0);
+
+        // The shape is continuous at the end of the gust
+        assert!((gust.velocity_factor_at(5.0 + duration, height) - 1.0).abs() < 1e-12);
+        assert!((gust.velocity_factor_at(5.0 + 0.999 * duration, height) - 1.0).abs() < 1e-3);
+
+        let peak = gust.velocity_factor_at(5.0 + 0.5 * duration, height);
+
+        assert!((peak - (1.0 + 0.74 * 0.4)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mexican_hat_peak_and_validation() {
+        let gust = GustModelBuilder::MexicanHat {
+            center_time: 10.0, 
+            width: 2.0, 
+            relative_amplitude: 0.3
+        }.build().unwrap();
+
+        assert!((gust.velocity_factor_at(10.0, 10.0) - 1.3).abs() < 1e-12);
+        assert!((gust.velocity_factor_at(12.0, 10.0) - 1.0).abs() < 1e-12);
+        assert!(gust.velocity_factor_at(14.0, 10.0) < 1.0);
+        assert!((gust.velocity_factor_at(100.0, 10.0) - 1.0).abs() < 1e-9);
+
+        let invalid = GustModelBuilder::ExtremeOperatingGust {
+            start_time: 0.0, 
+            duration: 0.0, 
+            relative_amplitude: 0.3
+        };
+
+        assert!(invalid.build().is_err());
+    }
+}
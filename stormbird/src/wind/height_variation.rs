@@ -5,11 +5,20 @@
 use serde::{Deserialize, Serialize};
 
 use stormath::type_aliases::Float;
+use stormath::consts::PI;
+use stormath::interpolation::linear_interpolation;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+use crate::error::Error;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Model of how the wind velocity varies with the height above the sea surface.
+///
+/// Note: the enum is not `Copy`, as the tabulated profile owns its data. Code that previously 
+/// copied the model must now clone or borrow it.
 pub enum HeightVariationModel {
     PowerModel(PowerModel),
     LogarithmicModel(LogarithmicModel),
+    TabulatedProfile(TabulatedProfile),
 }
 
 impl Default for HeightVariationModel {
@@ -25,9 +34,29 @@ impl HeightVariationModel {
                 model.velocity_increase_factor(height),
             HeightVariationModel::LogarithmicModel(model) => 
                 model.velocity_increase_factor(height),
+            HeightVariationModel::TabulatedProfile(model) => 
+                model.velocity_increase_factor(height),
+        }
+    }
+
+    /// The height where the velocity increase factor is one, i.e., the height the wind condition
+    /// refers to
+    pub fn reference_height(&self) -> Float {
+        match self {
+            HeightVariationModel::PowerModel(model) => model.reference_height,
+            HeightVariationModel::LogarithmicModel(model) => model.reference_height,
+            HeightVariationModel::TabulatedProfile(model) => model.reference_height,
         }
     }
 
+    /// Creates a tabulated profile from a CSV file. See [TabulatedProfile::from_csv_string] for
+    /// the format.
+    pub fn from_csv(file_path: &str) -> Result<Self, Error> {
+        let csv_string = std::fs::read_to_string(file_path)?;
+
+        Ok(Self::TabulatedProfile(TabulatedProfile::from_csv_string(&csv_string)?))
+    }
+
     pub fn from_json_string(json_string: &str) -> Self {
         serde_json::from_str(json_string).unwrap()
     }
@@ -38,21 +67,88 @@ impl HeightVariationModel {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// Stability of the atmospheric boundary layer, used to correct the height profiles. 
+///
+/// The corrections are based on Monin-Obukhov similarity theory, where the stability is described
+/// by the Obukhov length. A positive length means stable conditions, which gives stronger shear, 
+/// while a negative length means unstable conditions, which gives weaker shear due to more 
+/// vertical mixing.
+pub enum AtmosphericStability {
+    #[default]
+    /// No correction of the profile
+    Neutral,
+    /// Correction based on the Obukhov length, in meters
+    MoninObukhov(Float),
+}
+
+impl AtmosphericStability {
+    /// Returns the stability parameter, i.e., the height divided by the Obukhov length
+    pub fn stability_parameter(&self, height: Float) -> Float {
+        match self {
+            AtmosphericStability::Neutral => 0.0,
+            AtmosphericStability::MoninObukhov(obukhov_length) => {
+                if *obukhov_length != 0.0 {
+                    height / obukhov_length
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The integrated stability function for momentum, which is subtracted from the logarithmic 
+    /// profile. Uses the Businger-Dyer expressions.
+    pub fn integrated_stability_function(&self, height: Float) -> Float {
+        let zeta = self.stability_parameter(height);
+
+        if zeta >= 0.0 {
+            -5.0 * zeta
+        } else {
+            let x = (1.0 - 16.0 * zeta).powf(0.25);
+
+            2.0 * ((1.0 + x) / 2.0).ln() + ((1.0 + x.powi(2)) / 2.0).ln() - 2.0 * x.atan() + 
+            PI / 2.0
+        }
+    }
+
+    /// The non-dimensional wind shear, which is one in neutral conditions. Uses the Businger-Dyer
+    /// expressions.
+    pub fn non_dimensional_shear(&self, height: Float) -> Float {
+        let zeta = self.stability_parameter(height);
+
+        if zeta >= 0.0 {
+            1.0 + 5.0 * zeta
+        } else {
+            (1.0 - 16.0 * zeta).powf(-0.25)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PowerModel {
     #[serde(default="PowerModel::default_reference_height")]
     pub reference_height: Float,
     #[serde(default="PowerModel::default_power_factor")]
     pub power_factor: Float,
+    #[serde(default)]
+    /// Stability of the atmosphere. The power factor is scaled with the non-dimensional shear at 
+    /// the reference height, so that the given power factor applies to neutral conditions.
+    pub stability: AtmosphericStability,
 }
 
 impl PowerModel {
     pub fn default_reference_height() -> Float {10.0}
     pub fn default_power_factor() -> Float {1.0/9.0}
 
+    /// Returns the power factor corrected for the atmospheric stability
+    pub fn effective_power_factor(&self) -> Float {
+        self.power_factor * self.stability.non_dimensional_shear(self.reference_height)
+    }
+
     pub fn velocity_increase_factor(&self, height: Float) -> Float {
         if self.power_factor > 0.0 {
-            (height / self.reference_height).powf(self.power_factor)
+            (height / self.reference_height).powf(self.effective_power_factor())
         } else {
             1.0
         }
@@ -64,6 +160,7 @@ impl Default for PowerModel {
         Self {
             reference_height: Self::default_reference_height(),
             power_factor: Self::default_power_factor(),
+            stability: AtmosphericStability::default(),
         }
     }
 }
@@ -74,6 +171,9 @@ pub struct LogarithmicModel {
     pub reference_height: Float,
     #[serde(default="LogarithmicModel::surface_roughness")]
     pub surface_roughness: Float,
+    #[serde(default)]
+    /// Stability of the atmosphere, which adds a correction term to the logarithmic profile
+    pub stability: AtmosphericStability,
 }
 
 impl LogarithmicModel {
@@ -82,10 +182,237 @@ impl LogarithmicModel {
 
     pub fn velocity_increase_factor(&self, height: Float) -> Float {
         if self.surface_roughness > 0.0 {
-            (height / self.surface_roughness).ln() / 
-            (self.reference_height / self.surface_roughness).ln()
+            let profile = |z: Float| {
+                (z / self.surface_roughness).ln() - 
+                self.stability.integrated_stability_function(z)
+            };
+
+            profile(height) / profile(self.reference_height)
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "TabulatedProfileData")]
+/// Measured wind profile, given as wind velocities at different heights. The velocity is linearly
+/// interpolated between the heights, and held constant below the first and above the last height.
+///
+/// The data is checked both when the profile is created with [TabulatedProfile::new] and when it 
+/// is deserialized.
+pub struct TabulatedProfile {
+    pub reference_height: Float,
+    /// Heights in the profile, sorted in increasing order
+    pub heights: Vec<Float>,
+    /// Wind velocity at each height
+    pub velocities: Vec<Float>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Unchecked version of the [TabulatedProfile], used when deserializing
+struct TabulatedProfileData {
+    #[serde(default="TabulatedProfile::default_reference_height")]
+    reference_height: Float,
+    heights: Vec<Float>,
+    velocities: Vec<Float>,
+}
+
+impl TryFrom<TabulatedProfileData> for TabulatedProfile {
+    type Error = Error;
+
+    fn try_from(data: TabulatedProfileData) -> Result<Self, Self::Error> {
+        Self::check_data(&data.heights, &data.velocities)?;
+
+        Ok(Self {
+            reference_height: data.reference_height,
+            heights: data.heights,
+            velocities: data.velocities,
+        })
+    }
+}
+
+impl TabulatedProfile {
+    pub fn default_reference_height() -> Float {10.0}
+
+    /// Creates a new profile, after checking that the data is consistent.
+    pub fn new(heights: Vec<Float>, velocities: Vec<Float>) -> Result<Self, Error> {
+        Self::check_data(&heights, &velocities)?;
+
+        Ok(Self {
+            reference_height: Self::default_reference_height(),
+            heights,
+            velocities,
+        })
+    }
+
+    /// Checks that there is the same, non-zero, number of heights and velocities, and that the 
+    /// heights are sorted in increasing order.
+    fn check_data(heights: &[Float], velocities: &[Float]) -> Result<(), Error> {
+        if heights.is_empty() || heights.len() != velocities.len() {
+            return Err(Error::CustomStringError(
+                format!(
+                    "The profile must contain the same, non-zero, number of heights and \
+                    velocities. Heights: {}, velocities: {}",
+                    heights.len(), velocities.len()
+                )
+            ));
+        }
+
+        if heights.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(Error::CustomStringError(
+                "The heights in the profile must be sorted in increasing order".to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Parses a profile from a CSV string with two columns, where the first is the height and the
+    /// second is the wind velocity. A header line, and lines that are empty, are skipped.
+    pub fn from_csv_string(csv_string: &str) -> Result<Self, Error> {
+        let mut heights: Vec<Float> = Vec::new();
+        let mut velocities: Vec<Float> = Vec::new();
+
+        for (line_index, line) in csv_string.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let values: Vec<&str> = line.split(',').map(|value| value.trim()).collect();
+
+            let parsed_values = match values.as_slice() {
+                [height, velocity] => (height.parse::<Float>(), velocity.parse::<Float>()),
+                _ => return Err(Error::CustomStringError(
+                    format!("Expected two columns on line {} in the profile: {}", line_index + 1, line)
+                ))
+            };
+
+            match parsed_values {
+                (Ok(height), Ok(velocity)) => {
+                    heights.push(height);
+                    velocities.push(velocity);
+                },
+                _ if line_index == 0 => continue,
+                _ => return Err(Error::CustomStringError(
+                    format!("Could not parse line {} in the profile: {}", line_index + 1, line)
+                ))
+            }
+        }
+
+        Self::new(heights, velocities)
+    }
+
+    /// Returns the interpolated velocity at the height relative to the velocity at the reference
+    /// height.
+    pub fn velocity_increase_factor(&self, height: Float) -> Float {
+        let reference_velocity = linear_interpolation(
+            self.reference_height, &self.heights, &self.velocities
+        );
+
+        if reference_velocity > 0.0 {
+            linear_interpolation(height, &self.heights, &self.velocities) / reference_velocity
         } else {
             1.0
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_conditions_give_stronger_shear() {
+        let base_height = 5.0;
+        let tip_height = 50.0;
+
+        let stabilities = [
+            AtmosphericStability::MoninObukhov(200.0),
+            AtmosphericStability::Neutral,
+            AtmosphericStability::MoninObukhov(-200.0),
+        ];
+
+        for model_type in ["power", "logarithmic"] {
+            let velocity_ratios: Vec<Float> = stabilities.iter().map(|stability| {
+                let model = match model_type {
+                    "power" => HeightVariationModel::PowerModel(PowerModel {
+                        stability: *stability,
+                        ..Default::default()
+                    }),
+                    _ => HeightVariationModel::LogarithmicModel(LogarithmicModel {
+                        reference_height: LogarithmicModel::default_reference_height(),
+                        surface_roughness: LogarithmicModel::surface_roughness(),
+                        stability: *stability,
+                    }),
+                };
+
+                assert!((model.velocity_increase_factor(10.0) - 1.0).abs() < 1e-12);
+
+                model.velocity_increase_factor(tip_height) / 
+                model.velocity_increase_factor(base_height)
+            }).collect();
+
+            dbg!(model_type, &velocity_ratios);
+
+            assert!(
+                velocity_ratios[0] > velocity_ratios[1] && velocity_ratios[1] > velocity_ratios[2],
+                "Wrong ordering of the shear for the {} model: {:?}", model_type, velocity_ratios
+            );
+        }
+    }
+
+    #[test]
+    fn tabulated_profile_from_csv() {
+        let csv_string = "height, velocity\n\
+            5.0, 7.0\n\
+            10.0, 8.0\n\
+            50.0, 10.0\n";
+
+        let model = HeightVariationModel::TabulatedProfile(
+            TabulatedProfile::from_csv_string(csv_string).unwrap()
+        );
+
+        assert_eq!(model.velocity_increase_factor(10.0), 1.0);
+        assert!((model.velocity_increase_factor(30.0) - 9.0 / 8.0).abs() < 1e-12);
+
+        // The profile is clamped outside the range of the data
+        assert!((model.velocity_increase_factor(1.0) - 7.0 / 8.0).abs() < 1e-12);
+        assert!((model.velocity_increase_factor(200.0) - 10.0 / 8.0).abs() < 1e-12);
+
+        let unsorted = "10.0, 8.0\n5.0, 7.0\n";
+        let wrong_columns = "10.0, 8.0, 1.0\n";
+        let invalid_value = "10.0, 8.0\n20.0, fast\n";
+
+        assert!(TabulatedProfile::from_csv_string(unsorted).is_err());
+        assert!(TabulatedProfile::from_csv_string(wrong_columns).is_err());
+        assert!(TabulatedProfile::from_csv_string(invalid_value).is_err());
+        assert!(TabulatedProfile::from_csv_string("").is_err());
+    }
+
+    #[test]
+    fn tabulated_profile_is_checked_when_deserialized() {
+        let valid = r#"{"TabulatedProfile": {"heights": [5.0, 50.0], "velocities": [7.0, 10.0]}}"#;
+        let unsorted = r#"{"TabulatedProfile": {"heights": [50.0, 5.0], "velocities": [7.0, 10.0]}}"#;
+        let wrong_length = r#"{"TabulatedProfile": {"heights": [5.0, 50.0], "velocities": [7.0]}}"#;
+        let unknown_field = r#"{"TabulatedProfile": {
+            "heights": [5.0, 50.0], "velocities": [7.0, 10.0], "height_offset": 1.0
+        }}"#;
+
+        let model: HeightVariationModel = serde_json::from_str(valid).unwrap();
+
+        match model {
+            HeightVariationModel::TabulatedProfile(profile) => {
+                assert_eq!(profile.reference_height, TabulatedProfile::default_reference_height());
+            },
+            _ => panic!("Wrong model type deserialized"),
+        }
+
+        for invalid in [unsorted, wrong_length, unknown_field] {
+            assert!(serde_json::from_str::<HeightVariationModel>(invalid).is_err());
+        }
+    }
+}
@@ -51,9 +51,22 @@ impl InflowCorrectionsSingleDirection {
 pub struct InflowCorrections {
     pub apparent_wind_directions: Vec<Float>,
     pub corrections: Vec<InflowCorrectionsSingleDirection>,
+    #[serde(default)]
+    /// Optional minimum height for the corrections. Points below this height keep the 
+    /// uncorrected velocity, for instance to avoid applying corrections close to the deck that 
+    /// are derived from data further up along the span.
+    pub minimum_height: Option<Float>,
 }
 
 impl InflowCorrections {
+    /// Checks if the corrections should be applied at the given height
+    pub fn applies_at_height(&self, height: Float) -> bool {
+        match self.minimum_height {
+            Some(minimum_height) => height >= minimum_height,
+            None => true
+        }
+    }
+
     pub fn correct_velocity_single_sail(
         &self,
         wing_index: usize,
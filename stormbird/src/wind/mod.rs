@@ -3,6 +3,9 @@
 // License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
 
 pub mod height_variation;
+pub mod gust;
+pub mod veer;
 pub mod inflow_corrections;
 pub mod environment;
 pub mod wind_condition;
+pub mod composite;
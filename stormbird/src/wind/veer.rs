@@ -0,0 +1,95 @@
+// Copyright (C) 2024, NTNU
+// Author: Jarle Vinje Kramer <jarlekramer@gmail.com; jarle.a.kramer@ntnu.no>
+// License: GPL v3.0 (see separate file LICENSE or https://www.gnu.org/licenses/gpl-3.0.html)
+
+//! Models for the change in wind direction with height, i.e., wind veer, in the atmospheric 
+//! boundary layer.
+
+use serde::{Deserialize, Serialize};
+
+use stormath::type_aliases::Float;
+use stormath::interpolation::linear_interpolation;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Model for the offset of the wind direction as a function of height. The offset is added to the
+/// direction the wind is coming from, and is zero at the reference height of the model, so that
+/// the wind condition gives the direction at the reference height.
+pub enum VeerModel {
+    Linear(LinearVeer),
+    Tabulated(TabulatedVeer),
+}
+
+impl VeerModel {
+    /// Returns the offset of the wind direction, in radians, at the given height
+    pub fn direction_offset(&self, height: Float) -> Float {
+        match self {
+            VeerModel::Linear(model) => model.direction_offset(height),
+            VeerModel::Tabulated(model) => model.direction_offset(height),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Wind direction that changes linearly with height
+pub struct LinearVeer {
+    #[serde(default="LinearVeer::default_reference_height")]
+    pub reference_height: Float,
+    /// Change in direction per meter, in radians
+    pub veer_rate: Float,
+}
+
+impl LinearVeer {
+    pub fn default_reference_height() -> Float {10.0}
+
+    pub fn direction_offset(&self, height: Float) -> Float {
+        self.veer_rate * (height - self.reference_height)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Wind direction interpolated from a table of heights and directions. The directions are taken 
+/// relative to the interpolated value at the reference height.
+pub struct TabulatedVeer {
+    #[serde(default="LinearVeer::default_reference_height")]
+    pub reference_height: Float,
+    /// Heights in the table, sorted in increasing order
+    pub heights: Vec<Float>,
+    /// Direction of the wind at each height, in radians
+    pub directions: Vec<Float>,
+}
+
+impl TabulatedVeer {
+    pub fn direction_offset(&self, height: Float) -> Float {
+        let direction = |z: Float| linear_interpolation(z, &self.heights, &self.directions);
+
+        direction(height) - direction(self.reference_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn veer_is_zero_at_reference_height() {
+        let linear = VeerModel::Linear(LinearVeer {
+            reference_height: 20.0,
+            veer_rate: Float::from(0.1).to_radians(),
+        });
+
+        let tabulated = VeerModel::Tabulated(TabulatedVeer {
+            reference_height: 20.0,
+            heights: vec![0.0, 10.0, 50.0],
+            directions: vec![0.0, 0.05, 0.15],
+        });
+
+        assert_eq!(linear.direction_offset(20.0), 0.0);
+        assert!(tabulated.direction_offset(20.0).abs() < 1e-12);
+
+        assert!((linear.direction_offset(40.0) - Float::from(2.0).to_radians()).abs() < 1e-12);
+        assert!((tabulated.direction_offset(50.0) - 0.075).abs() < 1e-12);
+        assert!(tabulated.direction_offset(10.0) < 0.0);
+    }
+}
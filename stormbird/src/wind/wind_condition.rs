@@ -6,7 +6,9 @@
 use stormath::{
     type_aliases::Float,
     spatial_vector::SpatialVector,
+    interpolation::linear_interpolation,
 };
+use serde::{Serialize, Deserialize};
 
 #[cfg(feature = "padded_spatial_vectors")]
 const NED_ZERO_DIRECTION: SpatialVector = SpatialVector{0:[-1.0, 0.0, 0.0, 0.0]};
@@ -38,6 +40,49 @@ impl WindCondition {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Tabulated wind conditions as a function of time. Values between the data points are found 
+/// using linear interpolation.
+///
+/// The velocity and direction share the time stamps in `time` by default. If `direction_time` is
+/// given, the direction is instead tabulated on its own time stamps, so that speed and direction 
+/// can be scripted independently.
+pub struct WindConditionTimeSeries {
+    pub time: Vec<Float>,
+    pub velocity: Vec<Float>,
+    pub direction_coming_from: Vec<Float>,
+    #[serde(default)]
+    /// Optional separate time stamps for the direction values
+    pub direction_time: Option<Vec<Float>>,
+}
+
+impl WindConditionTimeSeries {
+    /// Creates a time series where the velocity and the direction have separate time stamps
+    pub fn from_independent_tracks(
+        velocity_time: Vec<Float>,
+        velocity: Vec<Float>,
+        direction_time: Vec<Float>,
+        direction_coming_from: Vec<Float>,
+    ) -> Self {
+        Self {
+            time: velocity_time,
+            velocity,
+            direction_coming_from,
+            direction_time: Some(direction_time),
+        }
+    }
+
+    pub fn wind_condition_at_time(&self, time: Float) -> WindCondition {
+        let direction_time = self.direction_time.as_ref().unwrap_or(&self.time);
+
+        WindCondition {
+            velocity: linear_interpolation(time, &self.time, &self.velocity),
+            direction_coming_from: linear_interpolation(time, direction_time, &self.direction_coming_from),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +129,37 @@ mod tests {
         assert!((west_wind_condition.direction_coming_from.to_degrees() + 90.0).abs() < allowable_error);
     }
 
+    #[test]
+    fn independent_speed_and_direction_tracks() {
+        let allowable_error = 1e-9;
+
+        let constant_speed = WindConditionTimeSeries::from_independent_tracks(
+            vec![0.0, 100.0],
+            vec![8.0, 8.0],
+            vec![0.0, 10.0, 20.0],
+            vec![0.0, 0.0, 1.0],
+        );
+
+        for (time, expected_direction) in [(5.0, 0.0), (15.0, 0.5), (20.0, 1.0)] {
+            let condition = constant_speed.wind_condition_at_time(time);
+
+            assert!((condition.velocity - 8.0).abs() < allowable_error);
+            assert!((condition.direction_coming_from - expected_direction).abs() < allowable_error);
+        }
+
+        let constant_direction = WindConditionTimeSeries::from_independent_tracks(
+            vec![0.0, 4.0],
+            vec![6.0, 10.0],
+            vec![0.0, 100.0],
+            vec![0.3, 0.3],
+        );
+
+        for (time, expected_velocity) in [(0.0, 6.0), (1.0, 7.0), (4.0, 10.0)] {
+            let condition = constant_direction.wind_condition_at_time(time);
+
+            assert!((condition.velocity - expected_velocity).abs() < allowable_error);
+            assert!((condition.direction_coming_from - 0.3).abs() < allowable_error);
+        }
+    }
+
 }
\ No newline at end of file